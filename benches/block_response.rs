@@ -0,0 +1,73 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use snarkos::{helpers::verify_difficulty_only, Client, Data, Message, MessageCodec};
+use snarkvm::{dpc::testnet2::Testnet2, traits::Network};
+
+use bytes::{BufMut, BytesMut};
+use criterion::{criterion_group, criterion_main, Criterion};
+use tokio_util::codec::Encoder;
+
+// Measures the cost of processing a spammed `BlockResponse`, with and without the cheap difficulty check.
+fn block_response(c: &mut Criterion) {
+    let header = Testnet2::genesis_block().header();
+
+    // The cheap filter, which discards blocks that were never mined.
+    c.bench_function("verify_difficulty_only", |b| b.iter(|| verify_difficulty_only(header)));
+
+    // The full verification, which every spammed block would otherwise incur.
+    c.bench_function("verify_full_header", |b| b.iter(|| header.is_valid()));
+}
+
+// Measures the cost of encoding a `BlockResponse`, with and without pre-sizing the outbound buffer.
+fn encode_block_response(c: &mut Criterion) {
+    let message = Message::<Testnet2, Client<Testnet2>>::BlockResponse(Data::Object(Testnet2::genesis_block().clone()));
+
+    // The encoder, which reserves the upper bound on the message size upfront.
+    c.bench_function("encode_block_response_presized", |b| {
+        b.iter(|| {
+            let mut buffer = BytesMut::new();
+            MessageCodec::default().encode(message.clone(), &mut buffer).unwrap();
+            buffer
+        })
+    });
+
+    // The encoding without pre-sizing, which grows the buffer as the message is serialized.
+    c.bench_function("encode_block_response", |b| {
+        b.iter(|| {
+            let mut buffer = BytesMut::new();
+            buffer.extend_from_slice(&0u32.to_le_bytes());
+            message.serialize_into(&mut (&mut buffer).writer()).unwrap();
+            buffer
+        })
+    });
+}
+
+// Measures the cost of validating a block, against that of verifying its transactions alone.
+fn validate_block(c: &mut Criterion) {
+    let block = Testnet2::genesis_block();
+
+    // The full validation, which is the single pass that every block added to the ledger incurs.
+    c.bench_function("validate_block", |b| b.iter(|| block.is_valid()));
+
+    // The verification of the transactions, which is repeated only to explain the rejection of an invalid block.
+    c.bench_function("verify_transactions", |b| {
+        b.iter(|| block.transactions().iter().all(|transaction| transaction.is_valid()))
+    });
+}
+
+criterion_group!(benches, block_response, encode_block_response, validate_block);
+criterion_main!(benches);
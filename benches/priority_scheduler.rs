@@ -0,0 +1,69 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use snarkos::helpers::{PriorityScheduler, TaskPriority};
+use snarkvm::{dpc::testnet2::Testnet2, traits::Network};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use rayon::ThreadPoolBuilder;
+use std::{
+    sync::{mpsc, Arc},
+    time::{Duration, Instant},
+};
+
+/// The number of best-effort tasks queued ahead of each verification.
+const BEST_EFFORT_TASKS: usize = 64;
+
+// Measures the latency of a block verification submitted behind a backlog of message serializations,
+// with the verification submitted as a verification task, and as a best-effort task in submission order.
+fn verification_latency(c: &mut Criterion) {
+    let pool = ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+    let scheduler = PriorityScheduler::new(Arc::new(pool), BEST_EFFORT_TASKS + 1);
+    let block = Testnet2::genesis_block();
+
+    for (name, priority) in [
+        ("verification_latency_prioritized", TaskPriority::Verification),
+        ("verification_latency_in_order", TaskPriority::BestEffort),
+    ] {
+        c.bench_function(name, |b| {
+            b.iter_custom(|iterations| {
+                let mut elapsed = Duration::default();
+                for _ in 0..iterations {
+                    // Queue the backlog of serializations.
+                    for _ in 0..BEST_EFFORT_TASKS {
+                        let _ = scheduler.spawn(TaskPriority::BestEffort, move || bincode::serialize(block).unwrap());
+                    }
+
+                    // Measure the time until the verification completes.
+                    let (sender, receiver) = mpsc::channel();
+                    let start = Instant::now();
+                    let _ = scheduler.spawn(priority, move || sender.send(block.header().is_valid()).unwrap());
+                    assert!(receiver.recv().unwrap());
+                    elapsed += start.elapsed();
+
+                    // Drain the backlog before the next iteration.
+                    while scheduler.queued(TaskPriority::BestEffort) > 0 {
+                        std::thread::yield_now();
+                    }
+                }
+                elapsed
+            })
+        });
+    }
+}
+
+criterion_group!(benches, verification_latency);
+criterion_main!(benches);
@@ -17,10 +17,45 @@
 // Re-export the metrics macros.
 pub use metrics::*;
 
-use metrics_exporter_prometheus::PrometheusBuilder;
+pub use metrics_exporter_prometheus::PrometheusHandle;
 
-pub fn initialize() -> Option<tokio::task::JoinHandle<()>> {
-    let (recorder, exporter) = PrometheusBuilder::new()
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder};
+use std::net::SocketAddr;
+
+/// The histogram of wall-clock times taken by the miner to find a valid block.
+pub const BLOCK_MINING_DURATION_SECONDS: &str = "snarkos_block_mining_duration_seconds";
+/// The bucket boundaries (in seconds) of the block mining duration histogram.
+pub const BLOCK_MINING_DURATION_BUCKETS: [f64; 7] = [1.0, 5.0, 30.0, 60.0, 120.0, 300.0, 600.0];
+
+/// The gauge of the number of peers connected to the node.
+pub const CONNECTED_PEERS: &str = "snarkos_connected_peers";
+/// The gauge of the latest block height of the ledger.
+pub const LATEST_BLOCK_HEIGHT: &str = "snarkos_latest_block_height";
+/// The gauge of the number of transactions in the memory pool.
+pub const MEMORY_POOL_SIZE: &str = "snarkos_memory_pool_size";
+/// The counter of the messages received from peers, labeled by message type.
+pub const MESSAGES_RECEIVED_TOTAL: &str = "snarkos_messages_received_total";
+/// The counter of the messages sent to peers, labeled by message type.
+pub const MESSAGES_SENT_TOTAL: &str = "snarkos_messages_sent_total";
+/// The counter of the bytes received from peers.
+pub const BYTES_RECEIVED_TOTAL: &str = "snarkos_bytes_received_total";
+/// The counter of the bytes sent to peers.
+pub const BYTES_SENT_TOTAL: &str = "snarkos_bytes_sent_total";
+/// The counter of the peers reported as slow.
+pub const SLOW_PEERS_TOTAL: &str = "snarkos_slow_peers_total";
+
+/// Returns a Prometheus builder with the buckets of the histograms configured.
+fn builder() -> PrometheusBuilder {
+    PrometheusBuilder::new().set_buckets_for_metric(
+        Matcher::Full(BLOCK_MINING_DURATION_SECONDS.to_string()),
+        &BLOCK_MINING_DURATION_BUCKETS,
+    )
+}
+
+/// Installs the Prometheus recorder, and serves the metrics in the Prometheus text format on the given address.
+pub fn initialize(listen_address: SocketAddr) -> Option<tokio::task::JoinHandle<()>> {
+    let (recorder, exporter) = builder()
+        .listen_address(listen_address)
         .build_with_exporter()
         .expect("can't build the prometheus exporter");
 
@@ -32,3 +67,62 @@ pub fn initialize() -> Option<tokio::task::JoinHandle<()>> {
 
     Some(metrics_exporter_task)
 }
+
+/// Installs the Prometheus recorder without serving the metrics, and returns the handle that renders them.
+pub fn initialize_recorder() -> PrometheusHandle {
+    let recorder = builder().build();
+    let handle = recorder.handle();
+
+    metrics::set_boxed_recorder(Box::new(recorder)).expect("can't set the prometheus recorder");
+
+    handle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_mining_duration_buckets() {
+        let recorder = builder().build();
+        let handle = recorder.handle();
+
+        // Record a block that took 45 seconds to mine.
+        recorder.record_histogram(&Key::from_name(BLOCK_MINING_DURATION_SECONDS), 45.0);
+
+        // Ensure the observation is only counted in the buckets from 60 seconds onwards.
+        let rendered = handle.render();
+        for (bucket, count) in [
+            ("1", 0),
+            ("5", 0),
+            ("30", 0),
+            ("60", 1),
+            ("120", 1),
+            ("300", 1),
+            ("600", 1),
+            ("+Inf", 1),
+        ] {
+            let line = format!("{}_bucket{{le=\"{}\"}} {}", BLOCK_MINING_DURATION_SECONDS, bucket, count);
+            assert!(rendered.contains(&line), "missing '{}' in:\n{}", line, rendered);
+        }
+    }
+
+    #[test]
+    fn test_node_metrics() {
+        let recorder = builder().build();
+        let handle = recorder.handle();
+
+        // Record the state of a node with 3 peers, which received 2 pings of 8 bytes each.
+        recorder.update_gauge(&Key::from_name(CONNECTED_PEERS), GaugeValue::Absolute(3.0));
+        let key = Key::from_parts(MESSAGES_RECEIVED_TOTAL, vec![Label::new("type", "Ping")]);
+        recorder.increment_counter(&key, 2);
+        recorder.increment_counter(&Key::from_name(BYTES_RECEIVED_TOTAL), 16);
+
+        // Ensure the metrics are rendered in the Prometheus text format.
+        let rendered = handle.render();
+        assert!(rendered.contains(&format!("{} 3", CONNECTED_PEERS)), "{}", rendered);
+        let line = format!("{}{{type=\"Ping\"}} 2", MESSAGES_RECEIVED_TOTAL);
+        assert!(rendered.contains(&line), "missing '{}' in:\n{}", line, rendered);
+        assert!(rendered.contains(&format!("{} 16", BYTES_RECEIVED_TOTAL)), "{}", rendered);
+    }
+}
@@ -14,67 +14,186 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::helpers::{NodeType, Status, Tasks};
+use crate::helpers::{
+    BlockVerifications,
+    DefaultFeePolicy,
+    FeePolicy,
+    MiningPause,
+    NodeCapabilities,
+    NodeType,
+    PriorityScheduler,
+    ProverStats,
+    ProvingProgress,
+    Status,
+    SyncStrategy,
+    Tasks,
+};
 use snarkvm::dpc::Network;
 
 use once_cell::sync::OnceCell;
-use rayon::{ThreadPool, ThreadPoolBuilder};
+use rayon::ThreadPool;
 use std::{
     collections::HashSet,
     fmt::Debug,
     marker::PhantomData,
     net::SocketAddr,
-    sync::{atomic::AtomicBool, Arc},
+    sync::{
+        atomic::{AtomicBool, AtomicU32},
+        Arc,
+    },
 };
 
 #[rustfmt::skip]
 pub trait Environment: 'static + Clone + Debug + Default + Send + Sync {
     type Network: Network;
+    /// The network-wide rules for the fees of unconfirmed transactions.
+    type FeePolicy: FeePolicy;
     /// The specified type of node.
     const NODE_TYPE: NodeType;
+    /// The capabilities advertised by the node to its peers.
+    const NODE_CAPABILITIES: NodeCapabilities = NodeCapabilities::from_node_type(Self::NODE_TYPE);
     /// The version of the network protocol; it can be incremented in order to force users to update.
-    const MESSAGE_VERSION: u32 = 12;
+    const MESSAGE_VERSION: u32 = 20;
+    /// The minimum version of the network protocol that a peer must run in order to connect.
+    const MINIMUM_PEER_PROTOCOL_VERSION: u32 = Self::MESSAGE_VERSION;
+    /// The maximum fork depth that is advertised to, or honored from, a peer, so that no peer can disable the stale-fork filter.
+    const MAXIMUM_ADVERTISED_FORK_DEPTH: u32 = 2 * <Self::Network as Network>::ALEO_MAXIMUM_FORK_DEPTH;
     /// If `true`, a mining node will craft public coinbase transactions.
     const COINBASE_IS_PUBLIC: bool = false;
+    /// If `true`, each message frame is followed by a CRC32 checksum of its payload, to detect corrupted frames.
+    /// It is disabled by default, as peers without checksums cannot decode such frames.
+    const CHECKSUM_ENABLED: bool = false;
+    /// The maximum number of block templates an operator may send in a single pool request.
+    const MAXIMUM_POOL_REQUEST_TEMPLATES: usize = 4;
 
     /// The port for communicating with the node server.
     const DEFAULT_NODE_PORT: u16 = 4130 + Self::Network::NETWORK_ID;
     /// The port for communicating with the RPC server.
     const DEFAULT_RPC_PORT: u16 = 3030 + Self::Network::NETWORK_ID;
+    /// If `true`, the node serves its metrics in the Prometheus text format, when built with the `prometheus` feature.
+    const ENABLE_METRICS_ENDPOINT: bool = false;
+    /// The port for serving the metrics endpoint, which is separate from the RPC server.
+    const METRICS_ENDPOINT_PORT: u16 = 9000 + Self::Network::NETWORK_ID;
 
     /// The list of beacon nodes to bootstrap the node server with.
     const BEACON_NODES: &'static [&'static str] = &[];
     /// The list of sync nodes to bootstrap the node server with.
     const SYNC_NODES: &'static [&'static str] = &["127.0.0.1:4135"];
+    /// The list of DNS seed hostnames (as `host:port`) to periodically resolve for candidate peers.
+    const DNS_SEEDS: &'static [&'static str] = &[];
+    /// The duration in seconds to sleep in between DNS seed resolutions.
+    const DNS_SEED_REFRESH_IN_SECS: u64 = 1800; // 30 minutes
 
     /// The duration in seconds to sleep in between heartbeat executions.
     const HEARTBEAT_IN_SECS: u64 = 9;
     /// The maximum duration in seconds permitted for establishing a connection with a node,
     /// before dropping the connection; it should be no greater than the `HEARTBEAT_IN_SECS`.
     const CONNECTION_TIMEOUT_IN_MILLIS: u64 = 500;
+    /// The duration in milliseconds for which a connecting node waits for a handshake that is sent without a `Hello`,
+    /// before sending its own `Hello`; it should be less than the `CONNECTION_TIMEOUT_IN_MILLIS`.
+    const HELLO_GRACE_PERIOD_IN_MILLIS: u64 = 200;
     /// The duration in seconds to sleep in between ping requests with a connected peer.
     const PING_SLEEP_IN_SECS: u64 = 60;
     /// The duration in seconds after which a connected peer is considered inactive or
     /// disconnected if no message has been received in the meantime.
     const RADIO_SILENCE_IN_SECS: u64 = 210; // 3.5 minutes
+    /// The maximum duration in seconds of an outbound connection before it is recycled, or `0` to never recycle connections.
+    const MAXIMUM_CONNECTION_AGE_IN_SECS: u64 = 0;
+    /// The maximum duration in milliseconds that small outbound messages are held back to be coalesced into a single write,
+    /// or `0` to write every message immediately.
+    const SEND_COALESCE_INTERVAL_IN_MILLIS: u64 = 0;
+    /// The duration in seconds over which repeated identical errors from a peer are collapsed into a single log summary.
+    const LOG_SUPPRESSION_WINDOW_IN_SECS: u64 = 60;
+    /// The round-trip time in milliseconds between a `Ping` and its `Pong`, above which a peer is considered slow.
+    const SLOW_PEER_RTT_THRESHOLD_IN_MILLIS: u64 = 5_000;
+    /// The duration in seconds for which a peer must remain slow before it is reported.
+    const SLOW_PEER_WINDOW_IN_SECS: u64 = 300; // 5 minutes
+    /// The duration in seconds for which an overloaded node asks a peer to throttle its requests.
+    const THROTTLE_DURATION_IN_SECS: u32 = 10;
+    /// The maximum duration in seconds for which a throttle request from a peer is honored.
+    const MAXIMUM_THROTTLE_DURATION_IN_SECS: u32 = 60;
+    /// The maximum number of outbound requests to a peer that are deferred until its throttle expires.
+    const MAXIMUM_DEFERRED_REQUESTS: usize = 64;
+    /// The duration in seconds for which a peer is banned, once its chain is found to be incompatible with the checkpoints of this node.
+    const INCOMPATIBLE_FORK_BAN_IN_SECS: u64 = 3600; // 1 hour
+    /// The maximum number of branches of unconfirmed blocks that are retained, beyond which the oldest forks are pruned.
+    const MAXIMUM_TRACKED_FORKS: usize = 16;
     /// The duration in seconds after which to expire a failure from a peer.
     const FAILURE_EXPIRY_TIME_IN_SECS: u64 = 7200; // 2 hours
+    /// The duration in seconds in between saves of the peer reputation store.
+    const PEER_REPUTATION_FLUSH_IN_SECS: u64 = 300; // 5 minutes
+    /// The duration in seconds after which the reputation of a peer that has not been updated is pruned.
+    const PEER_REPUTATION_MAXIMUM_AGE_IN_SECS: u64 = 30 * 24 * 60 * 60; // 30 days
+    /// The maximum duration in seconds for which a repeatedly restricted peer is restricted.
+    const MAXIMUM_RESTRICTION_DURATION_IN_SECS: u64 = 24 * 60 * 60; // 1 day
 
     /// The minimum number of peers required to maintain connections with.
     const MINIMUM_NUMBER_OF_PEERS: usize;
     /// The maximum number of peers permitted to maintain connections with.
     const MAXIMUM_NUMBER_OF_PEERS: usize;
+    /// The minimum number of connected peers required to mine, so that the node does not mine on a stale chain.
+    const MINIMUM_PEERS_TO_MINE: usize = 0;
+    /// The maximum number of inbound connections permitted, so that some peer slots remain for outbound connections.
+    const MAX_INBOUND_CONNECTIONS: usize = Self::MAXIMUM_NUMBER_OF_PEERS * 2 / 3;
     /// The maximum number of connection failures permitted by an inbound connecting peer.
     const MAXIMUM_CONNECTION_FAILURES: u32 = 3;
     /// The maximum number of candidate peers permitted to be stored in the node.
     const MAXIMUM_CANDIDATE_PEERS: usize = 10_000;
+    /// The maximum number of invalid addresses tolerated in a single `PeerResponse`, before it is counted as a failure.
+    const MAXIMUM_INVALID_PEER_ADDRESSES: usize = 8;
 
     /// The maximum size of a message that can be transmitted in the network.
     const MAXIMUM_MESSAGE_SIZE: usize = 128 * 1024 * 1024; // 128 MiB
+    /// The maximum serialized size of an unconfirmed transaction that is accepted for relay.
+    const MAXIMUM_TRANSACTION_SIZE: usize = 64 * 1024; // 64 KiB
     /// The maximum number of blocks that may be fetched in one request.
     const MAXIMUM_BLOCK_REQUEST: u32 = 250;
+    /// The maximum number of block requests that may be outstanding (pipelined) at once while syncing.
+    const SYNC_PIPELINE_DEPTH: u32 = 2;
+    /// The duration in seconds of the window over which the sync rate of the ledger is measured.
+    const SYNC_PROGRESS_WINDOW_IN_SECS: u64 = 120;
+    /// The strategy for selecting the peer to sync from.
+    const SYNC_STRATEGY: SyncStrategy = SyncStrategy::HeaviestChain;
+    /// The number of blocks behind the sync peer, beyond which the blocks are requested from the tip of a window downward.
+    const BACKWARD_SYNC_THRESHOLD: u32 = 1000;
+    /// The maximum number of blocks that are requested from the tip downward at once, while syncing in reverse order.
+    /// Note: This bounds the number of blocks that are buffered until they are contiguous with the canonical chain.
+    const BACKWARD_SYNC_WINDOW: u32 = 5000;
+    /// The number of most recent blocks that are served to peers, or `0` if no blocks are pruned.
+    const PRUNE_DEPTH: u32 = 0;
+    /// The number of block requests far beyond the latest block height tolerated from a peer, before each is counted as a failure.
+    const MAXIMUM_FUTURE_BLOCK_REQUESTS: u32 = 10;
     /// The maximum number of failures tolerated before disconnecting from a peer.
     const MAXIMUM_NUMBER_OF_FAILURES: usize = 1024;
+    /// The trusted block hashes at fixed block heights, as (block_height, block_hash), below which blocks skip the verification of their proofs.
+    const CHECKPOINTS: &'static [(u32, &'static str)] = &[];
+    /// If `true`, the node includes its own listener address in the `PeerResponse` messages it sends.
+    const SELF_ADVERTISE_IN_PEER_RESPONSE: bool = false;
+    /// The number of distinct peers whose most recent report of the address of this node is kept, to detect its external address.
+    const EXTERNAL_ADDR_REPORT_WINDOW: usize = 8;
+    /// The minimum number of distinct peers that must agree on the external address of this node, before it is detected.
+    const MINIMUM_EXTERNAL_ADDR_REPORTERS: usize = 3;
+    /// The maximum number of transaction IDs that may be shared in one mempool response.
+    const MAXIMUM_MEMPOOL_RESPONSE_SIZE: usize = 1024;
+    /// The minimum duration in seconds between two mempool requests served to the same peer.
+    const MEMPOOL_REQUEST_INTERVAL_IN_SECS: u64 = 60;
+    /// The maximum reported mempool size of a peer, above which unconfirmed transactions are not relayed to it.
+    const MAX_PEER_MEMPOOL_SIZE: u32 = 50_000;
+    /// The factor by which the weight per block that a peer advertises between two pings may exceed
+    /// the weight of the latest block of this node, to allow for the difficulty adjustments in between.
+    const CUMULATIVE_WEIGHT_TOLERANCE: u128 = 16;
+    /// The maximum number of full block verifications that may be performed simultaneously, or queued in the ledger.
+    /// Note: This is at least two, as one verification is reserved for the sync path.
+    const MAXIMUM_CONCURRENT_BLOCK_VERIFICATIONS: usize = 4;
+    /// The maximum number of gossiped blocks that may wait for a verification slot.
+    const MAXIMUM_QUEUED_BLOCK_VERIFICATIONS: usize = 16;
+    /// The maximum number of inbound ledger requests that may be buffered for each peer.
+    /// Note: This must hold at least a full pipeline of block responses from the peer.
+    const MAXIMUM_INBOUND_REQUESTS_PER_PEER: usize = (Self::MAXIMUM_BLOCK_REQUEST * Self::SYNC_PIPELINE_DEPTH) as usize + 256;
+    /// The maximum number of events buffered for each subscriber of the event channel.
+    const MAXIMUM_BUFFERED_EVENTS: usize = 1024;
+    /// The maximum number of `PoolResponse` messages per second accepted from a prover.
+    const MAX_POOL_RESPONSES_PER_SECOND: u32 = 100;
 
     /// Returns the list of beacon nodes to bootstrap the node server with.
     fn beacon_nodes() -> &'static HashSet<SocketAddr> {
@@ -100,22 +219,66 @@ pub trait Environment: 'static + Clone + Debug + Default + Send + Sync {
         STATUS.get_or_init(Status::new)
     }
     
+    /// Returns the limiter on simultaneous block verifications for the node.
+    fn block_verifications() -> &'static BlockVerifications {
+        static VERIFICATIONS: OnceCell<BlockVerifications> = OnceCell::new();
+        VERIFICATIONS.get_or_init(|| BlockVerifications::new(Self::MAXIMUM_CONCURRENT_BLOCK_VERIFICATIONS, Self::MAXIMUM_QUEUED_BLOCK_VERIFICATIONS))
+    }
+    
     /// Returns the terminator bit for the prover.
     fn terminator() -> &'static Arc<AtomicBool> {
         static TERMINATOR: OnceCell<Arc<AtomicBool>> = OnceCell::new();
         TERMINATOR.get_or_init(|| Arc::new(AtomicBool::new(false)))
     }
 
-    /// Returns a thread pool for the node to perform intensive operations.
+    /// Returns the pause state of the miner, as set by the operator of the node.
+    fn mining_pause() -> &'static MiningPause {
+        static MINING_PAUSE: OnceCell<MiningPause> = OnceCell::new();
+        MINING_PAUSE.get_or_init(MiningPause::new)
+    }
+
+    /// Pauses mining, aborting the block currently being mined.
+    fn pause_mining() {
+        Self::mining_pause().pause(Self::terminator())
+    }
+
+    /// Resumes mining, once the ledger is ready to mine again.
+    fn resume_mining() {
+        Self::mining_pause().resume()
+    }
+
+    /// Returns `true` if mining is paused by the operator.
+    fn is_mining_paused() -> bool {
+        Self::mining_pause().is_paused()
+    }
+
+    /// Returns the progress of the proof currently being generated by the node.
+    fn proving_progress() -> &'static ProvingProgress {
+        static PROVING_PROGRESS: OnceCell<ProvingProgress> = OnceCell::new();
+        PROVING_PROGRESS.get_or_init(ProvingProgress::new)
+    }
+
+    /// Returns the statistics of the proofs attempted by the node, and of the blocks it found.
+    fn prover_stats() -> &'static ProverStats {
+        static PROVER_STATS: OnceCell<ProverStats> = OnceCell::new();
+        PROVER_STATS.get_or_init(ProverStats::new)
+    }
+
+    /// Returns the number of transactions in the memory pool of the node.
+    fn memory_pool_size() -> &'static AtomicU32 {
+        static MEMORY_POOL_SIZE: OnceCell<AtomicU32> = OnceCell::new();
+        MEMORY_POOL_SIZE.get_or_init(|| AtomicU32::new(0))
+    }
+
+    /// Returns the depth of the deepest unconfirmed block held by the ledger, below the latest block.
+    fn orphan_depth() -> &'static AtomicU32 {
+        static ORPHAN_DEPTH: OnceCell<AtomicU32> = OnceCell::new();
+        ORPHAN_DEPTH.get_or_init(|| AtomicU32::new(0))
+    }
+
+    /// Returns a thread pool for the node to perform intensive operations, which is shared with the scheduler of the node.
     fn thread_pool() -> &'static Arc<ThreadPool> {
-        static POOL: OnceCell<Arc<ThreadPool>> = OnceCell::new();
-        POOL.get_or_init(|| {
-            Arc::new(ThreadPoolBuilder::new()
-                .stack_size(8 * 1024 * 1024)
-                .num_threads((num_cpus::get() * 7 / 8).max(2))
-                .build()
-                .expect("Failed to initialize a thread pool for the node"))
-        })
+        PriorityScheduler::global().thread_pool()
     }
 }
 
@@ -125,6 +288,7 @@ pub struct Client<N: Network>(PhantomData<N>);
 #[rustfmt::skip]
 impl<N: Network> Environment for Client<N> {
     type Network = N;
+    type FeePolicy = DefaultFeePolicy;
     const NODE_TYPE: NodeType = NodeType::Client;
     const MINIMUM_NUMBER_OF_PEERS: usize = 2;
     const MAXIMUM_NUMBER_OF_PEERS: usize = 21;
@@ -136,10 +300,12 @@ pub struct Miner<N: Network>(PhantomData<N>);
 #[rustfmt::skip]
 impl<N: Network> Environment for Miner<N> {
     type Network = N;
+    type FeePolicy = DefaultFeePolicy;
     const NODE_TYPE: NodeType = NodeType::Miner;
     const COINBASE_IS_PUBLIC: bool = true;
     const MINIMUM_NUMBER_OF_PEERS: usize = 1;
     const MAXIMUM_NUMBER_OF_PEERS: usize = 21;
+    const MINIMUM_PEERS_TO_MINE: usize = 2;
 }
 
 #[derive(Clone, Debug, Default)]
@@ -148,10 +314,12 @@ pub struct Operator<N: Network>(PhantomData<N>);
 #[rustfmt::skip]
 impl<N: Network> Environment for Operator<N> {
     type Network = N;
+    type FeePolicy = DefaultFeePolicy;
     const NODE_TYPE: NodeType = NodeType::Operator;
     const COINBASE_IS_PUBLIC: bool = true;
     const MINIMUM_NUMBER_OF_PEERS: usize = 1;
     const MAXIMUM_NUMBER_OF_PEERS: usize = 1000;
+    const MAX_POOL_RESPONSES_PER_SECOND: u32 = 20;
 }
 
 #[derive(Clone, Debug, Default)]
@@ -160,22 +328,37 @@ pub struct Prover<N: Network>(PhantomData<N>);
 #[rustfmt::skip]
 impl<N: Network> Environment for Prover<N> {
     type Network = N;
+    type FeePolicy = DefaultFeePolicy;
     const NODE_TYPE: NodeType = NodeType::Prover;
     const COINBASE_IS_PUBLIC: bool = true;
     const MINIMUM_NUMBER_OF_PEERS: usize = 2;
     const MAXIMUM_NUMBER_OF_PEERS: usize = 21;
 }
 
+#[derive(Clone, Debug, Default)]
+pub struct Observer<N: Network>(PhantomData<N>);
+
+#[rustfmt::skip]
+impl<N: Network> Environment for Observer<N> {
+    type Network = N;
+    type FeePolicy = DefaultFeePolicy;
+    const NODE_TYPE: NodeType = NodeType::Observer;
+    const MINIMUM_NUMBER_OF_PEERS: usize = 2;
+    const MAXIMUM_NUMBER_OF_PEERS: usize = 21;
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct SyncNode<N: Network>(PhantomData<N>);
 
 #[rustfmt::skip]
 impl<N: Network> Environment for SyncNode<N> {
     type Network = N;
+    type FeePolicy = DefaultFeePolicy;
     const NODE_TYPE: NodeType = NodeType::Sync;
     const MINIMUM_NUMBER_OF_PEERS: usize = 35;
     const MAXIMUM_NUMBER_OF_PEERS: usize = 1024;
     const HEARTBEAT_IN_SECS: u64 = 5;
+    const SELF_ADVERTISE_IN_PEER_RESPONSE: bool = true;
 }
 
 #[derive(Clone, Debug, Default)]
@@ -184,6 +367,7 @@ pub struct ClientTrial<N: Network>(PhantomData<N>);
 #[rustfmt::skip]
 impl<N: Network> Environment for ClientTrial<N> {
     type Network = N;
+    type FeePolicy = DefaultFeePolicy;
     const NODE_TYPE: NodeType = NodeType::Client;
     const SYNC_NODES: &'static [&'static str] = &[
         "144.126.219.193:4132", "165.232.145.194:4132", "143.198.164.241:4132", "188.166.7.13:4132", "167.99.40.226:4132",
@@ -200,6 +384,7 @@ pub struct MinerTrial<N: Network>(PhantomData<N>);
 #[rustfmt::skip]
 impl<N: Network> Environment for MinerTrial<N> {
     type Network = N;
+    type FeePolicy = DefaultFeePolicy;
     const NODE_TYPE: NodeType = NodeType::Miner;
     const SYNC_NODES: &'static [&'static str] = &[
         "144.126.219.193:4132", "165.232.145.194:4132", "143.198.164.241:4132", "188.166.7.13:4132", "167.99.40.226:4132",
@@ -217,6 +402,7 @@ pub struct OperatorTrial<N: Network>(PhantomData<N>);
 #[rustfmt::skip]
 impl<N: Network> Environment for OperatorTrial<N> {
     type Network = N;
+    type FeePolicy = DefaultFeePolicy;
     const NODE_TYPE: NodeType = NodeType::Operator;
     const SYNC_NODES: &'static [&'static str] = &[
         "144.126.219.193:4132", "165.232.145.194:4132", "143.198.164.241:4132", "188.166.7.13:4132", "167.99.40.226:4132",
@@ -226,6 +412,7 @@ impl<N: Network> Environment for OperatorTrial<N> {
     const MINIMUM_NUMBER_OF_PEERS: usize = 11;
     const MAXIMUM_NUMBER_OF_PEERS: usize = 1000;
     const COINBASE_IS_PUBLIC: bool = true;
+    const MAX_POOL_RESPONSES_PER_SECOND: u32 = 20;
 }
 
 #[derive(Clone, Debug, Default)]
@@ -234,6 +421,7 @@ pub struct ProverTrial<N: Network>(PhantomData<N>);
 #[rustfmt::skip]
 impl<N: Network> Environment for ProverTrial<N> {
     type Network = N;
+    type FeePolicy = DefaultFeePolicy;
     const NODE_TYPE: NodeType = NodeType::Prover;
     const SYNC_NODES: &'static [&'static str] = &[
         "144.126.219.193:4132", "165.232.145.194:4132", "143.198.164.241:4132", "188.166.7.13:4132", "167.99.40.226:4132",
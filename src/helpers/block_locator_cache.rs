@@ -0,0 +1,268 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::helpers::EncodingFormat;
+use snarkos_storage::BlockLocators;
+use snarkvm::dpc::prelude::*;
+
+use ::bytes::Bytes;
+use anyhow::Result;
+use std::{collections::HashMap, future::Future, sync::Arc};
+use tokio::sync::RwLock;
+
+///
+/// A cache of the serialized block locators of the current chain tip, served to peers in `Pong` messages.
+///
+/// Serializing the block locators for every `Ping` is costly for nodes that serve many peers,
+/// so the serialized block locators are computed once per chain tip and encoding format.
+/// The block locators themselves are cached as well, for the block locator diffs sent to each peer.
+/// The cache is invalidated as soon as it is accessed for a different chain tip.
+///
+#[derive(Clone, Debug)]
+pub struct BlockLocatorCache<N: Network> {
+    /// The block hash of the chain tip that the cached block locators were computed for.
+    block_hash: Option<N::BlockHash>,
+    /// The block locators of the chain tip, if they have been loaded from the ledger.
+    latest_block_locators: Option<Arc<BlockLocators<N>>>,
+    /// The serialized block locators for each encoding format.
+    block_locators: HashMap<EncodingFormat, Bytes>,
+}
+
+impl<N: Network> BlockLocatorCache<N> {
+    /// Initializes a new instance of the cache.
+    pub fn new() -> Self {
+        Self {
+            block_hash: None,
+            latest_block_locators: None,
+            block_locators: Default::default(),
+        }
+    }
+
+    /// Returns `true` if the cache holds block locators for the given chain tip.
+    pub fn is_current(&self, block_hash: &N::BlockHash) -> bool {
+        self.block_hash.as_ref() == Some(block_hash)
+    }
+
+    /// Returns the serialized block locators for the given chain tip and encoding format, if they are cached.
+    pub fn get(&self, block_hash: &N::BlockHash, format: EncodingFormat) -> Option<Bytes> {
+        match self.is_current(block_hash) {
+            true => self.block_locators.get(&format).cloned(),
+            false => None,
+        }
+    }
+
+    /// Returns the block locators for the given chain tip, if they are cached.
+    pub fn get_block_locators(&self, block_hash: &N::BlockHash) -> Option<Arc<BlockLocators<N>>> {
+        match self.is_current(block_hash) {
+            true => self.latest_block_locators.clone(),
+            false => None,
+        }
+    }
+
+    ///
+    /// Caches the serialized block locators for the given chain tip and encoding format.
+    /// If the chain tip has changed, the block locators of the previous chain tip are invalidated.
+    ///
+    pub fn insert(&mut self, block_hash: N::BlockHash, format: EncodingFormat, block_locators: Bytes) {
+        self.set_current(block_hash);
+        self.block_locators.insert(format, block_locators);
+    }
+
+    ///
+    /// Caches the block locators for the given chain tip.
+    /// If the chain tip has changed, the block locators of the previous chain tip are invalidated.
+    ///
+    pub fn insert_block_locators(&mut self, block_hash: N::BlockHash, block_locators: Arc<BlockLocators<N>>) {
+        self.set_current(block_hash);
+        self.latest_block_locators = Some(block_locators);
+    }
+
+    /// Removes all cached block locators.
+    pub fn invalidate(&mut self) {
+        self.block_hash = None;
+        self.latest_block_locators = None;
+        self.block_locators.clear();
+    }
+
+    /// Invalidates the cache if it holds the block locators of a different chain tip.
+    fn set_current(&mut self, block_hash: N::BlockHash) {
+        if !self.is_current(&block_hash) {
+            self.invalidate();
+            self.block_hash = Some(block_hash);
+        }
+    }
+
+    ///
+    /// Returns the block locators for the given chain tip from the given cache,
+    /// loading and caching them if they are not cached yet.
+    ///
+    pub async fn get_or_load(
+        cache: &RwLock<Self>,
+        block_hash: N::BlockHash,
+        load: impl FnOnce() -> BlockLocators<N>,
+    ) -> Arc<BlockLocators<N>> {
+        if let Some(block_locators) = cache.read().await.get_block_locators(&block_hash) {
+            return block_locators;
+        }
+
+        // Check the cache again, as another miss may have loaded the block locators in the meantime.
+        let mut cache = cache.write().await;
+        if let Some(block_locators) = cache.get_block_locators(&block_hash) {
+            return block_locators;
+        }
+        let block_locators = Arc::new(load());
+        cache.insert_block_locators(block_hash, block_locators.clone());
+        block_locators
+    }
+
+    ///
+    /// Returns the serialized block locators for the given chain tip and encoding format from the given cache,
+    /// serializing and caching them if they are not cached yet. Concurrent misses serialize the block locators once,
+    /// as the first miss holds the write lock while serializing, and the others are then served from the cache.
+    ///
+    pub async fn get_or_serialize<F: Future<Output = Result<Bytes>>>(
+        cache: &RwLock<Self>,
+        block_hash: N::BlockHash,
+        format: EncodingFormat,
+        serialize: impl FnOnce() -> F,
+    ) -> Result<Bytes> {
+        if let Some(block_locators) = cache.read().await.get(&block_hash, format) {
+            return Ok(block_locators);
+        }
+
+        // Check the cache again, as another miss may have serialized the block locators in the meantime.
+        let mut cache = cache.write().await;
+        if let Some(block_locators) = cache.get(&block_hash, format) {
+            return Ok(block_locators);
+        }
+        let block_locators = serialize().await?;
+        cache.insert(block_hash, format, block_locators.clone());
+        Ok(block_locators)
+    }
+}
+
+impl<N: Network> Default for BlockLocatorCache<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{dpc::testnet2::Testnet2, prelude::UniformRand};
+
+    use rand::thread_rng;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn test_block_locator_cache() {
+        let rng = &mut thread_rng();
+        let block_hash = <Testnet2 as Network>::BlockHash::rand(rng);
+        let block_locators = Bytes::from_static(b"block locators");
+
+        let mut cache = BlockLocatorCache::<Testnet2>::new();
+        assert!(!cache.is_current(&block_hash));
+        assert_eq!(None, cache.get(&block_hash, EncodingFormat::Bincode));
+
+        cache.insert(block_hash, EncodingFormat::Bincode, block_locators.clone());
+        assert!(cache.is_current(&block_hash));
+        assert_eq!(Some(block_locators), cache.get(&block_hash, EncodingFormat::Bincode));
+
+        cache.invalidate();
+        assert!(!cache.is_current(&block_hash));
+        assert_eq!(None, cache.get(&block_hash, EncodingFormat::Bincode));
+    }
+
+    #[test]
+    fn test_block_locator_cache_invalidated_by_new_tip() {
+        let rng = &mut thread_rng();
+        let previous_tip = <Testnet2 as Network>::BlockHash::rand(rng);
+        let new_tip = <Testnet2 as Network>::BlockHash::rand(rng);
+
+        let mut cache = BlockLocatorCache::<Testnet2>::new();
+        cache.insert(previous_tip, EncodingFormat::Bincode, Bytes::from_static(b"previous"));
+
+        // Ensure the block locators of the previous tip are not served for the new tip.
+        assert_eq!(None, cache.get(&new_tip, EncodingFormat::Bincode));
+
+        // Ensure caching the block locators of the new tip evicts those of the previous tip.
+        cache.insert(new_tip, EncodingFormat::Bincode, Bytes::from_static(b"new"));
+        assert_eq!(None, cache.get(&previous_tip, EncodingFormat::Bincode));
+        assert_eq!(Some(Bytes::from_static(b"new")), cache.get(&new_tip, EncodingFormat::Bincode));
+    }
+
+    #[tokio::test]
+    async fn test_block_locators_loaded_once_per_tip() {
+        let rng = &mut thread_rng();
+        let block_hash = <Testnet2 as Network>::BlockHash::rand(rng);
+        let cache = RwLock::new(BlockLocatorCache::<Testnet2>::new());
+        let number_of_loads = AtomicUsize::new(0);
+        let load = || {
+            number_of_loads.fetch_add(1, Ordering::SeqCst);
+            BlockLocators::<Testnet2>::default()
+        };
+
+        // Ensure the block locators are loaded once, and then served from the cache.
+        for _ in 0..10 {
+            BlockLocatorCache::get_or_load(&cache, block_hash, load).await;
+        }
+        assert_eq!(1, number_of_loads.load(Ordering::SeqCst));
+
+        // Ensure the serialized block locators of the same tip do not evict the block locators.
+        cache.write().await.insert(block_hash, EncodingFormat::Bincode, Bytes::from_static(b"block locators"));
+        assert!(cache.read().await.get_block_locators(&block_hash).is_some());
+
+        // Ensure a new chain tip loads the block locators again.
+        BlockLocatorCache::get_or_load(&cache, <Testnet2 as Network>::BlockHash::rand(rng), load).await;
+        assert_eq!(2, number_of_loads.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_misses_serialize_once() {
+        let rng = &mut thread_rng();
+        let block_hash = <Testnet2 as Network>::BlockHash::rand(rng);
+        let cache = Arc::new(RwLock::new(BlockLocatorCache::<Testnet2>::new()));
+        let number_of_serializations = Arc::new(AtomicUsize::new(0));
+
+        // Serve the block locators to 20 peers at once, counting each serialization of the block locators.
+        let serve = |block_hash| {
+            let (cache, number_of_serializations) = (cache.clone(), number_of_serializations.clone());
+            tokio::spawn(async move {
+                let serialize = || async {
+                    number_of_serializations.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                    Ok(Bytes::from_static(b"block locators"))
+                };
+                BlockLocatorCache::get_or_serialize(&cache, block_hash, EncodingFormat::Bincode, serialize).await
+            })
+        };
+        let responses = futures::future::join_all((0..20).map(|_| serve(block_hash))).await;
+
+        // Ensure the block locators are serialized once, and served to every peer.
+        assert_eq!(1, number_of_serializations.load(Ordering::SeqCst));
+        for response in responses {
+            assert_eq!(Bytes::from_static(b"block locators"), response.unwrap().unwrap());
+        }
+
+        // Ensure a new chain tip serializes the block locators again.
+        serve(<Testnet2 as Network>::BlockHash::rand(rng)).await.unwrap().unwrap();
+        assert_eq!(2, number_of_serializations.load(Ordering::SeqCst));
+    }
+}
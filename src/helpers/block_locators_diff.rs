@@ -0,0 +1,189 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use snarkos_storage::{BlockLocators, MAXIMUM_BLOCK_LOCATORS};
+use snarkvm::dpc::prelude::*;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The block hash of each block locator, and whether the block locator includes its block header.
+pub type BlockLocatorHashes<N> = BTreeMap<u32, (<N as Network>::BlockHash, bool)>;
+
+///
+/// The changes between two sets of block locators, sent in place of the full block locators in a `Pong`.
+///
+/// Consecutive block locators of a stable chain are largely identical, so once a peer holds the
+/// block locators of this node, only the removed and changed block locators are sent. The diff
+/// identifies its base by the chain tip, and can only be applied to the block locators of that tip.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct BlockLocatorsDiff<N: Network> {
+    /// The block height and block hash of the tip of the base block locators.
+    base_tip: (u32, N::BlockHash),
+    /// The block heights of the base block locators that are removed.
+    removed: Vec<u32>,
+    /// The block locators that are new or changed, relative to the base block locators.
+    changed: Vec<(u32, N::BlockHash, Option<BlockHeader<N>>)>,
+}
+
+impl<N: Network> BlockLocatorsDiff<N> {
+    ///
+    /// Returns the diff from the given base block locators to the given block locators,
+    /// or `None` if the base is empty or the diff would not be smaller than the block locators.
+    ///
+    pub fn new(base: &BlockLocatorHashes<N>, block_locators: &BlockLocators<N>) -> Option<Self> {
+        let base_tip = base.iter().next_back().map(|(height, (hash, _))| (*height, *hash))?;
+
+        let removed = base
+            .keys()
+            .filter(|height| !block_locators.contains_key(height))
+            .copied()
+            .collect::<Vec<_>>();
+        let changed = block_locators
+            .iter()
+            .filter(|(height, (hash, header))| base.get(height) != Some(&(*hash, header.is_some())))
+            .map(|(height, (hash, header))| (*height, *hash, header.clone()))
+            .collect::<Vec<_>>();
+
+        match changed.len() < block_locators.len() {
+            true => Some(Self {
+                base_tip,
+                removed,
+                changed,
+            }),
+            false => None,
+        }
+    }
+
+    /// Returns the block hash of each of the given block locators, to serve as the base of a subsequent diff.
+    pub fn hashes(block_locators: &BlockLocators<N>) -> BlockLocatorHashes<N> {
+        block_locators
+            .iter()
+            .map(|(height, (hash, header))| (*height, (*hash, header.is_some())))
+            .collect()
+    }
+
+    /// Returns the number of removed and changed block locators in the diff.
+    pub fn len(&self) -> usize {
+        self.removed.len() + self.changed.len()
+    }
+
+    /// Returns `true` if the diff contains no changes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    ///
+    /// Applies the diff to the given base block locators, returning the updated block locators.
+    /// Returns an error if the base block locators are not the ones the diff was computed against.
+    ///
+    pub fn apply(self, base: &BlockLocators<N>) -> Result<BlockLocators<N>> {
+        // Ensure the diff is within bounds, before applying it.
+        if self.len() > 2 * MAXIMUM_BLOCK_LOCATORS as usize {
+            return Err(anyhow!("Invalid number of changes in the block locators diff: {}", self.len()));
+        }
+        // Ensure the diff was computed against the given base block locators.
+        let base_tip = base.iter().next_back().map(|(height, (hash, _))| (*height, *hash));
+        if base_tip != Some(self.base_tip) {
+            return Err(anyhow!("The block locators diff does not apply to tip {:?}", base_tip));
+        }
+
+        let mut block_locators = (**base).clone();
+        for height in self.removed {
+            block_locators.remove(&height);
+        }
+        for (height, hash, header) in self.changed {
+            block_locators.insert(height, (hash, header));
+        }
+        BlockLocators::from(block_locators)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{dpc::testnet2::Testnet2, prelude::UniformRand};
+
+    use rand::thread_rng;
+
+    type BlockHash = <Testnet2 as Network>::BlockHash;
+
+    /// Returns block locators for the given block heights and hashes, without block headers.
+    fn block_locators(locators: &[(u32, BlockHash)]) -> BlockLocators<Testnet2> {
+        BlockLocators::from(locators.iter().map(|(height, hash)| (*height, (*hash, None))).collect()).unwrap()
+    }
+
+    #[test]
+    fn test_block_locators_diff() {
+        let rng = &mut thread_rng();
+        let hashes = (0..5).map(|_| BlockHash::rand(rng)).collect::<Vec<_>>();
+
+        // The chain advances by one block, which drops the oldest block locator.
+        let base = block_locators(&[(0, hashes[0]), (1, hashes[1]), (2, hashes[2]), (3, hashes[3])]);
+        let expected = block_locators(&[(0, hashes[0]), (2, hashes[2]), (3, hashes[3]), (4, hashes[4])]);
+
+        // Ensure the diff only carries the changes.
+        let diff = BlockLocatorsDiff::new(&BlockLocatorsDiff::hashes(&base), &expected).unwrap();
+        assert_eq!(vec![1], diff.removed);
+        assert_eq!(vec![(4, hashes[4], None)], diff.changed);
+        assert_eq!(2, diff.len());
+
+        // Ensure the diff reconstructs the block locators.
+        assert_eq!(expected, diff.clone().apply(&base).unwrap());
+
+        // Ensure the diff survives serialization.
+        let bytes = bincode::serialize(&diff).unwrap();
+        assert_eq!(diff, bincode::deserialize(&bytes).unwrap());
+    }
+
+    #[test]
+    fn test_block_locators_diff_header_change() {
+        let rng = &mut thread_rng();
+        let genesis_hash = Testnet2::genesis_block().hash();
+        let genesis_header = Testnet2::genesis_block().header().clone();
+        let hash = BlockHash::rand(rng);
+
+        // Ensure a block locator that only gains its block header is included in the diff.
+        let base = block_locators(&[(0, genesis_hash), (1, hash)]);
+        let locators = vec![(0, (genesis_hash, Some(genesis_header))), (1, (hash, None))];
+        let expected = BlockLocators::from(locators.into_iter().collect()).unwrap();
+        let diff = BlockLocatorsDiff::new(&BlockLocatorsDiff::hashes(&base), &expected).unwrap();
+        assert_eq!(1, diff.len());
+        assert_eq!(expected, diff.apply(&base).unwrap());
+    }
+
+    #[test]
+    fn test_block_locators_diff_fallback() {
+        let rng = &mut thread_rng();
+        let hashes = (0..6).map(|_| BlockHash::rand(rng)).collect::<Vec<_>>();
+
+        // Ensure there is no diff without a base, or if every block locator changed.
+        let base = block_locators(&[(0, hashes[0]), (1, hashes[1])]);
+        let disjoint = block_locators(&[(2, hashes[2]), (3, hashes[3])]);
+        assert!(BlockLocatorsDiff::new(&Default::default(), &base).is_none());
+        assert!(BlockLocatorsDiff::new(&BlockLocatorsDiff::hashes(&base), &disjoint).is_none());
+
+        // Ensure a diff can not be applied to block locators of a different tip.
+        let updated = block_locators(&[(0, hashes[0]), (1, hashes[1]), (2, hashes[2])]);
+        let diff = BlockLocatorsDiff::new(&BlockLocatorsDiff::hashes(&base), &updated).unwrap();
+        let drifted = block_locators(&[(0, hashes[0]), (1, hashes[4])]);
+        assert!(diff.clone().apply(&drifted).is_err());
+        assert!(diff.apply(&base).is_ok());
+    }
+}
@@ -14,18 +14,33 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{network::ledger::PeersState, Environment};
+use crate::{
+    helpers::{compare_chains, ChainTip, NodeCapabilities, SyncCandidate, SyncPeerStats},
+    network::ledger::PeersState,
+    Environment,
+};
 use snarkos_storage::{BlockLocators, LedgerState};
 use snarkvm::dpc::prelude::*;
 
-use std::net::SocketAddr;
+use rand::thread_rng;
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    net::SocketAddr,
+};
 
 /// Checks if any of the peers are ahead and have a larger block height, if they are on a fork, and their block locators.
-/// The maximum known block height and cumulative weight are tracked for the purposes of further operations.
+/// The maximum known block height, cumulative weight, and block hash are tracked for the purposes of further operations.
+/// Peers with an equally heavy chain are compared by the fork choice rule in `compare_chains`.
+/// Among the peers that are ahead, the peer to sync from is selected by `E::SYNC_STRATEGY`, using the given peer stats.
+/// The given excluded peers are skipped, such as those that reported the next blocks as unavailable.
 pub fn find_maximal_peer<N: Network, E: Environment>(
     peers_state: &PeersState<N>,
+    peer_stats: &HashMap<SocketAddr, SyncPeerStats>,
+    excluded_peers: &HashSet<SocketAddr>,
     maximum_block_height: &mut u32,
     maximum_cumulative_weight: &mut u128,
+    maximum_block_hash: &mut N::BlockHash,
 ) -> Option<(SocketAddr, bool, BlockLocators<N>)> {
     // Determine if the peers state has any sync nodes.
     // TODO: have nodes sync up to tip - 4096 with only sync nodes, then switch to syncing with the longest chain.
@@ -34,30 +49,55 @@ pub fn find_maximal_peer<N: Network, E: Environment>(
     //     peers_contains_sync_node |= sync_nodes.contains(ip);
     // }
 
-    let mut maximal_peer = None;
+    let maximum_tip = ChainTip::new(*maximum_cumulative_weight, *maximum_block_height, *maximum_block_hash);
+    let mut candidates = Vec::new();
 
     for (peer_ip, peer_state) in peers_state.iter() {
+        if excluded_peers.contains(peer_ip) {
+            continue;
+        }
         // Only update the maximal peer if there are no sync nodes or the peer is a sync node.
         if !peers_contains_sync_node || E::sync_nodes().contains(peer_ip) {
             // Update the maximal peer state if the peer is ahead and the peer knows if you are a fork or not.
             // This accounts for (Case 1 and Case 2(a))
-            if let Some((_, _, is_on_fork, block_height, block_locators)) = peer_state {
+            if let Some((_, capabilities, _, is_on_fork, block_height, block_locators)) = peer_state {
+                // Only request blocks from peers that store the full chain and serve block requests.
+                if !capabilities.contains(NodeCapabilities::STORES_FULL_CHAIN | NodeCapabilities::SERVES_BLOCK_REQUESTS) {
+                    continue;
+                }
                 // Retrieve the cumulative weight, defaulting to the block height if it does not exist.
                 let cumulative_weight = match block_locators.get_cumulative_weight(*block_height) {
                     Some(cumulative_weight) => cumulative_weight,
                     None => *block_height as u128,
                 };
-                // If the cumulative weight is more, set this peer as the maximal peer.
-                if cumulative_weight > *maximum_cumulative_weight && is_on_fork.is_some() {
-                    maximal_peer = Some((*peer_ip, is_on_fork.unwrap(), block_locators.clone()));
-                    *maximum_block_height = *block_height;
-                    *maximum_cumulative_weight = cumulative_weight;
+                // Retrieve the block hash of the tip of the peer, which the block locators always include.
+                let block_hash = match block_locators.get_block_hash(*block_height) {
+                    Some(block_hash) => block_hash,
+                    None => continue,
+                };
+                // If the chain of the peer is preferred, add this peer as a candidate to sync from.
+                let peer_tip = ChainTip::new(cumulative_weight, *block_height, block_hash);
+                if compare_chains(&peer_tip, &maximum_tip) == Ordering::Greater && is_on_fork.is_some() {
+                    candidates.push(SyncCandidate {
+                        peer_ip: *peer_ip,
+                        tip: peer_tip,
+                        stats: peer_stats.get(peer_ip).cloned().unwrap_or_default(),
+                    });
                 }
             }
         }
     }
 
-    maximal_peer
+    // Select the peer to sync from, and set it as the maximal peer.
+    let candidate = E::SYNC_STRATEGY.select(&candidates, &mut thread_rng())?;
+    *maximum_block_height = candidate.tip.block_height;
+    *maximum_cumulative_weight = candidate.tip.cumulative_weight;
+    *maximum_block_hash = candidate.tip.block_hash;
+
+    match peers_state.get(&candidate.peer_ip) {
+        Some(Some((_, _, _, Some(is_on_fork), _, block_locators))) => Some((candidate.peer_ip, *is_on_fork, block_locators.clone())),
+        _ => None,
+    }
 }
 
 /// Returns the common ancestor and the first deviating locator (if it exists),
@@ -177,8 +217,9 @@ pub(crate) fn handle_block_requests<N: Network, E: Environment>(
         return BlockRequestHandler::Abort(Case::Zero);
     }
 
-    // Case 1 - Ensure the peer has a heavier canonical chain than this ledger.
-    if latest_cumulative_weight >= maximum_cumulative_weight {
+    // Case 1 - Ensure the peer does not have a lighter canonical chain than this ledger.
+    // An equally heavy chain is only selected by `find_maximal_peer` if it wins the fork choice tie-break.
+    if latest_cumulative_weight > maximum_cumulative_weight {
         return BlockRequestHandler::Abort(Case::One);
     }
 
@@ -240,6 +281,38 @@ pub(crate) fn handle_block_requests<N: Network, E: Environment>(
     })
 }
 
+///
+/// Returns the start and end block heights of the next batch of block requests to pipeline,
+/// given the last requested block height and the number of outstanding block requests.
+/// Returns `None` if the pipeline is full or the peer has no further blocks to request.
+///
+pub(crate) fn pipeline_block_requests<E: Environment>(
+    start_block_height: u32,
+    last_requested_block_height: u32,
+    maximum_block_height: u32,
+    number_of_block_requests: usize,
+) -> Option<(u32, u32)> {
+    // Ensure the number of outstanding blocks is bounded by the pipeline depth.
+    let maximum_number_of_block_requests = E::SYNC_PIPELINE_DEPTH.saturating_mul(E::MAXIMUM_BLOCK_REQUEST) as usize;
+    if number_of_block_requests.saturating_add(E::MAXIMUM_BLOCK_REQUEST as usize) > maximum_number_of_block_requests {
+        return None;
+    }
+
+    // Ensure there are further blocks to request from the peer.
+    if last_requested_block_height >= maximum_block_height {
+        return None;
+    }
+
+    // Request the blocks immediately following the last requested block.
+    let start_block_height = std::cmp::max(start_block_height, last_requested_block_height + 1);
+    let end_block_height = std::cmp::min(
+        start_block_height.saturating_add(E::MAXIMUM_BLOCK_REQUEST - 1),
+        maximum_block_height,
+    );
+
+    Some((start_block_height, end_block_height))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -622,4 +695,38 @@ mod tests {
             assert_eq!(result, BlockRequestHandler::Abort(Case::TwoCC));
         }
     }
+
+    #[test]
+    fn test_pipeline_block_requests() {
+        type E = Client<Testnet2>;
+
+        let rng = &mut thread_rng();
+        let batch = E::MAXIMUM_BLOCK_REQUEST;
+        let capacity = (E::SYNC_PIPELINE_DEPTH * batch) as usize;
+
+        for _ in 0..ITERATIONS {
+            let start_block_height: u32 = rng.gen_range(1..100_000);
+            let last_requested_block_height = start_block_height + batch - 1;
+            let maximum_block_height = last_requested_block_height + rng.gen_range(1..batch * 4);
+
+            // The next batch begins immediately after the last requested block.
+            let (start, end) =
+                pipeline_block_requests::<E>(start_block_height, last_requested_block_height, maximum_block_height, batch as usize).unwrap();
+            assert_eq!(last_requested_block_height + 1, start);
+            assert_eq!(std::cmp::min(start + batch - 1, maximum_block_height), end);
+            assert!(start <= end);
+
+            // The pipeline does not exceed its capacity.
+            assert_eq!(
+                None,
+                pipeline_block_requests::<E>(start_block_height, last_requested_block_height, maximum_block_height, capacity)
+            );
+
+            // No blocks are requested beyond the maximum block height.
+            assert_eq!(
+                None,
+                pipeline_block_requests::<E>(start_block_height, maximum_block_height, maximum_block_height, 0)
+            );
+        }
+    }
 }
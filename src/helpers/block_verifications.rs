@@ -0,0 +1,144 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+///
+/// A limiter on the number of full block verifications that may be performed simultaneously.
+///
+/// Blocks on the canonical sync path (`BlockResponse`) always wait for a verification slot,
+/// whereas relayed gossip blocks (`UnconfirmedBlock`) are only admitted up to a bounded queue.
+/// Gossip blocks may never occupy every slot, so that one slot is always reserved for the sync path.
+/// As such, there are at least two slots, one of which is available to gossip blocks.
+///
+#[derive(Debug)]
+pub struct BlockVerifications {
+    /// The slots for all in-flight block verifications.
+    verifications: Semaphore,
+    /// The slots for in-flight gossip block verifications, one fewer than `verifications`.
+    gossip_verifications: Semaphore,
+    /// The slots for in-flight and queued gossip block verifications.
+    gossip_queue: Semaphore,
+}
+
+impl BlockVerifications {
+    /// Initializes a new instance of `BlockVerifications`.
+    pub fn new(maximum_verifications: usize, maximum_queued_gossip: usize) -> Self {
+        // Ensure one slot is reserved for the sync path, in addition to a slot for gossip blocks.
+        let maximum_verifications = maximum_verifications.max(2);
+        Self {
+            verifications: Semaphore::new(maximum_verifications),
+            gossip_verifications: Semaphore::new(maximum_verifications - 1),
+            gossip_queue: Semaphore::new(maximum_verifications + maximum_queued_gossip),
+        }
+    }
+
+    ///
+    /// Waits for a verification slot for a block on the canonical sync path.
+    ///
+    pub async fn acquire_for_sync(&self) -> BlockVerificationPermit<'_> {
+        let verification = self.verifications.acquire().await;
+        BlockVerificationPermit {
+            _verification: verification.expect("The block verifications semaphore is never closed"),
+            _gossip_verification: None,
+            _gossip_queue: None,
+        }
+    }
+
+    ///
+    /// Waits for a verification slot for a relayed gossip block.
+    /// Returns `None` if the gossip queue is full, in which case the block should be dropped.
+    ///
+    pub async fn acquire_for_gossip(&self) -> Option<BlockVerificationPermit<'_>> {
+        let gossip_queue = self.gossip_queue.try_acquire().ok()?;
+        let gossip_verification = self.gossip_verifications.acquire().await.ok()?;
+        let verification = self.verifications.acquire().await.ok()?;
+        Some(BlockVerificationPermit {
+            _verification: verification,
+            _gossip_verification: Some(gossip_verification),
+            _gossip_queue: Some(gossip_queue),
+        })
+    }
+
+    /// Returns the number of verification slots that are currently available.
+    pub fn available(&self) -> usize {
+        self.verifications.available_permits()
+    }
+}
+
+///
+/// A verification slot, which is released when dropped.
+///
+#[derive(Debug)]
+pub struct BlockVerificationPermit<'a> {
+    _verification: SemaphorePermit<'a>,
+    _gossip_verification: Option<SemaphorePermit<'a>>,
+    _gossip_queue: Option<SemaphorePermit<'a>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_gossip_queue_is_bounded() {
+        let verifications = Arc::new(BlockVerifications::new(2, 1));
+
+        // Only one gossip block may be verified at a time, leaving a slot for the sync path.
+        let first = verifications.acquire_for_gossip().await;
+        assert!(first.is_some());
+        assert_eq!(1, verifications.available());
+
+        // The sync path is not blocked by in-flight gossip verifications.
+        let sync = verifications.acquire_for_sync().await;
+        assert_eq!(0, verifications.available());
+        drop(sync);
+
+        // Fill the remaining gossip queue slots with waiting verifications.
+        let mut waiting = Vec::new();
+        for _ in 0..2 {
+            let verifications = verifications.clone();
+            waiting.push(tokio::spawn(async move { verifications.acquire_for_gossip().await.is_some() }));
+            tokio::task::yield_now().await;
+        }
+
+        // Excess gossip blocks are dropped.
+        assert!(verifications.acquire_for_gossip().await.is_none());
+
+        // Once the in-flight verification completes, the queued ones proceed.
+        drop(first);
+        for handle in waiting {
+            assert!(handle.await.unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sync_slot_is_reserved() {
+        let verifications = BlockVerifications::new(1, 1);
+
+        // Ensure a gossip block does not take the only slot, leaving a slot for the sync path.
+        let gossip = verifications.acquire_for_gossip().await;
+        assert!(gossip.is_some());
+        assert_eq!(1, verifications.available());
+
+        // Ensure the sync path still acquires its reserved slot.
+        let sync = tokio::time::timeout(std::time::Duration::from_secs(1), verifications.acquire_for_sync()).await;
+        assert!(sync.is_ok());
+        assert_eq!(0, verifications.available());
+    }
+}
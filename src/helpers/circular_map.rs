@@ -66,6 +66,13 @@ impl<K: Clone + PartialEq + Eq + Hash, V: Clone, const N: u32> CircularMap<K, V,
         self.map.get(key)
     }
 
+    ///
+    /// Returns an iterator over the values of the circular map, in arbitrary order.
+    ///
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.map.values()
+    }
+
     ///
     /// Inserts the given key-value pair into the circular map, returning a `bool`
     /// indicating whether the insertion took place.
@@ -97,6 +104,21 @@ impl<K: Clone + PartialEq + Eq + Hash, V: Clone, const N: u32> CircularMap<K, V,
         }
     }
 
+    ///
+    /// Retains only the key-value pairs for which the given predicate returns `true`.
+    ///
+    pub fn retain<F: Fn(&K, &V) -> bool>(&mut self, keep: F) {
+        self.map.retain(|key, value| keep(key, value));
+        // Rebuild the queue from the remaining keys, preserving their insertion order.
+        let mut queue = CircularQueue::with_capacity(N as usize);
+        for key in self.queue.asc_iter().flatten() {
+            if self.map.contains_key(key) {
+                queue.push(Some(key.clone()));
+            }
+        }
+        self.queue = queue;
+    }
+
     ///
     /// Removes all the entries from the circular map.
     ///
@@ -111,3 +133,194 @@ impl<K: Clone + PartialEq + Eq + Hash, V: Clone, const N: u32> Default for Circu
         Self::new()
     }
 }
+
+///
+/// A helper struct to maintain a bounded number of elements in a map, stored inline in a fixed-size array.
+///
+/// Unlike `CircularMap`, no heap allocation is made, and lookups scan the entries,
+/// so it is intended for small capacities on latency-sensitive paths.
+///
+#[derive(Clone, Debug)]
+pub struct ConstCircularMap<K: Clone + PartialEq, V: Clone, const CAP: usize> {
+    entries: [Option<(K, V)>; CAP],
+    /// The index of the next entry to be written, which holds the oldest entry once the map is full.
+    /// The entries are kept contiguous, from the oldest entry up to the head, so the slot at the head is free until the map is full.
+    head: usize,
+    len: usize,
+}
+
+impl<K: Clone + PartialEq, V: Clone, const CAP: usize> ConstCircularMap<K, V, CAP> {
+    /// Fails the compilation of any instantiation with a capacity of zero.
+    const NON_ZERO_CAPACITY: () = assert!(CAP > 0, "The capacity of a 'ConstCircularMap' must be greater than 0");
+
+    ///
+    /// Initializes a new instance of a circular map, of pre-defined size.
+    ///
+    pub fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::NON_ZERO_CAPACITY;
+
+        Self {
+            entries: [(); CAP].map(|_| None),
+            head: 0,
+            len: 0,
+        }
+    }
+
+    ///
+    /// Returns `true` if the circular map is empty.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    ///
+    /// Returns the number of key-value pairs in the circular map.
+    ///
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    ///
+    /// Returns `true` if the given key exists in the circular map.
+    ///
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    ///
+    /// Returns the value for the given key from the map, if it exists.
+    ///
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.entries.iter().flatten().find(|(k, _)| k == key).map(|(_, value)| value)
+    }
+
+    ///
+    /// Inserts the given key-value pair into the circular map, returning a `bool`
+    /// indicating whether the insertion took place.
+    ///
+    pub fn insert(&mut self, key: K, value: V) -> bool {
+        if !self.contains_key(&key) {
+            // Overwrite the oldest entry, if the map is full.
+            if self.entries[self.head].replace((key, value)).is_none() {
+                self.len += 1;
+            }
+            self.head = (self.head + 1) % CAP;
+
+            true
+        } else {
+            false
+        }
+    }
+
+    ///
+    /// Removes the key-value pair for the given key from the circular map.
+    /// The newer entries are shifted back by one slot, so the freed slot is the next one to be written.
+    ///
+    pub fn remove(&mut self, key: &K) {
+        // The index of the oldest entry.
+        let start = (self.head + CAP - self.len) % CAP;
+        let position = (0..self.len).find(|offset| matches!(&self.entries[(start + offset) % CAP], Some((k, _)) if k == key));
+        if let Some(position) = position {
+            for offset in position..self.len - 1 {
+                self.entries[(start + offset) % CAP] = self.entries[(start + offset + 1) % CAP].take();
+            }
+            self.entries[(start + self.len - 1) % CAP] = None;
+            self.head = (self.head + CAP - 1) % CAP;
+            self.len -= 1;
+        }
+    }
+}
+
+impl<K: Clone + PartialEq, V: Clone, const CAP: usize> Default for ConstCircularMap<K, V, CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retain() {
+        let mut map = CircularMap::<u32, u32, 16>::new();
+        for i in 0..10 {
+            assert!(map.insert(i, i * 3));
+        }
+        assert_eq!(10, map.len());
+
+        // Keep only the entries with even-valued data.
+        map.retain(|_, value| value % 2 == 0);
+        assert_eq!(5, map.len());
+        for i in 0..10 {
+            assert_eq!(i % 2 == 0, map.contains_key(&i));
+        }
+
+        // Ensure no stale queue entries evict the remaining entries.
+        for i in 10..21 {
+            assert!(map.insert(i, i));
+        }
+        assert_eq!(16, map.len());
+        for i in (0..10).step_by(2) {
+            assert!(map.contains_key(&i));
+        }
+    }
+
+    #[test]
+    fn test_const_circular_map() {
+        let mut map = ConstCircularMap::<u32, u32, 5>::new();
+        assert!(map.is_empty());
+        for i in 0..6 {
+            assert!(map.insert(i, i * 3));
+        }
+
+        // Ensure only the 5 newest entries remain, with the oldest evicted.
+        assert_eq!(5, map.len());
+        assert!(!map.contains_key(&0));
+        for i in 1..6 {
+            assert_eq!(Some(&(i * 3)), map.get(&i));
+        }
+
+        // Ensure an existing key is not inserted twice.
+        assert!(!map.insert(3, 0));
+        assert_eq!(Some(&9), map.get(&3));
+
+        // Ensure a removed entry frees its slot, without evicting the other entries.
+        map.remove(&1);
+        assert_eq!(4, map.len());
+        assert!(map.get(&1).is_none());
+        assert!(map.insert(6, 18));
+        assert_eq!(5, map.len());
+        assert!(map.insert(7, 21));
+        assert_eq!(5, map.len());
+        assert!(!map.contains_key(&2));
+    }
+
+    #[test]
+    fn test_const_circular_map_remove_before_full() {
+        let mut map = ConstCircularMap::<u32, u32, 5>::new();
+        for i in 0..3 {
+            assert!(map.insert(i, i));
+        }
+
+        // Remove an entry that is not in the slot at the head, while the map is not full.
+        map.remove(&1);
+        assert_eq!(2, map.len());
+
+        // Ensure the map fills up to its capacity, without evicting any live entries.
+        for i in 3..6 {
+            assert!(map.insert(i, i));
+        }
+        assert_eq!(5, map.len());
+        for i in [0, 2, 3, 4, 5] {
+            assert_eq!(Some(&i), map.get(&i));
+        }
+
+        // Ensure the oldest entry is evicted first, once the map is full.
+        assert!(map.insert(6, 6));
+        assert_eq!(5, map.len());
+        assert!(!map.contains_key(&0));
+        assert!(map.contains_key(&2));
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use snarkvm::dpc::prelude::*;
+
+///
+/// Returns `true` if the PoSW proof in the given block header meets the given expected difficulty target.
+///
+/// The expected difficulty target must be computed from the ledger of this node, and not taken from the header,
+/// as a peer could otherwise declare an arbitrarily easy difficulty target along with its proof.
+///
+/// This check only hashes the proof to compute its difficulty, and does not verify the proof itself.
+/// It is intended as a cheap filter, to discard blocks that were never mined prior to full verification.
+///
+pub fn verify_difficulty_only<N: Network>(header: &BlockHeader<N>, expected_difficulty_target: u64) -> bool {
+    match header.proof().to_proof_difficulty() {
+        Ok(proof_difficulty) => proof_difficulty <= expected_difficulty_target,
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::dpc::testnet2::Testnet2;
+
+    #[test]
+    fn test_verify_difficulty_only() {
+        // Ensure the genesis block header meets its difficulty target.
+        let genesis_header = Testnet2::genesis_block().header();
+        assert!(verify_difficulty_only(genesis_header, genesis_header.difficulty_target()));
+        assert!(genesis_header.is_valid());
+    }
+
+    #[test]
+    fn test_verify_difficulty_only_with_inflated_target() {
+        let genesis_header = Testnet2::genesis_block().header();
+        let proof_difficulty = genesis_header.proof().to_proof_difficulty().unwrap();
+
+        // Ensure a proof is rejected if it only meets the (easier) difficulty target that its header declares,
+        // and not the expected difficulty target.
+        assert!(proof_difficulty <= genesis_header.difficulty_target());
+        assert!(!verify_difficulty_only(genesis_header, proof_difficulty - 1));
+        assert!(verify_difficulty_only(genesis_header, proof_difficulty));
+    }
+}
@@ -0,0 +1,185 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::helpers::NodeCapabilities;
+
+use anyhow::Result;
+use serde::{de::DeserializeOwned, Serialize};
+use std::io::Write;
+
+///
+/// A serialization backend for the payloads of network messages.
+///
+pub trait Encoding {
+    /// Serializes the given value into bytes.
+    fn serialize<T: Serialize>(value: &T) -> Result<Vec<u8>>;
+
+    /// Serializes the given value into the given writer.
+    fn serialize_into<W: Write, T: Serialize>(writer: &mut W, value: &T) -> Result<()> {
+        Ok(writer.write_all(&Self::serialize(value)?)?)
+    }
+
+    /// Deserializes a value from the given bytes.
+    fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T>;
+}
+
+/// The bincode serialization backend, which is supported by every node.
+#[derive(Copy, Clone, Debug)]
+pub struct Bincode;
+
+impl Encoding for Bincode {
+    fn serialize<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+        Ok(bincode::serialize(value)?)
+    }
+
+    fn serialize_into<W: Write, T: Serialize>(writer: &mut W, value: &T) -> Result<()> {
+        Ok(bincode::serialize_into(writer, value)?)
+    }
+
+    fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+        Ok(bincode::deserialize(bytes)?)
+    }
+}
+
+/// The postcard serialization backend, which produces smaller encodings using variable-length integers.
+#[cfg(feature = "postcard")]
+#[derive(Copy, Clone, Debug)]
+pub struct Postcard;
+
+#[cfg(feature = "postcard")]
+impl Encoding for Postcard {
+    fn serialize<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+        Ok(postcard::to_stdvec(value)?)
+    }
+
+    fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+        Ok(postcard::from_bytes(bytes)?)
+    }
+}
+
+///
+/// The encoding of the message payloads on a connection, as negotiated during the handshake.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum EncodingFormat {
+    Bincode,
+    #[cfg(feature = "postcard")]
+    Postcard,
+}
+
+impl EncodingFormat {
+    ///
+    /// Returns the encoding format for a connection, given the capabilities of this node and of the peer.
+    /// The postcard encoding is only used if both nodes support it, and bincode is used otherwise.
+    ///
+    pub fn negotiate(local: NodeCapabilities, peer: NodeCapabilities) -> Self {
+        #[cfg(feature = "postcard")]
+        if local.contains(NodeCapabilities::ENCODES_POSTCARD) && peer.contains(NodeCapabilities::ENCODES_POSTCARD) {
+            return Self::Postcard;
+        }
+        let _ = (local, peer);
+        Self::Bincode
+    }
+
+    /// Serializes the given value into bytes.
+    pub fn serialize<T: Serialize>(&self, value: &T) -> Result<Vec<u8>> {
+        match self {
+            Self::Bincode => Bincode::serialize(value),
+            #[cfg(feature = "postcard")]
+            Self::Postcard => Postcard::serialize(value),
+        }
+    }
+
+    /// Serializes the given value into the given writer.
+    pub fn serialize_into<W: Write, T: Serialize>(&self, writer: &mut W, value: &T) -> Result<()> {
+        match self {
+            Self::Bincode => Bincode::serialize_into(writer, value),
+            #[cfg(feature = "postcard")]
+            Self::Postcard => Postcard::serialize_into(writer, value),
+        }
+    }
+
+    /// Deserializes a value from the given bytes.
+    pub fn deserialize<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T> {
+        match self {
+            Self::Bincode => Bincode::deserialize(bytes),
+            #[cfg(feature = "postcard")]
+            Self::Postcard => Postcard::deserialize(bytes),
+        }
+    }
+}
+
+impl Default for EncodingFormat {
+    fn default() -> Self {
+        Self::Bincode
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::NodeType;
+    use snarkvm::dpc::{prelude::*, testnet2::Testnet2};
+
+    fn check_round_trip<F: Encoding>() {
+        let block = Testnet2::genesis_block();
+
+        // Ensure a block round-trips.
+        let bytes = F::serialize(block).unwrap();
+        assert_eq!(*block, F::deserialize::<Block<Testnet2>>(&bytes).unwrap());
+
+        // Ensure serializing into a writer produces the same bytes.
+        let mut buffer = Vec::new();
+        F::serialize_into(&mut buffer, block.header()).unwrap();
+        assert_eq!(*block.header(), F::deserialize::<BlockHeader<Testnet2>>(&buffer).unwrap());
+        assert_eq!(F::serialize(block.header()).unwrap(), buffer);
+
+        // Ensure truncated bytes are rejected.
+        assert!(F::deserialize::<Block<Testnet2>>(&bytes[..bytes.len() / 2]).is_err());
+    }
+
+    #[test]
+    fn test_bincode_round_trip() {
+        check_round_trip::<Bincode>();
+    }
+
+    #[cfg(feature = "postcard")]
+    #[test]
+    fn test_postcard_round_trip() {
+        check_round_trip::<Postcard>();
+
+        // Ensure the postcard encoding is smaller than the bincode one.
+        let block = Testnet2::genesis_block();
+        assert!(Postcard::serialize(block).unwrap().len() < Bincode::serialize(block).unwrap().len());
+    }
+
+    #[test]
+    fn test_encoding_negotiation() {
+        let local = NodeCapabilities::from(NodeType::Client);
+
+        // Ensure bincode is used with a peer that does not support postcard.
+        let mut peer = NodeCapabilities::from(NodeType::Client);
+        peer.remove(NodeCapabilities::ENCODES_POSTCARD);
+        assert_eq!(EncodingFormat::Bincode, EncodingFormat::negotiate(local, peer));
+
+        // Ensure postcard is used only if both nodes support it.
+        peer.insert(NodeCapabilities::ENCODES_POSTCARD);
+        #[cfg(feature = "postcard")]
+        assert_eq!(EncodingFormat::Postcard, EncodingFormat::negotiate(local, peer));
+        #[cfg(not(feature = "postcard"))]
+        assert_eq!(EncodingFormat::Bincode, EncodingFormat::negotiate(local, peer));
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{helpers::NodeType, ConnectionDirection};
+use snarkvm::dpc::Network;
+
+use std::{net::SocketAddr, time::Duration};
+use tokio::sync::broadcast;
+
+///
+/// An event that the node emits to its subscribers on the event channel.
+///
+/// Events are emitted on a single channel, so every subscriber observes them in the order
+/// they were emitted. For a given transaction, the following ordering guarantees hold:
+/// - `TransactionAccepted` is emitted once the transaction enters the memory pool of the node.
+///   A transaction may be confirmed without ever being accepted, if it was only seen in a block.
+///   As the memory pool and the ledger are updated concurrently, `TransactionAccepted` may be
+///   emitted after `TransactionConfirmed`, if the transaction arrives as its block is added.
+/// - `TransactionConfirmed` is emitted once its block is added to the canonical chain.
+/// - `TransactionOrphaned` is emitted once a reorg removes its block from the canonical chain,
+///   and always follows the `TransactionConfirmed` event for that block. It precedes any
+///   `TransactionConfirmed` event for the transaction from the blocks of the new chain.
+///
+/// For a given peer, the following ordering guarantees hold:
+/// - `PeerConnected` is emitted once the handshake with the peer succeeds, and not for a connection
+///   that fails beforehand. The listener of the peer may still be unverified at this point.
+/// - `PeerDisconnected` is emitted once the peer is removed from the connected peers, for any reason,
+///   and always follows the `PeerConnected` event for that connection. A recycled connection emits a
+///   `PeerDisconnected`, followed by a new `PeerConnected` once the peer is reconnected.
+/// - `SlowPeer` is emitted while the peer is connected, once its round-trip time has stayed above
+///   `E::SLOW_PEER_RTT_THRESHOLD_IN_MILLIS` for `E::SLOW_PEER_WINDOW_IN_SECS`, with its latest round-trip time.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event<N: Network> {
+    /// TransactionAccepted := (transaction_id)
+    TransactionAccepted(N::TransactionID),
+    /// TransactionConfirmed := (transaction_id, block_height)
+    TransactionConfirmed(N::TransactionID, u32),
+    /// TransactionOrphaned := (transaction_id)
+    TransactionOrphaned(N::TransactionID),
+    /// PeerConnected := (peer_ip, version, node_type, direction, cumulative_weight)
+    PeerConnected(SocketAddr, u32, NodeType, ConnectionDirection, u128),
+    /// PeerDisconnected := (peer_ip)
+    PeerDisconnected(SocketAddr),
+    /// SlowPeer := (peer_ip, rtt)
+    SlowPeer(SocketAddr, Duration),
+}
+
+///
+/// A handle to the event channel of the node.
+///
+#[derive(Clone, Debug)]
+pub struct Events<N: Network> {
+    sender: broadcast::Sender<Event<N>>,
+}
+
+impl<N: Network> Events<N> {
+    ///
+    /// Initializes a new event channel, that buffers up to `capacity` events for each subscriber.
+    /// A subscriber that falls further behind misses the oldest events.
+    ///
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    ///
+    /// Returns a new subscription to the events emitted from this point onwards.
+    ///
+    pub fn subscribe(&self) -> broadcast::Receiver<Event<N>> {
+        self.sender.subscribe()
+    }
+
+    ///
+    /// Emits the given event to the current subscribers.
+    ///
+    pub fn emit(&self, event: Event<N>) {
+        // Emitting fails only if there are no subscribers, in which case the event is discarded.
+        let _ = self.sender.send(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{dpc::testnet2::Testnet2, prelude::UniformRand};
+
+    use rand::thread_rng;
+
+    #[tokio::test]
+    async fn test_events() {
+        let rng = &mut thread_rng();
+        let events = Events::<Testnet2>::new(16);
+
+        // Ensure emitting without subscribers is not an error.
+        let transaction_id = <Testnet2 as Network>::TransactionID::rand(rng);
+        events.emit(Event::TransactionAccepted(transaction_id));
+
+        // Ensure a subscriber observes the subsequent events, in order.
+        let mut subscriber = events.subscribe();
+        events.emit(Event::TransactionConfirmed(transaction_id, 1));
+        events.emit(Event::TransactionOrphaned(transaction_id));
+        assert_eq!(Event::TransactionConfirmed(transaction_id, 1), subscriber.recv().await.unwrap());
+        assert_eq!(Event::TransactionOrphaned(transaction_id), subscriber.recv().await.unwrap());
+    }
+}
@@ -0,0 +1,178 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+///
+/// A helper struct to buffer elements per key, and to dequeue them in a round-robin order
+/// across the keys, so that a single key can not monopolize the processing of the elements.
+///
+#[derive(Clone, Debug)]
+pub struct FairQueue<K: Clone + Eq + Hash, V> {
+    /// The buffered elements of each key, in insertion order.
+    buffers: HashMap<K, VecDeque<V>>,
+    /// The keys with buffered elements, in the order they are served.
+    order: VecDeque<K>,
+    /// The maximum number of elements that may be buffered for each key.
+    capacity_per_key: usize,
+}
+
+impl<K: Clone + Eq + Hash, V> FairQueue<K, V> {
+    ///
+    /// Initializes a new instance of a fair queue, with the given capacity per key.
+    ///
+    pub fn new(capacity_per_key: usize) -> Self {
+        Self {
+            buffers: Default::default(),
+            order: Default::default(),
+            capacity_per_key,
+        }
+    }
+
+    ///
+    /// Returns `true` if the fair queue is empty.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    ///
+    /// Returns the number of elements buffered for the given key.
+    ///
+    pub fn len_of(&self, key: &K) -> usize {
+        self.buffers.get(key).map(|buffer| buffer.len()).unwrap_or(0)
+    }
+
+    ///
+    /// Buffers the given element for the given key, returning the element back
+    /// if the buffer of the key is full.
+    ///
+    pub fn push(&mut self, key: K, value: V) -> Result<(), V> {
+        let capacity = self.capacity_per_key;
+        self.push_with_capacity(key, value, capacity)
+    }
+
+    ///
+    /// Buffers the given element for the given key, returning the element back
+    /// if the buffer of the key holds the given capacity, instead of the capacity per key.
+    ///
+    pub fn push_with_capacity(&mut self, key: K, value: V, capacity: usize) -> Result<(), V> {
+        let buffer = self.buffers.entry(key.clone()).or_default();
+        if buffer.len() >= capacity {
+            return Err(value);
+        }
+        if buffer.is_empty() {
+            self.order.push_back(key);
+        }
+        buffer.push_back(value);
+        Ok(())
+    }
+
+    ///
+    /// Removes and returns the next element, serving each key with buffered elements in turn.
+    ///
+    pub fn pop(&mut self) -> Option<V> {
+        let key = self.order.pop_front()?;
+        let buffer = self.buffers.get_mut(&key)?;
+        let value = buffer.pop_front();
+        if buffer.is_empty() {
+            self.buffers.remove(&key);
+        } else {
+            self.order.push_back(key);
+        }
+        value
+    }
+
+    ///
+    /// Removes the buffered elements for the given key that match the given predicate.
+    ///
+    pub fn remove_if(&mut self, key: &K, predicate: impl Fn(&V) -> bool) {
+        if let Some(buffer) = self.buffers.get_mut(key) {
+            buffer.retain(|value| !predicate(value));
+            if buffer.is_empty() {
+                self.remove(key);
+            }
+        }
+    }
+
+    ///
+    /// Removes all the buffered elements for the given key.
+    ///
+    pub fn remove(&mut self, key: &K) {
+        if self.buffers.remove(key).is_some() {
+            self.order.retain(|k| k != key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_robin() {
+        let mut queue = FairQueue::<u8, u32>::new(1024);
+
+        // Flood the queue with elements from one key, before a second key pushes its elements.
+        for i in 0..1000 {
+            assert!(queue.push(0, i).is_ok());
+        }
+        for i in 0..3 {
+            assert!(queue.push(1, 1000 + i).is_ok());
+        }
+
+        // Ensure the elements of the second key are served promptly, and in order.
+        let served: Vec<u32> = (0..6).filter_map(|_| queue.pop()).collect();
+        assert_eq!(vec![0, 1000, 1, 1001, 2, 1002], served);
+
+        // Ensure the remaining elements of the first key are still served, in order.
+        let served: Vec<u32> = std::iter::from_fn(|| queue.pop()).collect();
+        assert_eq!((3..1000).collect::<Vec<_>>(), served);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_capacity_per_key() {
+        let mut queue = FairQueue::<u8, u32>::new(2);
+
+        assert!(queue.push(0, 0).is_ok());
+        assert!(queue.push(0, 1).is_ok());
+        assert_eq!(Err(2), queue.push(0, 2));
+        assert_eq!(2, queue.len_of(&0));
+
+        // Ensure a full key does not prevent other keys from buffering elements.
+        assert!(queue.push(1, 3).is_ok());
+        assert_eq!(1, queue.len_of(&1));
+
+        // Ensure a key may exceed the capacity per key, up to a given capacity.
+        assert!(queue.push_with_capacity(0, 2, 3).is_ok());
+        assert_eq!(Err(4), queue.push_with_capacity(0, 4, 3));
+        assert_eq!(3, queue.len_of(&0));
+
+        // Ensure removing the matching elements of a key retains its other elements.
+        queue.remove_if(&0, |value| *value == 1);
+        assert_eq!(2, queue.len_of(&0));
+
+        // Ensure removing a key discards its elements.
+        queue.remove(&0);
+        assert_eq!(0, queue.len_of(&0));
+        assert_eq!(Some(3), queue.pop());
+        assert_eq!(None, queue.pop());
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::ops::BitOr;
+
+/// The bit that marks a `Hello` byte, which is never set in the first byte of a handshake that is sent without a `Hello`.
+const HELLO_MARKER: u8 = 1 << 7;
+
+///
+/// A bitmask of the connection features a node supports, exchanged in the `Hello` byte before the handshake.
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FeatureFlags(u8);
+
+impl FeatureFlags {
+    /// The node supports zstd compression of message frames.
+    pub const COMPRESSES_ZSTD: Self = Self(1 << 0);
+    /// The node supports batching multiple messages into a single write.
+    pub const BATCHES_MESSAGES: Self = Self(1 << 1);
+
+    /// Returns an empty set of features.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Returns the features supported by this node.
+    pub const fn local() -> Self {
+        let compression = if cfg!(feature = "zstd") { Self::COMPRESSES_ZSTD.0 } else { 0 };
+        Self(compression | Self::BATCHES_MESSAGES.0)
+    }
+
+    /// Returns the features of the given `Hello` byte.
+    pub const fn from_bits(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw bitmask of the features.
+    pub const fn bits(&self) -> u8 {
+        self.0
+    }
+
+    /// Returns the `Hello` byte that announces the features.
+    pub const fn to_hello(&self) -> u8 {
+        HELLO_MARKER | self.0
+    }
+
+    /// Returns the features of the given `Hello` byte, or `None` if the byte is not a `Hello`.
+    pub const fn from_hello(byte: u8) -> Option<Self> {
+        match byte & HELLO_MARKER == HELLO_MARKER {
+            true => Some(Self(byte & !HELLO_MARKER)),
+            false => None,
+        }
+    }
+
+    /// Returns `true` if all of the given features are set.
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns the features agreed with a peer, which are the features supported by both nodes.
+    pub const fn negotiate(&self, peer: Self) -> Self {
+        Self(self.0 & peer.0)
+    }
+}
+
+impl BitOr for FeatureFlags {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feature_flags_negotiation() {
+        let all = FeatureFlags::COMPRESSES_ZSTD | FeatureFlags::BATCHES_MESSAGES;
+        assert_eq!(0b11, all.bits());
+
+        // Ensure two compression-capable nodes agree on compression.
+        let agreed = all.negotiate(FeatureFlags::COMPRESSES_ZSTD);
+        assert!(agreed.contains(FeatureFlags::COMPRESSES_ZSTD));
+        assert!(!agreed.contains(FeatureFlags::BATCHES_MESSAGES));
+
+        // Ensure a node that lacks compression, or sends no `Hello`, disables it for the connection.
+        let agreed = all.negotiate(FeatureFlags::BATCHES_MESSAGES);
+        assert!(!agreed.contains(FeatureFlags::COMPRESSES_ZSTD));
+        assert_eq!(FeatureFlags::empty(), all.negotiate(FeatureFlags::empty()));
+
+        // Ensure unknown bits from a peer are never agreed upon.
+        assert_eq!(all, all.negotiate(FeatureFlags::from_bits(u8::MAX)));
+
+        // Ensure the features are recovered from a `Hello`, and the first byte of a handshake is not a `Hello`.
+        assert_eq!(Some(all), FeatureFlags::from_hello(all.to_hello()));
+        assert_eq!(Some(FeatureFlags::empty()), FeatureFlags::from_hello(FeatureFlags::empty().to_hello()));
+        assert_eq!(None, FeatureFlags::from_hello(0x26));
+
+        // Ensure this node only supports compression if it is built with zstd.
+        let local = FeatureFlags::local();
+        assert_eq!(cfg!(feature = "zstd"), local.contains(FeatureFlags::COMPRESSES_ZSTD));
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use snarkvm::{dpc::prelude::*, utilities::ToBytes};
+
+///
+/// The network-wide rules for the fees of unconfirmed transactions.
+///
+pub trait FeePolicy: Send + Sync + 'static {
+    /// Returns `true` if the given fee is acceptable for a transaction of the given size in bytes.
+    fn is_acceptable(fee: u64, transaction_size: usize) -> bool;
+
+    /// Returns the fee expected to confirm a transaction of the given size in bytes within the given number of blocks.
+    fn estimate_fee(transaction_size: usize, target_blocks: u32) -> u64;
+
+    /// Returns `true` if the fee of the given transaction is acceptable.
+    fn is_acceptable_transaction<N: Network>(transaction: &Transaction<N>) -> bool {
+        // A negative value balance mints value, and pays no fee.
+        let fee = transaction.value_balance().0.max(0) as u64;
+        match transaction.to_bytes_le() {
+            Ok(bytes) => Self::is_acceptable(fee, bytes.len()),
+            Err(_) => false,
+        }
+    }
+}
+
+///
+/// A fee policy that accepts every fee, and expects no fee to confirm a transaction.
+///
+#[derive(Clone, Debug, Default)]
+pub struct DefaultFeePolicy;
+
+impl FeePolicy for DefaultFeePolicy {
+    fn is_acceptable(_fee: u64, _transaction_size: usize) -> bool {
+        true
+    }
+
+    fn estimate_fee(_transaction_size: usize, _target_blocks: u32) -> u64 {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::dpc::testnet2::Testnet2;
+
+    use rand::thread_rng;
+
+    /// A fee policy that requires a fee of at least one per byte.
+    struct StrictFeePolicy;
+
+    impl FeePolicy for StrictFeePolicy {
+        fn is_acceptable(fee: u64, transaction_size: usize) -> bool {
+            fee >= transaction_size as u64
+        }
+
+        fn estimate_fee(transaction_size: usize, _target_blocks: u32) -> u64 {
+            transaction_size as u64
+        }
+    }
+
+    #[test]
+    fn test_default_fee_policy() {
+        // Ensure the default policy accepts every fee.
+        assert!(DefaultFeePolicy::is_acceptable(0, 0));
+        assert!(DefaultFeePolicy::is_acceptable(0, usize::MAX));
+        assert_eq!(0, DefaultFeePolicy::estimate_fee(1024, 1));
+    }
+
+    #[test]
+    fn test_strict_fee_policy() {
+        let rng = &mut thread_rng();
+        let address = Account::<Testnet2>::new(rng).address();
+
+        // Ensure a zero-fee transaction is rejected by a strict policy, and accepted by the default policy.
+        let (transaction, _) = Transaction::<Testnet2>::new_coinbase(address, AleoAmount(0), true, rng).unwrap();
+        assert_eq!(0, transaction.value_balance().0);
+        assert!(!StrictFeePolicy::is_acceptable_transaction(&transaction));
+        assert!(DefaultFeePolicy::is_acceptable_transaction(&transaction));
+
+        // Ensure a fee of at least the transaction size is accepted.
+        let transaction_size = transaction.to_bytes_le().unwrap().len();
+        assert!(StrictFeePolicy::is_acceptable(transaction_size as u64, transaction_size));
+        assert!(!StrictFeePolicy::is_acceptable(transaction_size as u64 - 1, transaction_size));
+    }
+}
@@ -0,0 +1,187 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+///
+/// A branch of unconfirmed blocks, each extending the one before it, that is not on the canonical chain.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForkBranch<H> {
+    /// The previous block hash of each block of the branch, from the first block to the tip.
+    pub previous_block_hashes: Vec<H>,
+    /// The block height of the tip of the branch.
+    pub tip_block_height: u32,
+    /// The block hash of the tip of the branch.
+    pub tip_block_hash: H,
+    /// The timestamp of the tip of the branch.
+    pub tip_timestamp: i64,
+}
+
+impl<H: Copy> ForkBranch<H> {
+    /// Returns the number of blocks in the branch.
+    pub fn len(&self) -> u32 {
+        self.previous_block_hashes.len() as u32
+    }
+
+    /// Returns `true` if the branch has no blocks.
+    pub fn is_empty(&self) -> bool {
+        self.previous_block_hashes.is_empty()
+    }
+
+    /// Returns the block hash that the branch forks from, which is the previous block hash of its first block.
+    pub fn base_block_hash(&self) -> Option<H> {
+        self.previous_block_hashes.first().copied()
+    }
+
+    /// Returns the block height that the branch forks from.
+    pub fn base_block_height(&self) -> u32 {
+        self.tip_block_height.saturating_sub(self.len())
+    }
+}
+
+///
+/// Returns the branches of the given unconfirmed blocks, given as `(previous_block_hash, block_hash, block_height, timestamp)`.
+/// As the unconfirmed blocks are keyed by their previous block hash, each block has at most one child, so the branches are disjoint.
+///
+pub fn fork_branches<H: Copy + Eq + Hash>(blocks: impl IntoIterator<Item = (H, H, u32, i64)>) -> Vec<ForkBranch<H>> {
+    let blocks: HashMap<H, (H, u32, i64)> = blocks
+        .into_iter()
+        .map(|(previous_block_hash, block_hash, block_height, timestamp)| (previous_block_hash, (block_hash, block_height, timestamp)))
+        .collect();
+    let block_hashes: HashSet<H> = blocks.values().map(|(block_hash, ..)| *block_hash).collect();
+
+    // Start each branch from a block whose parent is not an unconfirmed block, and follow it to its tip.
+    let mut branches = Vec::new();
+    for first_previous_block_hash in blocks.keys().filter(|previous_block_hash| !block_hashes.contains(previous_block_hash)) {
+        let mut previous_block_hash = *first_previous_block_hash;
+        let mut previous_block_hashes = Vec::new();
+        let mut tip = None;
+        while let Some(block) = blocks.get(&previous_block_hash) {
+            previous_block_hashes.push(previous_block_hash);
+            previous_block_hash = block.0;
+            tip = Some(*block);
+        }
+        if let Some((tip_block_hash, tip_block_height, tip_timestamp)) = tip {
+            branches.push(ForkBranch {
+                previous_block_hashes,
+                tip_block_height,
+                tip_block_hash,
+                tip_timestamp,
+            });
+        }
+    }
+    branches
+}
+
+///
+/// Returns the branches to prune, so that at most `maximum_tracked_forks` branches are retained.
+/// The oldest branches are pruned first, and the shortest of equally old branches.
+///
+/// A branch that extends the canonical chain at the latest block, or forks from it within `maximum_fork_depth`
+/// of the latest block height, is never pruned, though it counts toward the maximum.
+///
+pub fn fork_branches_to_prune<H: Copy + Eq>(
+    branches: Vec<ForkBranch<H>>,
+    latest_block_height: u32,
+    latest_block_hash: H,
+    maximum_fork_depth: u32,
+    maximum_tracked_forks: usize,
+) -> Vec<ForkBranch<H>> {
+    let number_of_excess_branches = branches.len().saturating_sub(maximum_tracked_forks);
+    let mut prunable_branches: Vec<ForkBranch<H>> = branches
+        .into_iter()
+        .filter(|branch| {
+            let extends_canonical_chain = branch.base_block_hash() == Some(latest_block_hash);
+            let is_within_fork_depth = branch.base_block_height().saturating_add(maximum_fork_depth) >= latest_block_height;
+            !extends_canonical_chain && !is_within_fork_depth
+        })
+        .collect();
+    prunable_branches.sort_by_key(|branch| (branch.tip_timestamp, branch.len()));
+    prunable_branches.truncate(number_of_excess_branches);
+    prunable_branches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the blocks of a branch of the given length, starting at the given block height.
+    fn branch(base_block_hash: u64, first_block_height: u32, length: u32, timestamp: i64) -> Vec<(u64, u64, u32, i64)> {
+        (0..length as u64)
+            .map(|offset| {
+                let previous_block_hash = if offset == 0 { base_block_hash } else { base_block_hash * 1000 + offset };
+                let block_hash = base_block_hash * 1000 + offset + 1;
+                (previous_block_hash, block_hash, first_block_height + offset as u32, timestamp)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fork_branches() {
+        let mut blocks = branch(1, 10, 3, 100);
+        blocks.extend(branch(2, 20, 1, 200));
+
+        let mut branches = fork_branches(blocks);
+        branches.sort_by_key(|branch| branch.tip_block_height);
+        assert_eq!(2, branches.len());
+
+        // Ensure each branch is followed from its first block to its tip.
+        assert_eq!(vec![1, 1001, 1002], branches[0].previous_block_hashes);
+        assert_eq!((12, 1003, 100), (branches[0].tip_block_height, branches[0].tip_block_hash, branches[0].tip_timestamp));
+        assert_eq!((Some(1), 9, 3), (branches[0].base_block_hash(), branches[0].base_block_height(), branches[0].len()));
+        assert_eq!(vec![2], branches[1].previous_block_hashes);
+        assert_eq!(20, branches[1].tip_block_height);
+    }
+
+    #[test]
+    fn test_fork_branches_to_prune() {
+        let latest_block_height = 1000;
+        let latest_block_hash = 9;
+        let maximum_fork_depth = 100;
+
+        let mut blocks = branch(1, 10, 3, 100); // Old and long.
+        blocks.extend(branch(2, 20, 1, 100)); // Old and short.
+        blocks.extend(branch(3, 30, 1, 300)); // Recent.
+        blocks.extend(branch(4, 950, 1, 50)); // Oldest, but within the fork depth.
+        blocks.extend(branch(latest_block_hash, 1001, 2, 10)); // Extends the canonical chain.
+        let branches = fork_branches(blocks);
+
+        // Ensure nothing is pruned within the maximum.
+        let prune = |maximum_tracked_forks| {
+            let pruned = fork_branches_to_prune(
+                branches.clone(),
+                latest_block_height,
+                latest_block_hash,
+                maximum_fork_depth,
+                maximum_tracked_forks,
+            );
+            pruned.iter().filter_map(|branch| branch.base_block_hash()).collect::<Vec<_>>()
+        };
+        assert!(prune(5).is_empty());
+
+        // Ensure the oldest branches are pruned first, and the shortest of equally old branches.
+        assert_eq!(vec![2], prune(4));
+        assert_eq!(vec![2, 1], prune(3));
+        assert_eq!(vec![2, 1, 3], prune(2));
+
+        // Ensure the canonical chain and the branches within the fork depth are never pruned.
+        assert_eq!(vec![2, 1, 3], prune(0));
+    }
+}
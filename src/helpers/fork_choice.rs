@@ -0,0 +1,118 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use snarkvm::dpc::prelude::*;
+
+use std::cmp::Ordering;
+
+///
+/// The tip of a chain, as compared by the fork choice rule.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChainTip<N: Network> {
+    /// The cumulative weight of the chain.
+    pub cumulative_weight: u128,
+    /// The block height of the tip.
+    pub block_height: u32,
+    /// The block hash of the tip.
+    pub block_hash: N::BlockHash,
+}
+
+impl<N: Network> ChainTip<N> {
+    /// Initializes a new instance of a chain tip.
+    pub fn new(cumulative_weight: u128, block_height: u32, block_hash: N::BlockHash) -> Self {
+        Self {
+            cumulative_weight,
+            block_height,
+            block_hash,
+        }
+    }
+}
+
+///
+/// Compares two chains, returning `Ordering::Greater` if the first chain is preferred.
+///
+/// Chains are compared by cumulative weight, and then by block height. If both are equal,
+/// the chain whose tip has the numerically smaller block hash is preferred, so that every
+/// honest node converges on the same fork, instead of keeping whichever fork it saw first.
+///
+pub fn compare_chains<N: Network>(first: &ChainTip<N>, second: &ChainTip<N>) -> Ordering {
+    first
+        .cumulative_weight
+        .cmp(&second.cumulative_weight)
+        .then(first.block_height.cmp(&second.block_height))
+        .then_with(|| block_hash_to_numeric_bytes::<N>(&second.block_hash).cmp(&block_hash_to_numeric_bytes::<N>(&first.block_hash)))
+}
+
+/// Returns the bytes of the given block hash in big-endian order, so they compare numerically.
+fn block_hash_to_numeric_bytes<N: Network>(block_hash: &N::BlockHash) -> Vec<u8> {
+    let mut bytes = block_hash.to_bytes_le().expect("Failed to serialize a block hash");
+    bytes.reverse();
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{dpc::testnet2::Testnet2, prelude::UniformRand};
+
+    use rand::thread_rng;
+
+    #[test]
+    fn test_compare_chains() {
+        let rng = &mut thread_rng();
+        let block_hash = <Testnet2 as Network>::BlockHash::rand(rng);
+
+        // Ensure the heavier chain is preferred, regardless of its height.
+        let heavier = ChainTip::<Testnet2>::new(11, 5, block_hash);
+        let lighter = ChainTip::<Testnet2>::new(10, 6, block_hash);
+        assert_eq!(Ordering::Greater, compare_chains(&heavier, &lighter));
+        assert_eq!(Ordering::Less, compare_chains(&lighter, &heavier));
+
+        // Ensure identical chains are equal.
+        assert_eq!(Ordering::Equal, compare_chains(&heavier, &heavier.clone()));
+    }
+
+    #[test]
+    fn test_equal_weight_tie_break() {
+        let rng = &mut thread_rng();
+
+        for _ in 0..10 {
+            // Initialize two distinct tips with equal cumulative weight and block height.
+            let first = ChainTip::<Testnet2>::new(100, 10, <Testnet2 as Network>::BlockHash::rand(rng));
+            let second = ChainTip::<Testnet2>::new(100, 10, <Testnet2 as Network>::BlockHash::rand(rng));
+            if first.block_hash == second.block_hash {
+                continue;
+            }
+
+            // Ensure the comparison is antisymmetric, so the selection does not depend on the order of discovery.
+            let ordering = compare_chains(&first, &second);
+            assert_ne!(Ordering::Equal, ordering);
+            assert_eq!(ordering.reverse(), compare_chains(&second, &first));
+
+            // Ensure a node on either tip selects the same tip, which has the numerically smaller block hash.
+            let select = |local: &ChainTip<Testnet2>, peer: &ChainTip<Testnet2>| match compare_chains(peer, local) {
+                Ordering::Greater => peer.clone(),
+                _ => local.clone(),
+            };
+            let selected = select(&first, &second);
+            assert_eq!(selected, select(&second, &first));
+            let other = if selected == first { &second } else { &first };
+            let selected_hash = block_hash_to_numeric_bytes::<Testnet2>(&selected.block_hash);
+            assert!(selected_hash < block_hash_to_numeric_bytes::<Testnet2>(&other.block_hash));
+        }
+    }
+}
@@ -0,0 +1,163 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// The maximum number of distinct messages tracked by a log rate limiter.
+const MAXIMUM_TRACKED_MESSAGES: usize = 64;
+
+///
+/// A rate limiter for log messages, which collapses repeated identical messages into periodic summaries.
+///
+/// The first occurrence of a message is logged as is. Further occurrences of the same message within
+/// the suppression window are counted, and summarized once the window has elapsed. Distinct messages
+/// are tracked separately, so one noisy error does not hide another.
+///
+#[derive(Clone, Debug)]
+pub struct LogRateLimiter {
+    /// The map of each message to the start of its suppression window and its number of suppressed occurrences.
+    messages: HashMap<String, (Instant, u64)>,
+    /// The duration of the suppression window.
+    window: Duration,
+}
+
+impl LogRateLimiter {
+    /// Initializes a new instance of the rate limiter, with the given suppression window.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            messages: Default::default(),
+            window,
+        }
+    }
+
+    ///
+    /// Records an occurrence of the given message at the given timestamp, and returns the line to log, if any.
+    /// A suppressed message returns `None`, and the first occurrence after its window has elapsed
+    /// returns the message with a summary of the suppressed occurrences.
+    ///
+    pub fn check(&mut self, now: Instant, message: &str) -> Option<String> {
+        if let Some((window_start, suppressed)) = self.messages.get_mut(message) {
+            if now.saturating_duration_since(*window_start) < self.window {
+                *suppressed += 1;
+                return None;
+            }
+            let line = Self::summarize(message, *suppressed, now.saturating_duration_since(*window_start));
+            *window_start = now;
+            *suppressed = 0;
+            return Some(line);
+        }
+
+        // Evict the message with the oldest window, to bound the memory used by a peer sending distinct errors.
+        if self.messages.len() >= MAXIMUM_TRACKED_MESSAGES {
+            let oldest = self.messages.iter().min_by_key(|(_, (window_start, _))| *window_start);
+            if let Some(oldest) = oldest.map(|(message, _)| message.clone()) {
+                self.messages.remove(&oldest);
+            }
+        }
+        self.messages.insert(message.to_string(), (now, 0));
+        Some(message.to_string())
+    }
+
+    ///
+    /// Returns the summaries of the messages with suppressed occurrences, and stops tracking all messages.
+    /// This is used to report the remaining suppressed occurrences, such as when a peer disconnects.
+    ///
+    pub fn drain(&mut self, now: Instant) -> Vec<String> {
+        self.messages
+            .drain()
+            .filter(|(_, (_, suppressed))| *suppressed > 0)
+            .map(|(message, (window_start, suppressed))| Self::summarize(&message, suppressed, now.saturating_duration_since(window_start)))
+            .collect()
+    }
+
+    /// Returns the given message, with a summary of its suppressed occurrences over the given duration.
+    fn summarize(message: &str, suppressed: u64, elapsed: Duration) -> String {
+        match suppressed {
+            0 => message.to_string(),
+            _ => format!(
+                "{} (this error occurred {} more times in the last {} seconds)",
+                message,
+                suppressed,
+                elapsed.as_secs()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn test_repeated_messages_are_summarized() {
+        let mut limiter = LogRateLimiter::new(WINDOW);
+        let start = Instant::now();
+
+        // Ensure the first occurrence is logged, and the repeated occurrences are suppressed.
+        assert_eq!(Some("error".to_string()), limiter.check(start, "error"));
+        for i in 1..=100 {
+            assert_eq!(None, limiter.check(start + Duration::from_millis(i * 100), "error"));
+        }
+
+        // Ensure the next occurrence after the window summarizes the suppressed occurrences.
+        let line = limiter.check(start + WINDOW, "error").unwrap();
+        assert_eq!("error (this error occurred 100 more times in the last 60 seconds)", line);
+
+        // Ensure a new window has started.
+        assert_eq!(None, limiter.check(start + WINDOW + Duration::from_secs(1), "error"));
+    }
+
+    #[test]
+    fn test_distinct_messages_are_not_collapsed() {
+        let mut limiter = LogRateLimiter::new(WINDOW);
+        let start = Instant::now();
+
+        assert_eq!(Some("first".to_string()), limiter.check(start, "first"));
+        assert_eq!(Some("second".to_string()), limiter.check(start, "second"));
+        assert_eq!(None, limiter.check(start, "first"));
+        assert_eq!(None, limiter.check(start, "second"));
+        assert_eq!(None, limiter.check(start, "second"));
+
+        // Ensure the suppressed occurrences are summarized for each message.
+        let mut summaries = limiter.drain(start + Duration::from_secs(10));
+        summaries.sort();
+        assert_eq!(
+            vec![
+                "first (this error occurred 1 more times in the last 10 seconds)".to_string(),
+                "second (this error occurred 2 more times in the last 10 seconds)".to_string(),
+            ],
+            summaries
+        );
+        assert!(limiter.drain(start + Duration::from_secs(10)).is_empty());
+    }
+
+    #[test]
+    fn test_tracked_messages_are_bounded() {
+        let mut limiter = LogRateLimiter::new(WINDOW);
+        let start = Instant::now();
+
+        for i in 0..(2 * MAXIMUM_TRACKED_MESSAGES as u64) {
+            let message = format!("error {}", i);
+            assert_eq!(Some(message.clone()), limiter.check(start + Duration::from_millis(i), &message));
+        }
+        assert_eq!(MAXIMUM_TRACKED_MESSAGES, limiter.messages.len());
+    }
+}
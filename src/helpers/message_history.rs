@@ -0,0 +1,118 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::VecDeque,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// The number of inbound messages retained in the message history of a peer.
+pub const MESSAGE_HISTORY_CAPACITY: usize = 20;
+
+///
+/// A ring buffer of the most recent inbound message types from a peer, for diagnostics and abuse detection.
+///
+#[derive(Clone, Debug, Default)]
+pub struct MessageHistory {
+    /// The message types and the timestamps at which they were received, from oldest to newest.
+    entries: VecDeque<(String, Instant)>,
+}
+
+impl MessageHistory {
+    /// Initializes a new instance of the message history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the given message type as received at the given timestamp, evicting the oldest entry if the history is full.
+    pub fn push(&mut self, message_type: &str, timestamp: Instant) {
+        if self.entries.len() == MESSAGE_HISTORY_CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((message_type.to_string(), timestamp));
+    }
+
+    /// Returns the number of entries in the message history.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the message history is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    ///
+    /// Returns the message types from oldest to newest, with the UNIX timestamp in milliseconds
+    /// at which each message was received.
+    ///
+    pub fn to_vec(&self) -> Vec<(String, u64)> {
+        let now = Instant::now();
+        let now_in_millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis() as u64);
+        self.entries
+            .iter()
+            .map(|(message_type, timestamp)| {
+                let elapsed = now.saturating_duration_since(*timestamp).as_millis() as u64;
+                (message_type.clone(), now_in_millis.saturating_sub(elapsed))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    #[test]
+    fn test_message_history() {
+        let mut history = MessageHistory::new();
+        assert!(history.is_empty());
+
+        let start = Instant::now();
+        history.push("Ping", start);
+        history.push("PeerRequest", start + Duration::from_millis(1));
+        history.push("Ping", start + Duration::from_millis(2));
+
+        let entries = history.to_vec();
+        assert_eq!(3, entries.len());
+        let message_types = entries.iter().map(|(message_type, _)| message_type.as_str()).collect::<Vec<_>>();
+        assert_eq!(vec!["Ping", "PeerRequest", "Ping"], message_types);
+        // The timestamps are non-decreasing, as messages are recorded in order.
+        assert!(entries.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    }
+
+    #[test]
+    fn test_message_history_capacity() {
+        let mut history = MessageHistory::new();
+        let start = Instant::now();
+        for i in 0..(MESSAGE_HISTORY_CAPACITY + 5) {
+            history.push(&format!("Message{}", i), start + Duration::from_millis(i as u64));
+        }
+
+        // Only the most recent messages are retained.
+        let entries = history.to_vec();
+        assert_eq!(MESSAGE_HISTORY_CAPACITY, entries.len());
+        assert_eq!("Message5", entries[0].0);
+        assert_eq!(
+            format!("Message{}", MESSAGE_HISTORY_CAPACITY + 4),
+            entries[MESSAGE_HISTORY_CAPACITY - 1].0
+        );
+    }
+}
@@ -0,0 +1,84 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+///
+/// The pause state of the miner, as set by the operator of the node.
+///
+/// Pausing raises the terminator, to abort the block currently being mined. Resuming only clears
+/// the pause state, and leaves the ledger to lower the terminator on its next status update, so
+/// that a terminator raised for a new block (or while peering or syncing) is not cleared early.
+///
+#[derive(Debug, Default)]
+pub struct MiningPause(AtomicBool);
+
+impl MiningPause {
+    /// Initializes a new instance of `MiningPause`.
+    pub fn new() -> Self {
+        Self(AtomicBool::new(false))
+    }
+
+    /// Pauses mining, and raises the given terminator to abort the block currently being mined.
+    pub fn pause(&self, terminator: &AtomicBool) {
+        self.0.store(true, Ordering::SeqCst);
+        terminator.store(true, Ordering::SeqCst);
+    }
+
+    /// Resumes mining, once the ledger lowers the terminator.
+    pub fn resume(&self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+
+    /// Returns `true` if mining is paused by the operator.
+    pub fn is_paused(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pause_and_resume() {
+        let terminator = AtomicBool::new(false);
+        let mining_pause = MiningPause::new();
+        assert!(!mining_pause.is_paused());
+
+        // Pausing raises the terminator.
+        mining_pause.pause(&terminator);
+        assert!(mining_pause.is_paused());
+        assert!(terminator.load(Ordering::SeqCst));
+
+        // Pausing again is a no-op.
+        mining_pause.pause(&terminator);
+        assert!(mining_pause.is_paused());
+
+        // Resuming leaves the terminator for the ledger to lower.
+        mining_pause.resume();
+        assert!(!mining_pause.is_paused());
+        assert!(terminator.load(Ordering::SeqCst));
+
+        // Resuming does not lower a terminator raised for a new block.
+        terminator.store(false, Ordering::SeqCst);
+        mining_pause.pause(&terminator);
+        mining_pause.resume();
+        terminator.store(true, Ordering::SeqCst);
+        assert!(!mining_pause.is_paused());
+        assert!(terminator.load(Ordering::SeqCst));
+    }
+}
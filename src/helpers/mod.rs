@@ -14,23 +14,95 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
 
+pub mod block_locator_cache;
+pub use block_locator_cache::*;
+
+pub mod block_locators_diff;
+pub use block_locators_diff::*;
+
 pub mod block_request;
 pub use block_request::*;
 
 pub mod block_requests;
 pub use block_requests::*;
 
+pub mod block_verifications;
+pub use block_verifications::*;
+
 pub mod circular_map;
 pub use circular_map::*;
 
+pub mod difficulty;
+pub use difficulty::*;
+
+pub mod encoding;
+pub use encoding::*;
+
+pub mod events;
+pub use events::*;
+
+pub mod fair_queue;
+pub use fair_queue::*;
+
+pub mod feature_flags;
+pub use feature_flags::*;
+
+pub mod fee_policy;
+pub use fee_policy::*;
+
+pub mod fork_branches;
+pub use fork_branches::*;
+
+pub mod fork_choice;
+pub use fork_choice::*;
+
+pub mod log_limiter;
+pub use log_limiter::*;
+
+pub mod message_history;
+pub use message_history::*;
+
+pub mod mining_pause;
+pub use mining_pause::*;
+
+pub mod node_capabilities;
+pub use node_capabilities::*;
+
 pub mod node_type;
 pub use node_type::*;
 
+pub mod peer_reputation;
+pub use peer_reputation::*;
+
+pub mod priority_scheduler;
+pub use priority_scheduler::*;
+
+pub mod prover_stats;
+pub use prover_stats::*;
+
+pub mod proving_progress;
+pub use proving_progress::*;
+
 pub mod tasks;
 pub use tasks::*;
 
+pub mod slow_peer;
+pub use slow_peer::*;
+
 pub mod status;
 pub use status::*;
 
+pub mod sync_direction;
+pub use sync_direction::*;
+
+pub mod sync_progress;
+pub use sync_progress::*;
+
+pub mod sync_strategy;
+pub use sync_strategy::*;
+
+pub mod throttle;
+pub use throttle::*;
+
 pub mod updater;
 pub use updater::*;
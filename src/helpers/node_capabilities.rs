@@ -0,0 +1,146 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::helpers::NodeType;
+
+use serde::{Deserialize, Serialize};
+use std::{fmt, ops::BitOr};
+
+///
+/// A bitmask of the features a node advertises to its peers during the handshake.
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct NodeCapabilities(u16);
+
+impl NodeCapabilities {
+    /// The node stores the full canonical chain.
+    pub const STORES_FULL_CHAIN: Self = Self(1 << 0);
+    /// The node serves RPC requests.
+    pub const SERVES_RPC: Self = Self(1 << 1);
+    /// The node accepts pool registrations from provers.
+    pub const ACCEPTS_POOL_REGISTRATIONS: Self = Self(1 << 2);
+    /// The node responds to block requests.
+    pub const SERVES_BLOCK_REQUESTS: Self = Self(1 << 3);
+    /// The node produces new blocks.
+    pub const PRODUCES_BLOCKS: Self = Self(1 << 4);
+    /// The node supports the postcard encoding for message payloads.
+    pub const ENCODES_POSTCARD: Self = Self(1 << 5);
+    /// The node supports incremental block locator diffs in place of full block locators.
+    pub const DIFFS_BLOCK_LOCATORS: Self = Self(1 << 6);
+
+    /// Returns an empty set of capabilities.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Returns the default capabilities of the given node type.
+    pub const fn from_node_type(node_type: NodeType) -> Self {
+        let encodings = if cfg!(feature = "postcard") { Self::ENCODES_POSTCARD.0 } else { 0 };
+        let full_node =
+            Self::STORES_FULL_CHAIN.0 | Self::SERVES_RPC.0 | Self::SERVES_BLOCK_REQUESTS.0 | Self::DIFFS_BLOCK_LOCATORS.0 | encodings;
+        match node_type {
+            NodeType::Client | NodeType::Sync | NodeType::Prover => Self(full_node),
+            NodeType::Miner => Self(full_node | Self::PRODUCES_BLOCKS.0),
+            NodeType::Operator => Self(full_node | Self::ACCEPTS_POOL_REGISTRATIONS.0 | Self::PRODUCES_BLOCKS.0),
+            NodeType::Observer => Self(Self::STORES_FULL_CHAIN.0 | Self::SERVES_RPC.0 | Self::DIFFS_BLOCK_LOCATORS.0 | encodings),
+            NodeType::Beacon => Self::empty(),
+        }
+    }
+
+    /// Returns the raw bitmask of the capabilities.
+    pub const fn bits(&self) -> u16 {
+        self.0
+    }
+
+    /// Returns `true` if all of the given capabilities are set.
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Sets the given capabilities.
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    /// Clears the given capabilities.
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+}
+
+impl From<NodeType> for NodeCapabilities {
+    fn from(node_type: NodeType) -> Self {
+        Self::from_node_type(node_type)
+    }
+}
+
+impl BitOr for NodeCapabilities {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl fmt::Display for NodeCapabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#06x}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_capabilities() {
+        let mut capabilities = NodeCapabilities::empty();
+        assert!(!capabilities.contains(NodeCapabilities::STORES_FULL_CHAIN));
+
+        capabilities.insert(NodeCapabilities::STORES_FULL_CHAIN | NodeCapabilities::SERVES_RPC);
+        assert!(capabilities.contains(NodeCapabilities::STORES_FULL_CHAIN));
+        assert!(capabilities.contains(NodeCapabilities::SERVES_RPC));
+        assert!(!capabilities.contains(NodeCapabilities::PRODUCES_BLOCKS));
+
+        capabilities.remove(NodeCapabilities::SERVES_RPC);
+        assert!(!capabilities.contains(NodeCapabilities::SERVES_RPC));
+
+        assert!(NodeCapabilities::from(NodeType::Operator).contains(NodeCapabilities::ACCEPTS_POOL_REGISTRATIONS));
+        assert!(!NodeCapabilities::from(NodeType::Miner).contains(NodeCapabilities::ACCEPTS_POOL_REGISTRATIONS));
+        assert!(!NodeCapabilities::from(NodeType::Beacon).contains(NodeCapabilities::STORES_FULL_CHAIN));
+        assert!(NodeCapabilities::from(NodeType::Observer).contains(NodeCapabilities::STORES_FULL_CHAIN));
+        assert!(!NodeCapabilities::from(NodeType::Observer).contains(NodeCapabilities::SERVES_BLOCK_REQUESTS));
+        assert!(!NodeCapabilities::from(NodeType::Observer).contains(NodeCapabilities::PRODUCES_BLOCKS));
+        assert!(NodeCapabilities::from(NodeType::Client).contains(NodeCapabilities::DIFFS_BLOCK_LOCATORS));
+        assert!(!NodeCapabilities::from(NodeType::Beacon).contains(NodeCapabilities::DIFFS_BLOCK_LOCATORS));
+    }
+
+    #[test]
+    fn test_node_capabilities_serialization() {
+        let all = NodeCapabilities::STORES_FULL_CHAIN
+            | NodeCapabilities::SERVES_RPC
+            | NodeCapabilities::ACCEPTS_POOL_REGISTRATIONS
+            | NodeCapabilities::SERVES_BLOCK_REQUESTS
+            | NodeCapabilities::PRODUCES_BLOCKS;
+
+        for capabilities in [NodeCapabilities::empty(), NodeCapabilities::from(NodeType::Client), all] {
+            let bytes = bincode::serialize(&capabilities).unwrap();
+            assert_eq!(2, bytes.len());
+            assert_eq!(capabilities.bits().to_le_bytes().to_vec(), bytes);
+            assert_eq!(capabilities, bincode::deserialize::<NodeCapabilities>(&bytes).unwrap());
+        }
+    }
+}
@@ -0,0 +1,234 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
+
+///
+/// The long-term record of the behavior of a peer, which is persisted across restarts.
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reputation {
+    /// The number of times the peer was restricted.
+    pub restrictions: u32,
+    /// The UNIX timestamp of the last restriction of the peer, if any.
+    pub last_restricted: Option<i64>,
+    /// The UNIX timestamp of the last update to the reputation of the peer.
+    pub last_updated: i64,
+}
+
+impl Reputation {
+    ///
+    /// Returns the duration in seconds of the latest restriction of the peer. The duration starts at
+    /// `base_in_secs` and doubles with each repeated restriction, up to `maximum_in_secs`.
+    ///
+    pub fn restriction_duration_in_secs(&self, base_in_secs: u64, maximum_in_secs: u64) -> u64 {
+        let multiplier = 2u64.saturating_pow(self.restrictions.saturating_sub(1));
+        base_in_secs.saturating_mul(multiplier).min(maximum_in_secs)
+    }
+}
+
+///
+/// A store of the reputation of each peer, that is periodically saved to disk and reloaded at startup.
+///
+/// A store without a path is kept in memory only. A corrupt store file is discarded on load,
+/// and replaced on the next save, so that it never prevents the node from starting.
+///
+#[derive(Clone, Debug, Default)]
+pub struct ReputationStore {
+    /// The path of the store file, if the store is persisted.
+    path: Option<PathBuf>,
+    /// The map of peers to their reputation.
+    reputations: HashMap<SocketAddr, Reputation>,
+}
+
+impl ReputationStore {
+    ///
+    /// Loads the store from the given path, if any, and prunes the reputations that have not been
+    /// updated within `maximum_age_in_secs` of the given UNIX timestamp.
+    ///
+    pub fn load(path: Option<PathBuf>, maximum_age_in_secs: i64, now: i64) -> Self {
+        let reputations = match &path {
+            Some(path) if path.exists() => match Self::read(path) {
+                Ok(reputations) => reputations,
+                Err(error) => {
+                    warn!("Discarding the corrupt peer reputation store at {}: {}", path.display(), error);
+                    HashMap::new()
+                }
+            },
+            _ => HashMap::new(),
+        };
+
+        let mut store = Self { path, reputations };
+        store.prune(maximum_age_in_secs, now);
+        store
+    }
+
+    /// Removes the reputations that have not been updated within `maximum_age_in_secs` of the given UNIX timestamp.
+    pub fn prune(&mut self, maximum_age_in_secs: i64, now: i64) {
+        self.reputations
+            .retain(|_, reputation| now.saturating_sub(reputation.last_updated) <= maximum_age_in_secs);
+    }
+
+    /// Returns the reputation of the given peer, if it is known.
+    pub fn get(&self, peer_ip: &SocketAddr) -> Option<&Reputation> {
+        self.reputations.get(peer_ip)
+    }
+
+    ///
+    /// Returns the peers whose latest restriction has not yet expired at the given UNIX timestamp,
+    /// along with the remaining duration in seconds of their restriction.
+    ///
+    pub fn restricted_peers(&self, base_in_secs: u64, maximum_in_secs: u64, now: i64) -> impl Iterator<Item = (SocketAddr, u64)> + '_ {
+        self.reputations.iter().filter_map(move |(peer_ip, reputation)| {
+            let elapsed = now.saturating_sub(reputation.last_restricted?).max(0) as u64;
+            let duration = reputation.restriction_duration_in_secs(base_in_secs, maximum_in_secs);
+            match duration > elapsed {
+                true => Some((*peer_ip, duration - elapsed)),
+                false => None,
+            }
+        })
+    }
+
+    /// Records a restriction of the given peer, at the given UNIX timestamp, and returns its updated reputation.
+    pub fn record_restriction(&mut self, peer_ip: SocketAddr, now: i64) -> Reputation {
+        let reputation = self.reputations.entry(peer_ip).or_default();
+        reputation.restrictions = reputation.restrictions.saturating_add(1);
+        reputation.last_restricted = Some(now);
+        reputation.last_updated = now;
+        *reputation
+    }
+
+    ///
+    /// Saves the store to its path, if it is persisted. The store is written to a temporary file first,
+    /// so that an interrupted save does not corrupt the previous store file.
+    ///
+    pub fn save(&self) -> Result<()> {
+        if let Some(path) = &self.path {
+            let temporary_path = path.with_extension("tmp");
+            fs::write(&temporary_path, bincode::serialize(&self.reputations)?)?;
+            fs::rename(&temporary_path, path)?;
+        }
+        Ok(())
+    }
+
+    /// Reads the reputations from the store file at the given path.
+    fn read(path: &Path) -> Result<HashMap<SocketAddr, Reputation>> {
+        Ok(bincode::deserialize(&fs::read(path)?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAXIMUM_AGE: i64 = 3600;
+    const BASE_RESTRICTION: u64 = 100;
+    const MAXIMUM_RESTRICTION: u64 = 1000;
+
+    fn temp_path() -> PathBuf {
+        let directory = tempfile::tempdir().expect("Failed to open temporary directory").into_path();
+        directory.join("peer_reputation.bin")
+    }
+
+    #[test]
+    fn test_reputation_persists_across_restarts() {
+        let path = temp_path();
+        let bad_peer: SocketAddr = "127.0.0.1:4131".parse().unwrap();
+
+        // Record the restriction of a peer, and save the store.
+        let mut store = ReputationStore::load(Some(path.clone()), MAXIMUM_AGE, 1000);
+        store.record_restriction(bad_peer, 1000);
+        store.save().unwrap();
+
+        // Ensure the reputations are reloaded, along with the remainder of the restriction.
+        let store = ReputationStore::load(Some(path), MAXIMUM_AGE, 1040);
+        assert_eq!(1, store.get(&bad_peer).unwrap().restrictions);
+        let restricted_peers = store.restricted_peers(BASE_RESTRICTION, MAXIMUM_RESTRICTION, 1040).collect::<Vec<_>>();
+        assert_eq!(vec![(bad_peer, 60)], restricted_peers);
+
+        // Ensure the restriction expires after its duration.
+        assert_eq!(0, store.restricted_peers(BASE_RESTRICTION, MAXIMUM_RESTRICTION, 1100).count());
+    }
+
+    #[test]
+    fn test_repeated_restrictions_are_longer() {
+        let peer_ip: SocketAddr = "127.0.0.1:4131".parse().unwrap();
+        let mut store = ReputationStore::load(None, MAXIMUM_AGE, 0);
+
+        // Ensure the restriction duration doubles with each repeated restriction, up to the maximum.
+        let durations = (1..=5)
+            .map(|_| store.record_restriction(peer_ip, 0).restriction_duration_in_secs(BASE_RESTRICTION, MAXIMUM_RESTRICTION))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![100, 200, 400, 800, 1000], durations);
+        assert_eq!(vec![(peer_ip, 1000)], store.restricted_peers(BASE_RESTRICTION, MAXIMUM_RESTRICTION, 0).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_prune() {
+        let stale_peer: SocketAddr = "127.0.0.1:4131".parse().unwrap();
+        let recent_peer: SocketAddr = "127.0.0.1:4132".parse().unwrap();
+
+        let mut store = ReputationStore::load(None, MAXIMUM_AGE, 0);
+        store.record_restriction(stale_peer, 0);
+        store.record_restriction(recent_peer, MAXIMUM_AGE);
+
+        // Ensure the reputations that have not been updated within the maximum age are pruned at runtime.
+        store.prune(MAXIMUM_AGE, MAXIMUM_AGE + 1);
+        assert!(store.get(&stale_peer).is_none());
+        assert!(store.get(&recent_peer).is_some());
+    }
+
+    #[test]
+    fn test_stale_reputations_are_pruned() {
+        let path = temp_path();
+        let stale_peer: SocketAddr = "127.0.0.1:4131".parse().unwrap();
+        let recent_peer: SocketAddr = "127.0.0.1:4132".parse().unwrap();
+
+        let mut store = ReputationStore::load(Some(path.clone()), MAXIMUM_AGE, 0);
+        store.record_restriction(stale_peer, 0);
+        store.record_restriction(recent_peer, MAXIMUM_AGE);
+        store.save().unwrap();
+
+        // Ensure only the reputations updated within the maximum age are reloaded.
+        let store = ReputationStore::load(Some(path), MAXIMUM_AGE, MAXIMUM_AGE + 1);
+        assert!(store.get(&stale_peer).is_none());
+        assert!(store.get(&recent_peer).is_some());
+    }
+
+    #[test]
+    fn test_corrupt_store_is_discarded() {
+        let path = temp_path();
+        let peer_ip: SocketAddr = "127.0.0.1:4131".parse().unwrap();
+        fs::write(&path, b"not a reputation store").unwrap();
+
+        // Ensure a corrupt store is discarded, rather than preventing the node from starting.
+        let mut store = ReputationStore::load(Some(path.clone()), MAXIMUM_AGE, 0);
+        assert!(store.get(&peer_ip).is_none());
+
+        // Ensure the corrupt store is replaced on the next save.
+        store.record_restriction(peer_ip, 0);
+        store.save().unwrap();
+        let store = ReputationStore::load(Some(path), MAXIMUM_AGE, 0);
+        assert_eq!(1, store.get(&peer_ip).unwrap().restrictions);
+    }
+}
@@ -0,0 +1,232 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::{anyhow, Result};
+use once_cell::sync::OnceCell;
+use parking_lot::Mutex;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::{collections::VecDeque, sync::Arc};
+use tokio::{sync::oneshot, task};
+
+/// The maximum number of tasks that may be queued for each priority in the scheduler of the node.
+const MAXIMUM_QUEUED_TASKS: usize = 1024;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// The priority of a task submitted to the scheduler.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TaskPriority {
+    /// The verification of blocks, which is always serviced first.
+    Verification,
+    /// Any other intensive work, which is serviced when no verification is pending.
+    BestEffort,
+}
+
+/// The queued tasks of each priority, in submission order.
+#[derive(Default)]
+struct TaskQueues {
+    verification: VecDeque<Job>,
+    best_effort: VecDeque<Job>,
+}
+
+impl TaskQueues {
+    /// Returns the queue of the given priority.
+    fn queue(&mut self, priority: TaskPriority) -> &mut VecDeque<Job> {
+        match priority {
+            TaskPriority::Verification => &mut self.verification,
+            TaskPriority::BestEffort => &mut self.best_effort,
+        }
+    }
+
+    /// Returns the next task to run, preferring the verification tasks.
+    fn pop(&mut self) -> Option<Job> {
+        self.verification.pop_front().or_else(|| self.best_effort.pop_front())
+    }
+}
+
+///
+/// A bounded scheduler over the work-stealing thread pool of the node, which services the queued
+/// verification tasks before the best-effort tasks, regardless of their submission order.
+///
+pub struct PriorityScheduler {
+    /// The thread pool running the tasks.
+    pool: Arc<ThreadPool>,
+    /// The queued tasks of each priority.
+    queues: Arc<Mutex<TaskQueues>>,
+    /// The maximum number of tasks that may be queued for each priority.
+    capacity: usize,
+}
+
+impl PriorityScheduler {
+    ///
+    /// Initializes a new instance of the scheduler, over the given thread pool and with the given capacity per priority.
+    ///
+    pub fn new(pool: Arc<ThreadPool>, capacity: usize) -> Self {
+        Self {
+            pool,
+            queues: Default::default(),
+            capacity,
+        }
+    }
+
+    ///
+    /// Returns the scheduler shared by the node, whose thread pool is also the thread pool of the environment,
+    /// so that the scheduled tasks and the other intensive operations of the node never oversubscribe the cores.
+    ///
+    /// This is not part of the environment, as the (de)serialization of messages is not generic over it.
+    ///
+    pub fn global() -> &'static Self {
+        static SCHEDULER: OnceCell<PriorityScheduler> = OnceCell::new();
+        SCHEDULER.get_or_init(|| {
+            let pool = ThreadPoolBuilder::new()
+                .stack_size(8 * 1024 * 1024)
+                .num_threads((num_cpus::get() * 7 / 8).max(2))
+                .panic_handler(|_| error!("A task of the scheduler panicked"))
+                .build()
+                .expect("Failed to initialize a thread pool for the node");
+            Self::new(Arc::new(pool), MAXIMUM_QUEUED_TASKS)
+        })
+    }
+
+    /// Returns the thread pool running the tasks.
+    pub fn thread_pool(&self) -> &Arc<ThreadPool> {
+        &self.pool
+    }
+
+    ///
+    /// Returns the number of tasks of the given priority that are waiting to run.
+    ///
+    pub fn queued(&self, priority: TaskPriority) -> usize {
+        self.queues.lock().queue(priority).len()
+    }
+
+    ///
+    /// Queues the given task with the given priority, or returns it if the queue of the priority is full.
+    ///
+    pub fn spawn<F: FnOnce() + Send + 'static>(&self, priority: TaskPriority, task: F) -> Result<(), F> {
+        {
+            let mut queues = self.queues.lock();
+            let queue = queues.queue(priority);
+            if queue.len() >= self.capacity {
+                return Err(task);
+            }
+            queue.push_back(Box::new(task));
+        }
+
+        // Every queued task is matched by a single run of the pool, which picks the most urgent task at that time.
+        let queues = self.queues.clone();
+        self.pool.spawn(move || {
+            let task = queues.lock().pop();
+            if let Some(task) = task {
+                task();
+            }
+        });
+
+        Ok(())
+    }
+
+    ///
+    /// Runs the given task with the given priority, and returns its output.
+    ///
+    /// If the queue of the priority is full, the task runs in a dedicated blocking task instead.
+    ///
+    pub async fn run<T: Send + 'static, F: FnOnce() -> T + Send + 'static>(&self, priority: TaskPriority, task: F) -> Result<T> {
+        let (sender, receiver) = oneshot::channel();
+        let job = move || {
+            // The receiver may have been dropped, in which case the output is no longer needed.
+            let _ = sender.send(task());
+        };
+
+        if let Err(job) = self.spawn(priority, job) {
+            trace!("The scheduler is saturated with {:?} tasks", priority);
+            task::spawn_blocking(job);
+        }
+
+        receiver.await.map_err(|_| anyhow!("The scheduled task did not complete"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc;
+
+    /// Returns a single-threaded scheduler, along with a sender that unblocks it once the returned task is started.
+    fn blocked_scheduler(capacity: usize) -> (PriorityScheduler, mpsc::Sender<()>) {
+        let pool = ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+        let scheduler = PriorityScheduler::new(Arc::new(pool), capacity);
+
+        let (started_sender, started_receiver) = mpsc::channel();
+        let (release_sender, release_receiver) = mpsc::channel::<()>();
+        let blocker = move || {
+            started_sender.send(()).unwrap();
+            release_receiver.recv().unwrap();
+        };
+        assert!(scheduler.spawn(TaskPriority::BestEffort, blocker).is_ok());
+
+        // Wait until the only thread of the scheduler is occupied.
+        started_receiver.recv().unwrap();
+        (scheduler, release_sender)
+    }
+
+    #[test]
+    fn test_verification_runs_before_best_effort() {
+        let (scheduler, release) = blocked_scheduler(10);
+
+        let (sender, receiver) = mpsc::channel();
+        for index in 0..3 {
+            let sender = sender.clone();
+            let task = move || sender.send(index).unwrap();
+            assert!(scheduler.spawn(TaskPriority::BestEffort, task).is_ok());
+        }
+        let task = move || sender.send(100).unwrap();
+        assert!(scheduler.spawn(TaskPriority::Verification, task).is_ok());
+        assert_eq!(3, scheduler.queued(TaskPriority::BestEffort));
+        assert_eq!(1, scheduler.queued(TaskPriority::Verification));
+
+        release.send(()).unwrap();
+        let order: Vec<_> = receiver.iter().take(4).collect();
+        assert_eq!(vec![100, 0, 1, 2], order);
+    }
+
+    #[test]
+    fn test_capacity_per_priority() {
+        let (scheduler, release) = blocked_scheduler(1);
+
+        assert!(scheduler.spawn(TaskPriority::BestEffort, || ()).is_ok());
+        assert!(scheduler.spawn(TaskPriority::BestEffort, || ()).is_err());
+
+        // The verification tasks have a queue of their own.
+        assert!(scheduler.spawn(TaskPriority::Verification, || ()).is_ok());
+        assert!(scheduler.spawn(TaskPriority::Verification, || ()).is_err());
+
+        release.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_run_when_saturated() {
+        let (scheduler, release) = blocked_scheduler(1);
+
+        assert!(scheduler.spawn(TaskPriority::BestEffort, || ()).is_ok());
+
+        // The queue is full, so the task runs outside of the scheduler.
+        assert_eq!(42, scheduler.run(TaskPriority::BestEffort, || 42).await.unwrap());
+
+        release.send(()).unwrap();
+        assert_eq!(7, scheduler.run(TaskPriority::Verification, || 7).await.unwrap());
+    }
+}
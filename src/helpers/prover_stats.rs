@@ -0,0 +1,139 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use parking_lot::Mutex;
+use std::time::{Duration, Instant};
+
+///
+/// The statistics of the proofs attempted by the miner or prover, from which its hashrate is estimated.
+///
+#[derive(Debug, Default)]
+pub struct ProverStats(Mutex<ProverStatsInner>);
+
+#[derive(Debug, Default)]
+struct ProverStatsInner {
+    /// The block height of the current block template, and the time it was received or built.
+    current_template: Option<(u32, Instant)>,
+    /// The time of the first proof attempt, from which the hashrate is measured.
+    first_attempt_at: Option<Instant>,
+    /// The total number of proof attempts.
+    proof_attempts_total: u64,
+    /// The total number of blocks found.
+    blocks_mined: u64,
+    /// The time the last block was found, if any.
+    last_block_found_at: Option<Instant>,
+}
+
+impl ProverStats {
+    /// Initializes a new instance of `ProverStats`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the block template with the given block height, as received or built at the given time.
+    pub fn record_template(&self, block_height: u32, now: Instant) {
+        self.0.lock().current_template = Some((block_height, now));
+    }
+
+    /// Records the given number of proof attempts, made up to the given time.
+    pub fn record_attempts(&self, attempts: u64, now: Instant) {
+        let mut stats = self.0.lock();
+        stats.first_attempt_at.get_or_insert(now);
+        stats.proof_attempts_total = stats.proof_attempts_total.saturating_add(attempts);
+    }
+
+    /// Records a block found at the given time.
+    pub fn record_block_found(&self, now: Instant) {
+        let mut stats = self.0.lock();
+        stats.blocks_mined = stats.blocks_mined.saturating_add(1);
+        stats.last_block_found_at = Some(now);
+    }
+
+    /// Returns the block height of the current block template, and its age at the given time, if one exists.
+    pub fn current_template(&self, now: Instant) -> Option<(u32, Duration)> {
+        let stats = self.0.lock();
+        stats
+            .current_template
+            .map(|(block_height, received_at)| (block_height, now.saturating_duration_since(received_at)))
+    }
+
+    /// Returns the total number of proof attempts.
+    pub fn proof_attempts_total(&self) -> u64 {
+        self.0.lock().proof_attempts_total
+    }
+
+    /// Returns the estimated number of proof attempts per second, since the first proof attempt.
+    pub fn hashrate_estimate(&self, now: Instant) -> f64 {
+        let stats = self.0.lock();
+        match stats
+            .first_attempt_at
+            .map(|first_attempt_at| now.saturating_duration_since(first_attempt_at))
+        {
+            Some(elapsed) if elapsed > Duration::ZERO => stats.proof_attempts_total as f64 / elapsed.as_secs_f64(),
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the total number of blocks found.
+    pub fn blocks_mined(&self) -> u64 {
+        self.0.lock().blocks_mined
+    }
+
+    /// Returns the time elapsed since the last block was found, at the given time, if any.
+    pub fn last_block_found(&self, now: Instant) -> Option<Duration> {
+        self.0
+            .lock()
+            .last_block_found_at
+            .map(|found_at| now.saturating_duration_since(found_at))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prover_stats() {
+        let stats = ProverStats::new();
+        let now = Instant::now();
+
+        // Ensure there are no statistics before the first proof attempt.
+        assert_eq!(None, stats.current_template(now));
+        assert_eq!(0, stats.proof_attempts_total());
+        assert_eq!(0.0, stats.hashrate_estimate(now));
+        assert_eq!(0, stats.blocks_mined());
+        assert_eq!(None, stats.last_block_found(now));
+
+        // Ensure the age of the current block template is measured from when it was recorded.
+        stats.record_template(10, now);
+        assert_eq!(
+            Some((10, Duration::from_secs(3))),
+            stats.current_template(now + Duration::from_secs(3))
+        );
+
+        // Ensure the hashrate is measured from the first proof attempt.
+        stats.record_attempts(10, now);
+        stats.record_attempts(10, now + Duration::from_secs(1));
+        assert_eq!(20, stats.proof_attempts_total());
+        assert_eq!(5.0, stats.hashrate_estimate(now + Duration::from_secs(4)));
+
+        // Ensure the blocks found are counted, along with the time since the last one.
+        stats.record_block_found(now);
+        stats.record_block_found(now + Duration::from_secs(1));
+        assert_eq!(2, stats.blocks_mined());
+        assert_eq!(Some(Duration::from_secs(2)), stats.last_block_found(now + Duration::from_secs(3)));
+    }
+}
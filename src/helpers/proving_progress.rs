@@ -0,0 +1,121 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use parking_lot::Mutex;
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// The number of recent proof durations used to estimate the duration of the next proof.
+const PROOF_DURATION_HISTORY: usize = 16;
+
+///
+/// The progress of the proof currently being generated by the miner or prover,
+/// and the durations of the recent proofs, which estimate the duration of the next proof.
+///
+#[derive(Debug, Default)]
+pub struct ProvingProgress(Mutex<(Option<Instant>, VecDeque<Duration>)>);
+
+impl ProvingProgress {
+    /// Initializes a new instance of `ProvingProgress`.
+    pub fn new() -> Self {
+        Self(Mutex::new((None, VecDeque::with_capacity(PROOF_DURATION_HISTORY))))
+    }
+
+    /// Marks the start of a proof attempt at the given time.
+    pub fn start(&self, now: Instant) {
+        self.0.lock().0 = Some(now);
+    }
+
+    /// Marks the end of the current proof attempt at the given time, and records its duration if it succeeded.
+    /// An aborted or failed attempt is not recorded, as it would skew the estimate of a full proof.
+    pub fn finish(&self, now: Instant, is_success: bool) {
+        let mut progress = self.0.lock();
+        if let (Some(started_at), true) = (progress.0.take(), is_success) {
+            if progress.1.len() == PROOF_DURATION_HISTORY {
+                progress.1.pop_front();
+            }
+            progress.1.push_back(now.saturating_duration_since(started_at));
+        }
+    }
+
+    /// Returns the time elapsed since the start of the current proof attempt, if one is in progress.
+    pub fn elapsed(&self, now: Instant) -> Option<Duration> {
+        self.0.lock().0.map(|started_at| now.saturating_duration_since(started_at))
+    }
+
+    /// Returns the estimated duration of a proof, as the average of the recent proof durations, if any were recorded.
+    pub fn estimated_total(&self) -> Option<Duration> {
+        let progress = self.0.lock();
+        match progress.1.is_empty() {
+            true => None,
+            false => Some(progress.1.iter().sum::<Duration>() / progress.1.len() as u32),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proving_progress() {
+        let progress = ProvingProgress::new();
+        let now = Instant::now();
+
+        // Ensure there is no progress nor estimate before the first proof.
+        assert_eq!(None, progress.elapsed(now));
+        assert_eq!(None, progress.estimated_total());
+
+        // Ensure the elapsed time is reported while proving, without an estimate.
+        progress.start(now);
+        assert_eq!(Some(Duration::from_secs(1)), progress.elapsed(now + Duration::from_secs(1)));
+        assert_eq!(None, progress.estimated_total());
+
+        // Ensure a successful proof is recorded in the estimate.
+        progress.finish(now + Duration::from_secs(2), true);
+        assert_eq!(None, progress.elapsed(now + Duration::from_secs(3)));
+        assert_eq!(Some(Duration::from_secs(2)), progress.estimated_total());
+
+        // Ensure an aborted proof is not recorded in the estimate.
+        progress.start(now);
+        progress.finish(now + Duration::from_secs(10), false);
+        assert_eq!(Some(Duration::from_secs(2)), progress.estimated_total());
+
+        // Ensure the estimate averages the recent proofs.
+        progress.start(now);
+        progress.finish(now + Duration::from_secs(4), true);
+        assert_eq!(Some(Duration::from_secs(3)), progress.estimated_total());
+    }
+
+    #[test]
+    fn test_proving_progress_history() {
+        let progress = ProvingProgress::new();
+        let now = Instant::now();
+
+        // Ensure only the most recent proofs are used for the estimate.
+        for _ in 0..PROOF_DURATION_HISTORY {
+            progress.start(now);
+            progress.finish(now + Duration::from_secs(100), true);
+        }
+        for _ in 0..PROOF_DURATION_HISTORY {
+            progress.start(now);
+            progress.finish(now + Duration::from_secs(1), true);
+        }
+        assert_eq!(Some(Duration::from_secs(1)), progress.estimated_total());
+    }
+}
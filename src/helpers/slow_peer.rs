@@ -0,0 +1,96 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::time::{Duration, Instant};
+
+///
+/// A detector for peers whose round-trip time stays above a threshold for a sustained window.
+///
+/// A single fast round trip resets the detector, so transient slowness is not reported.
+/// Once reported, a peer is not reported again until it has recovered.
+///
+#[derive(Clone, Debug, Default)]
+pub struct SlowPeerDetector {
+    /// The timestamp of the first slow round trip in the current slow streak.
+    slow_since: Option<Instant>,
+    /// If `true`, the current slow streak has already been reported.
+    is_reported: bool,
+}
+
+impl SlowPeerDetector {
+    /// Initializes a new instance of the detector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Records a round-trip time observed at the given timestamp, and returns `true` if the peer
+    /// has been slower than `threshold` for at least `window`, and has not been reported yet.
+    ///
+    pub fn update(&mut self, rtt: Duration, now: Instant, threshold: Duration, window: Duration) -> bool {
+        // A fast round trip ends the slow streak.
+        if rtt <= threshold {
+            self.slow_since = None;
+            self.is_reported = false;
+            return false;
+        }
+
+        // Report the peer once, if the slow streak has lasted for the full window.
+        let slow_since = *self.slow_since.get_or_insert(now);
+        if !self.is_reported && now.saturating_duration_since(slow_since) >= window {
+            self.is_reported = true;
+            return true;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THRESHOLD: Duration = Duration::from_secs(5);
+    const WINDOW: Duration = Duration::from_secs(300);
+
+    #[test]
+    fn test_sustained_slowness_is_reported_once() {
+        let mut detector = SlowPeerDetector::new();
+        let start = Instant::now();
+        let slow = THRESHOLD + Duration::from_secs(1);
+
+        // Ensure the peer is not reported before the window has elapsed.
+        for i in 0..5 {
+            assert!(!detector.update(slow, start + Duration::from_secs(i * 60), THRESHOLD, WINDOW));
+        }
+        // Ensure the peer is reported once the window has elapsed.
+        assert!(detector.update(slow, start + WINDOW, THRESHOLD, WINDOW));
+        // Ensure the peer is not reported again for the same slow streak.
+        assert!(!detector.update(slow, start + WINDOW * 2, THRESHOLD, WINDOW));
+    }
+
+    #[test]
+    fn test_transient_slowness_is_not_reported() {
+        let mut detector = SlowPeerDetector::new();
+        let start = Instant::now();
+        let slow = THRESHOLD + Duration::from_secs(1);
+
+        // Ensure a fast round trip in between slow ones resets the streak.
+        for i in 0..10 {
+            let rtt = if i % 4 == 3 { THRESHOLD } else { slow };
+            assert!(!detector.update(rtt, start + Duration::from_secs(i * 60), THRESHOLD, WINDOW));
+        }
+    }
+}
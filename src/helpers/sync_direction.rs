@@ -0,0 +1,277 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{collections::BTreeMap, net::SocketAddr};
+
+///
+/// The order in which the ledger requests the batches of blocks within its sync pipeline.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SyncDirection {
+    /// Requests the batches from the lowest block height upward.
+    Forward,
+    /// Requests the batches from the highest block height downward, so the progress reported to the user
+    /// starts from the tip of the peer, while the blocks are still committed in forward order.
+    Backward,
+}
+
+impl SyncDirection {
+    ///
+    /// Returns the direction to sync in, given the number of blocks this node is behind its peer,
+    /// and the number of blocks beyond which the batches are requested in reverse order.
+    ///
+    pub fn select(number_of_blocks_behind: u32, backward_sync_threshold: u32) -> Self {
+        match number_of_blocks_behind > backward_sync_threshold {
+            true => Self::Backward,
+            false => Self::Forward,
+        }
+    }
+
+    ///
+    /// Returns the given inclusive range of block heights as batches of at most `batch_size` blocks,
+    /// in the order they are to be requested.
+    ///
+    pub fn batches(&self, start_block_height: u32, end_block_height: u32, batch_size: u32) -> Vec<(u32, u32)> {
+        let batch_size = batch_size.max(1);
+        let mut batches = Vec::new();
+        if start_block_height > end_block_height {
+            return batches;
+        }
+
+        match self {
+            Self::Forward => {
+                let mut start = start_block_height;
+                loop {
+                    let end = start.saturating_add(batch_size - 1).min(end_block_height);
+                    batches.push((start, end));
+                    if end == end_block_height {
+                        break;
+                    }
+                    start = end + 1;
+                }
+            }
+            Self::Backward => {
+                let mut end = end_block_height;
+                loop {
+                    let start = end.saturating_sub(batch_size - 1).max(start_block_height);
+                    batches.push((start, end));
+                    if start == start_block_height {
+                        break;
+                    }
+                    end = start - 1;
+                }
+            }
+        }
+        batches
+    }
+}
+
+///
+/// A window of blocks that is requested from a peer in batches, from its highest block height downward.
+/// The blocks received for the window are buffered, until they are contiguous with the canonical chain.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BackwardSyncWindow {
+    /// The IP of the peer that the window is requested from.
+    peer_ip: SocketAddr,
+    /// The lowest block height of the window.
+    start_block_height: u32,
+    /// The highest block height that is yet to be requested, or `None` once the whole window is requested.
+    next_block_height: Option<u32>,
+}
+
+impl BackwardSyncWindow {
+    /// Initializes a new window for the given inclusive range of block heights.
+    pub fn new(peer_ip: SocketAddr, start_block_height: u32, end_block_height: u32) -> Self {
+        Self {
+            peer_ip,
+            start_block_height,
+            next_block_height: Some(end_block_height).filter(|end_block_height| *end_block_height >= start_block_height),
+        }
+    }
+
+    /// Returns the IP of the peer that the window is requested from.
+    pub fn peer_ip(&self) -> SocketAddr {
+        self.peer_ip
+    }
+
+    /// Returns `true` if every batch of the window has been requested.
+    pub fn is_requested(&self) -> bool {
+        self.next_block_height.is_none()
+    }
+
+    ///
+    /// Returns the next batch of at most `batch_size` blocks to request, directly below the batches requested so far,
+    /// or `None` if the whole window has been requested.
+    ///
+    pub fn next_batch(&mut self, batch_size: u32) -> Option<(u32, u32)> {
+        let end_block_height = self.next_block_height?;
+        let start_block_height = end_block_height
+            .saturating_sub(batch_size.max(1) - 1)
+            .max(self.start_block_height);
+        self.next_block_height = match start_block_height > self.start_block_height {
+            true => Some(start_block_height - 1),
+            false => None,
+        };
+        Some((start_block_height, end_block_height))
+    }
+}
+
+///
+/// A buffer of the blocks received while syncing, which releases them in forward order
+/// once they are contiguous with the canonical chain.
+///
+#[derive(Clone, Debug)]
+pub struct ContiguousBlocks<T> {
+    blocks: BTreeMap<u32, T>,
+}
+
+impl<T> ContiguousBlocks<T> {
+    /// Returns the number of buffered blocks.
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns `true` if there are no buffered blocks.
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Buffers the given block at the given block height, replacing any block buffered at the same height.
+    pub fn insert(&mut self, block_height: u32, block: T) {
+        self.blocks.insert(block_height, block);
+    }
+
+    ///
+    /// Removes and returns the blocks that are ready to be added, in forward order, given the next block height of the canonical chain.
+    /// These are the blocks at or below the next block height, followed by the contiguous blocks after it.
+    ///
+    pub fn pop_ready(&mut self, next_block_height: u32) -> Vec<T> {
+        let mut ready = Vec::new();
+        let mut block_height = next_block_height;
+        while let Some((&height, _)) = self.blocks.iter().next() {
+            if height > block_height {
+                break;
+            }
+            if height == block_height {
+                block_height = block_height.saturating_add(1);
+            }
+            if let Some(block) = self.blocks.remove(&height) {
+                ready.push(block);
+            }
+        }
+        ready
+    }
+
+    /// Retains only the buffered blocks for which the given predicate returns `true`.
+    pub fn retain(&mut self, mut predicate: impl FnMut(&T) -> bool) {
+        self.blocks.retain(|_, block| predicate(block));
+    }
+
+    /// Removes all buffered blocks.
+    pub fn clear(&mut self) {
+        self.blocks.clear();
+    }
+}
+
+impl<T> Default for ContiguousBlocks<T> {
+    fn default() -> Self {
+        Self {
+            blocks: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_direction() {
+        // Ensure the batches are requested from the tip downward only when far behind.
+        assert_eq!(SyncDirection::Forward, SyncDirection::select(1000, 1000));
+        assert_eq!(SyncDirection::Backward, SyncDirection::select(1001, 1000));
+
+        assert_eq!(vec![(0, 249), (250, 499), (500, 500)], SyncDirection::Forward.batches(0, 500, 250));
+        assert_eq!(vec![(251, 500), (1, 250), (0, 0)], SyncDirection::Backward.batches(0, 500, 250));
+        assert_eq!(vec![(7, 7)], SyncDirection::Backward.batches(7, 7, 250));
+        assert!(SyncDirection::Forward.batches(8, 7, 250).is_empty());
+    }
+
+    #[test]
+    fn test_backward_sync_commits_in_forward_order() {
+        let mut buffer = ContiguousBlocks::default();
+        let mut committed = Vec::new();
+
+        // Request the blocks 0 to 500 in reverse order, and deliver each batch in reverse order.
+        for (start_block_height, end_block_height) in SyncDirection::Backward.batches(0, 500, 250) {
+            for block_height in (start_block_height..=end_block_height).rev() {
+                buffer.insert(block_height, block_height);
+                let next_block_height = committed.len() as u32;
+                committed.extend(buffer.pop_ready(next_block_height));
+            }
+        }
+
+        // Ensure the blocks are committed in forward order.
+        assert_eq!((0..=500).collect::<Vec<u32>>(), committed);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_backward_sync_window() {
+        let peer_ip: SocketAddr = "127.0.0.1:4130".parse().unwrap();
+
+        // Ensure the whole window is requested from its tip downward, one batch at a time.
+        let mut window = BackwardSyncWindow::new(peer_ip, 0, 1000);
+        let mut batches = Vec::new();
+        while let Some(batch) = window.next_batch(250) {
+            batches.push(batch);
+        }
+        assert_eq!(vec![(751, 1000), (501, 750), (251, 500), (1, 250), (0, 0)], batches);
+        assert!(window.is_requested());
+
+        // Ensure an empty window has nothing to request.
+        let mut window = BackwardSyncWindow::new(peer_ip, 8, 7);
+        assert!(window.is_requested());
+        assert_eq!(None, window.next_batch(250));
+    }
+
+    #[test]
+    fn test_contiguous_blocks_retain() {
+        let mut buffer = ContiguousBlocks::default();
+        buffer.insert(1, ("peer_a", 1));
+        buffer.insert(2, ("peer_b", 2));
+
+        // Ensure the blocks of a disconnected peer are removed from the buffer.
+        buffer.retain(|(peer, _)| *peer != "peer_b");
+        assert_eq!(vec![("peer_a", 1)], buffer.pop_ready(1));
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_contiguous_blocks_stale() {
+        let mut buffer = ContiguousBlocks::default();
+        buffer.insert(3, 3);
+        buffer.insert(5, 5);
+        buffer.insert(6, 6);
+        buffer.insert(9, 9);
+
+        // Ensure a stale block is released, along with the blocks contiguous with the next block height.
+        assert_eq!(vec![3, 5, 6], buffer.pop_ready(5));
+        assert_eq!(1, buffer.len());
+        assert!(buffer.pop_ready(7).is_empty());
+    }
+}
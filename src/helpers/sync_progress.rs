@@ -0,0 +1,178 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// The estimated time remaining until the ledger reaches the target block height.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncEstimate {
+    /// The ledger has reached the target block height.
+    Synced,
+    /// The ledger has not advanced within the sampling window.
+    Stalled,
+    /// The estimated number of seconds remaining.
+    RemainingSecs(u64),
+}
+
+///
+/// An estimator of the sync rate of the ledger, as the number of blocks added per second.
+///
+/// The rate is measured over a sliding window of block height samples, which smooths over
+/// bursts of block responses. Samples are recorded at most once per second.
+///
+#[derive(Clone, Debug)]
+pub struct SyncProgress {
+    /// The block height samples within the window, as (timestamp, block_height).
+    samples: VecDeque<(Instant, u32)>,
+    /// The duration of the sampling window.
+    window: Duration,
+}
+
+impl SyncProgress {
+    /// Initializes a new instance of the estimator, with the given sampling window.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            samples: Default::default(),
+            window,
+        }
+    }
+
+    ///
+    /// Records the block height of the ledger at the given timestamp.
+    /// A block height below the latest sample, as after a revert, resets the estimator.
+    ///
+    pub fn record(&mut self, now: Instant, block_height: u32) {
+        match self.samples.back_mut() {
+            Some((_, latest_height)) if block_height < *latest_height => self.samples.clear(),
+            // Coalesce samples recorded within the same second.
+            Some((timestamp, latest_height)) if now.saturating_duration_since(*timestamp) < Duration::from_secs(1) => {
+                *latest_height = block_height;
+                return;
+            }
+            _ => (),
+        }
+        self.samples.push_back((now, block_height));
+        self.prune(now);
+    }
+
+    ///
+    /// Returns the number of blocks added per second within the window ending at the given timestamp.
+    ///
+    pub fn blocks_per_second(&self, now: Instant) -> f64 {
+        // The samples are in chronological order, so the first sample within the window is the oldest.
+        let is_within_window = |(timestamp, _): &&(Instant, u32)| now.saturating_duration_since(*timestamp) <= self.window;
+
+        match (self.samples.iter().find(is_within_window), self.samples.back()) {
+            (Some((oldest_timestamp, oldest_height)), Some((_, latest_height))) => {
+                let elapsed = now.saturating_duration_since(*oldest_timestamp).as_secs_f64();
+                match elapsed > 0.0 {
+                    true => latest_height.saturating_sub(*oldest_height) as f64 / elapsed,
+                    false => 0.0,
+                }
+            }
+            _ => 0.0,
+        }
+    }
+
+    ///
+    /// Returns the estimated time remaining for the ledger to advance from the given
+    /// latest block height to the target block height, at the current sync rate.
+    ///
+    pub fn estimate(&self, now: Instant, latest_block_height: u32, target_block_height: u32) -> SyncEstimate {
+        let remaining_blocks = target_block_height.saturating_sub(latest_block_height);
+        if remaining_blocks == 0 {
+            return SyncEstimate::Synced;
+        }
+
+        let blocks_per_second = self.blocks_per_second(now);
+        match blocks_per_second > 0.0 {
+            true => SyncEstimate::RemainingSecs((remaining_blocks as f64 / blocks_per_second).ceil() as u64),
+            false => SyncEstimate::Stalled,
+        }
+    }
+
+    /// Removes the samples that are older than the window, keeping the latest sample.
+    fn prune(&mut self, now: Instant) {
+        while self.samples.len() > 1 {
+            match self.samples.front() {
+                Some((timestamp, _)) if now.saturating_duration_since(*timestamp) > self.window => {
+                    self.samples.pop_front();
+                }
+                _ => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn test_estimate() {
+        let mut progress = SyncProgress::new(WINDOW);
+        let start = Instant::now();
+
+        // Ensure the estimator reports a stall before any progress is made.
+        progress.record(start, 0);
+        assert_eq!(SyncEstimate::Stalled, progress.estimate(start, 0, 1000));
+
+        // Advance the ledger by 10 blocks per second.
+        for i in 1..=30 {
+            progress.record(start + Duration::from_secs(i), i as u32 * 10);
+        }
+        let now = start + Duration::from_secs(30);
+        assert_eq!(10.0, progress.blocks_per_second(now));
+
+        // Ensure the estimate is based on the remaining blocks and the sync rate.
+        assert_eq!(SyncEstimate::RemainingSecs(70), progress.estimate(now, 300, 1000));
+        // Ensure the estimator reports the ledger is synced once it has reached the target.
+        assert_eq!(SyncEstimate::Synced, progress.estimate(now, 1000, 1000));
+    }
+
+    #[test]
+    fn test_stalled() {
+        let mut progress = SyncProgress::new(WINDOW);
+        let start = Instant::now();
+
+        // Advance the ledger, then stop.
+        progress.record(start, 0);
+        progress.record(start + Duration::from_secs(10), 100);
+
+        // Ensure the estimator reports a stall once the progress has left the window.
+        let now = start + Duration::from_secs(10) + WINDOW * 2;
+        assert_eq!(SyncEstimate::Stalled, progress.estimate(now, 100, 1000));
+    }
+
+    #[test]
+    fn test_revert_resets() {
+        let mut progress = SyncProgress::new(WINDOW);
+        let start = Instant::now();
+
+        progress.record(start, 100);
+        progress.record(start + Duration::from_secs(10), 200);
+        // Ensure a revert discards the earlier samples.
+        progress.record(start + Duration::from_secs(20), 150);
+        assert_eq!(0.0, progress.blocks_per_second(start + Duration::from_secs(20)));
+    }
+}
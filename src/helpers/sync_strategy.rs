@@ -0,0 +1,225 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::helpers::{compare_chains, ChainTip};
+use snarkvm::dpc::prelude::*;
+
+use rand::{seq::SliceRandom, Rng};
+use std::{
+    cmp::Ordering,
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
+
+/// The weight of the latest block rate in the moving average of the throughput of a peer.
+const THROUGHPUT_SMOOTHING: f64 = 0.2;
+
+///
+/// The strategy for selecting the peer to sync from, among the peers with a preferred chain.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SyncStrategy {
+    /// Selects the peer with the heaviest chain, preferring the lowest round-trip time among peers on the same tip.
+    HeaviestChain,
+    /// Selects the peer with the lowest round-trip time.
+    LowestRoundTripTime,
+    /// Selects the peer that has delivered blocks at the highest rate.
+    HighestThroughput,
+    /// Selects a random peer among the given number of peers with the heaviest chains.
+    RandomAmongTop(usize),
+}
+
+impl SyncStrategy {
+    ///
+    /// Returns the candidate to sync from, or `None` if there are no candidates.
+    /// Candidates without a measured round-trip time or throughput are ranked last by those measures.
+    ///
+    pub fn select<'a, N: Network, R: Rng>(&self, candidates: &'a [SyncCandidate<N>], rng: &mut R) -> Option<&'a SyncCandidate<N>> {
+        let by_chain = |a: &SyncCandidate<N>, b: &SyncCandidate<N>| compare_chains(&a.tip, &b.tip);
+        let by_round_trip_time = |a: &SyncCandidate<N>, b: &SyncCandidate<N>| {
+            let round_trip_time = |candidate: &SyncCandidate<N>| candidate.stats.round_trip_time.unwrap_or(Duration::MAX);
+            round_trip_time(b).cmp(&round_trip_time(a))
+        };
+        let by_throughput = |a: &SyncCandidate<N>, b: &SyncCandidate<N>| {
+            let throughput = |candidate: &SyncCandidate<N>| candidate.stats.throughput.unwrap_or(0.0);
+            throughput(a).partial_cmp(&throughput(b)).unwrap_or(Ordering::Equal)
+        };
+
+        match self {
+            Self::HeaviestChain => candidates
+                .iter()
+                .max_by(|a, b| by_chain(a, b).then_with(|| by_round_trip_time(a, b))),
+            Self::LowestRoundTripTime => candidates
+                .iter()
+                .max_by(|a, b| by_round_trip_time(a, b).then_with(|| by_chain(a, b))),
+            Self::HighestThroughput => candidates.iter().max_by(|a, b| by_throughput(a, b).then_with(|| by_chain(a, b))),
+            Self::RandomAmongTop(number_of_peers) => {
+                let mut ranked = candidates.iter().collect::<Vec<_>>();
+                ranked.sort_by(|a, b| by_chain(b, a).then_with(|| by_round_trip_time(b, a)));
+                ranked.truncate((*number_of_peers).max(1));
+                ranked.choose(rng).copied()
+            }
+        }
+    }
+}
+
+///
+/// A peer with a preferred chain, which may be selected to sync from.
+///
+#[derive(Clone, Debug)]
+pub struct SyncCandidate<N: Network> {
+    /// The IP of the peer.
+    pub peer_ip: SocketAddr,
+    /// The chain tip of the peer.
+    pub tip: ChainTip<N>,
+    /// The sync statistics of the peer.
+    pub stats: SyncPeerStats,
+}
+
+///
+/// The statistics of a peer that inform the selection of the peer to sync from.
+///
+#[derive(Clone, Debug, Default)]
+pub struct SyncPeerStats {
+    /// The round-trip time of the last `Ping` to the peer.
+    round_trip_time: Option<Duration>,
+    /// The moving average of the number of blocks per second delivered by the peer.
+    throughput: Option<f64>,
+    /// The timestamp of the last block delivered by the peer.
+    last_block_at: Option<Instant>,
+}
+
+impl SyncPeerStats {
+    /// Returns the round-trip time of the last `Ping` to the peer, if it has been measured.
+    pub fn round_trip_time(&self) -> Option<Duration> {
+        self.round_trip_time
+    }
+
+    /// Returns the number of blocks per second delivered by the peer, if it has been measured.
+    pub fn throughput(&self) -> Option<f64> {
+        self.throughput
+    }
+
+    /// Updates the round-trip time of the peer.
+    pub fn update_round_trip_time(&mut self, round_trip_time: Duration) {
+        self.round_trip_time = Some(round_trip_time);
+    }
+
+    /// Records a block delivered by the peer at the given time, and updates its throughput.
+    pub fn record_block(&mut self, now: Instant) {
+        if let Some(last_block_at) = self.last_block_at.replace(now) {
+            // Bound the rate of blocks that arrive together, such as a batch read from the same socket buffer.
+            let interval = now.saturating_duration_since(last_block_at).max(Duration::from_millis(1));
+            let rate = 1.0 / interval.as_secs_f64();
+            self.throughput = Some(match self.throughput {
+                Some(throughput) => throughput + THROUGHPUT_SMOOTHING * (rate - throughput),
+                None => rate,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{dpc::testnet2::Testnet2, prelude::UniformRand};
+
+    use rand::thread_rng;
+
+    /// Returns a candidate with the given cumulative weight, round-trip time in milliseconds, and block interval in milliseconds.
+    fn candidate(
+        port: u16,
+        tip: &ChainTip<Testnet2>,
+        cumulative_weight: u128,
+        round_trip_time: Option<u64>,
+        block_interval: Option<u64>,
+    ) -> SyncCandidate<Testnet2> {
+        let mut stats = SyncPeerStats::default();
+        if let Some(round_trip_time) = round_trip_time {
+            stats.update_round_trip_time(Duration::from_millis(round_trip_time));
+        }
+        if let Some(block_interval) = block_interval {
+            let now = Instant::now();
+            stats.record_block(now);
+            stats.record_block(now + Duration::from_millis(block_interval));
+        }
+        SyncCandidate {
+            peer_ip: SocketAddr::from(([127, 0, 0, 1], port)),
+            tip: ChainTip::new(cumulative_weight, tip.block_height, tip.block_hash),
+            stats,
+        }
+    }
+
+    #[test]
+    fn test_sync_strategy() {
+        let rng = &mut thread_rng();
+        let tip = ChainTip::<Testnet2>::new(0, 10, <Testnet2 as Network>::BlockHash::rand(rng));
+
+        // A heavy but slow peer, two lighter peers on the same tip, and a peer that was never measured.
+        let candidates = vec![
+            candidate(1, &tip, 100, Some(900), Some(1000)),
+            candidate(2, &tip, 50, Some(200), Some(100)),
+            candidate(3, &tip, 50, Some(100), Some(500)),
+            candidate(4, &tip, 10, None, None),
+        ];
+        let select = |strategy: SyncStrategy| strategy.select(&candidates, &mut thread_rng()).unwrap().peer_ip.port();
+
+        // Ensure each strategy selects the expected peer.
+        assert_eq!(1, select(SyncStrategy::HeaviestChain));
+        assert_eq!(3, select(SyncStrategy::LowestRoundTripTime));
+        assert_eq!(2, select(SyncStrategy::HighestThroughput));
+
+        // Ensure the random strategy only selects among the heaviest chains.
+        for _ in 0..20 {
+            assert!([1, 3].contains(&select(SyncStrategy::RandomAmongTop(2))));
+        }
+        assert_eq!(1, select(SyncStrategy::RandomAmongTop(0)));
+        assert!(SyncStrategy::HeaviestChain.select::<Testnet2, _>(&[], rng).is_none());
+    }
+
+    #[test]
+    fn test_sync_strategy_round_trip_time_tiebreak() {
+        let rng = &mut thread_rng();
+        let tip = ChainTip::<Testnet2>::new(0, 10, <Testnet2 as Network>::BlockHash::rand(rng));
+
+        // Ensure the heaviest chain is preferred, and the lowest round-trip time breaks ties on the same tip.
+        let candidates = vec![
+            candidate(1, &tip, 100, None, None),
+            candidate(2, &tip, 100, Some(300), None),
+            candidate(3, &tip, 100, Some(100), None),
+            candidate(4, &tip, 90, Some(10), None),
+        ];
+        assert_eq!(3, SyncStrategy::HeaviestChain.select(&candidates, rng).unwrap().peer_ip.port());
+    }
+
+    #[test]
+    fn test_sync_peer_stats() {
+        let mut stats = SyncPeerStats::default();
+        assert_eq!(None, stats.throughput());
+
+        // Ensure a single block does not measure the throughput.
+        let now = Instant::now();
+        stats.record_block(now);
+        assert_eq!(None, stats.throughput());
+
+        // Ensure the throughput follows the block rate, as a moving average.
+        stats.record_block(now + Duration::from_millis(100));
+        assert!((stats.throughput().unwrap() - 10.0).abs() < 1e-9);
+        stats.record_block(now + Duration::from_millis(600));
+        let throughput = stats.throughput().unwrap();
+        assert!(throughput < 10.0 && throughput > 2.0);
+    }
+}
@@ -14,29 +14,81 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
 
-use tokio::sync::mpsc;
+use std::time::Duration;
+use tokio::{
+    sync::{mpsc, oneshot},
+    time::{timeout_at, Instant},
+};
 
+#[async_trait::async_trait]
 pub trait DropJoinable: Send + Sync + 'static {
     fn destroy(self);
+
+    /// Returns `true` if the task has completed, without waiting for it.
+    fn is_finished(&self) -> bool;
+
+    /// Waits for the task to complete until the given deadline, and returns `true` if it had to be forcibly stopped.
+    async fn join_until(self, deadline: Instant) -> bool;
 }
 
+#[async_trait::async_trait]
 impl<X: Send + Sync + 'static> DropJoinable for tokio::task::JoinHandle<X> {
     fn destroy(self) {
         self.abort();
     }
+
+    fn is_finished(&self) -> bool {
+        tokio::task::JoinHandle::is_finished(self)
+    }
+
+    async fn join_until(mut self, deadline: Instant) -> bool {
+        match timeout_at(deadline, &mut self).await {
+            Ok(_) => false,
+            Err(_) => {
+                self.abort();
+                true
+            }
+        }
+    }
 }
 
+#[async_trait::async_trait]
 impl<X: Send + Sync + 'static> DropJoinable for std::thread::JoinHandle<X> {
     fn destroy(self) {
         tokio::task::spawn_blocking(move || {
             self.join().map_err(|e| error!("Can't join a thread: {:?}", e)).ok();
         });
     }
+
+    fn is_finished(&self) -> bool {
+        std::thread::JoinHandle::is_finished(self)
+    }
+
+    /// Threads can not be aborted, so a thread that has not completed by the deadline is left detached.
+    async fn join_until(self, deadline: Instant) -> bool {
+        let join = tokio::task::spawn_blocking(move || {
+            self.join().map_err(|e| error!("Can't join a thread: {:?}", e)).ok();
+        });
+        timeout_at(deadline, join).await.is_err()
+    }
+}
+
+/// A request to the listener that holds the tasks.
+#[derive(Debug)]
+enum TasksRequest<T: DropJoinable> {
+    /// Append := (task)
+    Append(T),
+    /// Flush
+    Flush,
+    /// FlushWithTimeout := (deadline, number_of_aborted_tasks)
+    FlushWithTimeout(Instant, oneshot::Sender<usize>),
+    /// Count := ((number_of_running_tasks, number_of_completed_tasks))
+    Count(oneshot::Sender<(usize, usize)>),
 }
 
 #[derive(Debug)]
 pub struct Tasks<T: DropJoinable> {
-    sender: mpsc::UnboundedSender<Option<T>>,
+    sender: mpsc::UnboundedSender<TasksRequest<T>>,
 }
 
 impl<T: DropJoinable> Clone for Tasks<T> {
@@ -61,23 +113,65 @@ impl<T: DropJoinable> Tasks<T> {
     }
 
     pub fn append(&self, item: T) {
-        self.sender.send(Some(item)).ok();
+        self.sender.send(TasksRequest::Append(item)).ok();
     }
 
     pub fn flush(&self) {
-        self.sender.send(None).ok();
+        self.sender.send(TasksRequest::Flush).ok();
+    }
+
+    ///
+    /// Waits for the tasks to complete within the given timeout, and forcibly aborts the tasks
+    /// that are still running once it elapses. Returns the number of aborted tasks.
+    ///
+    pub async fn flush_with_timeout(&self, timeout: Duration) -> usize {
+        let (sender, receiver) = oneshot::channel();
+        let request = TasksRequest::FlushWithTimeout(Instant::now() + timeout, sender);
+        if self.sender.send(request).is_err() {
+            return 0;
+        }
+        receiver.await.unwrap_or(0)
+    }
+
+    /// Returns the number of tasks that are still running.
+    pub async fn running_count(&self) -> usize {
+        self.count().await.0
+    }
+
+    /// Returns the number of tasks that have completed, and have not been flushed yet.
+    pub async fn completed_count(&self) -> usize {
+        self.count().await.1
+    }
+
+    /// Returns the number of running tasks and the number of completed tasks, without waiting for any task.
+    async fn count(&self) -> (usize, usize) {
+        let (sender, receiver) = oneshot::channel();
+        if self.sender.send(TasksRequest::Count(sender)).is_err() {
+            return (0, 0);
+        }
+        receiver.await.unwrap_or((0, 0))
     }
 
-    async fn drop_listener(mut receiver: mpsc::UnboundedReceiver<Option<T>>) {
+    async fn drop_listener(mut receiver: mpsc::UnboundedReceiver<TasksRequest<T>>) {
         let mut values = vec![];
-        while let Some(value) = receiver.recv().await {
-            match value {
-                Some(x) => values.push(x),
-                None => {
+        while let Some(request) = receiver.recv().await {
+            match request {
+                TasksRequest::Append(x) => values.push(x),
+                TasksRequest::Flush => {
                     for value in values.drain(..) {
                         value.destroy();
                     }
                 }
+                TasksRequest::FlushWithTimeout(deadline, sender) => {
+                    let joins = values.drain(..).map(|value| value.join_until(deadline));
+                    let results = futures::future::join_all(joins).await;
+                    let number_of_aborted_tasks = results.into_iter().filter(|aborted| *aborted).count();
+                    sender.send(number_of_aborted_tasks).ok();
+                }
+                TasksRequest::Count(sender) => {
+                    let number_of_running_tasks = values.iter().filter(|value| !value.is_finished()).count();
+                    sender.send((number_of_running_tasks, values.len() - number_of_running_tasks)).ok();
+                }
             }
         }
         for value in values {
@@ -85,3 +179,48 @@ impl<T: DropJoinable> Tasks<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_flush_with_timeout() {
+        let tasks = Tasks::new();
+
+        // Append a task that completes, and a task that sleeps forever.
+        tasks.append(tokio::spawn(async {}));
+        tasks.append(tokio::spawn(futures::future::pending::<()>()));
+
+        // Ensure only the task that sleeps forever is aborted, once the timeout elapses.
+        let start = std::time::Instant::now();
+        assert_eq!(1, tasks.flush_with_timeout(Duration::from_millis(100)).await);
+        assert!(start.elapsed() < Duration::from_millis(200));
+
+        // Ensure there are no tasks left to abort.
+        assert_eq!(0, tasks.flush_with_timeout(Duration::from_millis(100)).await);
+    }
+
+    #[tokio::test]
+    async fn test_running_count() {
+        let tasks = Tasks::new();
+
+        // Append two tasks that sleep, and a task that completes.
+        tasks.append(tokio::spawn(tokio::time::sleep(Duration::from_millis(200))));
+        tasks.append(tokio::spawn(tokio::time::sleep(Duration::from_millis(200))));
+        let completed = tokio::spawn(async {});
+        while !completed.is_finished() {
+            tokio::task::yield_now().await;
+        }
+        tasks.append(completed);
+
+        // Ensure only the sleeping tasks are running.
+        assert_eq!(2, tasks.running_count().await);
+        assert_eq!(1, tasks.completed_count().await);
+
+        // Ensure no tasks are running once the sleeping tasks have completed.
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        assert_eq!(0, tasks.running_count().await);
+        assert_eq!(3, tasks.completed_count().await);
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::time::{Duration, Instant};
+
+///
+/// The state of the throttle requested by a peer, which slows down outbound requests to that peer.
+///
+/// The honored duration of each throttle is capped, and a throttle can neither be extended while
+/// it is active nor renewed until an equal cooldown has elapsed. This ensures a peer can not use
+/// throttle requests to indefinitely silence this node.
+///
+#[derive(Clone, Debug, Default)]
+pub struct PeerThrottle {
+    /// The timestamp at which the current throttle expires.
+    throttled_until: Option<Instant>,
+    /// The timestamp before which new throttle requests are ignored.
+    cooldown_until: Option<Instant>,
+}
+
+impl PeerThrottle {
+    /// Initializes a new instance of the throttle.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Records a throttle request received at the given timestamp, and returns the honored duration,
+    /// which is at most `maximum_duration`. Returns `None` if the request is ignored.
+    ///
+    pub fn request(&mut self, now: Instant, duration: Duration, maximum_duration: Duration) -> Option<Duration> {
+        // Ignore the request if a throttle is active or cooling down.
+        if self.cooldown_until.map_or(false, |cooldown_until| now < cooldown_until) {
+            return None;
+        }
+
+        let duration = duration.min(maximum_duration);
+        if duration.as_secs() == 0 {
+            return None;
+        }
+
+        let throttled_until = now + duration;
+        self.throttled_until = Some(throttled_until);
+        self.cooldown_until = Some(throttled_until + duration);
+        Some(duration)
+    }
+
+    /// Returns `true` if outbound requests to the peer are throttled at the given timestamp.
+    pub fn is_throttled(&self, now: Instant) -> bool {
+        self.throttled_until.map_or(false, |throttled_until| now < throttled_until)
+    }
+
+    /// Returns the duration from the given timestamp until the current throttle expires.
+    pub fn time_until_expiry(&self, now: Instant) -> Duration {
+        self.throttled_until
+            .map_or(Duration::ZERO, |throttled_until| throttled_until.saturating_duration_since(now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAXIMUM_DURATION: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn test_throttle_is_honored() {
+        let mut throttle = PeerThrottle::new();
+        let start = Instant::now();
+        assert!(!throttle.is_throttled(start));
+
+        // Ensure a throttle request is honored for the requested duration.
+        let duration = Duration::from_secs(10);
+        assert_eq!(Some(duration), throttle.request(start, duration, MAXIMUM_DURATION));
+        assert!(throttle.is_throttled(start + Duration::from_secs(9)));
+        assert!(!throttle.is_throttled(start + duration));
+
+        // Ensure the time until the throttle expires counts down to zero.
+        assert_eq!(Duration::from_secs(4), throttle.time_until_expiry(start + Duration::from_secs(6)));
+        assert_eq!(Duration::ZERO, throttle.time_until_expiry(start + duration + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_throttle_is_capped() {
+        let mut throttle = PeerThrottle::new();
+        let start = Instant::now();
+
+        // Ensure the honored duration is capped.
+        let duration = Duration::from_secs(u32::MAX as u64);
+        assert_eq!(Some(MAXIMUM_DURATION), throttle.request(start, duration, MAXIMUM_DURATION));
+        assert!(!throttle.is_throttled(start + MAXIMUM_DURATION));
+
+        // Ensure repeated requests can not extend the throttle, nor renew it during the cooldown.
+        assert_eq!(None, throttle.request(start + Duration::from_secs(30), duration, MAXIMUM_DURATION));
+        assert_eq!(None, throttle.request(start + MAXIMUM_DURATION, duration, MAXIMUM_DURATION));
+        assert!(!throttle.is_throttled(start + MAXIMUM_DURATION + Duration::from_secs(1)));
+
+        // Ensure a throttle may be requested again once the cooldown has elapsed.
+        let now = start + MAXIMUM_DURATION * 2;
+        assert_eq!(Some(MAXIMUM_DURATION), throttle.request(now, MAXIMUM_DURATION, MAXIMUM_DURATION));
+        assert!(throttle.is_throttled(now));
+    }
+
+    #[test]
+    fn test_empty_throttle_is_ignored() {
+        let mut throttle = PeerThrottle::new();
+        let start = Instant::now();
+
+        // Ensure a zero-duration request is ignored, and does not start a cooldown.
+        assert_eq!(None, throttle.request(start, Duration::from_secs(0), MAXIMUM_DURATION));
+        assert!(!throttle.is_throttled(start));
+        assert!(throttle.request(start, Duration::from_secs(1), MAXIMUM_DURATION).is_some());
+    }
+}
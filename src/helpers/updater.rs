@@ -15,12 +15,70 @@
 // along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
 
 use colored::Colorize;
-use self_update::{backends::github, version::bump_is_greater, Status};
+use ed25519_dalek::{PublicKey, Signature};
+use hyper::header::{HeaderValue, ACCEPT};
+use self_update::{backends::github, version::bump_is_greater, Download, Extract, Move, Status};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::{convert::TryFrom, fs, path::Path};
+
+/// The hex-encoded ed25519 public key used to sign `snarkos` releases.
+pub const SNARKOS_UPDATE_PUBLIC_KEY: &str = "1089e5849c0fdbda78bec3626ea6dc20f3e76a9d7c8045fe610c0cf86a8b3dc4";
+
+///
+/// The manifest published alongside each `snarkos` release.
+///
+#[derive(Clone, Debug, Deserialize)]
+pub struct UpdateManifest {
+    /// The version of the release.
+    pub version: String,
+    /// The hex-encoded ed25519 signature over `sha256(binary)`.
+    pub signature: String,
+}
+
+///
+/// Returns `true` if the given hex-encoded signature is a valid ed25519 signature
+/// over `sha256(binary)` for the given hex-encoded public key.
+///
+pub fn verify_signature(binary: &[u8], sig_hex: &str, pubkey_hex: &str) -> bool {
+    let verify = || -> Option<bool> {
+        let public_key = PublicKey::from_bytes(&hex::decode(pubkey_hex).ok()?).ok()?;
+        let signature = Signature::try_from(&hex::decode(sig_hex).ok()?[..]).ok()?;
+        Some(public_key.verify_strict(&Sha256::digest(binary), &signature).is_ok())
+    };
+    verify().unwrap_or(false)
+}
+
+///
+/// Verifies the given binary against the release manifest with the given public key, and replaces the current executable with it.
+/// A binary without a manifest is rejected as having an invalid signature.
+/// The given directory is used to stage the new executable, and must reside on the same filesystem.
+///
+pub fn install(binary: &[u8], manifest: Option<&UpdateManifest>, public_key: &str, staging_dir: &Path) -> Result<(), UpdaterError> {
+    match manifest {
+        Some(manifest) if verify_signature(binary, &manifest.signature, public_key) => (),
+        _ => return Err(UpdaterError::InvalidSignature),
+    }
+
+    let new_executable = staging_dir.join(format!("{}.new", Updater::SNARKOS_BIN_NAME));
+    fs::write(&new_executable, binary)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&new_executable, fs::Permissions::from_mode(0o755))?;
+    }
+
+    Move::from_source(&new_executable)
+        .replace_using_temp(&staging_dir.join(format!("{}.old", Updater::SNARKOS_BIN_NAME)))
+        .to_dest(&std::env::current_exe()?)?;
+    Ok(())
+}
 
 pub struct Updater;
 
 impl Updater {
     const SNARKOS_BIN_NAME: &'static str = "snarkos";
+    const SNARKOS_MANIFEST_NAME: &'static str = "manifest.json";
     const SNARKOS_REPO_NAME: &'static str = "snarkOS";
     const SNARKOS_REPO_OWNER: &'static str = "AleoHQ";
 
@@ -39,25 +97,71 @@ impl Updater {
         Ok(output)
     }
 
-    /// Update `snarkOS` to the specified release.
+    /// Update `snarkOS` to the specified release, after verifying the signature of the release binary.
+    /// A release without a manifest signed with `SNARKOS_UPDATE_PUBLIC_KEY` is not installed.
     pub fn update_to_release(show_output: bool, version: Option<String>) -> Result<Status, UpdaterError> {
-        let mut update_builder = github::Update::configure();
+        let current_version = env!("CARGO_PKG_VERSION");
 
-        update_builder
+        let updater = github::Update::configure()
             .repo_owner(Self::SNARKOS_REPO_OWNER)
             .repo_name(Self::SNARKOS_REPO_NAME)
             .bin_name(Self::SNARKOS_BIN_NAME)
-            .current_version(env!("CARGO_PKG_VERSION"))
-            .show_download_progress(show_output)
-            .no_confirm(true)
-            .show_output(show_output);
+            .current_version(current_version)
+            .build()?;
+
+        let release = match version {
+            None => {
+                let release = updater.get_latest_release()?;
+                if !bump_is_greater(current_version, &release.version)? {
+                    return Ok(Status::UpToDate(current_version.to_string()));
+                }
+                release
+            }
+            Some(v) => updater.get_release_version(&v)?,
+        };
+
+        // Locate the release binary for this target.
+        let target = self_update::get_target();
+        let binary_asset = release
+            .asset_for(target)
+            .ok_or_else(|| UpdaterError::MissingAsset(target.to_string()))?;
 
-        let status = match version {
-            None => update_builder.build()?.update()?,
-            Some(v) => update_builder.target_version_tag(&v).build()?.update()?,
+        // Locate the manifest containing the signature of the release binary, which is rejected in `install` if missing.
+        let manifest = match release.assets.iter().find(|asset| asset.name == Self::SNARKOS_MANIFEST_NAME) {
+            Some(manifest_asset) => {
+                let manifest = Self::download(&manifest_asset.download_url, false)?;
+                Some(serde_json::from_slice::<UpdateManifest>(&manifest)?)
+            }
+            None => None,
         };
 
-        Ok(status)
+        // Download and extract the release binary into a staging directory next to the current executable.
+        let current_executable = std::env::current_exe()?;
+        let staging_name = format!(".{}-update-{}", Self::SNARKOS_BIN_NAME, release.version);
+        let staging_dir = current_executable.parent().unwrap_or_else(|| Path::new(".")).join(staging_name);
+        fs::create_dir_all(&staging_dir)?;
+
+        let result = (|| -> Result<(), UpdaterError> {
+            let archive_path = staging_dir.join(&binary_asset.name);
+            fs::write(&archive_path, Self::download(&binary_asset.download_url, show_output)?)?;
+
+            let bin_name = format!("{}{}", Self::SNARKOS_BIN_NAME, std::env::consts::EXE_SUFFIX);
+            Extract::from_source(&archive_path).extract_file(&staging_dir, &bin_name)?;
+
+            install(
+                &fs::read(staging_dir.join(&bin_name))?,
+                manifest.as_ref(),
+                SNARKOS_UPDATE_PUBLIC_KEY,
+                &staging_dir,
+            )
+        })();
+        let _ = fs::remove_dir_all(&staging_dir);
+        result?;
+
+        if show_output {
+            println!("Verified the signature of snarkos v{}", release.version);
+        }
+        Ok(Status::Updated(release.version))
     }
 
     /// Check if there is an available update for `aleo` and return the newest release.
@@ -79,6 +183,16 @@ impl Updater {
         }
     }
 
+    /// Downloads the release asset at the given URL.
+    fn download(url: &str, show_progress: bool) -> Result<Vec<u8>, UpdaterError> {
+        let mut bytes = Vec::new();
+        Download::from_url(url)
+            .set_header(ACCEPT, HeaderValue::from_static("application/octet-stream"))
+            .show_progress(show_progress)
+            .download_to(&mut bytes)?;
+        Ok(bytes)
+    }
+
     /// Display the CLI message.
     pub fn print_cli() -> String {
         if let Ok(latest_version) = Self::update_available() {
@@ -99,6 +213,12 @@ pub enum UpdaterError {
 
     #[error("The current version {} is more recent than the release version {}", _0, _1)]
     OldReleaseVersion(String, String),
+
+    #[error("The release is missing the asset {}", _0)]
+    MissingAsset(String),
+
+    #[error("The signature of the release binary is invalid")]
+    InvalidSignature,
 }
 
 impl From<self_update::errors::Error> for UpdaterError {
@@ -106,3 +226,67 @@ impl From<self_update::errors::Error> for UpdaterError {
         UpdaterError::Crate("self_update", error.to_string())
     }
 }
+
+impl From<std::io::Error> for UpdaterError {
+    fn from(error: std::io::Error) -> Self {
+        UpdaterError::Crate("std::io", error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for UpdaterError {
+    fn from(error: serde_json::Error) -> Self {
+        UpdaterError::Crate("serde_json", error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_PUBLIC_KEY: &str = "89a7cbf2b5f018636f3a863edc23f1924a36b5a89d0a27b9660db91e5cb2ad23";
+    const TEST_SIGNATURE: &str = "8564b8dc921873cc669e5cd44cf75db8201950a3eecf30e48cfe93dcc0b0099b\
+                                  a981deab94331747be7382bae41e2715db153637dd8b66affa7df8f35c16fb0b";
+    const TEST_BINARY: &[u8] = b"snarkos";
+
+    #[test]
+    fn test_verify_signature() {
+        assert!(verify_signature(TEST_BINARY, TEST_SIGNATURE, TEST_PUBLIC_KEY));
+
+        // Verification fails for a different public key.
+        assert!(!verify_signature(TEST_BINARY, TEST_SIGNATURE, SNARKOS_UPDATE_PUBLIC_KEY));
+
+        // Verification fails for malformed inputs.
+        assert!(!verify_signature(TEST_BINARY, "", TEST_PUBLIC_KEY));
+        assert!(!verify_signature(TEST_BINARY, TEST_SIGNATURE, "not hex"));
+    }
+
+    #[test]
+    fn test_verify_signature_of_tampered_binary() {
+        let mut tampered = TEST_BINARY.to_vec();
+        tampered[0] ^= 1;
+        assert!(!verify_signature(&tampered, TEST_SIGNATURE, TEST_PUBLIC_KEY));
+    }
+
+    #[test]
+    fn test_install_rejects_invalid_signature() {
+        let manifest = UpdateManifest {
+            version: "0.0.0".to_string(),
+            signature: TEST_SIGNATURE.to_string(),
+        };
+        let staging_dir = tempfile::tempdir().unwrap();
+
+        let result = install(TEST_BINARY, Some(&manifest), SNARKOS_UPDATE_PUBLIC_KEY, staging_dir.path());
+        assert!(matches!(result, Err(UpdaterError::InvalidSignature)));
+        assert!(!staging_dir.path().join("snarkos.new").exists());
+    }
+
+    #[test]
+    fn test_install_rejects_missing_manifest() {
+        let staging_dir = tempfile::tempdir().unwrap();
+
+        // Ensure a binary is not installed without a manifest.
+        let result = install(TEST_BINARY, None, TEST_PUBLIC_KEY, staging_dir.path());
+        assert!(matches!(result, Err(UpdaterError::InvalidSignature)));
+        assert!(!staging_dir.path().join("snarkos.new").exists());
+    }
+}
@@ -15,8 +15,30 @@
 // along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-    helpers::{block_requests::*, BlockRequest, CircularMap, NodeType, State},
+    helpers::{
+        block_requests::*,
+        fork_branches,
+        fork_branches_to_prune,
+        BackwardSyncWindow,
+        BlockRequest,
+        BlockVerificationPermit,
+        CircularMap,
+        ContiguousBlocks,
+        Event,
+        Events,
+        FairQueue,
+        ForkBranch,
+        NodeCapabilities,
+        NodeType,
+        PriorityScheduler,
+        State,
+        SyncDirection,
+        SyncPeerStats,
+        SyncProgress,
+        TaskPriority,
+    },
     Data,
+    DisconnectReason,
     Environment,
     LedgerReader,
     Message,
@@ -25,20 +47,21 @@ use crate::{
     ProverRequest,
     ProverRouter,
 };
-use snarkos_storage::{storage::Storage, BlockLocators, LedgerState, MAXIMUM_LINEAR_BLOCK_LOCATORS};
+use snarkos_storage::{storage::Storage, BlockLocators, Checkpoints, InvalidBlock, LedgerState, MAXIMUM_LINEAR_BLOCK_LOCATORS};
 use snarkvm::dpc::prelude::*;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use chrono::Utc;
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     net::SocketAddr,
     path::Path,
     sync::{atomic::Ordering, Arc},
     time::{Duration, Instant},
 };
 use tokio::{
-    sync::{mpsc, oneshot, Mutex, RwLock},
+    sync::{mpsc, oneshot, Mutex, Notify, RwLock},
     task,
 };
 
@@ -56,21 +79,56 @@ type LedgerHandler<N> = mpsc::Receiver<LedgerRequest<N>>;
 ///
 #[derive(Debug)]
 pub enum LedgerRequest<N: Network> {
-    /// BlockResponse := (peer_ip, block, prover_router)
-    BlockResponse(SocketAddr, Block<N>, ProverRouter<N>),
+    /// BlockResponse := (peer_ip, block, prover_router, verification_permit)
+    BlockResponse(SocketAddr, Block<N>, ProverRouter<N>, Option<BlockVerificationPermit<'static>>),
+    /// BlockUnavailable := (peer_ip, start_block_height, end_block_height (inclusive))
+    BlockUnavailable(SocketAddr, u32, u32),
+    /// BlockResponseEnd := (peer_ip, highest_available_block_height)
+    BlockResponseEnd(SocketAddr, u32),
     /// Disconnect := (peer_ip, message)
     Disconnect(SocketAddr, String),
     /// Failure := (peer_ip, failure)
     Failure(SocketAddr, String),
     /// Heartbeat := (prover_router)
     Heartbeat(ProverRouter<N>),
-    /// Pong := (peer_ip, node_type, status, is_fork, block_locators)
-    Pong(SocketAddr, NodeType, State, Option<bool>, BlockLocators<N>),
-    /// UnconfirmedBlock := (peer_ip, block, prover_router)
-    UnconfirmedBlock(SocketAddr, Block<N>, ProverRouter<N>),
+    /// Pong := (peer_ip, node_type, capabilities, status, is_fork, block_locators, round_trip_time)
+    Pong(SocketAddr, NodeType, NodeCapabilities, State, Option<bool>, BlockLocators<N>, Option<Duration>),
+    /// UnconfirmedBlock := (peer_ip, block, prover_router, verification_permit)
+    UnconfirmedBlock(SocketAddr, Block<N>, ProverRouter<N>, Option<BlockVerificationPermit<'static>>),
 }
 
-pub type PeersState<N> = HashMap<SocketAddr, Option<(NodeType, State, Option<bool>, u32, BlockLocators<N>)>>;
+impl<N: Network> LedgerRequest<N> {
+    /// Returns the IP of the peer that the request originates from, if it exists.
+    fn peer_ip(&self) -> Option<SocketAddr> {
+        match self {
+            Self::BlockResponse(peer_ip, ..)
+            | Self::BlockUnavailable(peer_ip, ..)
+            | Self::BlockResponseEnd(peer_ip, ..)
+            | Self::Disconnect(peer_ip, ..)
+            | Self::Failure(peer_ip, ..)
+            | Self::Pong(peer_ip, ..)
+            | Self::UnconfirmedBlock(peer_ip, ..) => Some(*peer_ip),
+            Self::Heartbeat(..) => None,
+        }
+    }
+
+    /// Returns `true` if the request must not be dropped when the inbound queue of its peer is full.
+    fn requires_delivery(&self) -> bool {
+        matches!(self, Self::BlockResponse(..) | Self::Failure(..) | Self::Pong(..))
+    }
+}
+
+pub type PeersState<N> = HashMap<SocketAddr, Option<(NodeType, NodeCapabilities, State, Option<bool>, u32, BlockLocators<N>)>>;
+
+/// Shorthand for a read-only handle to the ledger state of each peer.
+pub(crate) type PeersStateReader<N> = Arc<RwLock<PeersState<N>>>;
+
+/// Shorthand for a read-only handle to the sync progress of the ledger.
+pub(crate) type SyncProgressReader = Arc<RwLock<SyncProgress>>;
+
+/// Shorthand for a read-only handle to the unconfirmed blocks of the ledger, keyed by previous block hash.
+pub(crate) type UnconfirmedBlocksReader<N> =
+    Arc<RwLock<CircularMap<<N as Network>::BlockHash, Block<N>, { MAXIMUM_UNCONFIRMED_BLOCKS }>>>;
 
 ///
 /// A ledger for a specific network on the node server.
@@ -88,26 +146,44 @@ pub struct Ledger<N: Network, E: Environment> {
     /// A lock to ensure methods that need to be mutually-exclusive are enforced.
     /// In this context, `add_block`, and `revert_to_block_height` must be mutually-exclusive.
     canon_lock: Arc<Mutex<()>>,
-    /// A map of previous block hashes to unconfirmed blocks.
-    unconfirmed_blocks: RwLock<CircularMap<N::BlockHash, Block<N>, { MAXIMUM_UNCONFIRMED_BLOCKS }>>,
-    /// The map of each peer to their ledger state := (node_type, status, is_fork, latest_block_height, block_locators).
-    peers_state: RwLock<PeersState<N>>,
+    /// The trusted block hashes, below which blocks skip the verification of their proofs.
+    checkpoints: Checkpoints<N>,
+    /// A map of previous block hashes to unconfirmed blocks, including the blocks of any fork branches.
+    /// Only the most recent `MAXIMUM_UNCONFIRMED_BLOCKS` are retained, as the oldest are evicted first,
+    /// and only `E::MAXIMUM_TRACKED_FORKS` branches, as the oldest stale forks are pruned.
+    unconfirmed_blocks: UnconfirmedBlocksReader<N>,
+    /// The requested blocks that were received ahead of the canonical chain, along with the peer that sent them.
+    sync_blocks: RwLock<ContiguousBlocks<(SocketAddr, Block<N>)>>,
+    /// The window of blocks that is being requested from the tip downward, while syncing in reverse order.
+    backward_sync: RwLock<Option<BackwardSyncWindow>>,
+    /// The map of each peer to their ledger state := (node_type, capabilities, status, is_fork, latest_block_height, block_locators).
+    peers_state: Arc<RwLock<PeersState<N>>>,
     /// The map of each peer to their block requests := HashMap<(block_height, block_hash), timestamp>
     block_requests: RwLock<HashMap<SocketAddr, HashMap<BlockRequest<N>, i64>>>,
+    /// The map of each peer to the highest block height that it reported as unavailable.
+    unavailable_blocks: RwLock<HashMap<SocketAddr, u32>>,
+    /// The map of each peer to their sync statistics, which inform the selection of the peer to sync from.
+    sync_peer_stats: RwLock<HashMap<SocketAddr, SyncPeerStats>>,
     /// A lock to ensure methods that need to be mutually-exclusive are enforced.
     /// In this context, `update_ledger`, `add_block`, and `update_block_requests` must be mutually-exclusive.
     block_requests_lock: Arc<Mutex<()>>,
     /// The timestamp of the last successful block update.
     last_block_update_timestamp: RwLock<Instant>,
+    /// The estimator of the sync rate of the ledger.
+    sync_progress: Arc<RwLock<SyncProgress>>,
     /// The map of each peer to their failure messages := (failure_message, timestamp).
     failures: RwLock<HashMap<SocketAddr, Vec<(String, i64)>>>,
     /// The peers router of the node.
     peers_router: PeersRouter<N, E>,
+    /// The event channel of the node.
+    events: Events<N>,
+    /// The inbound queue of the ledger requests, which serves the peers in a round-robin order.
+    inbound_queue: Arc<parking_lot::Mutex<FairQueue<Option<SocketAddr>, LedgerRequest<N>>>>,
 }
 
 impl<N: Network, E: Environment> Ledger<N, E> {
     /// Initializes a new instance of the ledger.
-    pub async fn open<S: Storage, P: AsRef<Path> + Copy>(path: P, peers_router: PeersRouter<N, E>) -> Result<Arc<Self>> {
+    pub async fn open<S: Storage, P: AsRef<Path> + Copy>(path: P, peers_router: PeersRouter<N, E>, events: Events<N>) -> Result<Arc<Self>> {
         // Initialize an mpsc channel for sending requests to the `Ledger` struct.
         let (ledger_router, mut ledger_handler) = mpsc::channel(1024);
 
@@ -117,28 +193,80 @@ impl<N: Network, E: Environment> Ledger<N, E> {
             canon: Arc::new(LedgerState::open_writer::<S, P>(path)?),
             canon_reader: LedgerState::open_reader::<S, P>(path)?,
             canon_lock: Arc::new(Mutex::new(())),
+            checkpoints: Checkpoints::from_strs(E::CHECKPOINTS)?,
             unconfirmed_blocks: Default::default(),
+            sync_blocks: Default::default(),
+            backward_sync: Default::default(),
             peers_state: Default::default(),
             block_requests: Default::default(),
+            unavailable_blocks: Default::default(),
+            sync_peer_stats: Default::default(),
             block_requests_lock: Arc::new(Mutex::new(())),
             last_block_update_timestamp: RwLock::new(Instant::now()),
+            sync_progress: Arc::new(RwLock::new(SyncProgress::new(Duration::from_secs(E::SYNC_PROGRESS_WINDOW_IN_SECS)))),
             failures: Default::default(),
             peers_router,
+            events,
+            // Initialize the inbound queue of the ledger, so that a single peer flooding the ledger
+            // with requests does not starve the other peers.
+            inbound_queue: Arc::new(parking_lot::Mutex::new(FairQueue::new(E::MAXIMUM_INBOUND_REQUESTS_PER_PEER))),
         });
+        let queue = ledger.inbound_queue.clone();
+        let queue_notify = Arc::new(Notify::new());
 
-        // Initialize the handler for the ledger.
+        // Initialize the dispatcher for the ledger.
         {
-            let ledger = ledger.clone();
+            let queue = queue.clone();
+            let queue_notify = queue_notify.clone();
             let (router, handler) = oneshot::channel();
             E::tasks().append(task::spawn(async move {
                 // Notify the outer function that the task is ready.
                 let _ = router.send(());
                 // Asynchronously wait for a ledger request.
                 while let Some(request) = ledger_handler.recv().await {
-                    // Hold the ledger write lock briefly, to update the state of the ledger.
-                    // Note: Do not wrap this call in a `task::spawn` as `BlockResponse` messages
-                    // will end up being processed out of order.
-                    ledger.update(request).await;
+                    let peer_ip = request.peer_ip();
+                    let mut queue = queue.lock();
+                    match request {
+                        // A disconnect supersedes any pending requests from the peer.
+                        LedgerRequest::Disconnect(..) => queue.remove(&peer_ip),
+                        // A pong supersedes any pending pong from the peer, so a peer flooding pongs holds a single slot.
+                        LedgerRequest::Pong(..) => queue.remove_if(&peer_ip, |request| matches!(request, LedgerRequest::Pong(..))),
+                        _ => (),
+                    }
+                    // The requests that must be delivered may exceed the capacity of the queue of the peer, up to twice its size,
+                    // so the dispatcher never waits on a single peer. Beyond that, the peer is flooding the ledger.
+                    let capacity = match request.requires_delivery() {
+                        true => 2 * E::MAXIMUM_INBOUND_REQUESTS_PER_PEER,
+                        false => E::MAXIMUM_INBOUND_REQUESTS_PER_PEER,
+                    };
+                    match queue.push_with_capacity(peer_ip, request, capacity) {
+                        Ok(()) => queue_notify.notify_one(),
+                        Err(_) => warn!("Dropping a ledger request from {:?}, as its inbound queue is full", peer_ip),
+                    }
+                }
+            }));
+            // Wait until the ledger dispatcher is ready.
+            let _ = handler.await;
+        }
+
+        // Initialize the handler for the ledger.
+        {
+            let ledger = ledger.clone();
+            let (router, handler) = oneshot::channel();
+            E::tasks().append(task::spawn(async move {
+                // Notify the outer function that the task is ready.
+                let _ = router.send(());
+                loop {
+                    // Take the next ledger request, serving each peer in turn.
+                    let request = queue.lock().pop();
+                    match request {
+                        // Hold the ledger write lock briefly, to update the state of the ledger.
+                        // Note: Do not wrap this call in a `task::spawn` as `BlockResponse` messages
+                        // will end up being processed out of order.
+                        Some(request) => ledger.update(request).await,
+                        // Asynchronously wait for a ledger request.
+                        None => queue_notify.notified().await,
+                    }
                 }
             }));
             // Wait until the ledger handler is ready.
@@ -162,6 +290,29 @@ impl<N: Network, E: Environment> Ledger<N, E> {
         self.ledger_router.clone()
     }
 
+    /// Returns a handle to the event channel of the node.
+    pub fn events(&self) -> Events<N> {
+        self.events.clone()
+    }
+
+    /// Returns a handle to the ledger state of each peer.
+    /// The handle must only be used for reads, as the ledger is the sole writer of the peers state.
+    pub(crate) fn peers_state(&self) -> PeersStateReader<N> {
+        self.peers_state.clone()
+    }
+
+    /// Returns a handle to the sync progress of the ledger.
+    /// The handle must only be used for reads, as the ledger is the sole writer of the sync progress.
+    pub(crate) fn sync_progress(&self) -> SyncProgressReader {
+        self.sync_progress.clone()
+    }
+
+    /// Returns a handle to the unconfirmed blocks of the ledger.
+    /// The handle must only be used for reads, as the ledger is the sole writer of the unconfirmed blocks.
+    pub(crate) fn unconfirmed_blocks(&self) -> UnconfirmedBlocksReader<N> {
+        self.unconfirmed_blocks.clone()
+    }
+
     pub(super) async fn shut_down(&self) -> (Arc<Mutex<()>>, Arc<Mutex<()>>, Arc<parking_lot::RwLock<()>>) {
         debug!("Ledger is shutting down...");
 
@@ -171,12 +322,13 @@ impl<N: Network, E: Environment> Ledger<N, E> {
 
         // Clear the unconfirmed blocks.
         self.unconfirmed_blocks.write().await.clear();
+        self.sync_blocks.write().await.clear();
         trace!("[ShuttingDown] Pending queue has been cleared");
 
         // Disconnect all connected peers.
         let connected_peers = self.peers_state.read().await.keys().copied().collect::<Vec<_>>();
         for peer_ip in connected_peers {
-            self.disconnect(peer_ip, "shutting down").await;
+            self.disconnect(peer_ip, DisconnectReason::ShuttingDown, "shutting down").await;
         }
         trace!("[ShuttingDown] Disconnect message has been sent to all connected peers");
 
@@ -195,11 +347,32 @@ impl<N: Network, E: Environment> Ledger<N, E> {
     ///
     pub(super) async fn update(&self, request: LedgerRequest<N>) {
         match request {
-            LedgerRequest::BlockResponse(peer_ip, block, prover_router) => {
+            // The verification permit of a block is held until its request is processed, which bounds the verifications in flight.
+            LedgerRequest::BlockResponse(peer_ip, block, prover_router, _verification_permit) => {
                 // Remove the block request from the ledger.
                 if self.remove_block_request(peer_ip, block.height()).await {
-                    // On success, process the block response.
-                    self.add_block(block, &prover_router).await;
+                    // Record the delivery of the block, to measure the throughput of the peer.
+                    self.sync_peer_stats
+                        .write()
+                        .await
+                        .entry(peer_ip)
+                        .or_default()
+                        .record_block(Instant::now());
+                    // Buffer the block until the blocks before it arrive, as the blocks may be requested in reverse order.
+                    let blocks = {
+                        let mut sync_blocks = self.sync_blocks.write().await;
+                        sync_blocks.insert(block.height(), (peer_ip, block));
+                        sync_blocks.pop_ready(self.canon.latest_block_height().saturating_add(1))
+                    };
+                    // On success, process the block responses in forward order.
+                    let mut is_block_added = false;
+                    for (_, block) in blocks {
+                        is_block_added |= self.add_block(block, &prover_router).await;
+                    }
+                    if is_block_added {
+                        // Add any pending blocks that arrived out of order.
+                        self.add_unconfirmed_blocks(&prover_router).await;
+                    }
                     // Check if syncing with this peer is complete.
                     if self
                         .block_requests
@@ -210,12 +383,25 @@ impl<N: Network, E: Environment> Ledger<N, E> {
                         .unwrap_or(false)
                     {
                         trace!("All block requests with {} have been processed", peer_ip);
-                        self.update_block_requests().await;
                     }
+                    // Pipeline the next block requests, if there is capacity.
+                    self.update_block_requests().await;
                 }
             }
+            LedgerRequest::BlockUnavailable(peer_ip, start_block_height, end_block_height) => {
+                // Mark the blocks as unavailable from the peer, and remove its block requests for them.
+                self.add_unavailable_blocks(peer_ip, start_block_height, end_block_height).await;
+                // Request the blocks from a different peer.
+                self.update_block_requests().await;
+            }
+            LedgerRequest::BlockResponseEnd(peer_ip, block_height) => {
+                // Complete the block requests to the peer beyond its highest available block.
+                self.end_block_requests(peer_ip, block_height).await;
+                // Request the remaining blocks from a different peer, if one is ahead.
+                self.update_block_requests().await;
+            }
             LedgerRequest::Disconnect(peer_ip, message) => {
-                self.disconnect(peer_ip, &message).await;
+                self.disconnect(peer_ip, DisconnectReason::NoReasonGiven, &message).await;
             }
             LedgerRequest::Failure(peer_ip, failure) => {
                 self.add_failure(peer_ip, failure).await;
@@ -246,17 +432,31 @@ impl<N: Network, E: Environment> Ledger<N, E> {
                     self.peers_state.read().await.len()
                 );
             }
-            LedgerRequest::Pong(peer_ip, node_type, status, is_fork, block_locators) => {
+            LedgerRequest::Pong(peer_ip, node_type, capabilities, status, is_fork, block_locators, round_trip_time) => {
                 // Ensure the peer has been initialized in the ledger.
                 self.initialize_peer(peer_ip).await;
+                // Update the round-trip time of the peer.
+                if let Some(round_trip_time) = round_trip_time {
+                    self.sync_peer_stats
+                        .write()
+                        .await
+                        .entry(peer_ip)
+                        .or_default()
+                        .update_round_trip_time(round_trip_time);
+                }
                 // Process the pong.
-                self.update_peer(peer_ip, node_type, status, is_fork, block_locators).await;
+                self.update_peer(peer_ip, node_type, capabilities, status, is_fork, block_locators)
+                    .await;
             }
-            LedgerRequest::UnconfirmedBlock(peer_ip, block, prover_router) => {
+            LedgerRequest::UnconfirmedBlock(peer_ip, block, prover_router, _verification_permit) => {
                 // Ensure the node is not peering.
                 if !E::status().is_peering() {
                     // Process the unconfirmed block.
                     self.add_block(block.clone(), &prover_router).await;
+                    // Observer nodes only follow the chain, and never relay blocks.
+                    if E::NODE_TYPE == NodeType::Observer {
+                        return;
+                    }
                     // Propagate the unconfirmed block to the connected peers.
                     let message = Message::UnconfirmedBlock(block.height(), block.hash(), Data::Object(block));
                     let request = PeersRequest::MessagePropagate(peer_ip, message);
@@ -271,7 +471,7 @@ impl<N: Network, E: Environment> Ledger<N, E> {
     ///
     /// Disconnects the given peer from the ledger.
     ///
-    async fn disconnect(&self, peer_ip: SocketAddr, message: &str) {
+    async fn disconnect(&self, peer_ip: SocketAddr, reason: DisconnectReason, message: &str) {
         info!("Disconnecting from {} ({})", peer_ip, message);
         // Remove all entries of the peer from the ledger.
         self.remove_peer(&peer_ip).await;
@@ -280,7 +480,7 @@ impl<N: Network, E: Environment> Ledger<N, E> {
         // Send a `Disconnect` message to the peer.
         if let Err(error) = self
             .peers_router
-            .send(PeersRequest::MessageSend(peer_ip, Message::Disconnect))
+            .send(PeersRequest::MessageSend(peer_ip, Message::Disconnect(reason)))
             .await
         {
             warn!("[Disconnect] {}", error);
@@ -303,7 +503,7 @@ impl<N: Network, E: Environment> Ledger<N, E> {
         // Send a `Disconnect` message to the peer.
         if let Err(error) = self
             .peers_router
-            .send(PeersRequest::MessageSend(peer_ip, Message::Disconnect))
+            .send(PeersRequest::MessageSend(peer_ip, Message::Disconnect(DisconnectReason::NoReasonGiven)))
             .await
         {
             warn!("[Disconnect] {}", error);
@@ -314,6 +514,29 @@ impl<N: Network, E: Environment> Ledger<N, E> {
         }
     }
 
+    ///
+    /// Disconnects and bans the given peer from the ledger, for `E::INCOMPATIBLE_FORK_BAN_IN_SECS`.
+    ///
+    async fn disconnect_and_ban(&self, peer_ip: SocketAddr, message: &str) {
+        info!("Disconnecting and banning {} ({})", peer_ip, message);
+        // Remove all entries of the peer from the ledger.
+        self.remove_peer(&peer_ip).await;
+        // Update the status of the ledger.
+        self.update_status().await;
+        // Send a `Disconnect` message to the peer.
+        if let Err(error) = self
+            .peers_router
+            .send(PeersRequest::MessageSend(peer_ip, Message::Disconnect(DisconnectReason::IncompatibleFork)))
+            .await
+        {
+            warn!("[Disconnect] {}", error);
+        }
+        // Route a `PeerBanned` to the peers.
+        if let Err(error) = self.peers_router.send(PeersRequest::PeerBanned(peer_ip)).await {
+            warn!("[PeerBanned] {}", error);
+        }
+    }
+
     ///
     /// Performs a heartbeat update for the sync nodes.
     ///
@@ -330,7 +553,7 @@ impl<N: Network, E: Environment> Ledger<N, E> {
 
             // Check if any of the peers are ahead and have a larger block height.
             for (peer_ip, peer_state) in peers_state.iter() {
-                if let Some((node_type, status, Some(_), block_height, block_locators)) = peer_state {
+                if let Some((node_type, _, status, Some(_), block_height, block_locators)) = peer_state {
                     // Retrieve the cumulative weight, defaulting to the block height if it does not exist.
                     let cumulative_weight = match block_locators.get_cumulative_weight(*block_height) {
                         Some(cumulative_weight) => cumulative_weight,
@@ -362,16 +585,7 @@ impl<N: Network, E: Environment> Ledger<N, E> {
     ///
     async fn update_ledger(&self, prover_router: &ProverRouter<N>) {
         // Check for candidate blocks to fast forward the ledger.
-        let mut block_hash = self.canon.latest_block_hash();
-        let unconfirmed_blocks_snapshot = self.unconfirmed_blocks.read().await.clone();
-        while let Some(unconfirmed_block) = unconfirmed_blocks_snapshot.get(&block_hash) {
-            // Attempt to add the unconfirmed block.
-            match self.add_block(unconfirmed_block.clone(), prover_router).await {
-                // Upon success, update the block hash iterator.
-                true => block_hash = unconfirmed_block.hash(),
-                false => break,
-            }
-        }
+        self.add_unconfirmed_blocks(prover_router).await;
 
         // If the timestamp of the last block increment has surpassed the preset limit,
         // the ledger is likely syncing from invalid state, and should revert by one block.
@@ -397,6 +611,82 @@ impl<N: Network, E: Environment> Ledger<N, E> {
             self.revert_to_block_height(self.canon.latest_block_height().saturating_sub(1))
                 .await;
         }
+
+        // Update the depth of the deepest unconfirmed block, as advertised to peers.
+        self.update_orphan_depth().await;
+    }
+
+    ///
+    /// Updates the depth of the deepest unconfirmed block below the latest block, as advertised to peers.
+    ///
+    async fn update_orphan_depth(&self) {
+        let latest_block_height = self.canon.latest_block_height();
+        let unconfirmed_blocks = self.unconfirmed_blocks.read().await;
+        let block_heights = unconfirmed_blocks.values().map(|block| block.height());
+        E::orphan_depth().store(Self::orphan_depth(block_heights, latest_block_height), Ordering::SeqCst);
+    }
+
+    ///
+    /// Returns the depth of the deepest of the given unconfirmed block heights below the given latest block height.
+    /// An unconfirmed block at the latest block height is on a fork one block deep, and one above it is on no fork.
+    ///
+    fn orphan_depth(block_heights: impl Iterator<Item = u32>, latest_block_height: u32) -> u32 {
+        block_heights
+            .filter(|block_height| *block_height <= latest_block_height)
+            .map(|block_height| latest_block_height - block_height + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    ///
+    /// Prunes the oldest branches of unconfirmed blocks that are on stale forks, so that at most
+    /// `E::MAXIMUM_TRACKED_FORKS` branches are retained.
+    ///
+    async fn prune_fork_branches(&self) {
+        let mut unconfirmed_blocks = self.unconfirmed_blocks.write().await;
+        let branches = Self::fork_branches(&unconfirmed_blocks);
+        let pruned_branches = fork_branches_to_prune(
+            branches,
+            self.canon.latest_block_height(),
+            self.canon.latest_block_hash(),
+            N::ALEO_MAXIMUM_FORK_DEPTH,
+            E::MAXIMUM_TRACKED_FORKS,
+        );
+        for branch in pruned_branches {
+            trace!("Pruning the fork branch at block {} ({})", branch.tip_block_height, branch.tip_block_hash);
+            for previous_block_hash in &branch.previous_block_hashes {
+                unconfirmed_blocks.remove(previous_block_hash);
+            }
+        }
+    }
+
+    ///
+    /// Returns the branches of the given unconfirmed blocks.
+    ///
+    pub(crate) fn fork_branches(
+        unconfirmed_blocks: &CircularMap<N::BlockHash, Block<N>, { MAXIMUM_UNCONFIRMED_BLOCKS }>,
+    ) -> Vec<ForkBranch<N::BlockHash>> {
+        fork_branches(
+            unconfirmed_blocks
+                .values()
+                .map(|block| (block.previous_block_hash(), block.hash(), block.height(), block.timestamp())),
+        )
+    }
+
+    ///
+    /// Adds the unconfirmed blocks that extend the canonical chain, in order of block height.
+    ///
+    async fn add_unconfirmed_blocks(&self, prover_router: &ProverRouter<N>) {
+        let mut block_hash = self.canon.latest_block_hash();
+        let unconfirmed_blocks_snapshot = self.unconfirmed_blocks.read().await.clone();
+        while let Some(unconfirmed_block) = unconfirmed_blocks_snapshot.get(&block_hash) {
+            // Attempt to add the unconfirmed block.
+            match self.add_block(unconfirmed_block.clone(), prover_router).await {
+                // Upon success, update the block hash iterator.
+                true => block_hash = unconfirmed_block.hash(),
+                false => break,
+            }
+        }
     }
 
     ///
@@ -429,7 +719,7 @@ impl<N: Network, E: Environment> Ledger<N, E> {
             let latest_cumulative_weight = self.canon.latest_cumulative_weight();
             // Iterate through the connected peers, to determine if the ledger state is out of date.
             for (_, peer_state) in self.peers_state.read().await.iter() {
-                if let Some((_, _, Some(_), block_height, block_locators)) = peer_state {
+                if let Some((_, _, _, Some(_), block_height, block_locators)) = peer_state {
                     // Retrieve the cumulative weight, defaulting to the block height if it does not exist.
                     let cumulative_weight = match block_locators.get_cumulative_weight(*block_height) {
                         Some(cumulative_weight) => cumulative_weight,
@@ -445,19 +735,39 @@ impl<N: Network, E: Environment> Ledger<N, E> {
             }
         }
 
-        // If the node is `Peering` or `Syncing`, it should not be mining.
-        if status == State::Peering || status == State::Syncing {
+        // If the node is `Peering` or `Syncing`, or is waiting for enough peers to mine, it should not be mining.
+        let number_of_connected_peers = self.peers_state.read().await.len();
+        if Self::is_mining_permitted(status, number_of_connected_peers) {
+            // Set the terminator bit to `false` to ensure it is allowed to mine, unless mining is paused.
+            E::terminator().store(E::is_mining_paused(), Ordering::SeqCst);
+        } else {
+            if E::NODE_TYPE == NodeType::Miner && number_of_connected_peers < E::MINIMUM_PEERS_TO_MINE {
+                debug!(
+                    "Waiting for peers to mine ({} of {} connected)",
+                    number_of_connected_peers,
+                    E::MINIMUM_PEERS_TO_MINE
+                );
+            }
             // Set the terminator bit to `true` to ensure it does not mine.
             E::terminator().store(true, Ordering::SeqCst);
-        } else {
-            // Set the terminator bit to `false` to ensure it is allowed to mine.
-            E::terminator().store(false, Ordering::SeqCst);
         }
 
         // Update the ledger to the determined status.
         E::status().update(status);
     }
 
+    ///
+    /// Returns `true` if a node with the given status and number of connected peers is permitted to mine.
+    /// A node that is not synced, or is connected to fewer than `E::MINIMUM_PEERS_TO_MINE` peers,
+    /// risks mining on a stale chain.
+    ///
+    fn is_mining_permitted(status: State, number_of_connected_peers: usize) -> bool {
+        match status {
+            State::Ready | State::Mining => number_of_connected_peers >= E::MINIMUM_PEERS_TO_MINE,
+            State::Peering | State::Syncing | State::ShuttingDown => false,
+        }
+    }
+
     ///
     /// Adds the given block:
     ///     1) as the next block in the ledger if the block height increments by one, or
@@ -509,7 +819,7 @@ impl<N: Network, E: Environment> Ledger<N, E> {
                 // Filter out the undesirable unconfirmed blocks, if it exists.
                 true => self.unconfirmed_blocks.write().await.remove(&unconfirmed_previous_block_hash),
                 // Attempt to add the unconfirmed block as the next block in the canonical chain.
-                false => match self.canon.add_next_block(&unconfirmed_block) {
+                false => match self.add_next_block(&unconfirmed_block).await {
                     Ok(()) => {
                         info!(
                             "Ledger successfully advanced to block {} ({})",
@@ -519,11 +829,19 @@ impl<N: Network, E: Environment> Ledger<N, E> {
 
                         // Update the timestamp of the last block increment.
                         *self.last_block_update_timestamp.write().await = Instant::now();
+                        // Update the sync progress with the new block height.
+                        self.sync_progress.write().await.record(Instant::now(), unconfirmed_block_height);
                         // Set the terminator bit to `true` to ensure the miner updates state.
                         E::terminator().store(true, Ordering::SeqCst);
                         // On success, filter the unconfirmed blocks of this block, if it exists.
                         self.unconfirmed_blocks.write().await.remove(&unconfirmed_previous_block_hash);
 
+                        // Notify the subscribers of the confirmed transactions.
+                        for transaction in unconfirmed_block.transactions().iter() {
+                            self.events
+                                .emit(Event::TransactionConfirmed(transaction.transaction_id(), unconfirmed_block_height));
+                        }
+
                         // On success, filter the memory pool of its transactions, if they exist.
                         if let Err(error) = prover_router.send(ProverRequest::MemoryPoolClear(Some(unconfirmed_block))).await {
                             error!("[MemoryPoolClear]: {}", error);
@@ -543,6 +861,8 @@ impl<N: Network, E: Environment> Ledger<N, E> {
                 .insert(unconfirmed_previous_block_hash, unconfirmed_block)
             {
                 trace!("Added unconfirmed block {} to the pending queue", unconfirmed_block_height);
+                self.prune_fork_branches().await;
+                self.update_orphan_depth().await;
             } else {
                 trace!(
                     "Pending queue already contains unconfirmed block {} ({})",
@@ -554,10 +874,72 @@ impl<N: Network, E: Environment> Ledger<N, E> {
         false
     }
 
+    ///
+    /// Adds the given block as the next block in the canonical chain, as a verification task of the
+    /// scheduler, so that its verification is not held up by the (de)serialization of messages.
+    ///
+    async fn add_next_block(&self, block: &Block<N>) -> Result<()> {
+        let (canon, checkpoints, block) = (self.canon.clone(), self.checkpoints.clone(), block.clone());
+        PriorityScheduler::global()
+            .run(TaskPriority::Verification, move || match canon.add_next_block_with_checkpoints(&block, &checkpoints) {
+                // If the block is invalid, name its invalid transaction, if there is one.
+                // Note: This only runs once the block is rejected, so that a valid block verifies its transactions once.
+                Err(error) if error.is::<InvalidBlock>() => Self::verify_transactions(&block).and(Err(error)),
+                result => result,
+            })
+            .await?
+    }
+
+    ///
+    /// Verifies the transactions of the given block concurrently, returning an error that names an invalid transaction.
+    /// This is used to explain the rejection of an invalid block, as `Block::is_valid` already verifies its transactions.
+    ///
+    fn verify_transactions(block: &Block<N>) -> Result<()> {
+        Self::verify_concurrently(block.transactions(), |index, transaction| match transaction.is_valid() {
+            true => Ok(()),
+            false => Err(anyhow!(
+                "Transaction {} ({}) in block {} is invalid",
+                index,
+                transaction.transaction_id(),
+                block.height()
+            )),
+        })
+    }
+
+    ///
+    /// Verifies the given items concurrently on the thread pool, returning the first error found.
+    /// Once an item fails, the remaining items are skipped.
+    ///
+    fn verify_concurrently<T: Sync>(items: &[T], verify: impl Fn(usize, &T) -> Result<()> + Sync) -> Result<()> {
+        let first_error: parking_lot::Mutex<Option<anyhow::Error>> = Default::default();
+        E::thread_pool().install(|| {
+            items.into_par_iter().enumerate().for_each(|(index, item)| {
+                if first_error.lock().is_some() {
+                    return;
+                }
+                if let Err(error) = verify(index, item) {
+                    first_error.lock().get_or_insert(error);
+                }
+            })
+        });
+        match first_error.into_inner() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
     ///
     /// Reverts the ledger state back to height `block_height`, returning `true` on success.
     ///
     async fn revert_to_block_height(&self, block_height: u32) -> bool {
+        // Ensure the ledger does not revert past a checkpoint it has already reached.
+        if let Some(checkpoint_height) = self.checkpoints.latest_block_height() {
+            if block_height < checkpoint_height && self.canon.latest_block_height() >= checkpoint_height {
+                warn!("Ledger cannot revert to block {} past checkpoint {}", block_height, checkpoint_height);
+                return false;
+            }
+        }
+
         // Acquire the lock for the canon chain.
         let _canon_lock = self.canon_lock.lock().await;
 
@@ -567,15 +949,26 @@ impl<N: Network, E: Environment> Ledger<N, E> {
 
                 // Update the last block update timestamp.
                 *self.last_block_update_timestamp.write().await = Instant::now();
+                // Reset the sync progress from the reverted block height.
+                let latest_block_height = self.canon.latest_block_height();
+                self.sync_progress.write().await.record(Instant::now(), latest_block_height);
                 // Set the terminator bit to `true` to ensure the miner resets state.
                 E::terminator().store(true, Ordering::SeqCst);
 
+                // Reset the blocks received ahead of the reverted chain.
+                self.sync_blocks.write().await.clear();
+                *self.backward_sync.write().await = None;
                 // Lock unconfirmed_blocks for further processing.
                 let mut unconfirmed_blocks = self.unconfirmed_blocks.write().await;
 
                 // Ensure the removed blocks are not in the unconfirmed blocks.
                 for removed_block in removed_blocks {
                     unconfirmed_blocks.remove(&removed_block.previous_block_hash());
+
+                    // Notify the subscribers of the orphaned transactions.
+                    for transaction in removed_block.transactions().iter() {
+                        self.events.emit(Event::TransactionOrphaned(transaction.transaction_id()));
+                    }
                 }
                 true
             }
@@ -613,7 +1006,15 @@ impl<N: Network, E: Environment> Ledger<N, E> {
     async fn remove_peer(&self, peer_ip: &SocketAddr) {
         self.peers_state.write().await.remove(peer_ip);
         self.block_requests.write().await.remove(peer_ip);
+        self.unavailable_blocks.write().await.remove(peer_ip);
+        self.sync_peer_stats.write().await.remove(peer_ip);
         self.failures.write().await.remove(peer_ip);
+        // Remove the blocks buffered from the peer, along with the window being synced from it.
+        self.sync_blocks.write().await.retain(|(ip, _)| ip != peer_ip);
+        let mut backward_sync = self.backward_sync.write().await;
+        if backward_sync.as_ref().map_or(false, |window| window.peer_ip() == *peer_ip) {
+            *backward_sync = None;
+        }
     }
 
     ///
@@ -623,6 +1024,7 @@ impl<N: Network, E: Environment> Ledger<N, E> {
         &self,
         peer_ip: SocketAddr,
         node_type: NodeType,
+        capabilities: NodeCapabilities,
         status: State,
         is_fork: Option<bool>,
         block_locators: BlockLocators<N>,
@@ -672,6 +1074,17 @@ impl<N: Network, E: Environment> Ledger<N, E> {
                 }
             }
 
+            // Ensure the peer is not on a chain that this ledger can never switch to.
+            // A peer that is merely behind this ledger is kept, as it may still catch up.
+            let latest_block_height = self.canon.latest_block_height();
+            let canonical_block_hash = |block_height| self.canon.get_block_hash(block_height).ok();
+            if let Some(incompatibility) =
+                Self::find_incompatibility(&self.checkpoints, &block_locators, latest_block_height, canonical_block_hash)
+            {
+                self.disconnect_and_ban(peer_ip, &incompatibility).await;
+                return;
+            }
+
             // If the given fork status is None, check if it can be updated.
             let is_fork = match is_fork {
                 Some(is_fork) => Some(is_fork),
@@ -697,18 +1110,66 @@ impl<N: Network, E: Environment> Ledger<N, E> {
             );
 
             match self.peers_state.write().await.get_mut(&peer_ip) {
-                Some(peer_state) => *peer_state = Some((node_type, status, is_fork, latest_block_height_of_peer, block_locators)),
+                Some(peer_state) => {
+                    *peer_state = Some((
+                        node_type,
+                        capabilities,
+                        status,
+                        is_fork,
+                        latest_block_height_of_peer,
+                        block_locators,
+                    ))
+                }
                 None => self.add_failure(peer_ip, format!("Missing ledger state for {}", peer_ip)).await,
             };
         }
     }
 
+    ///
+    /// Returns the reason the given block locators of a peer are permanently incompatible with this ledger, if they are.
+    ///
+    /// The block locators are incompatible if they conflict with a checkpoint, or if they fork from this ledger
+    /// at or below a checkpoint that this ledger has reached, as the ledger never reverts past such a checkpoint.
+    ///
+    fn find_incompatibility(
+        checkpoints: &Checkpoints<N>,
+        block_locators: &BlockLocators<N>,
+        latest_block_height: u32,
+        canonical_block_hash: impl Fn(u32) -> Option<N::BlockHash>,
+    ) -> Option<String> {
+        // Retrieve the block height of the latest checkpoint, if this ledger has reached it.
+        let reached_checkpoint = checkpoints.latest_block_height().filter(|checkpoint| *checkpoint <= latest_block_height);
+
+        for (block_height, (block_hash, _)) in block_locators.iter() {
+            if !checkpoints.is_consistent(*block_height, block_hash) {
+                return Some(format!("block {} conflicts with a checkpoint", block_height));
+            }
+            if let Some(checkpoint) = reached_checkpoint {
+                if *block_height <= checkpoint && canonical_block_hash(*block_height).map_or(false, |hash| hash != *block_hash) {
+                    return Some(format!("block {} forks below the checkpoint at block {}", block_height, checkpoint));
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the peers whose last advertised status is `State::Syncing`.
+    fn syncing_peers(peers_state: &PeersState<N>) -> impl Iterator<Item = SocketAddr> + '_ {
+        peers_state
+            .iter()
+            .filter(|(_, peer_state)| matches!(peer_state, Some((_, _, State::Syncing, ..))))
+            .map(|(peer_ip, _)| *peer_ip)
+    }
+
     ///
     /// Proceeds to send block requests to a connected peer, if the ledger is out of date.
     ///
     async fn update_block_requests(&self) {
-        // Ensure the ledger is not awaiting responses from outstanding block requests.
-        if self.number_of_block_requests().await > 0 {
+        // Ensure the ledger has capacity for another batch of block requests.
+        let number_of_block_requests = self.number_of_block_requests().await;
+        if number_of_block_requests.saturating_add(E::MAXIMUM_BLOCK_REQUEST as usize)
+            > E::SYNC_PIPELINE_DEPTH.saturating_mul(E::MAXIMUM_BLOCK_REQUEST) as usize
+        {
             return;
         }
 
@@ -720,17 +1181,54 @@ impl<N: Network, E: Environment> Ledger<N, E> {
         // Prioritize the sync nodes before regular peers.
         let mut maximum_block_height = latest_block_height;
         let mut maximum_cumulative_weight = latest_cumulative_weight;
+        let mut maximum_block_hash = self.canon.latest_block_hash();
+
+        // Skip the peers that reported the next block as unavailable.
+        let excluded_peers: HashSet<SocketAddr> = self
+            .unavailable_blocks
+            .read()
+            .await
+            .iter()
+            .filter(|(_, unavailable_block_height)| **unavailable_block_height > latest_block_height)
+            .map(|(peer_ip, _)| *peer_ip)
+            .collect();
 
         // Check if any of the peers are ahead and have a larger block height.
-        if let Some((peer_ip, maximal_peer_is_on_fork, maximum_block_locators)) = find_maximal_peer::<N, E>(
-            &*self.peers_state.read().await,
-            &mut maximum_block_height,
-            &mut maximum_cumulative_weight,
-        ) {
-            // Case 1 - Ensure the peer has a heavier canonical chain than this ledger.
+        let maximal_peer = {
+            let peers_state = self.peers_state.read().await;
+            let sync_peer_stats = self.sync_peer_stats.read().await;
+
+            // While this node is syncing, skip the peers that are syncing as well, as they are unreliable sources of blocks.
+            let mut preferred_excluded_peers = excluded_peers.clone();
+            if E::status().is_syncing() {
+                preferred_excluded_peers.extend(Self::syncing_peers(&peers_state));
+            }
+
+            find_maximal_peer::<N, E>(
+                &peers_state,
+                &sync_peer_stats,
+                &preferred_excluded_peers,
+                &mut maximum_block_height,
+                &mut maximum_cumulative_weight,
+                &mut maximum_block_hash,
+            )
+            // Fall back to the syncing peers, if no other peer is ahead of this ledger.
+            .or_else(|| {
+                find_maximal_peer::<N, E>(
+                    &peers_state,
+                    &sync_peer_stats,
+                    &excluded_peers,
+                    &mut maximum_block_height,
+                    &mut maximum_cumulative_weight,
+                    &mut maximum_block_hash,
+                )
+            })
+        };
+        if let Some((peer_ip, maximal_peer_is_on_fork, maximum_block_locators)) = maximal_peer {
+            // Case 1 - Ensure the peer does not have a lighter canonical chain than this ledger.
             // Note: this check is duplicated in `handle_block_requests`, as it is fast
             // and allows us to skip acquiring `_block_requests_lock`.
-            if latest_cumulative_weight >= maximum_cumulative_weight {
+            if latest_cumulative_weight > maximum_cumulative_weight {
                 return;
             }
 
@@ -764,7 +1262,7 @@ impl<N: Network, E: Environment> Ledger<N, E> {
                 // Disconnect from the peer if it is misbehaving and proceed to abort.
                 BlockRequestHandler::AbortAndDisconnect(_, ref reason) => {
                     drop(_block_requests_lock);
-                    self.disconnect(peer_ip, reason).await;
+                    self.disconnect(peer_ip, DisconnectReason::NoReasonGiven, reason).await;
                     return;
                 }
                 // Proceed to send block requests to a connected peer, if the ledger is out of date.
@@ -773,6 +1271,44 @@ impl<N: Network, E: Environment> Ledger<N, E> {
                 }
             };
 
+            // Request the blocks from the tip downward, if this ledger is far behind the peer, and is not on a fork.
+            let direction = match ledger_is_on_fork {
+                true => SyncDirection::Forward,
+                false => SyncDirection::select(maximum_block_height.saturating_sub(latest_block_height), E::BACKWARD_SYNC_THRESHOLD),
+            };
+
+            let (start_block_height, end_block_height) = match (direction, number_of_block_requests > 0) {
+                // Pipeline the next batch of the backward sync window, directly below the batches requested so far.
+                (SyncDirection::Backward, _) => {
+                    match self.next_backward_batch(peer_ip, start_block_height, maximum_block_height).await {
+                        Some(block_heights) => block_heights,
+                        None => return,
+                    }
+                }
+                // If there are outstanding block requests, pipeline the next batch after the last requested block.
+                (SyncDirection::Forward, true) => {
+                    let block_requests = self.block_requests.read().await;
+                    // Only pipeline block requests with the peer being synced from, and never across a fork revert.
+                    let is_sole_peer = block_requests.iter().all(|(ip, requests)| *ip == peer_ip || requests.is_empty());
+                    let last_requested_block_height = block_requests
+                        .get(&peer_ip)
+                        .and_then(|requests| requests.keys().map(|request| request.block_height()).max());
+                    match (ledger_is_on_fork, is_sole_peer, last_requested_block_height) {
+                        (false, true, Some(last_requested_block_height)) => match pipeline_block_requests::<E>(
+                            start_block_height,
+                            last_requested_block_height,
+                            maximum_block_height,
+                            number_of_block_requests,
+                        ) {
+                            Some(block_heights) => block_heights,
+                            None => return,
+                        },
+                        _ => return,
+                    }
+                }
+                (SyncDirection::Forward, false) => (start_block_height, end_block_height),
+            };
+
             // Revert the ledger, if it is on a fork.
             if ledger_is_on_fork {
                 // If the revert operation fails, abort.
@@ -782,49 +1318,79 @@ impl<N: Network, E: Environment> Ledger<N, E> {
                 }
             }
 
-            // Send a `BlockRequest` message to the peer.
+            // Send a `BlockRequest` message to the peer for each batch, in the order of the sync direction.
+            let batches = direction.batches(start_block_height, end_block_height, E::MAXIMUM_BLOCK_REQUEST);
+            let fork_block_locators = match ledger_is_on_fork {
+                true => Some(&maximum_block_locators),
+                false => None,
+            };
+            for (start_block_height, end_block_height) in batches {
+                if !self
+                    .request_blocks(peer_ip, start_block_height, end_block_height, fork_block_locators)
+                    .await
+                {
+                    return;
+                }
+            }
+        }
+    }
+
+    ///
+    /// Sends a `BlockRequest` to the given peer for each range of the given blocks that is not yet requested from it,
+    /// and logs a block request for each of those blocks. If the ledger was reverted to follow a fork, the expected
+    /// block hashes are given by the block locators of the fork. Returns `false` if the peers router is closed.
+    /// The caller must hold the lock for block requests.
+    ///
+    async fn request_blocks(
+        &self,
+        peer_ip: SocketAddr,
+        start_block_height: u32,
+        end_block_height: u32,
+        fork_block_locators: Option<&BlockLocators<N>>,
+    ) -> bool {
+        // Coalesce the range with the outstanding block requests to the peer, so that only the blocks
+        // that are not yet requested are requested, and a duplicate request is a no-op.
+        let gaps = self
+            .block_requests
+            .read()
+            .await
+            .get(&peer_ip)
+            .map(|block_requests| Self::coalesce_block_request(block_requests, start_block_height, end_block_height));
+        let gaps = match gaps {
+            Some(gaps) => gaps,
+            None => {
+                self.add_failure(peer_ip, format!("Missing block requests for {}", peer_ip)).await;
+                return true;
+            }
+        };
+        if gaps.is_empty() {
+            trace!(
+                "Skipping a duplicate request for blocks {} to {} from {}",
+                start_block_height,
+                end_block_height,
+                peer_ip
+            );
+            return true;
+        }
+
+        for (start_block_height, end_block_height) in gaps {
             debug!("Requesting blocks {} to {} from {}", start_block_height, end_block_height, peer_ip);
             let request = PeersRequest::MessageSend(peer_ip, Message::BlockRequest(start_block_height, end_block_height));
             if let Err(error) = self.peers_router.send(request).await {
                 warn!("[BlockRequest] {}", error);
-                return;
+                return false;
             }
 
-            // Filter out any pre-existing block requests for the peer.
-            let mut missing_block_requests = false;
-            let mut new_block_heights = Vec::new();
-            if let Some(block_requests) = self.block_requests.read().await.get(&peer_ip) {
+            // Log each block request to ensure the peer responds with all requested blocks.
+            if let Some(locked_block_requests) = self.block_requests.write().await.get_mut(&peer_ip) {
                 for block_height in start_block_height..=end_block_height {
-                    if !block_requests.contains_key(&block_height.into()) {
-                        new_block_heights.push(block_height);
-                    }
-                }
-            } else {
-                self.add_failure(peer_ip, format!("Missing block requests for {}", peer_ip)).await;
-                missing_block_requests = true;
-            }
-
-            if !missing_block_requests && !new_block_heights.is_empty() {
-                // Log each block request to ensure the peer responds with all requested blocks.
-                if let Some(locked_block_requests) = self.block_requests.write().await.get_mut(&peer_ip) {
-                    for block_height in new_block_heights {
-                        // If the ledger is on a fork and was reverted, include the expected new block hash for the fork.
-                        match ledger_is_on_fork {
-                            true => {
-                                self.add_block_request(
-                                    peer_ip,
-                                    block_height,
-                                    maximum_block_locators.get_block_hash(block_height),
-                                    locked_block_requests,
-                                )
-                                .await
-                            }
-                            false => self.add_block_request(peer_ip, block_height, None, locked_block_requests).await,
-                        };
-                    }
+                    // If the ledger is on a fork and was reverted, include the expected new block hash for the fork.
+                    let block_hash = fork_block_locators.and_then(|block_locators| block_locators.get_block_hash(block_height));
+                    self.add_block_request(peer_ip, block_height, block_hash, locked_block_requests).await;
                 }
             }
         }
+        true
     }
 
     ///
@@ -884,6 +1450,95 @@ impl<N: Network, E: Environment> Ledger<N, E> {
         }
     }
 
+    ///
+    /// Marks the given blocks as unavailable from the specified peer, and removes its block requests for them.
+    ///
+    async fn add_unavailable_blocks(&self, peer_ip: SocketAddr, start_block_height: u32, end_block_height: u32) {
+        // Acquire the lock for block requests.
+        let _block_requests_lock = self.block_requests_lock.lock().await;
+
+        if let Some(requests) = self.block_requests.write().await.get_mut(&peer_ip) {
+            requests.retain(|request, _| !(start_block_height..=end_block_height).contains(&request.block_height()));
+        }
+        let mut unavailable_blocks = self.unavailable_blocks.write().await;
+        let unavailable_block_height = unavailable_blocks.entry(peer_ip).or_insert(end_block_height);
+        *unavailable_block_height = (*unavailable_block_height).max(end_block_height);
+    }
+
+    ///
+    /// Removes the block requests to the specified peer beyond its given highest available block height,
+    /// and lowers the block height of the peer to it, so that the blocks are not requested from it again.
+    ///
+    async fn end_block_requests(&self, peer_ip: SocketAddr, highest_block_height: u32) {
+        // Acquire the lock for block requests.
+        let _block_requests_lock = self.block_requests_lock.lock().await;
+
+        if let Some(requests) = self.block_requests.write().await.get_mut(&peer_ip) {
+            Self::retain_block_requests_until(requests, highest_block_height);
+        }
+        if let Some(Some((_, _, _, _, block_height, _))) = self.peers_state.write().await.get_mut(&peer_ip) {
+            *block_height = (*block_height).min(highest_block_height);
+        }
+    }
+
+    ///
+    /// Returns the ranges of block heights in the given range that are not yet requested, skipping every
+    /// outstanding block request in the range. Returns no range if the whole range is already requested.
+    ///
+    fn coalesce_block_request(
+        requests: &HashMap<BlockRequest<N>, i64>,
+        start_block_height: u32,
+        end_block_height: u32,
+    ) -> Vec<(u32, u32)> {
+        let mut gaps: Vec<(u32, u32)> = Vec::new();
+        for block_height in start_block_height..=end_block_height {
+            if requests.contains_key(&block_height.into()) {
+                continue;
+            }
+            match gaps.last_mut() {
+                Some((_, end)) if *end + 1 == block_height => *end = block_height,
+                _ => gaps.push((block_height, block_height)),
+            }
+        }
+        gaps
+    }
+
+    ///
+    /// Retains only the given block requests up to and including the given block height.
+    ///
+    fn retain_block_requests_until(requests: &mut HashMap<BlockRequest<N>, i64>, block_height: u32) {
+        requests.retain(|request, _| request.block_height() <= block_height);
+    }
+
+    ///
+    /// Returns the next batch of blocks to request from the given peer while syncing in reverse order.
+    /// A new window is started from the given start block height once the current window is fully requested
+    /// and none of its requests are outstanding, or if the current window is from another peer.
+    ///
+    async fn next_backward_batch(&self, peer_ip: SocketAddr, start_block_height: u32, maximum_block_height: u32) -> Option<(u32, u32)> {
+        let has_block_requests = self
+            .block_requests
+            .read()
+            .await
+            .get(&peer_ip)
+            .map_or(false, |requests| !requests.is_empty());
+
+        let mut backward_sync = self.backward_sync.write().await;
+        let is_window_active = match &*backward_sync {
+            Some(window) => window.peer_ip() == peer_ip && (!window.is_requested() || has_block_requests),
+            None => false,
+        };
+        if !is_window_active {
+            let end_block_height = start_block_height.saturating_add(E::BACKWARD_SYNC_WINDOW.max(1) - 1);
+            *backward_sync = Some(BackwardSyncWindow::new(
+                peer_ip,
+                start_block_height,
+                end_block_height.min(maximum_block_height),
+            ));
+        }
+        backward_sync.as_mut()?.next_batch(E::MAXIMUM_BLOCK_REQUEST)
+    }
+
     ///
     /// Removes block requests that have expired.
     ///
@@ -931,7 +1586,317 @@ impl<N: Network, E: Environment> Ledger<N, E> {
             .collect::<Vec<_>>();
 
         for peer_ip in peers_to_disconnect {
-            self.disconnect(peer_ip, "exceeded failure limit").await;
+            self.disconnect(peer_ip, DisconnectReason::TooManyFailures, "exceeded failure limit").await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        environment::{Client, Miner},
+        helpers::DefaultFeePolicy,
+        network::Peer,
+    };
+    use snarkos_storage::storage::rocksdb::RocksDB;
+    use snarkvm::{dpc::testnet2::Testnet2, prelude::UniformRand};
+
+    use once_cell::sync::OnceCell;
+    use rand::thread_rng;
+    use std::sync::atomic::AtomicU32;
+
+    #[derive(Clone, Debug, Default)]
+    struct OrphanNode;
+
+    #[rustfmt::skip]
+    impl Environment for OrphanNode {
+        type Network = Testnet2;
+        type FeePolicy = DefaultFeePolicy;
+        const NODE_TYPE: NodeType = NodeType::Client;
+        const MINIMUM_NUMBER_OF_PEERS: usize = 2;
+        const MAXIMUM_NUMBER_OF_PEERS: usize = 21;
+
+        // The depth of the deepest unconfirmed block is kept apart from the other environments, as the ledger under test sets it.
+        fn orphan_depth() -> &'static AtomicU32 {
+            static ORPHAN_DEPTH: OnceCell<AtomicU32> = OnceCell::new();
+            ORPHAN_DEPTH.get_or_init(|| AtomicU32::new(0))
+        }
+    }
+
+    #[test]
+    fn test_verify_concurrently() {
+        type L = Ledger<Testnet2, Miner<Testnet2>>;
+        let transactions: Vec<u32> = (0..100).collect();
+
+        // Ensure a block of valid transactions is accepted.
+        assert!(L::verify_concurrently(&transactions, |_, _| Ok(())).is_ok());
+
+        // Ensure a block where transaction 50 is invalid is rejected, with an error naming that transaction.
+        let error = L::verify_concurrently(&transactions, |index, transaction| match *transaction == 50 {
+            true => Err(anyhow!("Transaction {} is invalid", index)),
+            false => Ok(()),
+        })
+        .unwrap_err();
+        assert_eq!("Transaction 50 is invalid", error.to_string());
+    }
+
+    #[test]
+    fn test_verify_transactions() {
+        type L = Ledger<Testnet2, Miner<Testnet2>>;
+
+        // Ensure the transactions of the genesis block are valid.
+        assert!(L::verify_transactions(Testnet2::genesis_block()).is_ok());
+    }
+
+    #[test]
+    fn test_requires_delivery() {
+        let peer_ip: SocketAddr = "127.0.0.1:4130".parse().unwrap();
+
+        // Ensure the responses that the sync pipeline waits on are never dropped.
+        assert!(LedgerRequest::<Testnet2>::Failure(peer_ip, "failure".to_string()).requires_delivery());
+        // Ensure the other requests may be dropped when the queue of the peer is full.
+        assert!(!LedgerRequest::<Testnet2>::BlockResponseEnd(peer_ip, 0).requires_delivery());
+        assert!(!LedgerRequest::<Testnet2>::BlockUnavailable(peer_ip, 0, 1).requires_delivery());
+
+        // Ensure the queue of each peer holds a full pipeline of block responses.
+        let pipeline = (Miner::<Testnet2>::MAXIMUM_BLOCK_REQUEST * Miner::<Testnet2>::SYNC_PIPELINE_DEPTH) as usize;
+        assert!(Miner::<Testnet2>::MAXIMUM_INBOUND_REQUESTS_PER_PEER > pipeline);
+    }
+
+    #[test]
+    fn test_is_mining_permitted() {
+        type L = Ledger<Testnet2, Miner<Testnet2>>;
+        let minimum_peers_to_mine = Miner::<Testnet2>::MINIMUM_PEERS_TO_MINE;
+
+        // Ensure a synced miner does not mine below the minimum number of peers.
+        assert!(!L::is_mining_permitted(State::Ready, 0));
+        assert!(!L::is_mining_permitted(State::Ready, minimum_peers_to_mine - 1));
+
+        // Ensure a synced miner mines once the minimum number of peers is met.
+        assert!(L::is_mining_permitted(State::Ready, minimum_peers_to_mine));
+        assert!(L::is_mining_permitted(State::Mining, minimum_peers_to_mine + 1));
+
+        // Ensure a miner that is not synced does not mine, regardless of its peers.
+        assert!(!L::is_mining_permitted(State::Peering, minimum_peers_to_mine));
+        assert!(!L::is_mining_permitted(State::Syncing, minimum_peers_to_mine));
+        assert!(!L::is_mining_permitted(State::ShuttingDown, minimum_peers_to_mine));
+    }
+
+    #[test]
+    fn test_syncing_peers() {
+        type L = Ledger<Testnet2, Miner<Testnet2>>;
+
+        let capabilities = NodeCapabilities::from(NodeType::Client);
+        let peer_state = |status| Some((NodeType::Client, capabilities, status, Some(false), 100, Default::default()));
+
+        let syncing_peer: SocketAddr = "127.0.0.1:4131".parse().unwrap();
+        let mut peers_state = PeersState::<Testnet2>::new();
+        peers_state.insert(syncing_peer, peer_state(State::Syncing));
+        peers_state.insert("127.0.0.1:4132".parse().unwrap(), peer_state(State::Ready));
+        peers_state.insert("127.0.0.1:4133".parse().unwrap(), None);
+
+        // Ensure only the peer that last advertised it is syncing is reported.
+        assert_eq!(vec![syncing_peer], L::syncing_peers(&peers_state).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_retain_block_requests_until() {
+        type L = Ledger<Testnet2, Miner<Testnet2>>;
+
+        let mut requests: HashMap<BlockRequest<Testnet2>, i64> = (195..=205).map(|block_height| (block_height.into(), 0)).collect();
+
+        // Ensure the requests beyond the highest available block height are completed, and the last block is kept.
+        L::retain_block_requests_until(&mut requests, 200);
+        assert_eq!(6, requests.len());
+        assert!(requests.contains_key(&200.into()));
+        assert!(!requests.contains_key(&201.into()));
+
+        // Ensure a highest available block height below all requests completes all of them.
+        L::retain_block_requests_until(&mut requests, 194);
+        assert!(requests.is_empty());
+    }
+
+    #[test]
+    fn test_coalesce_block_request() {
+        type L = Ledger<Testnet2, Miner<Testnet2>>;
+
+        // Ensure a range without outstanding block requests is requested in full.
+        let mut requests: HashMap<BlockRequest<Testnet2>, i64> = HashMap::new();
+        assert_eq!(vec![(1, 10)], L::coalesce_block_request(&requests, 1, 10));
+        requests.extend((1..=10).map(|block_height| (block_height.into(), 0)));
+
+        // Ensure a range that is already requested is a no-op.
+        assert!(L::coalesce_block_request(&requests, 1, 10).is_empty());
+        assert!(L::coalesce_block_request(&requests, 3, 7).is_empty());
+
+        // Ensure an outstanding block request in the middle of the range is skipped.
+        requests.remove(&2.into());
+        requests.remove(&3.into());
+        requests.remove(&8.into());
+        assert_eq!(vec![(2, 3), (8, 8), (11, 15)], L::coalesce_block_request(&requests, 1, 15));
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_block_requests() {
+        let (peers_router, mut peers_handler) = mpsc::channel(1024);
+        let (prover_router, _prover_handler) = mpsc::channel(1024);
+        let path = tempfile::tempdir().expect("Failed to open temporary directory").into_path();
+        let ledger = Ledger::<Testnet2, Client<Testnet2>>::open::<RocksDB, _>(&path, peers_router, Events::new(16))
+            .await
+            .expect("Failed to initialize ledger");
+        let peer_ip: SocketAddr = "127.0.0.1:4131".parse().unwrap();
+        ledger.initialize_peer(peer_ip).await;
+
+        let mut sent_block_requests = || {
+            let mut block_requests = Vec::new();
+            while let Ok(request) = peers_handler.try_recv() {
+                if let PeersRequest::MessageSend(_, Message::BlockRequest(start, end)) = request {
+                    block_requests.push((start, end));
+                }
+            }
+            block_requests
+        };
+
+        // Issue the same block request twice, and ensure only one is sent and outstanding.
+        assert!(ledger.request_blocks(peer_ip, 0, 4, None).await);
+        assert!(ledger.request_blocks(peer_ip, 0, 4, None).await);
+        assert_eq!(vec![(0, 4)], sent_block_requests());
+        assert_eq!(5, ledger.number_of_block_requests().await);
+
+        // Process one response, and ensure it completes its block request without a failure.
+        let block = Testnet2::genesis_block().clone();
+        ledger
+            .update(LedgerRequest::BlockResponse(peer_ip, block, prover_router.clone(), None))
+            .await;
+        assert!(!ledger.contains_block_request(peer_ip, 0).await);
+        assert_eq!(4, ledger.number_of_block_requests().await);
+        assert!(ledger.failures.read().await[&peer_ip].is_empty());
+
+        // Ensure an overlapping request only requests the blocks that are not outstanding.
+        assert!(ledger.request_blocks(peer_ip, 0, 6, None).await);
+        assert_eq!(vec![(0, 0), (5, 6)], sent_block_requests());
+        assert_eq!(7, ledger.number_of_block_requests().await);
+        assert!(ledger.failures.read().await[&peer_ip].is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pong_flood() {
+        let (peers_router, _peers_handler) = mpsc::channel(1024);
+        let path = tempfile::tempdir().expect("Failed to open temporary directory").into_path();
+        let ledger = Ledger::<Testnet2, Client<Testnet2>>::open::<RocksDB, _>(&path, peers_router, Events::new(16))
+            .await
+            .expect("Failed to initialize ledger");
+        let flooding_peer: SocketAddr = "127.0.0.1:4131".parse().unwrap();
+        let other_peer: SocketAddr = "127.0.0.1:4132".parse().unwrap();
+
+        let capabilities = NodeCapabilities::from(NodeType::Client);
+        let block_locators = ledger.reader().latest_block_locators();
+        let pong = |peer_ip| {
+            let block_locators = block_locators.clone();
+            LedgerRequest::Pong(peer_ip, NodeType::Client, capabilities, State::Ready, Some(false), block_locators, None)
+        };
+
+        // Stall the ledger handler on the state of the peers, while one peer floods the ledger with pongs.
+        let peers_state = ledger.peers_state.write().await;
+        for _ in 0..4 * Client::<Testnet2>::MAXIMUM_INBOUND_REQUESTS_PER_PEER {
+            ledger.router().send(pong(flooding_peer)).await.unwrap();
         }
+        ledger.router().send(pong(other_peer)).await.unwrap();
+
+        // Ensure the pong of the other peer is dispatched, as the pongs of the flooding peer coalesce into a single slot.
+        let is_dispatched = || ledger.inbound_queue.lock().len_of(&Some(other_peer)) == 1;
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !is_dispatched() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("The pong of the other peer was not dispatched");
+        assert!(ledger.inbound_queue.lock().len_of(&Some(flooding_peer)) <= 1);
+
+        // Ensure the other peer is served, once the ledger handler resumes.
+        drop(peers_state);
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !matches!(ledger.peers_state.read().await.get(&other_peer), Some(Some(..))) {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("The pong of the other peer was not processed");
+    }
+
+    #[test]
+    fn test_find_incompatibility() {
+        type L = Ledger<Testnet2, Miner<Testnet2>>;
+        let rng = &mut thread_rng();
+
+        let canonical_hashes: Vec<_> = (0..=10).map(|_| <Testnet2 as Network>::BlockHash::rand(rng)).collect();
+        let canonical_block_hash = |block_height: u32| canonical_hashes.get(block_height as usize).copied();
+        let checkpoints = Checkpoints::<Testnet2>::new(vec![(5, canonical_hashes[5])]);
+        let locators = |locators: Vec<(u32, <Testnet2 as Network>::BlockHash)>| {
+            BlockLocators::<Testnet2>::from(locators.into_iter().map(|(height, hash)| (height, (hash, None))).collect()).unwrap()
+        };
+
+        // Ensure a peer on the canonical chain is compatible, even if it is behind this ledger.
+        let behind = locators(vec![(0, canonical_hashes[0]), (3, canonical_hashes[3])]);
+        assert_eq!(None, L::find_incompatibility(&checkpoints, &behind, 10, canonical_block_hash));
+
+        // Ensure a peer that forks above the checkpoint is compatible, as the fork may still be resolved.
+        let fork = locators(vec![(5, canonical_hashes[5]), (8, <Testnet2 as Network>::BlockHash::rand(rng))]);
+        assert_eq!(None, L::find_incompatibility(&checkpoints, &fork, 10, canonical_block_hash));
+
+        // Ensure a peer that conflicts with the checkpoint is incompatible.
+        let conflicting = locators(vec![(5, <Testnet2 as Network>::BlockHash::rand(rng))]);
+        assert!(L::find_incompatibility(&checkpoints, &conflicting, 10, canonical_block_hash).is_some());
+        assert!(L::find_incompatibility(&checkpoints, &conflicting, 4, canonical_block_hash).is_some());
+
+        // Ensure a peer that forks below the checkpoint is incompatible, once this ledger has reached the checkpoint.
+        let fork = locators(vec![(3, <Testnet2 as Network>::BlockHash::rand(rng))]);
+        assert!(L::find_incompatibility(&checkpoints, &fork, 10, canonical_block_hash).is_some());
+        assert_eq!(None, L::find_incompatibility(&checkpoints, &fork, 4, canonical_block_hash));
+    }
+
+    #[tokio::test]
+    async fn test_orphan_depth_in_ping() {
+        let (peers_router, _peers_handler) = mpsc::channel(1024);
+        let path = tempfile::tempdir().expect("Failed to open temporary directory").into_path();
+        let ledger = Ledger::<Testnet2, OrphanNode>::open::<RocksDB, _>(&path, peers_router, Events::new(16))
+            .await
+            .expect("Failed to initialize ledger");
+        let fork_depth = |message: Message<Testnet2, OrphanNode>| match message {
+            Message::Ping(_, fork_depth, ..) => fork_depth,
+            _ => panic!("Expected a 'Ping'"),
+        };
+        let maximum_fork_depth = <Testnet2 as Network>::ALEO_MAXIMUM_FORK_DEPTH;
+
+        // Ensure a ledger without unconfirmed blocks advertises the maximum fork depth.
+        assert_eq!(0, OrphanNode::orphan_depth().load(Ordering::SeqCst));
+        assert_eq!(maximum_fork_depth, fork_depth(Peer::ping(&ledger.reader())));
+
+        // Add an unconfirmed block on a fork at the latest block height, to the pool of the ledger.
+        let block = Testnet2::genesis_block().clone();
+        ledger.unconfirmed_blocks.write().await.insert(block.previous_block_hash(), block);
+        ledger.update_orphan_depth().await;
+
+        // Ensure the depth of the orphan is tracked, and the outgoing `Ping` advertises no less than the maximum fork depth.
+        assert_eq!(1, OrphanNode::orphan_depth().load(Ordering::SeqCst));
+        let advertised_fork_depth = fork_depth(Peer::ping(&ledger.reader()));
+        assert_eq!(maximum_fork_depth.max(1), advertised_fork_depth);
+        assert!(advertised_fork_depth <= OrphanNode::MAXIMUM_ADVERTISED_FORK_DEPTH);
+    }
+
+    #[test]
+    fn test_orphan_depth() {
+        type L = Ledger<Testnet2, Miner<Testnet2>>;
+
+        // Ensure no unconfirmed blocks, or only those ahead of the latest block, are on no fork.
+        assert_eq!(0, L::orphan_depth(std::iter::empty(), 100));
+        assert_eq!(0, L::orphan_depth([101, 150].iter().copied(), 100));
+
+        // Ensure the deepest unconfirmed block below the latest block determines the depth.
+        assert_eq!(1, L::orphan_depth([100, 101].iter().copied(), 100));
+        assert_eq!(91, L::orphan_depth([95, 10, 101].iter().copied(), 100));
+        assert_eq!(101, L::orphan_depth([0].iter().copied(), 100));
     }
 }
@@ -15,7 +15,7 @@
 // along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-    helpers::{NodeType, State},
+    helpers::{BlockLocatorsDiff, EncodingFormat, FeatureFlags, NodeCapabilities, NodeType, PriorityScheduler, State, TaskPriority},
     Environment,
 };
 use snarkos_storage::BlockLocators;
@@ -23,11 +23,27 @@ use snarkvm::{dpc::posw::PoSWProof, prelude::*};
 
 use ::bytes::{Buf, BufMut, Bytes, BytesMut};
 use anyhow::{anyhow, Result};
-use serde::{de::DeserializeOwned, Serialize};
-use std::{io::Write, marker::PhantomData, net::SocketAddr};
-use tokio::task;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{convert::TryInto, io::Write, marker::PhantomData, net::SocketAddr};
 use tokio_util::codec::{Decoder, Encoder};
 
+/// A conservative upper bound on the serialized size of a block header, in bytes.
+const BLOCK_HEADER_SIZE_BOUND: usize = 1024;
+/// A conservative upper bound on the serialized size of a transaction, in bytes.
+const TRANSACTION_SIZE_BOUND: usize = 16 * 1024;
+/// A conservative upper bound on the serialized size of a PoSW proof, in bytes.
+const PROOF_SIZE_BOUND: usize = 1024;
+/// A conservative upper bound on the serialized size of the fixed fields of a message, in bytes.
+const FIXED_FIELDS_SIZE_BOUND: usize = 256;
+/// The upper bound on the encoded size of a message that may be coalesced with other messages into a single write.
+const COALESCE_SIZE_BOUND: usize = 2 * 1024;
+/// The minimum size of a message frame to be compressed, in bytes, as smaller frames rarely shrink.
+#[cfg(feature = "zstd")]
+const COMPRESSION_SIZE_THRESHOLD: usize = 1024;
+/// The zstd compression level of the message frames, which favors speed over ratio.
+#[cfg(feature = "zstd")]
+const COMPRESSION_LEVEL: i32 = 1;
+
 /// This object enables deferred deserialization / ahead-of-time serialization for objects that
 /// take a while to deserialize / serialize, in order to allow these operations to be non-blocking.
 #[derive(Clone, Debug)]
@@ -47,7 +63,10 @@ impl<T: 'static + Serialize + DeserializeOwned + Send> Data<T> {
     pub async fn deserialize(self) -> bincode::Result<T> {
         match self {
             Self::Object(x) => Ok(x),
-            Self::Buffer(bytes) => match task::spawn_blocking(move || bincode::deserialize(&bytes)).await {
+            Self::Buffer(bytes) => match PriorityScheduler::global()
+                .run(TaskPriority::BestEffort, move || bincode::deserialize(&bytes))
+                .await
+            {
                 Ok(x) => x,
                 Err(error) => Err(Box::new(bincode::ErrorKind::Custom(format!(
                     "Dedicated deserialization failed: {}",
@@ -66,7 +85,10 @@ impl<T: 'static + Serialize + DeserializeOwned + Send> Data<T> {
 
     pub async fn serialize(self) -> bincode::Result<Bytes> {
         match self {
-            Self::Object(x) => match task::spawn_blocking(move || bincode::serialize(&x)).await {
+            Self::Object(x) => match PriorityScheduler::global()
+                .run(TaskPriority::BestEffort, move || bincode::serialize(&x))
+                .await
+            {
                 Ok(bytes) => bytes.map(|vec| vec.into()),
                 Err(error) => Err(Box::new(bincode::ErrorKind::Custom(format!(
                     "Dedicated serialization failed: {}",
@@ -76,6 +98,80 @@ impl<T: 'static + Serialize + DeserializeOwned + Send> Data<T> {
             Self::Buffer(bytes) => Ok(bytes),
         }
     }
+
+    /// Returns the size of the buffer, or the given upper bound on the serialized size of the object.
+    fn size_upper_bound<F: FnOnce(&T) -> usize>(&self, object_size_bound: F) -> usize {
+        match self {
+            Self::Object(x) => object_size_bound(x),
+            Self::Buffer(bytes) => bytes.len(),
+        }
+    }
+
+    /// Deserializes the data using the given encoding format, as a best-effort task of the scheduler.
+    pub async fn deserialize_with(self, format: EncodingFormat) -> Result<T> {
+        match self {
+            Self::Object(x) => Ok(x),
+            Self::Buffer(bytes) => match PriorityScheduler::global()
+                .run(TaskPriority::BestEffort, move || format.deserialize(&bytes))
+                .await
+            {
+                Ok(x) => x,
+                Err(error) => Err(anyhow!("Dedicated deserialization failed: {}", error)),
+            },
+        }
+    }
+
+    /// Serializes the data using the given encoding format, as a best-effort task of the scheduler.
+    pub async fn serialize_with(self, format: EncodingFormat) -> Result<Bytes> {
+        match self {
+            Self::Object(x) => match PriorityScheduler::global()
+                .run(TaskPriority::BestEffort, move || format.serialize(&x))
+                .await
+            {
+                Ok(bytes) => bytes.map(|vec| vec.into()),
+                Err(error) => Err(anyhow!("Dedicated serialization failed: {}", error)),
+            },
+            Self::Buffer(bytes) => Ok(bytes),
+        }
+    }
+}
+
+/// The reason for an operator to reject a share submitted by a prover.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum PoolRejectReason {
+    /// The nonce was already submitted for the current block template.
+    DuplicateNonce = 0,
+    /// The proof could not be deserialized.
+    MalformedProof,
+    /// The prover submitted more shares than the operator accepts per second.
+    SubmissionRateExceeded,
+    /// The proof is not a valid PoSW proof for the block template and nonce.
+    InvalidProof,
+    /// The block template of the share was replaced while the pool was paused.
+    StaleTemplate,
+}
+
+/// The reason for a node to disconnect from a peer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum DisconnectReason {
+    /// No reason was given for the disconnect.
+    NoReasonGiven = 0,
+    /// The node is shutting down.
+    ShuttingDown,
+    /// The peer exceeded the maximum number of failures.
+    TooManyFailures,
+    /// The node exceeded its maximum number of connections.
+    TooManyPeers,
+    /// The peer is running an unsupported version of the network protocol.
+    VersionMismatch,
+    /// The peer exceeded the rate limit of a message type.
+    RateLimitExceeded,
+    /// The peer deviated from the expected sequence of handshake messages.
+    ProtocolViolation,
+    /// The peer is on a chain that is incompatible with the checkpoints of this node.
+    IncompatibleFork,
 }
 
 #[derive(Clone, Debug)]
@@ -84,18 +180,18 @@ pub enum Message<N: Network, E: Environment> {
     BlockRequest(u32, u32),
     /// BlockResponse := (block)
     BlockResponse(Data<Block<N>>),
-    /// ChallengeRequest := (version, fork_depth, node_type, status, listener_port, nonce, cumulative_weight)
-    ChallengeRequest(u32, u32, NodeType, State, u16, u64, u128),
+    /// ChallengeRequest := (version, fork_depth, node_type, capabilities, status, listener_port, nonce, cumulative_weight)
+    ChallengeRequest(u32, u32, NodeType, NodeCapabilities, State, u16, u64, u128),
     /// ChallengeResponse := (block_header)
     ChallengeResponse(Data<BlockHeader<N>>),
-    /// Disconnect := ()
-    Disconnect,
-    /// PeerRequest := ()
-    PeerRequest,
-    /// PeerResponse := (\[peer_ip\])
-    PeerResponse(Vec<SocketAddr>),
-    /// Ping := (version, fork_depth, node_type, status, block_hash, block_header)
-    Ping(u32, u32, NodeType, State, N::BlockHash, Data<BlockHeader<N>>),
+    /// Disconnect := (reason)
+    Disconnect(DisconnectReason),
+    /// PeerRequest := (listener_ip)
+    PeerRequest(Option<SocketAddr>),
+    /// PeerResponse := (requester_ip, \[peer_ip\])
+    PeerResponse(Option<SocketAddr>, Vec<SocketAddr>),
+    /// Ping := (version, fork_depth, node_type, status, block_hash, block_header, mempool_size)
+    Ping(u32, u32, NodeType, State, N::BlockHash, Data<BlockHeader<N>>, u32),
     /// Pong := (is_fork, block_locators)
     Pong(Option<bool>, Data<BlockLocators<N>>),
     /// UnconfirmedBlock := (block_height, block_hash, block)
@@ -108,6 +204,26 @@ pub enum Message<N: Network, E: Environment> {
     PoolRequest(u64, Data<BlockTemplate<N>>),
     /// PoolResponse := (address, nonce, proof)
     PoolResponse(Address<N>, N::PoSWNonce, Data<PoSWProof<N>>),
+    /// PoolReject := (reason)
+    PoolReject(PoolRejectReason),
+    /// PoolRequestMulti := (\[(share_difficulty, block_template)\])
+    PoolRequestMulti(Vec<(u64, Data<BlockTemplate<N>>)>),
+    /// MempoolRequest := ()
+    MempoolRequest,
+    /// MempoolResponse := (\[transaction_id\])
+    MempoolResponse(Vec<N::TransactionID>),
+    /// TransactionRequest := (\[transaction_id\])
+    TransactionRequest(Vec<N::TransactionID>),
+    /// Throttle := (duration_in_secs)
+    Throttle(u32),
+    /// BlockUnavailable := (start_block_height, end_block_height (inclusive))
+    BlockUnavailable(u32, u32),
+    /// PongDiff := (is_fork, block_locators_diff)
+    PongDiff(Option<bool>, Data<BlockLocatorsDiff<N>>),
+    /// PongResync := ()
+    PongResync,
+    /// BlockResponseEnd := (highest_available_block_height)
+    BlockResponseEnd(u32),
     /// Unused
     #[allow(unused)]
     Unused(PhantomData<E>),
@@ -116,14 +232,14 @@ pub enum Message<N: Network, E: Environment> {
 impl<N: Network, E: Environment> Message<N, E> {
     /// Returns the message name.
     #[inline]
-    pub fn name(&self) -> &str {
+    pub fn name(&self) -> &'static str {
         match self {
             Self::BlockRequest(..) => "BlockRequest",
             Self::BlockResponse(..) => "BlockResponse",
             Self::ChallengeRequest(..) => "ChallengeRequest",
             Self::ChallengeResponse(..) => "ChallengeResponse",
-            Self::Disconnect => "Disconnect",
-            Self::PeerRequest => "PeerRequest",
+            Self::Disconnect(..) => "Disconnect",
+            Self::PeerRequest(..) => "PeerRequest",
             Self::PeerResponse(..) => "PeerResponse",
             Self::Ping(..) => "Ping",
             Self::Pong(..) => "Pong",
@@ -132,6 +248,16 @@ impl<N: Network, E: Environment> Message<N, E> {
             Self::PoolRegister(..) => "PoolRegister",
             Self::PoolRequest(..) => "PoolRequest",
             Self::PoolResponse(..) => "PoolResponse",
+            Self::PoolReject(..) => "PoolReject",
+            Self::PoolRequestMulti(..) => "PoolRequestMulti",
+            Self::MempoolRequest => "MempoolRequest",
+            Self::MempoolResponse(..) => "MempoolResponse",
+            Self::TransactionRequest(..) => "TransactionRequest",
+            Self::Throttle(..) => "Throttle",
+            Self::BlockUnavailable(..) => "BlockUnavailable",
+            Self::PongDiff(..) => "PongDiff",
+            Self::PongResync => "PongResync",
+            Self::BlockResponseEnd(..) => "BlockResponseEnd",
             Self::Unused(..) => "Unused",
         }
     }
@@ -144,8 +270,8 @@ impl<N: Network, E: Environment> Message<N, E> {
             Self::BlockResponse(..) => 1,
             Self::ChallengeRequest(..) => 2,
             Self::ChallengeResponse(..) => 3,
-            Self::Disconnect => 4,
-            Self::PeerRequest => 5,
+            Self::Disconnect(..) => 4,
+            Self::PeerRequest(..) => 5,
             Self::PeerResponse(..) => 6,
             Self::Ping(..) => 7,
             Self::Pong(..) => 8,
@@ -154,10 +280,104 @@ impl<N: Network, E: Environment> Message<N, E> {
             Self::PoolRegister(..) => 11,
             Self::PoolRequest(..) => 12,
             Self::PoolResponse(..) => 13,
-            Self::Unused(..) => 14,
+            Self::PoolReject(..) => 14,
+            Self::PoolRequestMulti(..) => 15,
+            Self::MempoolRequest => 16,
+            Self::MempoolResponse(..) => 17,
+            Self::TransactionRequest(..) => 18,
+            Self::Throttle(..) => 19,
+            Self::BlockUnavailable(..) => 20,
+            Self::PongDiff(..) => 21,
+            Self::PongResync => 22,
+            Self::BlockResponseEnd(..) => 23,
+            Self::Unused(..) => 24,
         }
     }
 
+    ///
+    /// Returns a conservative upper bound on the size of the encoded message, including its length prefix and ID.
+    /// This is used to pre-size the outbound buffer, and does not perform any serialization.
+    ///
+    pub fn byte_size_upper_bound(&self) -> usize {
+        let block_size_bound = |block: &Block<N>| BLOCK_HEADER_SIZE_BOUND + block.transactions().len() * TRANSACTION_SIZE_BOUND;
+        let block_template_size_bound =
+            |template: &BlockTemplate<N>| BLOCK_HEADER_SIZE_BOUND + (template.transactions().len() + 1) * TRANSACTION_SIZE_BOUND;
+
+        let data_size_bound = match self {
+            Self::BlockResponse(block) | Self::UnconfirmedBlock(_, _, block) => block.size_upper_bound(block_size_bound),
+            Self::ChallengeResponse(block_header) | Self::Ping(_, _, _, _, _, block_header, _) => {
+                block_header.size_upper_bound(|_| BLOCK_HEADER_SIZE_BOUND)
+            }
+            Self::PeerResponse(_, peer_ips) => (peer_ips.len() + 1) * std::mem::size_of::<SocketAddr>(),
+            Self::Pong(_, block_locators) => {
+                block_locators.size_upper_bound(|block_locators| block_locators.len() * (FIXED_FIELDS_SIZE_BOUND + BLOCK_HEADER_SIZE_BOUND))
+            }
+            Self::PongDiff(_, block_locators_diff) => block_locators_diff
+                .size_upper_bound(|block_locators_diff| block_locators_diff.len() * (FIXED_FIELDS_SIZE_BOUND + BLOCK_HEADER_SIZE_BOUND)),
+            Self::UnconfirmedTransaction(..) => TRANSACTION_SIZE_BOUND,
+            Self::PoolRequest(_, block_template) => block_template.size_upper_bound(block_template_size_bound),
+            Self::PoolResponse(_, _, proof) => proof.size_upper_bound(|_| PROOF_SIZE_BOUND),
+            Self::PoolRequestMulti(block_templates) => block_templates
+                .iter()
+                .map(|(_, block_template)| FIXED_FIELDS_SIZE_BOUND + block_template.size_upper_bound(block_template_size_bound))
+                .sum(),
+            Self::MempoolResponse(transaction_ids) | Self::TransactionRequest(transaction_ids) => {
+                transaction_ids.len() * std::mem::size_of::<N::TransactionID>()
+            }
+            _ => 0,
+        };
+
+        // Account for the length prefix, the message ID, and the fixed fields of the message.
+        (4 + 2 + FIXED_FIELDS_SIZE_BOUND + data_size_bound).min(E::MAXIMUM_MESSAGE_SIZE)
+    }
+
+    ///
+    /// Returns `true` if the message is small enough to be coalesced with other outbound messages into a single write.
+    /// A `Disconnect` is never coalesced, as the connection is closed right after it is sent.
+    ///
+    pub fn is_coalescible(&self) -> bool {
+        !matches!(self, Self::Disconnect(..)) && self.byte_size_upper_bound() <= COALESCE_SIZE_BOUND
+    }
+
+    ///
+    /// Serializes the deferred payloads of the message using the given encoding format.
+    /// The fixed fields of the message are always encoded with bincode, so this is a no-op for bincode.
+    ///
+    pub async fn encode_payloads(self, format: EncodingFormat) -> Result<Self> {
+        if format == EncodingFormat::Bincode {
+            return Ok(self);
+        }
+
+        Ok(match self {
+            Self::BlockResponse(block) => Self::BlockResponse(Data::Buffer(block.serialize_with(format).await?)),
+            Self::Ping(version, fork_depth, node_type, status, block_hash, block_header, mempool_size) => {
+                let block_header = Data::Buffer(block_header.serialize_with(format).await?);
+                Self::Ping(version, fork_depth, node_type, status, block_hash, block_header, mempool_size)
+            }
+            Self::Pong(is_fork, block_locators) => Self::Pong(is_fork, Data::Buffer(block_locators.serialize_with(format).await?)),
+            Self::PongDiff(is_fork, block_locators_diff) => {
+                Self::PongDiff(is_fork, Data::Buffer(block_locators_diff.serialize_with(format).await?))
+            }
+            Self::UnconfirmedBlock(block_height, block_hash, block) => {
+                Self::UnconfirmedBlock(block_height, block_hash, Data::Buffer(block.serialize_with(format).await?))
+            }
+            Self::PoolRequest(share_difficulty, block_template) => {
+                Self::PoolRequest(share_difficulty, Data::Buffer(block_template.serialize_with(format).await?))
+            }
+            Self::PoolResponse(address, nonce, proof) => {
+                Self::PoolResponse(address, nonce, Data::Buffer(proof.serialize_with(format).await?))
+            }
+            Self::PoolRequestMulti(block_templates) => {
+                let mut encoded = Vec::with_capacity(block_templates.len());
+                for (share_difficulty, block_template) in block_templates {
+                    encoded.push((share_difficulty, Data::Buffer(block_template.serialize_with(format).await?)));
+                }
+                Self::PoolRequestMulti(encoded)
+            }
+            message => message,
+        })
+    }
+
     /// Returns the message data as bytes.
     #[inline]
     pub fn serialize_data_into<W: Write>(&self, writer: &mut W) -> Result<()> {
@@ -167,30 +387,32 @@ impl<N: Network, E: Environment> Message<N, E> {
                 Ok(writer.write_all(&bytes)?)
             }
             Self::BlockResponse(block) => block.serialize_blocking_into(writer),
-            Self::ChallengeRequest(version, fork_depth, node_type, status, listener_port, nonce, cumulative_weight) => {
+            Self::ChallengeRequest(version, fork_depth, node_type, capabilities, status, listener_port, nonce, cumulative_weight) => {
                 Ok(bincode::serialize_into(
                     writer,
-                    &(version, fork_depth, node_type, status, listener_port, nonce, cumulative_weight),
+                    &(
+                        version,
+                        fork_depth,
+                        node_type,
+                        capabilities,
+                        status,
+                        listener_port,
+                        nonce,
+                        cumulative_weight,
+                    ),
                 )?)
             }
             Self::ChallengeResponse(block_header) => Ok(block_header.serialize_blocking_into(writer)?),
-            Self::Disconnect => Ok(()),
-            Self::PeerRequest => Ok(()),
-            Self::PeerResponse(peer_ips) => Ok(bincode::serialize_into(writer, peer_ips)?),
-            Self::Ping(version, fork_depth, node_type, status, block_hash, block_header) => {
+            Self::Disconnect(reason) => Ok(bincode::serialize_into(writer, reason)?),
+            Self::PeerRequest(listener_ip) => Ok(bincode::serialize_into(writer, listener_ip)?),
+            Self::PeerResponse(requester_ip, peer_ips) => Ok(bincode::serialize_into(writer, &(requester_ip, peer_ips))?),
+            Self::Ping(version, fork_depth, node_type, status, block_hash, block_header, mempool_size) => {
                 bincode::serialize_into(&mut *writer, &(version, fork_depth, node_type, status, block_hash))?;
-                block_header.serialize_blocking_into(writer)
+                block_header.serialize_blocking_into(writer)?;
+                Ok(writer.write_all(&mempool_size.to_le_bytes())?)
             }
             Self::Pong(is_fork, block_locators) => {
-                let serialized_is_fork: u8 = match is_fork {
-                    None => 0,
-                    Some(fork) => match fork {
-                        true => 1,
-                        false => 2,
-                    },
-                };
-
-                writer.write_all(&[serialized_is_fork])?;
+                writer.write_all(&[Self::serialize_is_fork(*is_fork)])?;
                 block_locators.serialize_blocking_into(writer)
             }
             Self::UnconfirmedBlock(block_height, block_hash, block) => {
@@ -209,6 +431,50 @@ impl<N: Network, E: Environment> Message<N, E> {
                 bincode::serialize_into(&mut *writer, nonce)?;
                 proof.serialize_blocking_into(writer)
             }
+            Self::PoolReject(reason) => Ok(bincode::serialize_into(writer, reason)?),
+            Self::PoolRequestMulti(block_templates) => {
+                // Ensure the number of block templates is within bounds.
+                if block_templates.is_empty() || block_templates.len() > E::MAXIMUM_POOL_REQUEST_TEMPLATES {
+                    return Err(anyhow!(
+                        "Invalid number of block templates in 'PoolRequestMulti': {}",
+                        block_templates.len()
+                    ));
+                }
+
+                writer.write_all(&[block_templates.len() as u8])?;
+                for (share_difficulty, block_template) in block_templates {
+                    // Serialize the block template first, in order to prefix it with its length.
+                    let mut buffer = Vec::new();
+                    block_template.serialize_blocking_into(&mut buffer)?;
+
+                    writer.write_all(&share_difficulty.to_le_bytes())?;
+                    writer.write_all(&(buffer.len() as u32).to_le_bytes())?;
+                    writer.write_all(&buffer)?;
+                }
+                Ok(())
+            }
+            Self::MempoolRequest => Ok(()),
+            Self::MempoolResponse(transaction_ids) | Self::TransactionRequest(transaction_ids) => {
+                // Ensure the number of transaction IDs is within bounds.
+                if transaction_ids.len() > E::MAXIMUM_MEMPOOL_RESPONSE_SIZE {
+                    return Err(anyhow!(
+                        "Invalid number of transaction IDs in '{}': {}",
+                        self.name(),
+                        transaction_ids.len()
+                    ));
+                }
+                Ok(bincode::serialize_into(writer, transaction_ids)?)
+            }
+            Self::Throttle(duration_in_secs) => Ok(bincode::serialize_into(writer, duration_in_secs)?),
+            Self::BlockUnavailable(start_block_height, end_block_height) => {
+                Ok(bincode::serialize_into(writer, &(start_block_height, end_block_height))?)
+            }
+            Self::PongDiff(is_fork, block_locators_diff) => {
+                writer.write_all(&[Self::serialize_is_fork(*is_fork)])?;
+                block_locators_diff.serialize_blocking_into(writer)
+            }
+            Self::PongResync => Ok(()),
+            Self::BlockResponseEnd(block_height) => Ok(bincode::serialize_into(writer, block_height)?),
             Self::Unused(_) => Ok(()),
         }
     }
@@ -237,41 +503,54 @@ impl<N: Network, E: Environment> Message<N, E> {
             0 => Self::BlockRequest(bincode::deserialize(&data[0..4])?, bincode::deserialize(&data[4..8])?),
             1 => Self::BlockResponse(Data::Buffer(data.to_vec().into())),
             2 => {
-                let (version, fork_depth, node_type, status, listener_port, nonce, cumulative_weight) = bincode::deserialize(data)?;
-                Self::ChallengeRequest(version, fork_depth, node_type, status, listener_port, nonce, cumulative_weight)
+                let (version, fork_depth, node_type, capabilities, status, listener_port, nonce, cumulative_weight) =
+                    bincode::deserialize(data)?;
+                Self::ChallengeRequest(
+                    version,
+                    fork_depth,
+                    node_type,
+                    capabilities,
+                    status,
+                    listener_port,
+                    nonce,
+                    cumulative_weight,
+                )
             }
             3 => Self::ChallengeResponse(Data::Buffer(data.to_vec().into())),
-            4 => match data.is_empty() {
-                true => Self::Disconnect,
-                false => return Err(anyhow!("Invalid 'Disconnect' message: {:?} {:?}", buffer, data)),
-            },
-            5 => match data.is_empty() {
-                true => Self::PeerRequest,
-                false => return Err(anyhow!("Invalid 'PeerRequest' message: {:?} {:?}", buffer, data)),
-            },
-            6 => Self::PeerResponse(bincode::deserialize(data)?),
+            4 => Self::Disconnect(bincode::deserialize(data)?),
+            5 => Self::PeerRequest(bincode::deserialize(data)?),
+            6 => {
+                let (requester_ip, peer_ips) = bincode::deserialize(data)?;
+                Self::PeerResponse(requester_ip, peer_ips)
+            }
             7 => {
+                // Ensure the buffer contains the fixed-size fields.
+                if data.len() < 52 {
+                    return Err(anyhow!("Invalid 'Ping' message: {:?}", buffer));
+                }
+                // Ensure the buffer contains a block header, between the fixed-size fields and the memory pool size.
+                if data.len() == 52 {
+                    return Err(anyhow!("Invalid 'Ping' message: missing the block header"));
+                }
                 let (version, fork_depth, node_type, status, block_hash) = bincode::deserialize(&data[0..48])?;
-                let block_header = Data::Buffer(data[48..].to_vec().into());
+                let block_header = Data::Buffer(data[48..data.len() - 4].to_vec().into());
+                let mempool_size = u32::from_le_bytes(data[data.len() - 4..].try_into()?);
 
-                Self::Ping(version, fork_depth, node_type, status, block_hash, block_header)
-            }
-            8 => {
-                let is_fork = match data[0] {
-                    0 => None,
-                    1 => Some(true),
-                    2 => Some(false),
-                    _ => return Err(anyhow!("Invalid 'Pong' message: {:?} {:?}", buffer, data)),
-                };
-
-                Self::Pong(is_fork, Data::Buffer(data[1..].to_vec().into()))
+                Self::Ping(version, fork_depth, node_type, status, block_hash, block_header, mempool_size)
             }
+            8 => Self::Pong(Self::deserialize_is_fork("Pong", data)?, Data::Buffer(data[1..].to_vec().into())),
             9 => Self::UnconfirmedBlock(
                 bincode::deserialize(&data[0..4])?,
                 bincode::deserialize(&data[4..36])?,
                 Data::Buffer(data[36..].to_vec().into()),
             ),
-            10 => Self::UnconfirmedTransaction(bincode::deserialize(data)?),
+            10 => {
+                // Reject an oversized transaction before deserializing it.
+                if data.len() > E::MAXIMUM_TRANSACTION_SIZE {
+                    return Err(OversizedTransaction(data.len()).into());
+                }
+                Self::UnconfirmedTransaction(bincode::deserialize(data)?)
+            }
             11 => Self::PoolRegister(bincode::deserialize(data)?),
             12 => Self::PoolRequest(bincode::deserialize(&data[0..8])?, Data::Buffer(data[8..].to_vec().into())),
             13 => Self::PoolResponse(
@@ -279,34 +558,249 @@ impl<N: Network, E: Environment> Message<N, E> {
                 bincode::deserialize(&data[32..64])?,
                 Data::Buffer(data[64..].to_vec().into()),
             ),
+            14 => Self::PoolReject(bincode::deserialize(data)?),
+            15 => {
+                // Ensure the number of block templates is within bounds.
+                let num_templates = match data.first() {
+                    Some(num_templates) => *num_templates as usize,
+                    None => return Err(anyhow!("Invalid 'PoolRequestMulti' message: {:?}", buffer)),
+                };
+                if num_templates == 0 || num_templates > E::MAXIMUM_POOL_REQUEST_TEMPLATES {
+                    return Err(anyhow!(
+                        "Invalid number of block templates in 'PoolRequestMulti': {}",
+                        num_templates
+                    ));
+                }
+
+                let mut block_templates = Vec::with_capacity(num_templates);
+                let mut data = &data[1..];
+                for _ in 0..num_templates {
+                    if data.len() < 12 {
+                        return Err(anyhow!("Invalid 'PoolRequestMulti' message: {:?}", buffer));
+                    }
+                    let share_difficulty = u64::from_le_bytes(data[0..8].try_into()?);
+                    let length = u32::from_le_bytes(data[8..12].try_into()?) as usize;
+                    if data.len() < 12 + length {
+                        return Err(anyhow!("Invalid 'PoolRequestMulti' message: {:?}", buffer));
+                    }
+                    block_templates.push((share_difficulty, Data::Buffer(data[12..12 + length].to_vec().into())));
+                    data = &data[12 + length..];
+                }
+
+                // Ensure there are no trailing bytes.
+                if !data.is_empty() {
+                    return Err(anyhow!("Invalid 'PoolRequestMulti' message: {:?}", buffer));
+                }
+
+                Self::PoolRequestMulti(block_templates)
+            }
+            16 => match data.is_empty() {
+                true => Self::MempoolRequest,
+                false => return Err(anyhow!("Invalid 'MempoolRequest' message: {:?} {:?}", buffer, data)),
+            },
+            17 => Self::MempoolResponse(Self::deserialize_transaction_ids("MempoolResponse", data)?),
+            18 => Self::TransactionRequest(Self::deserialize_transaction_ids("TransactionRequest", data)?),
+            19 => Self::Throttle(bincode::deserialize(data)?),
+            20 => {
+                let (start_block_height, end_block_height) = bincode::deserialize(data)?;
+                Self::BlockUnavailable(start_block_height, end_block_height)
+            }
+            21 => Self::PongDiff(
+                Self::deserialize_is_fork("PongDiff", data)?,
+                Data::Buffer(data[1..].to_vec().into()),
+            ),
+            22 => match data.is_empty() {
+                true => Self::PongResync,
+                false => return Err(anyhow!("Invalid 'PongResync' message: {:?} {:?}", buffer, data)),
+            },
+            23 => Self::BlockResponseEnd(bincode::deserialize(data)?),
             _ => return Err(anyhow!("Invalid message ID {}", id)),
         };
 
         Ok(message)
     }
+
+    /// Returns the byte encoding of the fork status in a `Pong` or `PongDiff`.
+    fn serialize_is_fork(is_fork: Option<bool>) -> u8 {
+        match is_fork {
+            None => 0,
+            Some(true) => 1,
+            Some(false) => 2,
+        }
+    }
+
+    /// Deserializes the fork status from the first byte of a `Pong` or `PongDiff`.
+    fn deserialize_is_fork(name: &str, data: &[u8]) -> Result<Option<bool>> {
+        match data.first() {
+            Some(0) => Ok(None),
+            Some(1) => Ok(Some(true)),
+            Some(2) => Ok(Some(false)),
+            _ => Err(anyhow!("Invalid '{}' message: {:?}", name, data)),
+        }
+    }
+
+    /// Deserializes a bounded list of transaction IDs, checking the length prefix before allocating.
+    fn deserialize_transaction_ids(name: &str, data: &[u8]) -> Result<Vec<N::TransactionID>> {
+        let num_transaction_ids = match data.get(0..8) {
+            Some(bytes) => u64::from_le_bytes(bytes.try_into()?),
+            None => return Err(anyhow!("Invalid '{}' message: {:?}", name, data)),
+        };
+        if num_transaction_ids > E::MAXIMUM_MEMPOOL_RESPONSE_SIZE as u64 {
+            return Err(anyhow!("Invalid number of transaction IDs in '{}': {}", name, num_transaction_ids));
+        }
+        Ok(bincode::deserialize(data)?)
+    }
+}
+
+/// An unconfirmed transaction that exceeds `E::MAXIMUM_TRANSACTION_SIZE`, with its serialized size.
+#[derive(Debug, Error)]
+#[error("Transaction of size {} is too large", _0)]
+pub struct OversizedTransaction(pub usize);
+
+///
+/// The codec for the messages on a connection, which compresses the message frames if agreed with the peer.
+///
+/// If compression is agreed upon, each frame is prefixed with a flag byte,
+/// which is set if the rest of the frame is compressed with zstd.
+///
+pub struct MessageCodec<N: Network, E: Environment> {
+    /// The features agreed with the peer in the `Hello` exchange.
+    features: FeatureFlags,
+    _phantom: PhantomData<(N, E)>,
+}
+
+impl<N: Network, E: Environment> MessageCodec<N, E> {
+    /// Returns a new codec for the given agreed features.
+    pub fn new(features: FeatureFlags) -> Self {
+        Self {
+            features,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the features agreed with the peer.
+    pub fn features(&self) -> FeatureFlags {
+        self.features
+    }
+
+    /// Returns `true` if the message frames are compressed.
+    fn is_compressed(&self) -> bool {
+        cfg!(feature = "zstd") && self.features.contains(FeatureFlags::COMPRESSES_ZSTD)
+    }
+
+    /// Appends the checksum of the payload of the frame at the given offset, if checksums are enabled.
+    fn append_checksum(dst: &mut BytesMut, frame_offset: usize) {
+        if E::CHECKSUM_ENABLED {
+            let checksum = crc32fast::hash(&dst[frame_offset + 4..]);
+            dst.extend_from_slice(&checksum.to_le_bytes());
+        }
+    }
+
+    /// Returns the message ID of the given frame, unless it is compressed or too short.
+    fn message_id(&self, frame: &[u8]) -> Option<u16> {
+        let payload = match self.is_compressed() {
+            true => match frame.split_first() {
+                Some((0, payload)) => payload,
+                _ => return None,
+            },
+            false => frame,
+        };
+        payload.get(..2).map(|id| u16::from_le_bytes([id[0], id[1]]))
+    }
+
+    /// Serializes the given message into a flagged frame, compressing it if it is large enough.
+    #[cfg(feature = "zstd")]
+    fn encode_compressed(message: Message<N, E>, dst: &mut BytesMut) -> Result<()> {
+        let mut payload = Vec::with_capacity(message.byte_size_upper_bound());
+        message.serialize_into(&mut payload)?;
+
+        let (flag, payload) = match payload.len() >= COMPRESSION_SIZE_THRESHOLD {
+            true => (1u8, zstd::bulk::compress(&payload, COMPRESSION_LEVEL)?),
+            false => (0u8, payload),
+        };
+
+        dst.reserve(4 + 1 + payload.len());
+        dst.extend_from_slice(&(1 + payload.len() as u32).to_le_bytes());
+        dst.put_u8(flag);
+        dst.extend_from_slice(&payload);
+        Ok(())
+    }
+
+    /// Deserializes the given flagged frame into a message, decompressing it if the flag is set.
+    #[cfg(feature = "zstd")]
+    fn decode_compressed(frame: &[u8]) -> Result<Message<N, E>> {
+        match frame.split_first() {
+            Some((0, payload)) => Message::deserialize(payload),
+            Some((1, payload)) => Message::deserialize(&Self::decompress(payload)?),
+            _ => Err(anyhow!("Invalid compression flag in message frame")),
+        }
+    }
+
+    /// Decompresses the given payload, failing if it exceeds the maximum message size.
+    /// The payload is streamed through the decoder, so the buffer only grows with the decompressed data.
+    #[cfg(feature = "zstd")]
+    fn decompress(payload: &[u8]) -> Result<Vec<u8>> {
+        use std::io::Read;
+
+        let mut buffer = Vec::new();
+        zstd::stream::Decoder::new(payload)?
+            .take(E::MAXIMUM_MESSAGE_SIZE as u64 + 1)
+            .read_to_end(&mut buffer)?;
+        match buffer.len() <= E::MAXIMUM_MESSAGE_SIZE {
+            true => Ok(buffer),
+            false => Err(anyhow!("Decompressed message exceeds {} bytes", E::MAXIMUM_MESSAGE_SIZE)),
+        }
+    }
+}
+
+impl<N: Network, E: Environment> Default for MessageCodec<N, E> {
+    fn default() -> Self {
+        Self::new(FeatureFlags::empty())
+    }
 }
 
-impl<N: Network, E: Environment> Encoder<Message<N, E>> for Message<N, E> {
+impl<N: Network, E: Environment> Encoder<Message<N, E>> for MessageCodec<N, E> {
     type Error = anyhow::Error;
 
     fn encode(&mut self, message: Message<N, E>, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        // Retrieve the offset of the frame, as the checksum covers the new frame only.
+        let frame_offset = dst.len();
+
+        #[cfg(feature = "prometheus")]
+        snarkos_metrics::increment_counter!(snarkos_metrics::MESSAGES_SENT_TOTAL, "type" => message.name());
+
+        #[cfg(feature = "zstd")]
+        if self.is_compressed() {
+            Self::encode_compressed(message, dst)?;
+            Self::append_checksum(dst, frame_offset);
+            #[cfg(feature = "prometheus")]
+            snarkos_metrics::counter!(snarkos_metrics::BYTES_SENT_TOTAL, (dst.len() - frame_offset) as u64);
+            return Ok(());
+        }
+
+        // Reserve enough room for the message upfront, to avoid repeated reallocations.
+        dst.reserve(message.byte_size_upper_bound());
+
         // Prepare the room for the length of the payload.
         dst.extend_from_slice(&0u32.to_le_bytes());
 
         // Serialize the payload directly into dst.
         message.serialize_into(&mut dst.writer())?;
 
-        // Calculate the length of the serialized payload.
-        let len_slice = (dst[4..].len() as u32).to_le_bytes();
+        // Calculate the length of the serialized payload, as `dst` may already hold earlier frames.
+        let len_slice = (dst[frame_offset + 4..].len() as u32).to_le_bytes();
 
         // Overwrite the initial 4B reserved before with the length of the payload.
-        dst[..4].copy_from_slice(&len_slice);
+        dst[frame_offset..frame_offset + 4].copy_from_slice(&len_slice);
 
+        Self::append_checksum(dst, frame_offset);
+        #[cfg(feature = "prometheus")]
+        snarkos_metrics::counter!(snarkos_metrics::BYTES_SENT_TOTAL, (dst.len() - frame_offset) as u64);
         Ok(())
     }
 }
 
-impl<N: Network, E: Environment> Decoder for Message<N, E> {
+impl<N: Network, E: Environment> Decoder for MessageCodec<N, E> {
     type Error = std::io::Error;
     type Item = Message<N, E>;
 
@@ -330,26 +824,559 @@ impl<N: Network, E: Environment> Decoder for Message<N, E> {
             ));
         }
 
-        if source.len() < 4 + length {
+        // The checksum of the payload follows the frame, if checksums are enabled.
+        let checksum_length = if E::CHECKSUM_ENABLED { 4 } else { 0 };
+
+        if source.len() < 4 + length + checksum_length {
             // The full message has not yet arrived.
             //
             // We reserve more space in the buffer. This is not strictly
             // necessary, but is a good idea performance-wise.
-            source.reserve(4 + length - source.len());
+            source.reserve(4 + length + checksum_length - source.len());
 
             // We inform `Framed` that we need more bytes to form the next frame.
             return Ok(None);
         }
 
+        // Ensure the payload matches its checksum, so that a corrupted frame is not deserialized.
+        let frame = &source[4..][..length];
+        if E::CHECKSUM_ENABLED {
+            let checksum = u32::from_le_bytes([source[4 + length], source[5 + length], source[6 + length], source[7 + length]]);
+            if crc32fast::hash(frame) != checksum {
+                let error = match self.message_id(frame) {
+                    Some(id) => format!("Invalid checksum for a frame of message ID {}", id),
+                    None => "Invalid checksum for a compressed frame".to_string(),
+                };
+                source.advance(4 + length + checksum_length);
+                return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, error));
+            }
+        }
+
         // Convert the buffer to a message, or fail if it is not valid.
-        let message = match Message::deserialize(&source[4..][..length]) {
+        let message = match self.is_compressed() {
+            #[cfg(feature = "zstd")]
+            true => Self::decode_compressed(frame),
+            _ => Message::deserialize(frame),
+        };
+        let message = match message.map_err(|error| error.downcast::<OversizedTransaction>()) {
             Ok(message) => Ok(Some(message)),
-            Err(error) => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, error)),
+            // Preserve an oversized transaction as the source, so that the sender may be penalized.
+            Err(Ok(error)) => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, error)),
+            Err(Err(error)) => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, error)),
         };
 
         // Use `advance` to modify the source such that it no longer contains this frame.
-        source.advance(4 + length);
+        source.advance(4 + length + checksum_length);
+
+        #[cfg(feature = "prometheus")]
+        {
+            snarkos_metrics::counter!(snarkos_metrics::BYTES_RECEIVED_TOTAL, (4 + length + checksum_length) as u64);
+            if let Ok(Some(message)) = &message {
+                snarkos_metrics::increment_counter!(snarkos_metrics::MESSAGES_RECEIVED_TOTAL, "type" => message.name());
+            }
+        }
 
         message
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{environment::Prover, helpers::DefaultFeePolicy};
+    use snarkvm::dpc::testnet2::Testnet2;
+
+    use rand::{thread_rng, Rng};
+
+    #[test]
+    fn test_challenge_request_with_capabilities() {
+        let rng = &mut thread_rng();
+
+        let capabilities = NodeCapabilities::from(NodeType::Operator) | NodeCapabilities::SERVES_RPC;
+        let (listener_port, nonce, cumulative_weight): (u16, u64, u128) = (rng.gen(), rng.gen(), rng.gen());
+
+        // Serialize a `ChallengeRequest` message with the capabilities.
+        let message = Message::<Testnet2, Prover<Testnet2>>::ChallengeRequest(
+            Prover::<Testnet2>::MESSAGE_VERSION,
+            <Testnet2 as Network>::ALEO_MAXIMUM_FORK_DEPTH,
+            NodeType::Operator,
+            capabilities,
+            State::Ready,
+            listener_port,
+            nonce,
+            cumulative_weight,
+        );
+        let mut buffer = Vec::new();
+        message.serialize_into(&mut buffer).unwrap();
+
+        // Ensure the capabilities occupy 2 bytes, directly after the version, fork depth, and node type.
+        assert_eq!(&capabilities.bits().to_le_bytes(), &buffer[14..16]);
+
+        // Ensure the message round-trips.
+        match Message::<Testnet2, Prover<Testnet2>>::deserialize(&buffer).unwrap() {
+            Message::ChallengeRequest(
+                _,
+                _,
+                node_type,
+                candidate_capabilities,
+                status,
+                candidate_port,
+                candidate_nonce,
+                candidate_weight,
+            ) => {
+                assert_eq!(NodeType::Operator, node_type);
+                assert_eq!(capabilities, candidate_capabilities);
+                assert_eq!(State::Ready, status);
+                assert_eq!(listener_port, candidate_port);
+                assert_eq!(nonce, candidate_nonce);
+                assert_eq!(cumulative_weight, candidate_weight);
+            }
+            message => panic!("Expected a 'ChallengeRequest', found '{}'", message.name()),
+        }
+    }
+
+    #[test]
+    fn test_disconnect_with_reason() {
+        // Ensure a `Disconnect` message round-trips with its reason.
+        let message = Message::<Testnet2, Prover<Testnet2>>::Disconnect(DisconnectReason::VersionMismatch);
+        let mut buffer = Vec::new();
+        message.serialize_into(&mut buffer).unwrap();
+
+        match Message::<Testnet2, Prover<Testnet2>>::deserialize(&buffer).unwrap() {
+            Message::Disconnect(reason) => assert_eq!(DisconnectReason::VersionMismatch, reason),
+            message => panic!("Expected a 'Disconnect', found '{}'", message.name()),
+        }
+
+        // Ensure a `Disconnect` message without a reason is rejected.
+        assert!(Message::<Testnet2, Prover<Testnet2>>::deserialize(&[4, 0]).is_err());
+    }
+
+    #[test]
+    fn test_throttle() {
+        // Ensure a `Throttle` message round-trips with its duration.
+        let message = Message::<Testnet2, Prover<Testnet2>>::Throttle(10);
+        let mut buffer = Vec::new();
+        message.serialize_into(&mut buffer).unwrap();
+        match Message::<Testnet2, Prover<Testnet2>>::deserialize(&buffer).unwrap() {
+            Message::Throttle(duration_in_secs) => assert_eq!(10, duration_in_secs),
+            message => panic!("Expected a 'Throttle', found '{}'", message.name()),
+        }
+
+        // Ensure a truncated `Throttle` is rejected.
+        assert!(Message::<Testnet2, Prover<Testnet2>>::deserialize(&buffer[..4]).is_err());
+    }
+
+    #[test]
+    fn test_block_unavailable() {
+        // Ensure a `BlockUnavailable` message round-trips with its block range.
+        let message = Message::<Testnet2, Prover<Testnet2>>::BlockUnavailable(0, 50);
+        let mut buffer = Vec::new();
+        message.serialize_into(&mut buffer).unwrap();
+        match Message::<Testnet2, Prover<Testnet2>>::deserialize(&buffer).unwrap() {
+            Message::BlockUnavailable(start_block_height, end_block_height) => assert_eq!((0, 50), (start_block_height, end_block_height)),
+            message => panic!("Expected a 'BlockUnavailable', found '{}'", message.name()),
+        }
+    }
+
+    #[test]
+    fn test_block_response_end() {
+        // Ensure a `BlockResponseEnd` message round-trips with its block height.
+        let message = Message::<Testnet2, Prover<Testnet2>>::BlockResponseEnd(50);
+        let mut buffer = Vec::new();
+        message.serialize_into(&mut buffer).unwrap();
+        match Message::<Testnet2, Prover<Testnet2>>::deserialize(&buffer).unwrap() {
+            Message::BlockResponseEnd(block_height) => assert_eq!(50, block_height),
+            message => panic!("Expected a 'BlockResponseEnd', found '{}'", message.name()),
+        }
+    }
+
+    #[test]
+    fn test_pong_diff() {
+        let genesis_block = Testnet2::genesis_block();
+        let block_hash = <Testnet2 as Network>::BlockHash::default();
+
+        // Prepare the diff for a chain that advanced by one block.
+        let locators = vec![(0, (genesis_block.hash(), None)), (1, (block_hash, None))];
+        let base = BlockLocators::<Testnet2>::from(locators[..1].iter().cloned().collect()).unwrap();
+        let block_locators = BlockLocators::from(locators.into_iter().collect()).unwrap();
+        let diff = BlockLocatorsDiff::new(&BlockLocatorsDiff::hashes(&base), &block_locators).unwrap();
+
+        // Ensure a `PongDiff` message round-trips with its fork status and diff.
+        let message = Message::<Testnet2, Prover<Testnet2>>::PongDiff(Some(false), Data::Object(diff.clone()));
+        let mut buffer = Vec::new();
+        message.serialize_into(&mut buffer).unwrap();
+        match Message::<Testnet2, Prover<Testnet2>>::deserialize(&buffer).unwrap() {
+            Message::PongDiff(is_fork, candidate_diff) => {
+                assert_eq!(Some(false), is_fork);
+                assert_eq!(diff, candidate_diff.deserialize_blocking().unwrap());
+            }
+            message => panic!("Expected a 'PongDiff', found '{}'", message.name()),
+        }
+
+        // Ensure a `PongDiff` with an invalid fork status is rejected.
+        assert!(Message::<Testnet2, Prover<Testnet2>>::deserialize(&[21, 0, 3]).is_err());
+        assert!(Message::<Testnet2, Prover<Testnet2>>::deserialize(&[21, 0]).is_err());
+
+        // Ensure a `PongResync` with trailing bytes is rejected.
+        let message = Message::<Testnet2, Prover<Testnet2>>::deserialize(&[22, 0]);
+        assert!(matches!(message, Ok(Message::PongResync)));
+        assert!(Message::<Testnet2, Prover<Testnet2>>::deserialize(&[22, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn test_is_coalescible() {
+        let genesis_block = Testnet2::genesis_block();
+
+        // Ensure small control messages may be coalesced.
+        let ping = Message::<Testnet2, Prover<Testnet2>>::Ping(
+            Prover::<Testnet2>::MESSAGE_VERSION,
+            <Testnet2 as Network>::ALEO_MAXIMUM_FORK_DEPTH,
+            NodeType::Prover,
+            State::Ready,
+            genesis_block.hash(),
+            Data::Object(genesis_block.header().clone()),
+            0,
+        );
+        assert!(ping.is_coalescible());
+        assert!(Message::<Testnet2, Prover<Testnet2>>::BlockRequest(0, 10).is_coalescible());
+
+        // Ensure blocks and disconnects are written immediately.
+        assert!(!Message::<Testnet2, Prover<Testnet2>>::BlockResponse(Data::Object(genesis_block.clone())).is_coalescible());
+        assert!(!Message::<Testnet2, Prover<Testnet2>>::Disconnect(DisconnectReason::TooManyPeers).is_coalescible());
+    }
+
+    #[test]
+    fn test_coalesced_messages_are_independent_frames() {
+        // Encode several messages into the same buffer, as is done when coalescing writes.
+        let mut buffer = BytesMut::new();
+        let mut codec = MessageCodec::<Testnet2, Prover<Testnet2>>::default();
+        codec.encode(Message::BlockRequest(0, 10), &mut buffer).unwrap();
+        codec.encode(Message::MempoolRequest, &mut buffer).unwrap();
+        codec.encode(Message::Throttle(5), &mut buffer).unwrap();
+
+        // Ensure each message is decoded as its own frame, in order.
+        assert!(matches!(codec.decode(&mut buffer).unwrap(), Some(Message::BlockRequest(0, 10))));
+        assert!(matches!(codec.decode(&mut buffer).unwrap(), Some(Message::MempoolRequest)));
+        assert!(matches!(codec.decode(&mut buffer).unwrap(), Some(Message::Throttle(5))));
+        assert!(codec.decode(&mut buffer).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_codec_without_compression() {
+        let message = || Message::<Testnet2, Prover<Testnet2>>::MempoolResponse(vec![Default::default(); 128]);
+
+        // Ensure a connection with a peer that lacks compression uses plain frames.
+        let features = FeatureFlags::local().negotiate(FeatureFlags::BATCHES_MESSAGES);
+        let mut codec = MessageCodec::<Testnet2, Prover<Testnet2>>::new(features);
+        let mut buffer = BytesMut::new();
+        codec.encode(message(), &mut buffer).unwrap();
+
+        let mut expected = Vec::new();
+        message().serialize_into(&mut expected).unwrap();
+        assert_eq!(&(expected.len() as u32).to_le_bytes(), &buffer[..4]);
+        assert_eq!(&expected[..], &buffer[4..]);
+
+        // Ensure the plain frame is decoded.
+        match codec.decode(&mut buffer).unwrap() {
+            Some(Message::MempoolResponse(transaction_ids)) => assert_eq!(128, transaction_ids.len()),
+            _ => panic!("Expected a 'MempoolResponse'"),
+        }
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_codec_with_compression() {
+        let message = || Message::<Testnet2, Prover<Testnet2>>::MempoolResponse(vec![Default::default(); 128]);
+
+        // Ensure two compression-capable nodes compress large frames.
+        let features = FeatureFlags::local().negotiate(FeatureFlags::local());
+        let mut codec = MessageCodec::<Testnet2, Prover<Testnet2>>::new(features);
+        let mut buffer = BytesMut::new();
+        codec.encode(message(), &mut buffer).unwrap();
+
+        let mut uncompressed = Vec::new();
+        message().serialize_into(&mut uncompressed).unwrap();
+        assert_eq!(1, buffer[4]);
+        assert!(buffer.len() < uncompressed.len());
+
+        // Ensure small frames are flagged as uncompressed.
+        codec.encode(Message::Throttle(5), &mut buffer).unwrap();
+
+        // Ensure both frames are decoded, in order.
+        match codec.decode(&mut buffer).unwrap() {
+            Some(Message::MempoolResponse(transaction_ids)) => assert_eq!(128, transaction_ids.len()),
+            _ => panic!("Expected a 'MempoolResponse'"),
+        }
+        assert_eq!(0, buffer[4]);
+        assert!(matches!(codec.decode(&mut buffer).unwrap(), Some(Message::Throttle(5))));
+
+        // Ensure a frame with an invalid compression flag is rejected.
+        let mut buffer = BytesMut::from(&[3, 0, 0, 0, 2, 19, 0][..]);
+        assert!(codec.decode(&mut buffer).is_err());
+    }
+
+    #[cfg(feature = "zstd")]
+    #[derive(Clone, Debug, Default)]
+    struct SmallMessageNode;
+
+    #[cfg(feature = "zstd")]
+    #[rustfmt::skip]
+    impl Environment for SmallMessageNode {
+        type Network = Testnet2;
+        type FeePolicy = DefaultFeePolicy;
+        const NODE_TYPE: NodeType = NodeType::Client;
+        const MINIMUM_NUMBER_OF_PEERS: usize = 2;
+        const MAXIMUM_NUMBER_OF_PEERS: usize = 21;
+        const MAXIMUM_MESSAGE_SIZE: usize = 1024;
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_codec_rejects_oversized_decompression() {
+        // Ensure a small compressed frame that decompresses beyond the maximum message size is rejected.
+        let payload = zstd::bulk::compress(&[0u8; 4096], COMPRESSION_LEVEL).unwrap();
+        assert!(payload.len() < SmallMessageNode::MAXIMUM_MESSAGE_SIZE);
+        let mut buffer = BytesMut::new();
+        buffer.extend_from_slice(&(1 + payload.len() as u32).to_le_bytes());
+        buffer.extend_from_slice(&[1]);
+        buffer.extend_from_slice(&payload);
+
+        let mut codec = MessageCodec::<Testnet2, SmallMessageNode>::new(FeatureFlags::local());
+        assert!(codec.decode(&mut buffer).is_err());
+    }
+
+    #[derive(Clone, Debug, Default)]
+    struct ChecksumNode;
+
+    #[rustfmt::skip]
+    impl Environment for ChecksumNode {
+        type Network = Testnet2;
+        type FeePolicy = DefaultFeePolicy;
+        const NODE_TYPE: NodeType = NodeType::Client;
+        const CHECKSUM_ENABLED: bool = true;
+        const MINIMUM_NUMBER_OF_PEERS: usize = 2;
+        const MAXIMUM_NUMBER_OF_PEERS: usize = 21;
+    }
+
+    #[test]
+    fn test_codec_with_checksum() {
+        let peer_ips = vec!["127.0.0.1:4130".parse().unwrap(); 4];
+        let message = || Message::<Testnet2, ChecksumNode>::PeerResponse(None, peer_ips.clone());
+        let mut codec = MessageCodec::<Testnet2, ChecksumNode>::default();
+
+        // Ensure the checksum of the payload follows the frame.
+        let mut buffer = BytesMut::new();
+        codec.encode(message(), &mut buffer).unwrap();
+        let mut payload = Vec::new();
+        message().serialize_into(&mut payload).unwrap();
+        assert_eq!(4 + payload.len() + 4, buffer.len());
+        assert_eq!(&crc32fast::hash(&payload).to_le_bytes(), &buffer[4 + payload.len()..]);
+
+        // Ensure a frame with a valid checksum is decoded.
+        let decoded = codec.decode(&mut buffer.clone()).unwrap();
+        assert!(matches!(decoded, Some(Message::PeerResponse(None, ips)) if ips == peer_ips));
+
+        // Ensure a frame with a corrupted payload is rejected, and skipped.
+        buffer[10] ^= 1;
+        codec.encode(Message::Throttle(5), &mut buffer).unwrap();
+        assert_eq!(std::io::ErrorKind::InvalidData, codec.decode(&mut buffer).unwrap_err().kind());
+        assert!(matches!(codec.decode(&mut buffer).unwrap(), Some(Message::Throttle(5))));
+
+        // Ensure a truncated checksum waits for the rest of the frame.
+        let mut buffer = BytesMut::new();
+        codec.encode(message(), &mut buffer).unwrap();
+        buffer.truncate(buffer.len() - 1);
+        assert!(codec.decode(&mut buffer).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_codec_rejects_oversized_transaction() {
+        let mut codec = MessageCodec::<Testnet2, Prover<Testnet2>>::default();
+        let frame = |size: usize| {
+            let mut buffer = BytesMut::new();
+            buffer.extend_from_slice(&(2 + size as u32).to_le_bytes());
+            buffer.extend_from_slice(&10u16.to_le_bytes());
+            buffer.extend_from_slice(&vec![0u8; size]);
+            buffer
+        };
+        let is_oversized = |error: &std::io::Error| error.get_ref().map_or(false, |error| error.is::<OversizedTransaction>());
+
+        // Ensure an oversized transaction is rejected before it is deserialized.
+        let mut buffer = frame(Prover::<Testnet2>::MAXIMUM_TRANSACTION_SIZE + 1);
+        let error = codec.decode(&mut buffer).unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, error.kind());
+        assert!(is_oversized(&error));
+        assert!(buffer.is_empty());
+
+        // Ensure an invalid transaction within the limit fails deserialization instead.
+        let error = codec.decode(&mut frame(16)).unwrap_err();
+        assert!(!is_oversized(&error));
+    }
+
+    #[test]
+    fn test_ping_with_mempool_size() {
+        let genesis_block = Testnet2::genesis_block();
+        let mempool_size = Prover::<Testnet2>::MAX_PEER_MEMPOOL_SIZE + 1;
+
+        // Serialize a `Ping` message with the memory pool size.
+        let message = Message::<Testnet2, Prover<Testnet2>>::Ping(
+            Prover::<Testnet2>::MESSAGE_VERSION,
+            <Testnet2 as Network>::ALEO_MAXIMUM_FORK_DEPTH,
+            NodeType::Prover,
+            State::Ready,
+            genesis_block.hash(),
+            Data::Object(genesis_block.header().clone()),
+            mempool_size,
+        );
+        let mut buffer = Vec::new();
+        message.serialize_into(&mut buffer).unwrap();
+
+        // Ensure the message round-trips.
+        match Message::<Testnet2, Prover<Testnet2>>::deserialize(&buffer).unwrap() {
+            Message::Ping(_, _, _, _, block_hash, _, candidate_mempool_size) => {
+                assert_eq!(genesis_block.hash(), block_hash);
+                assert_eq!(mempool_size, candidate_mempool_size);
+            }
+            message => panic!("Expected a 'Ping', found '{}'", message.name()),
+        }
+
+        // Ensure a truncated `Ping` is rejected.
+        assert!(Message::<Testnet2, Prover<Testnet2>>::deserialize(&buffer[..50]).is_err());
+    }
+
+    #[test]
+    fn test_ping_without_block_header() {
+        let genesis_block = Testnet2::genesis_block();
+
+        // Serialize the fixed-size fields of a `Ping` message, without a block header.
+        let mut buffer = 7u16.to_le_bytes().to_vec();
+        bincode::serialize_into(
+            &mut buffer,
+            &(
+                Prover::<Testnet2>::MESSAGE_VERSION,
+                <Testnet2 as Network>::ALEO_MAXIMUM_FORK_DEPTH,
+                NodeType::Prover,
+                State::Ready,
+                genesis_block.hash(),
+            ),
+        )
+        .unwrap();
+        assert_eq!(2 + 48, buffer.len());
+
+        // Ensure a `Ping` with exactly the 48 bytes of the fixed-size fields is rejected.
+        assert!(Message::<Testnet2, Prover<Testnet2>>::deserialize(&buffer).is_err());
+
+        // Ensure a `Ping` with a memory pool size, but an empty block header, is rejected with a descriptive error.
+        buffer.extend_from_slice(&0u32.to_le_bytes());
+        let error = Message::<Testnet2, Prover<Testnet2>>::deserialize(&buffer).unwrap_err();
+        assert!(error.to_string().contains("missing the block header"));
+    }
+
+    #[test]
+    fn test_byte_size_upper_bound() {
+        let genesis_block = Testnet2::genesis_block();
+
+        // Ensure the upper bound holds for deferred objects and for serialized buffers.
+        let block = Data::Object(genesis_block.clone());
+        let serialized_block = Data::Buffer(bincode::serialize(genesis_block).unwrap().into());
+        for data in [block, serialized_block] {
+            let message = Message::<Testnet2, Prover<Testnet2>>::BlockResponse(data);
+            let upper_bound = message.byte_size_upper_bound();
+
+            let mut buffer = BytesMut::new();
+            Message::PeerRequest(None).encode(message, &mut buffer).unwrap();
+            assert!(buffer.len() <= upper_bound);
+            // Ensure the buffer was not reallocated while encoding.
+            assert!(buffer.capacity() >= upper_bound);
+        }
+
+        // Ensure the upper bound holds for messages without deferred objects.
+        let message = Message::<Testnet2, Prover<Testnet2>>::PeerResponse(
+            Some("127.0.0.1:4131".parse().unwrap()),
+            vec!["127.0.0.1:4132".parse().unwrap(); 100],
+        );
+        let mut buffer = Vec::new();
+        message.serialize_into(&mut buffer).unwrap();
+        assert!(buffer.len() + 4 <= message.byte_size_upper_bound());
+    }
+
+    #[test]
+    fn test_pool_request_multi_bounds() {
+        // Ensure a `PoolRequestMulti` with no block templates is rejected.
+        let message = Message::<Testnet2, Prover<Testnet2>>::PoolRequestMulti(vec![]);
+        assert!(message.serialize_into(&mut Vec::new()).is_err());
+        assert!(Message::<Testnet2, Prover<Testnet2>>::deserialize(&[15, 0, 0]).is_err());
+
+        // Ensure a `PoolRequestMulti` with too many block templates is rejected.
+        let num_templates = Prover::<Testnet2>::MAXIMUM_POOL_REQUEST_TEMPLATES as u8 + 1;
+        let mut buffer = vec![15, 0, num_templates];
+        for _ in 0..num_templates {
+            buffer.extend_from_slice(&u64::MAX.to_le_bytes());
+            buffer.extend_from_slice(&0u32.to_le_bytes());
+        }
+        assert!(Message::<Testnet2, Prover<Testnet2>>::deserialize(&buffer).is_err());
+
+        // Ensure a truncated `PoolRequestMulti` is rejected.
+        let mut buffer = vec![15, 0, 1];
+        buffer.extend_from_slice(&u64::MAX.to_le_bytes());
+        buffer.extend_from_slice(&8u32.to_le_bytes());
+        buffer.extend_from_slice(&[0u8; 4]);
+        assert!(Message::<Testnet2, Prover<Testnet2>>::deserialize(&buffer).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pool_response_with_malformed_proof() {
+        let rng = &mut thread_rng();
+
+        // Initialize a prover address and nonce.
+        let address = Account::<Testnet2>::new(rng).address();
+        let nonce = <Testnet2 as Network>::PoSWNonce::rand(rng);
+
+        // Initialize a corrupt proof buffer.
+        let corrupt_proof: Vec<u8> = (0..rng.gen_range(1..1024)).map(|_| rng.gen()).collect();
+
+        // Serialize a `PoolResponse` message with the corrupt proof.
+        let message = Message::<Testnet2, Prover<Testnet2>>::PoolResponse(address, nonce, Data::Buffer(corrupt_proof.into()));
+        let mut buffer = Vec::new();
+        message.serialize_into(&mut buffer).unwrap();
+
+        // Ensure the address and nonce are recovered, and the proof fails to deserialize without panicking.
+        match Message::<Testnet2, Prover<Testnet2>>::deserialize(&buffer).unwrap() {
+            Message::PoolResponse(candidate_address, candidate_nonce, proof) => {
+                assert_eq!(address, candidate_address);
+                assert_eq!(nonce, candidate_nonce);
+                assert!(proof.deserialize().await.is_err());
+            }
+            message => panic!("Expected a 'PoolResponse', found '{}'", message.name()),
+        }
+    }
+
+    #[test]
+    fn test_mempool_response_bounds() {
+        let rng = &mut thread_rng();
+
+        // Ensure a `MempoolResponse` round-trips.
+        let transaction_ids: Vec<_> = (0..10).map(|_| <Testnet2 as Network>::TransactionID::rand(rng)).collect();
+        let message = Message::<Testnet2, Prover<Testnet2>>::MempoolResponse(transaction_ids.clone());
+        let mut buffer = Vec::new();
+        message.serialize_into(&mut buffer).unwrap();
+        match Message::<Testnet2, Prover<Testnet2>>::deserialize(&buffer).unwrap() {
+            Message::MempoolResponse(candidate_transaction_ids) => assert_eq!(transaction_ids, candidate_transaction_ids),
+            message => panic!("Expected a 'MempoolResponse', found '{}'", message.name()),
+        }
+
+        // Ensure a `MempoolResponse` with too many transaction IDs is rejected.
+        let num_transaction_ids = Prover::<Testnet2>::MAXIMUM_MEMPOOL_RESPONSE_SIZE + 1;
+        let transaction_ids = vec![<Testnet2 as Network>::TransactionID::rand(rng); num_transaction_ids];
+        let message = Message::<Testnet2, Prover<Testnet2>>::MempoolResponse(transaction_ids);
+        assert!(message.serialize_into(&mut Vec::new()).is_err());
+
+        let mut buffer = vec![17, 0];
+        buffer.extend_from_slice(&(num_transaction_ids as u64).to_le_bytes());
+        assert!(Message::<Testnet2, Prover<Testnet2>>::deserialize(&buffer).is_err());
+
+        // Ensure a `MempoolRequest` with trailing bytes is rejected.
+        assert!(Message::<Testnet2, Prover<Testnet2>>::deserialize(&[16, 0]).is_ok());
+        assert!(Message::<Testnet2, Prover<Testnet2>>::deserialize(&[16, 0, 0]).is_err());
+    }
+}
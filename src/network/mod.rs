@@ -15,7 +15,7 @@
 // along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
 
 pub(crate) mod ledger;
-pub(crate) use ledger::{LedgerRequest, LedgerRouter};
+pub(crate) use ledger::{LedgerRequest, LedgerRouter, PeersStateReader, SyncProgressReader, UnconfirmedBlocksReader};
 
 pub mod message;
 pub use message::*;
@@ -29,6 +29,9 @@ pub(crate) use peer::*;
 pub(crate) mod peers;
 pub(crate) use peers::*;
 
+pub mod proof_backend;
+pub use proof_backend::*;
+
 pub mod prover;
 pub use prover::*;
 
@@ -24,6 +24,7 @@ use crate::{
     Message,
     PeersRequest,
     PeersRouter,
+    PoolRejectReason,
     ProverRouter,
 };
 use snarkos_storage::{storage::Storage, OperatorState};
@@ -32,14 +33,17 @@ use snarkvm::dpc::{prelude::*, PoSWProof};
 use anyhow::Result;
 use rand::thread_rng;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{HashMap, HashSet, VecDeque},
     net::SocketAddr,
     path::Path,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     time::{Duration, Instant},
 };
 use tokio::{
-    sync::{mpsc, oneshot, RwLock},
+    sync::{mpsc, oneshot, Notify, RwLock, Semaphore},
     task,
 };
 
@@ -58,12 +62,146 @@ pub enum OperatorRequest<N: Network> {
     PoolRegister(SocketAddr, Address<N>),
     /// PoolResponse := (peer_ip, prover_address, nonce, proof)
     PoolResponse(SocketAddr, Address<N>, N::PoSWNonce, PoSWProof<N>),
+    /// PoolDisconnect := (peer_ip)
+    PoolDisconnect(SocketAddr),
+    /// QueuedPoolResponse := (peer_ip, prover_address, header_root, nonce, proof)
+    QueuedPoolResponse(SocketAddr, Address<N>, N::BlockHeaderRoot, N::PoSWNonce, PoSWProof<N>),
 }
 
 /// The predefined base share difficulty.
 const BASE_SHARE_DIFFICULTY: u64 = u64::MAX;
 /// The operator heartbeat in seconds.
 const HEARTBEAT_IN_SECONDS: Duration = Duration::from_secs(1);
+/// The minimum variance in the share-submission intervals of a prover, below which the prover is flagged.
+const MIN_SUBMISSION_INTERVAL_VARIANCE_MS: u64 = 10;
+/// The number of share-submission intervals of a prover that are kept in its rolling window.
+const SUBMISSION_INTERVAL_WINDOW: usize = 8;
+/// The number of rate-limited shares from a prover tolerated before disconnecting from it.
+const MAX_RATE_LIMITED_SHARES: u32 = 50;
+/// The maximum number of shares queued while the pool is paused.
+const MAXIMUM_QUEUED_POOL_RESPONSES: usize = 10_000;
+/// The maximum number of shares whose PoSW proofs are verified concurrently.
+const POOL_VERIFICATION_CONCURRENCY: usize = 8;
+
+/// Shorthand for a share queued while the pool is paused := (peer_ip, prover_address, header_root, nonce, proof),
+/// where the header root is that of the block template when the share was received.
+type QueuedPoolResponse<N> = (
+    SocketAddr,
+    Address<N>,
+    <N as Network>::BlockHeaderRoot,
+    <N as Network>::PoSWNonce,
+    PoSWProof<N>,
+);
+
+///
+/// A filter of the nonces submitted by a prover for the current block template.
+///
+#[derive(Debug)]
+struct DuplicateShareFilter<N: Network> {
+    nonces: HashSet<N::PoSWNonce>,
+}
+
+impl<N: Network> DuplicateShareFilter<N> {
+    /// Inserts the given nonce into the filter, returning `false` if it was already seen.
+    fn insert(&mut self, nonce: N::PoSWNonce) -> bool {
+        self.nonces.insert(nonce)
+    }
+}
+
+impl<N: Network> Default for DuplicateShareFilter<N> {
+    fn default() -> Self {
+        Self {
+            nonces: Default::default(),
+        }
+    }
+}
+
+///
+/// A rolling window of the intervals between the shares submitted by a prover.
+/// Suspiciously regular intervals may indicate a prover that is gaming the share difficulty.
+///
+#[derive(Debug, Default)]
+struct SubmissionIntervals {
+    /// The timestamp of the last share submitted by the prover.
+    last_submitted: Option<Instant>,
+    /// The most recent intervals between shares, in milliseconds.
+    intervals_ms: VecDeque<f64>,
+    /// If `true`, the variance of the intervals is below `MIN_SUBMISSION_INTERVAL_VARIANCE_MS`.
+    is_flagged: bool,
+}
+
+impl SubmissionIntervals {
+    /// Records a share submitted at the given timestamp, returning `true` if the prover is newly flagged.
+    fn record(&mut self, now: Instant) -> bool {
+        if let Some(last_submitted) = self.last_submitted.replace(now) {
+            if self.intervals_ms.len() == SUBMISSION_INTERVAL_WINDOW {
+                self.intervals_ms.pop_front();
+            }
+            self.intervals_ms
+                .push_back(now.saturating_duration_since(last_submitted).as_secs_f64() * 1000.0);
+        }
+
+        // Only flag the prover once the rolling window is full.
+        let was_flagged = self.is_flagged;
+        let is_window_full = self.intervals_ms.len() == SUBMISSION_INTERVAL_WINDOW;
+        self.is_flagged = is_window_full && self.variance_ms() < MIN_SUBMISSION_INTERVAL_VARIANCE_MS as f64;
+        self.is_flagged && !was_flagged
+    }
+
+    /// Returns the variance of the intervals in the rolling window.
+    fn variance_ms(&self) -> f64 {
+        if self.intervals_ms.is_empty() {
+            return 0.0;
+        }
+        let count = self.intervals_ms.len() as f64;
+        let mean = self.intervals_ms.iter().sum::<f64>() / count;
+        self.intervals_ms.iter().map(|interval| (interval - mean).powi(2)).sum::<f64>() / count
+    }
+}
+
+///
+/// A token bucket limiting the rate at which a prover may submit shares.
+/// The bucket holds up to one second of shares, and refills continuously at the permitted rate.
+///
+#[derive(Debug)]
+struct SubmissionRateLimiter {
+    /// The number of shares the prover may currently submit.
+    tokens: f64,
+    /// The timestamp at which the bucket was last refilled.
+    last_refill: Instant,
+    /// The number of shares from the prover that exceeded the rate limit.
+    num_rate_limited: u32,
+}
+
+impl SubmissionRateLimiter {
+    /// Initializes a new full bucket for the given rate, at the given timestamp.
+    fn new(rate_per_second: u32, now: Instant) -> Self {
+        Self {
+            tokens: rate_per_second as f64,
+            last_refill: now,
+            num_rate_limited: 0,
+        }
+    }
+
+    /// Records a share submitted at the given timestamp, returning `false` if it exceeds the given rate.
+    fn try_acquire(&mut self, rate_per_second: u32, now: Instant) -> bool {
+        // Refill the bucket for the time elapsed since the last refill.
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate_per_second as f64).min(rate_per_second as f64);
+        self.last_refill = now;
+
+        match self.tokens >= 1.0 {
+            true => {
+                self.tokens -= 1.0;
+                true
+            }
+            false => {
+                self.num_rate_limited = self.num_rate_limited.saturating_add(1);
+                false
+            }
+        }
+    }
+}
 
 ///
 /// An operator for a program on a specific network in the node server.
@@ -80,8 +218,24 @@ pub struct Operator<N: Network, E: Environment> {
     block_template: RwLock<Option<BlockTemplate<N>>>,
     /// A list of provers and their associated state := (last_submitted, share_difficulty)
     provers: RwLock<HashMap<Address<N>, (Instant, u64)>>,
+    /// A map of prover IPs to the addresses of the provers that registered before a block template existed.
+    pending_provers: RwLock<HashMap<SocketAddr, Address<N>>>,
+    /// A notifier to update the block template ahead of the next heartbeat.
+    block_template_notify: Notify,
     /// A list of the known nonces for the current round.
     known_nonces: RwLock<HashSet<N::PoSWNonce>>,
+    /// A map of provers to the nonces they submitted for the current round.
+    share_filters: RwLock<HashMap<Address<N>, DuplicateShareFilter<N>>>,
+    /// A map of provers to the intervals between their submitted shares.
+    submission_intervals: RwLock<HashMap<Address<N>, SubmissionIntervals>>,
+    /// A map of prover IPs to the rate limiters of their submitted shares.
+    submission_rates: RwLock<HashMap<SocketAddr, SubmissionRateLimiter>>,
+    /// Set to `true` if the pool is paused, in which case no block templates are issued, and shares are queued.
+    is_paused: AtomicBool,
+    /// The shares received while the pool is paused, which are processed once it resumes.
+    queued_responses: RwLock<VecDeque<QueuedPoolResponse<N>>>,
+    /// The permits for the shares being processed, which bound the number of PoSW proofs verified concurrently.
+    verification_permits: Arc<Semaphore>,
     /// The operator router of the node.
     operator_router: OperatorRouter<N>,
     /// The pool of unconfirmed transactions.
@@ -111,14 +265,28 @@ impl<N: Network, E: Environment> Operator<N, E> {
     ) -> Result<Arc<Self>> {
         // Initialize an mpsc channel for sending requests to the `Operator` struct.
         let (operator_router, mut operator_handler) = mpsc::channel(1024);
+        // Open the operator state, and restore a pause that was in effect before a restart.
+        let state = Arc::new(OperatorState::open_writer::<S, P>(path)?);
+        let is_paused = state.is_pool_paused()?;
+        if is_paused && E::NODE_TYPE == NodeType::Operator {
+            warn!("The pool is paused for maintenance. Please call 'resumepool' to resume it");
+        }
         // Initialize the operator.
         let operator = Arc::new(Self {
             address,
             local_ip,
-            state: Arc::new(OperatorState::open_writer::<S, P>(path)?),
+            state,
             block_template: RwLock::new(None),
             provers: Default::default(),
+            pending_provers: Default::default(),
+            block_template_notify: Notify::new(),
             known_nonces: Default::default(),
+            share_filters: Default::default(),
+            submission_intervals: Default::default(),
+            submission_rates: Default::default(),
+            is_paused: AtomicBool::new(is_paused),
+            queued_responses: Default::default(),
+            verification_permits: Arc::new(Semaphore::new(POOL_VERIFICATION_CONCURRENCY)),
             operator_router,
             memory_pool,
             peers_router,
@@ -136,7 +304,25 @@ impl<N: Network, E: Environment> Operator<N, E> {
                 let _ = router.send(());
                 // Asynchronously wait for a operator request.
                 while let Some(request) = operator_handler.recv().await {
-                    operator_clone.update(request).await;
+                    match request {
+                        // Process the shares concurrently, up to `POOL_VERIFICATION_CONCURRENCY` at once,
+                        // so that the verification of one PoSW proof does not hold back the other requests.
+                        OperatorRequest::PoolResponse(..) | OperatorRequest::QueuedPoolResponse(..) => {
+                            let permit = match operator_clone.verification_permits.clone().acquire_owned().await {
+                                Ok(permit) => permit,
+                                Err(error) => {
+                                    error!("[PoolResponse] {}", error);
+                                    break;
+                                }
+                            };
+                            let operator = operator_clone.clone();
+                            task::spawn(async move {
+                                operator.update(request).await;
+                                drop(permit);
+                            });
+                        }
+                        request => operator_clone.update(request).await,
+                    }
                 }
             }));
             // Wait until the operator handler is ready.
@@ -186,14 +372,28 @@ impl<N: Network, E: Environment> Operator<N, E> {
                                     *operator.block_template.write().await = Some(block_template);
                                     // Clear the set of known nonces.
                                     operator.known_nonces.write().await.clear();
+                                    // Clear the duplicate share filters of the provers.
+                                    operator.share_filters.write().await.clear();
                                 }
                                 Ok(Err(error_message)) => error!("{}", error_message),
                                 Err(error) => error!("{}", error),
                             };
                         }
 
-                        // Proceed to sleep for a preset amount of time.
-                        tokio::time::sleep(HEARTBEAT_IN_SECONDS).await;
+                        // Route the block template to the provers that registered before it existed, unless the pool is paused.
+                        let block_template = operator.block_template.read().await.clone();
+                        if let (Some(block_template), false) = (block_template, operator.is_paused()) {
+                            let pending_provers: Vec<_> = operator.pending_provers.write().await.drain().collect();
+                            for (peer_ip, address) in pending_provers {
+                                operator.send_pool_request(peer_ip, address, block_template.clone()).await;
+                            }
+                        }
+
+                        // Proceed to sleep until the next heartbeat, or until a prover awaits a block template.
+                        tokio::select! {
+                            _ = tokio::time::sleep(HEARTBEAT_IN_SECONDS) => (),
+                            _ = operator.block_template_notify.notified() => (),
+                        }
                     }
                 }));
                 // Wait until the operator handler is ready.
@@ -226,6 +426,68 @@ impl<N: Network, E: Environment> Operator<N, E> {
         self.state.get_shares_for_prover(&self.ledger_reader, prover)
     }
 
+    /// Returns the known provers := (prover, share_difficulty, submission_interval_variance_ms, is_flagged)
+    pub async fn provers(&self) -> Vec<(Address<N>, u64, f64, bool)> {
+        let submission_intervals = self.submission_intervals.read().await;
+        self.provers
+            .read()
+            .await
+            .iter()
+            .map(|(prover, (_, share_difficulty))| match submission_intervals.get(prover) {
+                Some(intervals) => (*prover, *share_difficulty, intervals.variance_ms(), intervals.is_flagged),
+                None => (*prover, *share_difficulty, 0.0, false),
+            })
+            .collect()
+    }
+
+    /// Returns `true` if the pool is paused for maintenance.
+    pub fn is_paused(&self) -> bool {
+        self.is_paused.load(Ordering::SeqCst)
+    }
+
+    /// Returns the number of shares queued while the pool is paused.
+    pub async fn number_of_queued_responses(&self) -> usize {
+        self.queued_responses.read().await.len()
+    }
+
+    ///
+    /// Pauses the pool for maintenance, without disconnecting the provers.
+    /// While paused, no block templates are issued, and the shares received are queued until the pool resumes.
+    ///
+    pub fn pause(&self) -> Result<()> {
+        self.state.set_pool_paused(true)?;
+        self.is_paused.store(true, Ordering::SeqCst);
+        info!("The pool is paused");
+        Ok(())
+    }
+
+    ///
+    /// Resumes the pool, routing the shares queued while it was paused to be processed in the background,
+    /// and issuing the block template to the provers that registered in the meantime.
+    ///
+    pub async fn resume(&self) -> Result<()> {
+        self.state.set_pool_paused(false)?;
+        self.is_paused.store(false, Ordering::SeqCst);
+
+        // Route the queued shares to the operator handler, in the order they were received,
+        // so that their PoSW proofs are verified concurrently, without holding up the caller.
+        let queued_responses: Vec<_> = self.queued_responses.write().await.drain(..).collect();
+        info!("The pool is resumed, processing {} queued shares", queued_responses.len());
+        let operator_router = self.operator_router.clone();
+        E::tasks().append(task::spawn(async move {
+            for (peer_ip, prover, header_root, nonce, proof) in queued_responses {
+                let request = OperatorRequest::QueuedPoolResponse(peer_ip, prover, header_root, nonce, proof);
+                if let Err(error) = operator_router.send(request).await {
+                    warn!("[QueuedPoolResponse] {}", error);
+                }
+            }
+        }));
+
+        // Issue the block template to the provers that registered while the pool was paused.
+        self.block_template_notify.notify_one();
+        Ok(())
+    }
+
     ///
     /// Performs the given `request` to the operator.
     /// All requests must go through this `update`, so that a unified view is preserved.
@@ -233,101 +495,599 @@ impl<N: Network, E: Environment> Operator<N, E> {
     pub(super) async fn update(&self, request: OperatorRequest<N>) {
         match request {
             OperatorRequest::PoolRegister(peer_ip, address) => {
-                if let Some(block_template) = self.block_template.read().await.clone() {
-                    // Ensure this prover exists in the list first, and retrieve their share difficulty.
-                    let share_difficulty = self
-                        .provers
-                        .write()
-                        .await
-                        .entry(address)
-                        .or_insert((Instant::now(), BASE_SHARE_DIFFICULTY))
-                        .1;
-
-                    // Route a `PoolRequest` to the peer.
-                    let message = Message::PoolRequest(share_difficulty, Data::Object(block_template));
-                    if let Err(error) = self.peers_router.send(PeersRequest::MessageSend(peer_ip, message)).await {
-                        warn!("[PoolRequest] {}", error);
+                let block_template = self.block_template.read().await.clone();
+                match block_template {
+                    // Route the block template to the prover once the pool resumes.
+                    Some(_) if self.is_paused() => {
+                        debug!("[PoolRegister] The pool is paused, deferring the block template for {}", peer_ip);
+                        self.pending_provers.write().await.insert(peer_ip, address);
+                    }
+                    // Route the current block template to the prover, without waiting for the next heartbeat.
+                    Some(block_template) => self.send_pool_request(peer_ip, address, block_template).await,
+                    // Route the block template to the prover as soon as it exists.
+                    None => {
+                        debug!("[PoolRegister] No current block template exists for {}", peer_ip);
+                        self.pending_provers.write().await.insert(peer_ip, address);
+                        self.block_template_notify.notify_one();
                     }
-                } else {
-                    warn!("[PoolRegister] No current block template exists");
                 }
             }
             OperatorRequest::PoolResponse(peer_ip, prover, nonce, proof) => {
-                if let Some(block_template) = self.block_template.read().await.clone() {
-                    // Ensure the given nonce from the prover is new.
-                    if self.known_nonces.read().await.contains(&nonce) {
-                        warn!("[PoolResponse] Peer {} sent a duplicate share", peer_ip);
-                        // TODO (julesdesmit): punish?
-                        return;
-                    }
+                // Ensure the prover does not submit shares faster than permitted, before verifying the share.
+                if !self.is_within_submission_rate(peer_ip).await {
+                    warn!("[PoolResponse] Peer {} exceeded the share submission rate", peer_ip);
+                    self.reject_share(peer_ip, prover, PoolRejectReason::SubmissionRateExceeded).await;
+                    return;
+                }
 
-                    // Update known nonces.
-                    self.known_nonces.write().await.insert(nonce);
-
-                    // Retrieve the share difficulty for the given prover.
-                    let share_difficulty = {
-                        let provers = self.provers.read().await.clone();
-                        match provers.get(&prover) {
-                            Some((_, share_difficulty)) => *share_difficulty,
-                            None => {
-                                self.provers.write().await.insert(prover, (Instant::now(), BASE_SHARE_DIFFICULTY));
-                                BASE_SHARE_DIFFICULTY
-                            }
-                        }
+                // Queue the share while the pool is paused, to process it once the pool resumes,
+                // along with the header root of the block template that it is meant to solve.
+                if self.is_paused() {
+                    let header_root = match &*self.block_template.read().await {
+                        Some(block_template) => block_template.to_header_root().ok(),
+                        None => None,
                     };
-
-                    // Ensure the share difficulty target is met, and the PoSW proof is valid.
-                    let block_height = block_template.block_height();
-                    if !N::posw().verify(
-                        block_height,
-                        share_difficulty,
-                        &[*block_template.to_header_root().unwrap(), *nonce],
-                        &proof,
-                    ) {
-                        warn!("[PoolResponse] PoSW proof verification failed");
-                        return;
+                    let mut queued_responses = self.queued_responses.write().await;
+                    match (header_root, queued_responses.len() < MAXIMUM_QUEUED_POOL_RESPONSES) {
+                        (Some(header_root), true) => queued_responses.push_back((peer_ip, prover, header_root, nonce, proof)),
+                        _ => warn!("[PoolResponse] The pool is paused, dropping a share from {}", peer_ip),
                     }
+                    return;
+                }
 
-                    // Update the internal state for this prover.
-                    if let Some(ref mut prover) = self.provers.write().await.get_mut(&prover) {
-                        prover.0 = Instant::now();
-                    } else {
-                        error!("Prover should have existing info");
-                        return;
+                self.process_pool_response(peer_ip, prover, nonce, proof).await;
+            }
+            OperatorRequest::QueuedPoolResponse(peer_ip, prover, header_root, nonce, proof) => {
+                // Reject the share explicitly, if its block template was replaced while the pool was paused.
+                // Note: The prover is not penalized, as the share was valid work when it was received.
+                let is_stale = match &*self.block_template.read().await {
+                    Some(block_template) => block_template.to_header_root().ok() != Some(header_root),
+                    None => true,
+                };
+                if is_stale {
+                    debug!("[QueuedPoolResponse] Rejecting a stale share from {}", peer_ip);
+                    let message = Message::PoolReject(PoolRejectReason::StaleTemplate);
+                    if let Err(error) = self.peers_router.send(PeersRequest::MessageSend(peer_ip, message)).await {
+                        warn!("[PoolReject] {}", error);
                     }
+                    return;
+                }
 
-                    // Increment the share count for the prover.
-                    let coinbase_record = block_template.coinbase_record().clone();
-                    match self.state.increment_share(block_height, coinbase_record, &prover) {
-                        Ok(..) => info!(
-                            "Operator has received a valid share from {} ({}) for block {}",
-                            prover, peer_ip, block_height,
-                        ),
-                        Err(error) => error!("{}", error),
+                self.process_pool_response(peer_ip, prover, nonce, proof).await;
+            }
+            OperatorRequest::PoolDisconnect(peer_ip) => {
+                // Remove the state that is kept for the connection of the prover.
+                self.submission_rates.write().await.remove(&peer_ip);
+                self.pending_provers.write().await.remove(&peer_ip);
+            }
+        }
+    }
+
+    ///
+    /// Processes the given share from a prover, and broadcasts the block if the share satisfies its difficulty.
+    ///
+    async fn process_pool_response(&self, peer_ip: SocketAddr, prover: Address<N>, nonce: N::PoSWNonce, proof: PoSWProof<N>) {
+        if let Some(block_template) = self.block_template.read().await.clone() {
+            // Ensure the given nonce from the prover is new, and update the known nonces.
+            // Note: The nonce is checked and inserted under one write lock, so concurrent duplicates are rejected.
+            let is_new_share = self.share_filters.write().await.entry(prover).or_default().insert(nonce);
+            if !is_new_share || !self.known_nonces.write().await.insert(nonce) {
+                warn!("[PoolResponse] Peer {} sent a duplicate share", peer_ip);
+                self.reject_share(peer_ip, prover, PoolRejectReason::DuplicateNonce).await;
+                return;
+            }
+
+            // Retrieve the share difficulty for the given prover.
+            let share_difficulty = {
+                let provers = self.provers.read().await.clone();
+                match provers.get(&prover) {
+                    Some((_, share_difficulty)) => *share_difficulty,
+                    None => {
+                        self.provers.write().await.insert(prover, (Instant::now(), BASE_SHARE_DIFFICULTY));
+                        BASE_SHARE_DIFFICULTY
                     }
+                }
+            };
 
-                    // If the block has satisfactory difficulty and is valid, proceed to broadcast it.
-                    let previous_block_hash = block_template.previous_block_hash();
-                    let transactions = block_template.transactions().clone();
-                    if let Ok(block_header) = BlockHeader::<N>::from(
-                        block_template.previous_ledger_root(),
-                        block_template.transactions().transactions_root(),
-                        BlockHeaderMetadata::new(&block_template),
-                        nonce,
-                        proof,
-                    ) {
-                        if let Ok(block) = Block::from(previous_block_hash, block_header, transactions) {
-                            info!("Operator has found unconfirmed block {} ({})", block.height(), block.hash());
-                            let request = LedgerRequest::UnconfirmedBlock(self.local_ip, block, self.prover_router.clone());
-                            if let Err(error) = self.ledger_router.send(request).await {
-                                warn!("Failed to broadcast mined block - {}", error);
-                            }
-                        }
+            // Ensure the PoSW proof is valid, by verifying it on the thread pool, regardless of its difficulty.
+            let block_height = block_template.block_height();
+            let inputs = [*block_template.to_header_root().unwrap(), *nonce];
+            let proof_clone = proof.clone();
+            let result = task::spawn_blocking(move || {
+                E::thread_pool().install(move || N::posw().verify(block_height, u64::MAX, &inputs, &proof_clone))
+            })
+            .await;
+            if !matches!(result, Ok(true)) {
+                warn!("[PoolResponse] Peer {} sent an invalid PoSW proof", peer_ip);
+                self.reject_share(peer_ip, prover, PoolRejectReason::InvalidProof).await;
+                return;
+            }
+
+            // Ensure the share difficulty target is met.
+            match proof.to_proof_difficulty() {
+                Ok(proof_difficulty) if proof_difficulty <= share_difficulty => (),
+                _ => {
+                    warn!("[PoolResponse] Peer {} sent a share below the share difficulty", peer_ip);
+                    return;
+                }
+            }
+
+            // Update the internal state for this prover.
+            if let Some(ref mut prover) = self.provers.write().await.get_mut(&prover) {
+                prover.0 = Instant::now();
+            } else {
+                error!("Prover should have existing info");
+                return;
+            }
+
+            // Update the share-submission intervals for this prover, and flag suspiciously regular submissions.
+            let is_newly_flagged = self
+                .submission_intervals
+                .write()
+                .await
+                .entry(prover)
+                .or_default()
+                .record(Instant::now());
+            if is_newly_flagged {
+                warn!(
+                    "Prover {} ({}) is submitting shares at suspiciously regular intervals",
+                    prover, peer_ip
+                );
+            }
+
+            // Increment the share count for the prover.
+            let coinbase_record = block_template.coinbase_record().clone();
+            match self.state.increment_share(block_height, coinbase_record, &prover) {
+                Ok(..) => info!(
+                    "Operator has received a valid share from {} ({}) for block {}",
+                    prover, peer_ip, block_height,
+                ),
+                Err(error) => error!("{}", error),
+            }
+
+            // If the block has satisfactory difficulty and is valid, proceed to broadcast it.
+            let previous_block_hash = block_template.previous_block_hash();
+            let transactions = block_template.transactions().clone();
+            if let Ok(block_header) = BlockHeader::<N>::from(
+                block_template.previous_ledger_root(),
+                block_template.transactions().transactions_root(),
+                BlockHeaderMetadata::new(&block_template),
+                nonce,
+                proof,
+            ) {
+                if let Ok(block) = Block::from(previous_block_hash, block_header, transactions) {
+                    info!("Operator has found unconfirmed block {} ({})", block.height(), block.hash());
+                    let request = LedgerRequest::UnconfirmedBlock(self.local_ip, block, self.prover_router.clone(), None);
+                    if let Err(error) = self.ledger_router.send(request).await {
+                        warn!("Failed to broadcast mined block - {}", error);
                     }
-                } else {
-                    warn!("[PoolResponse] No current block template exists");
                 }
             }
+        } else {
+            warn!("[PoolResponse] No current block template exists");
+        }
+    }
+
+    ///
+    /// Routes a `PoolRequest` with the given block template to the given prover.
+    ///
+    async fn send_pool_request(&self, peer_ip: SocketAddr, address: Address<N>, block_template: BlockTemplate<N>) {
+        // Ensure this prover exists in the list first, and retrieve their share difficulty.
+        let share_difficulty = self
+            .provers
+            .write()
+            .await
+            .entry(address)
+            .or_insert((Instant::now(), BASE_SHARE_DIFFICULTY))
+            .1;
+
+        // Route a `PoolRequest` to the peer.
+        let message = Message::PoolRequest(share_difficulty, Data::Object(block_template));
+        if let Err(error) = self.peers_router.send(PeersRequest::MessageSend(peer_ip, message)).await {
+            warn!("[PoolRequest] {}", error);
+        }
+    }
+
+    ///
+    /// Returns `true` if a share from the given peer is within `E::MAX_POOL_RESPONSES_PER_SECOND`.
+    /// If the peer has exceeded the rate too many times, a disconnect is routed to the ledger.
+    ///
+    async fn is_within_submission_rate(&self, peer_ip: SocketAddr) -> bool {
+        let now = Instant::now();
+        let mut submission_rates = self.submission_rates.write().await;
+        let limiter = submission_rates
+            .entry(peer_ip)
+            .or_insert_with(|| SubmissionRateLimiter::new(E::MAX_POOL_RESPONSES_PER_SECOND, now));
+        if limiter.try_acquire(E::MAX_POOL_RESPONSES_PER_SECOND, now) {
+            return true;
+        }
+
+        if limiter.num_rate_limited >= MAX_RATE_LIMITED_SHARES {
+            submission_rates.remove(&peer_ip);
+            let message = format!("exceeded the share submission rate {} times", MAX_RATE_LIMITED_SHARES);
+            if let Err(error) = self.ledger_router.send(LedgerRequest::Disconnect(peer_ip, message)).await {
+                warn!("[Disconnect] {}", error);
+            }
+        }
+        false
+    }
+
+    ///
+    /// Sends a `PoolReject` to the given prover, and records a failure for the peer.
+    ///
+    async fn reject_share(&self, peer_ip: SocketAddr, prover: Address<N>, reason: PoolRejectReason) {
+        // Route a `PoolReject` to the peer.
+        let message = Message::PoolReject(reason);
+        if let Err(error) = self.peers_router.send(PeersRequest::MessageSend(peer_ip, message)).await {
+            warn!("[PoolReject] {}", error);
+        }
+        // Route a `Failure` to the ledger.
+        let failure = format!("Prover {} submitted a rejected share ({:?})", prover, reason);
+        if let Err(error) = self.ledger_router.send(LedgerRequest::Failure(peer_ip, failure)).await {
+            warn!("[Failure] {}", error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CpuProofBackend, ProofBackend};
+    use snarkos_storage::{storage::rocksdb::RocksDB, LedgerState};
+    use snarkvm::{dpc::testnet2::Testnet2, prelude::UniformRand};
+
+    use rand::thread_rng;
+
+    type E = crate::environment::Operator<Testnet2>;
+
+    fn temp_dir() -> std::path::PathBuf {
+        tempfile::tempdir().expect("Failed to open temporary directory").into_path()
+    }
+
+    /// Initializes an operator with the given address, along with the handler of its peers requests.
+    async fn operator(
+        address: Option<Address<Testnet2>>,
+        ledger: LedgerReader<Testnet2>,
+    ) -> (Arc<Operator<Testnet2, E>>, mpsc::Receiver<PeersRequest<Testnet2, E>>) {
+        let (peers_router, peers_handler) = mpsc::channel(1024);
+        let (ledger_router, _ledger_handler) = mpsc::channel(1024);
+        let (prover_router, _prover_handler) = mpsc::channel(1024);
+        let operator = Operator::<Testnet2, E>::open::<RocksDB, _>(
+            &temp_dir(),
+            address,
+            "127.0.0.1:4133".parse().unwrap(),
+            Arc::new(RwLock::new(MemoryPool::new())),
+            peers_router,
+            ledger,
+            ledger_router,
+            prover_router,
+        )
+        .await
+        .expect("Failed to initialize operator");
+        (operator, peers_handler)
+    }
+
+    /// Waits for the next `PoolRequest` routed to a prover, and returns the prover IP and the block height of its template.
+    async fn next_pool_request(peers_handler: &mut mpsc::Receiver<PeersRequest<Testnet2, E>>) -> (SocketAddr, u32) {
+        match peers_handler.recv().await.expect("Failed to receive a peers request") {
+            PeersRequest::MessageSend(peer_ip, Message::PoolRequest(_, Data::Object(block_template))) => {
+                (peer_ip, block_template.block_height())
+            }
+            request => panic!("Unexpected peers request: {:?}", request),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pool_request_on_register_with_cached_template() {
+        let rng = &mut thread_rng();
+
+        // Initialize an operator without its update loop, and cache a block template.
+        let ledger = Arc::new(LedgerState::<Testnet2>::open_writer::<RocksDB, _>(temp_dir()).expect("Failed to initialize ledger"));
+        let (operator, mut peers_handler) = operator(None, ledger.clone()).await;
+        let recipient = Account::<Testnet2>::new(rng).address();
+        *operator.block_template.write().await = Some(ledger.get_block_template(recipient, true, &[], rng).unwrap());
+
+        // Register a prover, through the handler of the operator requests.
+        let prover_ip: SocketAddr = "127.0.0.1:4134".parse().unwrap();
+        let start = Instant::now();
+        operator
+            .router()
+            .send(OperatorRequest::PoolRegister(prover_ip, recipient))
+            .await
+            .unwrap();
+
+        // Ensure the prover receives the cached block template without waiting for the next heartbeat.
+        let (peer_ip, block_height) = next_pool_request(&mut peers_handler).await;
+        assert!(start.elapsed() < Duration::from_millis(100));
+        assert_eq!(prover_ip, peer_ip);
+        assert_eq!(1, block_height);
+    }
+
+    #[tokio::test]
+    async fn test_pool_request_on_register_without_cached_template() {
+        let rng = &mut thread_rng();
+
+        // Initialize an operator, and register a prover before its first block template is produced.
+        let ledger = Arc::new(LedgerState::<Testnet2>::open_writer::<RocksDB, _>(temp_dir()).expect("Failed to initialize ledger"));
+        let recipient = Account::<Testnet2>::new(rng).address();
+        let (operator, mut peers_handler) = operator(Some(recipient), ledger).await;
+        let prover_ip: SocketAddr = "127.0.0.1:4134".parse().unwrap();
+        operator.update(OperatorRequest::PoolRegister(prover_ip, recipient)).await;
+
+        // Ensure the prover receives the block template once it is produced, without registering again.
+        let (peer_ip, block_height) = next_pool_request(&mut peers_handler).await;
+        assert_eq!(prover_ip, peer_ip);
+        assert_eq!(1, block_height);
+        assert!(operator.pending_provers.read().await.is_empty());
+
+        // Ensure a prover that registers afterwards receives the cached block template immediately.
+        let start = Instant::now();
+        operator.update(OperatorRequest::PoolRegister(prover_ip, recipient)).await;
+        let (peer_ip, _) = next_pool_request(&mut peers_handler).await;
+        assert!(start.elapsed() < Duration::from_millis(100));
+        assert_eq!(prover_ip, peer_ip);
+
+        // Clear the block template, register a prover, and restore the block template, without yielding to the update loop.
+        // As the test runtime is single-threaded, the update loop then finds the restored block template once it wakes up,
+        // so that only the time for the registration to wake it up is measured, and not the time to produce a block template.
+        let block_template = operator.block_template.write().await.take();
+        let start = Instant::now();
+        operator.update(OperatorRequest::PoolRegister(prover_ip, recipient)).await;
+        assert!(operator.pending_provers.read().await.contains_key(&prover_ip));
+        *operator.block_template.write().await = block_template;
+
+        // Ensure the prover receives the block template within 500ms, without waiting for the next heartbeat.
+        let (peer_ip, _) = next_pool_request(&mut peers_handler).await;
+        assert!(start.elapsed() < Duration::from_millis(500));
+        assert_eq!(prover_ip, peer_ip);
+        assert!(operator.pending_provers.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pool_disconnect() {
+        let rng = &mut thread_rng();
+
+        // Initialize an operator without its update loop, and register a prover before a block template exists.
+        let ledger = Arc::new(LedgerState::<Testnet2>::open_writer::<RocksDB, _>(temp_dir()).expect("Failed to initialize ledger"));
+        let (operator, _peers_handler) = operator(None, ledger).await;
+        let recipient = Account::<Testnet2>::new(rng).address();
+        let prover_ip: SocketAddr = "127.0.0.1:4134".parse().unwrap();
+        operator.update(OperatorRequest::PoolRegister(prover_ip, recipient)).await;
+
+        // Submit a share, which starts tracking the submission rate of the prover.
+        let proof = Testnet2::genesis_block().header().proof().clone();
+        operator
+            .update(OperatorRequest::PoolResponse(prover_ip, recipient, UniformRand::rand(rng), proof))
+            .await;
+        assert!(operator.submission_rates.read().await.contains_key(&prover_ip));
+        assert!(operator.pending_provers.read().await.contains_key(&prover_ip));
+
+        // Ensure the state of the prover is removed once it disconnects.
+        operator.update(OperatorRequest::PoolDisconnect(prover_ip)).await;
+        assert!(operator.submission_rates.read().await.is_empty());
+        assert!(operator.pending_provers.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pause_and_resume_pool() {
+        let rng = &mut thread_rng();
+
+        // Initialize an operator without its update loop, and cache a block template.
+        let ledger = Arc::new(LedgerState::<Testnet2>::open_writer::<RocksDB, _>(temp_dir()).expect("Failed to initialize ledger"));
+        let (operator, mut peers_handler) = operator(None, ledger.clone()).await;
+        let recipient = Account::<Testnet2>::new(rng).address();
+        *operator.block_template.write().await = Some(ledger.get_block_template(recipient, true, &[], rng).unwrap());
+
+        // Pause the pool, and ensure a prover that registers does not receive a block template.
+        operator.pause().expect("Failed to pause the pool");
+        assert!(operator.is_paused());
+        let prover_ip: SocketAddr = "127.0.0.1:4134".parse().unwrap();
+        operator.update(OperatorRequest::PoolRegister(prover_ip, recipient)).await;
+        assert!(peers_handler.try_recv().is_err());
+        assert!(operator.pending_provers.read().await.contains_key(&prover_ip));
+
+        // Submit 3 shares, and ensure they are queued without being processed.
+        let proof = Testnet2::genesis_block().header().proof().clone();
+        for _ in 0..3 {
+            let request = OperatorRequest::PoolResponse(prover_ip, recipient, UniformRand::rand(rng), proof.clone());
+            operator.update(request).await;
+        }
+        assert_eq!(3, operator.number_of_queued_responses().await);
+        assert!(operator.known_nonces.read().await.is_empty());
+
+        // Resume the pool, and ensure all 3 shares are processed in the background.
+        operator.resume().await.expect("Failed to resume the pool");
+        assert!(!operator.is_paused());
+        assert_eq!(0, operator.number_of_queued_responses().await);
+        for _ in 0..3 {
+            match peers_handler.recv().await.expect("Failed to receive a peers request") {
+                PeersRequest::MessageSend(peer_ip, Message::PoolReject(reason)) => {
+                    assert_eq!(prover_ip, peer_ip);
+                    assert_eq!(PoolRejectReason::InvalidProof, reason);
+                }
+                request => panic!("Unexpected peers request: {:?}", request),
+            }
+        }
+        assert_eq!(3, operator.known_nonces.read().await.len());
+    }
+
+    #[tokio::test]
+    async fn test_resume_pool_rejects_stale_shares() {
+        let rng = &mut thread_rng();
+
+        // Initialize an operator without its update loop, and cache a block template.
+        let ledger = Arc::new(LedgerState::<Testnet2>::open_writer::<RocksDB, _>(temp_dir()).expect("Failed to initialize ledger"));
+        let (operator, mut peers_handler) = operator(None, ledger.clone()).await;
+        let recipient = Account::<Testnet2>::new(rng).address();
+        *operator.block_template.write().await = Some(ledger.get_block_template(recipient, true, &[], rng).unwrap());
+
+        // Pause the pool, and queue a share for the cached block template.
+        operator.pause().expect("Failed to pause the pool");
+        let prover_ip: SocketAddr = "127.0.0.1:4134".parse().unwrap();
+        let proof = Testnet2::genesis_block().header().proof().clone();
+        let request = OperatorRequest::PoolResponse(prover_ip, recipient, UniformRand::rand(rng), proof);
+        operator.update(request).await;
+        assert_eq!(1, operator.number_of_queued_responses().await);
+
+        // Replace the block template while the pool is paused.
+        let other_recipient = Account::<Testnet2>::new(rng).address();
+        *operator.block_template.write().await = Some(ledger.get_block_template(other_recipient, true, &[], rng).unwrap());
+
+        // Resume the pool, and ensure the share is rejected as stale, without verifying it.
+        operator.resume().await.expect("Failed to resume the pool");
+        match peers_handler.recv().await.expect("Failed to receive a peers request") {
+            PeersRequest::MessageSend(peer_ip, Message::PoolReject(reason)) => {
+                assert_eq!(prover_ip, peer_ip);
+                assert_eq!(PoolRejectReason::StaleTemplate, reason);
+            }
+            request => panic!("Unexpected peers request: {:?}", request),
+        }
+        assert!(operator.known_nonces.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_verify_pool_responses_concurrently() {
+        let rng = &mut thread_rng();
+
+        // Initialize an operator without its update loop, and cache a block template.
+        let ledger = Arc::new(LedgerState::<Testnet2>::open_writer::<RocksDB, _>(temp_dir()).expect("Failed to initialize ledger"));
+        let (operator, mut peers_handler) = operator(None, ledger.clone()).await;
+        let recipient = Account::<Testnet2>::new(rng).address();
+        let block_template = ledger.get_block_template(recipient, true, &[], rng).unwrap();
+        *operator.block_template.write().await = Some(block_template.clone());
+
+        // Prove the block template for the valid share, and reuse the genesis proof for the invalid share.
+        let (nonce, proof) = CpuProofBackend.prove(&block_template, &AtomicBool::new(false)).unwrap();
+        let invalid_proof = Testnet2::genesis_block().header().proof().clone();
+
+        // Submit the valid and the invalid share simultaneously, from different provers.
+        let (valid_ip, valid_prover): (SocketAddr, _) = ("127.0.0.1:4134".parse().unwrap(), recipient);
+        let (invalid_ip, invalid_prover): (SocketAddr, _) = ("127.0.0.1:4135".parse().unwrap(), Account::<Testnet2>::new(rng).address());
+        let valid_request = OperatorRequest::PoolResponse(valid_ip, valid_prover, nonce, proof);
+        let invalid_request = OperatorRequest::PoolResponse(invalid_ip, invalid_prover, UniformRand::rand(rng), invalid_proof);
+        tokio::join!(operator.update(valid_request), operator.update(invalid_request));
+
+        // Ensure only the invalid share is rejected.
+        match peers_handler.try_recv() {
+            Ok(PeersRequest::MessageSend(peer_ip, Message::PoolReject(reason))) => {
+                assert_eq!(invalid_ip, peer_ip);
+                assert_eq!(PoolRejectReason::InvalidProof, reason);
+            }
+            request => panic!("Unexpected peers request: {:?}", request),
         }
+        assert!(peers_handler.try_recv().is_err());
+
+        // Ensure only the valid share is credited.
+        let shares = operator
+            .state
+            .get_shares_for_block(block_template.block_height(), block_template.coinbase_record().clone())
+            .unwrap();
+        assert_eq!(Some(&1), shares.get(&valid_prover));
+        assert_eq!(None, shares.get(&invalid_prover));
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_pool_response() {
+        let rng = &mut thread_rng();
+
+        // Initialize an operator without its update loop, and cache a block template.
+        let ledger = Arc::new(LedgerState::<Testnet2>::open_writer::<RocksDB, _>(temp_dir()).expect("Failed to initialize ledger"));
+        let (operator, mut peers_handler) = operator(None, ledger.clone()).await;
+        let recipient = Account::<Testnet2>::new(rng).address();
+        let block_template = ledger.get_block_template(recipient, true, &[], rng).unwrap();
+        *operator.block_template.write().await = Some(block_template.clone());
+
+        // Submit the same valid share twice within the window of the block template.
+        let (nonce, proof) = CpuProofBackend.prove(&block_template, &AtomicBool::new(false)).unwrap();
+        let prover_ip: SocketAddr = "127.0.0.1:4134".parse().unwrap();
+        for _ in 0..2 {
+            let request = OperatorRequest::PoolResponse(prover_ip, recipient, nonce, proof.clone());
+            operator.update(request).await;
+        }
+
+        // Ensure only the second submission is rejected, as a duplicate nonce.
+        match peers_handler.try_recv() {
+            Ok(PeersRequest::MessageSend(peer_ip, Message::PoolReject(reason))) => {
+                assert_eq!(prover_ip, peer_ip);
+                assert_eq!(PoolRejectReason::DuplicateNonce, reason);
+            }
+            request => panic!("Unexpected peers request: {:?}", request),
+        }
+        assert!(peers_handler.try_recv().is_err());
+
+        // Ensure the share is only credited once.
+        let shares = operator
+            .state
+            .get_shares_for_block(block_template.block_height(), block_template.coinbase_record().clone())
+            .unwrap();
+        assert_eq!(Some(&1), shares.get(&recipient));
+    }
+
+    #[test]
+    fn test_duplicate_share_filter() {
+        let rng = &mut thread_rng();
+
+        let mut filter = DuplicateShareFilter::<Testnet2>::default();
+        let nonce = <Testnet2 as Network>::PoSWNonce::rand(rng);
+
+        // The first submission of the nonce is accepted.
+        assert!(filter.insert(nonce));
+        // The second submission of the nonce is rejected.
+        assert!(!filter.insert(nonce));
+        // The share is only counted once.
+        assert_eq!(1, filter.nonces.len());
+
+        // A different nonce is accepted.
+        assert!(filter.insert(<Testnet2 as Network>::PoSWNonce::rand(rng)));
+        assert_eq!(2, filter.nonces.len());
+    }
+
+    #[test]
+    fn test_submission_interval_variance() {
+        let mut intervals = SubmissionIntervals::default();
+        let mut now = Instant::now();
+
+        // Submit 10 shares at exactly 1000ms intervals.
+        let mut is_newly_flagged = false;
+        for _ in 0..10 {
+            now += Duration::from_millis(1000);
+            is_newly_flagged |= intervals.record(now);
+        }
+        // Ensure the prover is flagged for the zero variance.
+        assert!(is_newly_flagged);
+        assert!(intervals.is_flagged);
+        assert_eq!(0.0, intervals.variance_ms());
+
+        // Submit 10 shares at noisy intervals.
+        for i in 0..10 {
+            now += Duration::from_millis(500 + (i * 337) % 1000);
+            assert!(!intervals.record(now));
+        }
+        // Ensure the flag is cleared.
+        assert!(!intervals.is_flagged);
+        assert!(intervals.variance_ms() >= MIN_SUBMISSION_INTERVAL_VARIANCE_MS as f64);
+    }
+
+    #[test]
+    fn test_submission_rate_limiter() {
+        const RATE: u32 = 100;
+        let now = Instant::now();
+        let mut limiter = SubmissionRateLimiter::new(RATE, now);
+
+        // Submit 200 shares within one second, at the same instant.
+        let num_processed = (0..200).filter(|_| limiter.try_acquire(RATE, now)).count();
+        // Ensure exactly 100 shares are processed, and 100 are rejected.
+        assert_eq!(100, num_processed);
+        assert_eq!(100, limiter.num_rate_limited);
+
+        // Ensure the bucket refills at the permitted rate.
+        let now = now + Duration::from_millis(100);
+        let num_processed = (0..20).filter(|_| limiter.try_acquire(RATE, now)).count();
+        assert_eq!(10, num_processed);
+
+        // Ensure the bucket holds no more than one second of shares.
+        let now = now + Duration::from_secs(10);
+        let num_processed = (0..200).filter(|_| limiter.try_acquire(RATE, now)).count();
+        assert_eq!(100, num_processed);
     }
 }
@@ -15,41 +15,79 @@
 // along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-    helpers::{NodeType, State, Status},
+    helpers::{
+        verify_difficulty_only,
+        BlockLocatorCache,
+        BlockLocatorHashes,
+        BlockLocatorsDiff,
+        EncodingFormat,
+        FeatureFlags,
+        LogRateLimiter,
+        MessageHistory,
+        NodeCapabilities,
+        NodeType,
+        PeerThrottle,
+        SlowPeerDetector,
+        State,
+        Status,
+    },
     network::{
+        ConnectionDirection,
         ConnectionResult,
+        DisconnectReason,
         LedgerReader,
         LedgerRequest,
         LedgerRouter,
         Message,
+        MessageCodec,
+        MessageHistories,
         OperatorRequest,
         OperatorRouter,
+        OversizedTransaction,
+        Peers,
         PeersRequest,
         PeersRouter,
+        PoolRejectReason,
         ProverRequest,
         ProverRouter,
     },
     Data,
     Environment,
 };
+use snarkos_storage::BlockLocators;
 use snarkvm::dpc::prelude::*;
 
 use anyhow::{anyhow, Result};
 use futures::SinkExt;
+use rand::{thread_rng, Rng};
 use std::{
-    collections::HashMap,
-    net::SocketAddr,
+    collections::{HashMap, VecDeque},
+    net::{IpAddr, SocketAddr},
+    sync::{atomic::Ordering, Arc},
     time::{Duration, Instant, SystemTime},
 };
-use tokio::{net::TcpStream, sync::mpsc, task, time::timeout};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::TcpStream,
+    sync::{mpsc, oneshot, RwLock},
+    task,
+    time::timeout,
+};
 use tokio_stream::StreamExt;
-use tokio_util::codec::Framed;
+use tokio_util::codec::{Framed, FramedParts};
 
 /// Shorthand for the parent half of the `Peer` outbound message channel.
 pub(crate) type OutboundRouter<N, E> = mpsc::Sender<Message<N, E>>;
 /// Shorthand for the child half of the `Peer` outbound message channel.
 type OutboundHandler<N, E> = mpsc::Receiver<Message<N, E>>;
 
+///
+/// A deviation of a peer from the expected sequence of handshake messages, as (socket_ip, description).
+///
+#[derive(Debug, Error)]
+#[error("Protocol violation from {}: {}", _0, _1)]
+struct ProtocolViolation(SocketAddr, String);
+
 ///
 /// The state for each connected client.
 ///
@@ -60,17 +98,27 @@ pub(crate) struct Peer<N: Network, E: Environment> {
     version: u32,
     /// The node type of the peer.
     node_type: NodeType,
+    /// The capabilities advertised by the peer.
+    capabilities: NodeCapabilities,
+    /// The encoding format of the message payloads, as negotiated with the peer.
+    encoding: EncodingFormat,
+    /// The connection features, as agreed with the peer in the `Hello` exchange.
+    features: FeatureFlags,
     /// The node type of the peer.
     status: Status,
     /// The block header of the peer.
     block_header: BlockHeader<N>,
+    /// The block height and cumulative weight last advertised by the peer, starting from the cumulative weight of its handshake.
+    advertised_weight: (u32, u128),
     /// The timestamp of the last message received from this peer.
     last_seen: Instant,
     /// The TCP socket that handles sending and receiving data with this peer.
-    outbound_socket: Framed<TcpStream, Message<N, E>>,
+    outbound_socket: Framed<TcpStream, MessageCodec<N, E>>,
     /// The `outbound_handler` half of the MPSC message channel, used to receive messages from peers.
     /// When a message is received on this `OutboundHandler`, it will be written to the socket.
     outbound_handler: OutboundHandler<N, E>,
+    /// The `outbound_router` half of the MPSC message channel, which identifies this connection among the connected peers.
+    outbound_router: OutboundRouter<N, E>,
     /// The map of block hashes to their last seen timestamp.
     seen_inbound_blocks: HashMap<N::BlockHash, SystemTime>,
     /// The map of transaction IDs to their last seen timestamp.
@@ -79,23 +127,62 @@ pub(crate) struct Peer<N: Network, E: Environment> {
     seen_outbound_blocks: HashMap<N::BlockHash, SystemTime>,
     /// The map of peers to a map of transaction IDs to their last seen timestamp.
     seen_outbound_transactions: HashMap<N::TransactionID, SystemTime>,
+    /// The timestamp of the last mempool request served to this peer.
+    last_mempool_request: Option<Instant>,
+    /// The number of transactions in the memory pool of the peer, as last reported by the peer.
+    mempool_size: u32,
+    /// The fork depth advertised by the peer in its last `Ping`, which bounds the forks accepted from the peer.
+    fork_depth: u32,
+    /// The timestamp of the last `Ping` sent to this peer.
+    last_ping_sent: Option<Instant>,
+    /// The timestamp of the last `Pong` received from this peer, if any.
+    last_pong_at: Option<Instant>,
+    /// The detector for sustained slow round trips with this peer.
+    slow_peer_detector: SlowPeerDetector,
+    /// The throttle requested by this peer, which slows down outbound requests to it.
+    throttle: PeerThrottle,
+    /// The outbound requests to this peer that are deferred until its throttle expires.
+    deferred_requests: VecDeque<Message<N, E>>,
+    /// The number of block requests from this peer that started far beyond the latest block height.
+    future_block_requests: u32,
+    /// The timestamp of when the connection with this peer was established.
+    connected_at: Instant,
+    /// The maximum age of the connection with this peer before it is recycled, if any.
+    maximum_connection_age: Option<Duration>,
+    /// The deadline by which the coalesced outbound messages must be written to the socket, if any are pending.
+    coalesce_deadline: Option<Instant>,
+    /// The rate limiter for the errors logged for this peer, which collapses repeated identical errors.
+    error_log: LogRateLimiter,
+    /// The block locators last sent to this peer, which serve as the base of the next block locators diff.
+    sent_block_locators: Option<BlockLocatorHashes<N>>,
+    /// The block locators last received from this peer, against which the next block locators diff is applied.
+    received_block_locators: Option<BlockLocators<N>>,
 }
 
 impl<N: Network, E: Environment> Peer<N, E> {
     /// Create a new instance of `Peer`.
     async fn new(
-        stream: TcpStream,
+        mut stream: TcpStream,
         local_ip: SocketAddr,
         local_nonce: u64,
         peers_router: &PeersRouter<N, E>,
         ledger_reader: &LedgerReader<N>,
-        connected_nonces: &[u64],
+        connected_nonces: &[(SocketAddr, u64)],
+        direction: ConnectionDirection,
     ) -> Result<Self> {
+        // Agree on the connection features with the peer, before any message is exchanged.
+        let hello_timeout = Duration::from_millis(E::CONNECTION_TIMEOUT_IN_MILLIS);
+        let hello = Self::exchange_hello(&mut stream, FeatureFlags::local(), direction);
+        let (features, first_byte) = match timeout(hello_timeout, hello).await {
+            Ok(result) => result?,
+            Err(_) => return Err(anyhow!("Timed out waiting for the 'Hello' of {}", stream.peer_addr()?)),
+        };
+
         // Construct the socket.
-        let mut outbound_socket = Framed::new(stream, Message::<N, E>::PeerRequest);
+        let mut outbound_socket = Self::framed(stream, features, first_byte);
 
         // Perform the handshake before proceeding.
-        let (peer_ip, peer_nonce, node_type, status) = Peer::handshake(
+        let (peer_ip, peer_nonce, version, node_type, capabilities, status, cumulative_weight) = Peer::handshake(
             &mut outbound_socket,
             local_ip,
             local_nonce,
@@ -104,64 +191,643 @@ impl<N: Network, E: Environment> Peer<N, E> {
         )
         .await?;
 
+        // Negotiate the encoding format of the message payloads with the peer.
+        let encoding = EncodingFormat::negotiate(NodeCapabilities::from(E::NODE_TYPE), capabilities);
+
         // Send the first `Ping` message to the peer.
-        let message = Message::Ping(
-            E::MESSAGE_VERSION,
-            N::ALEO_MAXIMUM_FORK_DEPTH,
-            E::NODE_TYPE,
-            E::status().get(),
-            ledger_reader.latest_block_hash(),
-            Data::Object(ledger_reader.latest_block_header()),
-        );
+        let message = Self::ping(ledger_reader);
         trace!("Sending '{}' to {}", message.name(), peer_ip);
-        outbound_socket.send(message).await?;
+        outbound_socket.send(message.encode_payloads(encoding).await?).await?;
+
+        // An outbound connection was opened to the listener, unless the peer claimed another listener port.
+        let is_listener_verified = direction == ConnectionDirection::Outbound && outbound_socket.get_ref().peer_addr()? == peer_ip;
 
         // Create a channel for this peer.
         let (outbound_router, outbound_handler) = mpsc::channel(1024);
 
         // Add an entry for this `Peer` in the connected peers.
+        // Nothing beyond this point may fail, as the peer is only disconnected once its handler has started.
         peers_router
-            .send(PeersRequest::PeerConnected(peer_ip, peer_nonce, outbound_router))
+            .send(PeersRequest::PeerConnected(
+                peer_ip,
+                peer_nonce,
+                version,
+                node_type,
+                direction,
+                cumulative_weight,
+                outbound_router.clone(),
+            ))
             .await?;
 
+        // Verify the listener of the peer, before it is shared with other peers in a `PeerResponse`.
+        if is_listener_verified {
+            if let Err(error) = peers_router.send(PeersRequest::PeerVerified(peer_ip)).await {
+                warn!("[PeerVerified] {}", error);
+            }
+        } else {
+            let peers_router = peers_router.clone();
+            E::tasks().append(task::spawn(async move {
+                match Self::verify_listener(peer_ip, peer_nonce).await {
+                    Ok(()) => {
+                        if let Err(error) = peers_router.send(PeersRequest::PeerVerified(peer_ip)).await {
+                            warn!("[PeerVerified] {}", error);
+                        }
+                    }
+                    Err(error) => debug!("Unable to verify the listener of {}: {}", peer_ip, error),
+                }
+            }));
+        }
+
         Ok(Peer {
             listener_ip: peer_ip,
             version: 0,
             node_type,
+            capabilities,
+            encoding,
+            features,
             status,
             block_header: N::genesis_block().header().clone(),
+            advertised_weight: (0, cumulative_weight),
             last_seen: Instant::now(),
             outbound_socket,
             outbound_handler,
+            outbound_router,
             seen_inbound_blocks: Default::default(),
             seen_inbound_transactions: Default::default(),
             seen_outbound_blocks: Default::default(),
             seen_outbound_transactions: Default::default(),
+            last_mempool_request: None,
+            mempool_size: 0,
+            fork_depth: N::ALEO_MAXIMUM_FORK_DEPTH,
+            last_ping_sent: Some(Instant::now()),
+            last_pong_at: None,
+            slow_peer_detector: SlowPeerDetector::new(),
+            throttle: PeerThrottle::new(),
+            deferred_requests: Default::default(),
+            future_block_requests: 0,
+            connected_at: Instant::now(),
+            maximum_connection_age: Self::maximum_connection_age(direction, &mut thread_rng()),
+            coalesce_deadline: None,
+            error_log: LogRateLimiter::new(Duration::from_secs(E::LOG_SUPPRESSION_WINDOW_IN_SECS)),
+            sent_block_locators: None,
+            received_block_locators: None,
         })
     }
 
+    ///
+    /// Returns `true` if the given protocol version of a peer is at least `E::MINIMUM_PEER_PROTOCOL_VERSION`.
+    /// Newer versions are accepted.
+    ///
+    fn is_version_supported(version: u32) -> bool {
+        version >= E::MINIMUM_PEER_PROTOCOL_VERSION
+    }
+
+    ///
+    /// Returns an error if the cumulative weight in the given block header has decreased,
+    /// or has increased implausibly, relative to the (height, weight) previously advertised by the peer.
+    ///
+    /// The weight of a block is `u64::MAX / difficulty_target`, so the weight per block advertised by the peer
+    /// is bounded by the weight of the latest block of this node, up to `E::CUMULATIVE_WEIGHT_TOLERANCE` times.
+    /// A node that is not synced cannot judge the difficulty of the current chain, and only checks for a decrease.
+    ///
+    /// A decrease is expected if the peer reverted blocks, or switched to a fork of the chain of this node,
+    /// so it is only flagged if the block header is on the chain of this node, above the previous height.
+    ///
+    fn check_cumulative_weight(
+        previous: (u32, u128),
+        block_header: &BlockHeader<N>,
+        is_fork: Option<bool>,
+        ledger_reader: &LedgerReader<N>,
+    ) -> Result<(), String> {
+        let maximum_weight_per_block = match E::status().is_ready() || E::status().is_mining() {
+            true => {
+                let latest_block_weight = u64::MAX / ledger_reader.latest_block_difficulty_target().max(1);
+                (latest_block_weight as u128).saturating_mul(E::CUMULATIVE_WEIGHT_TOLERANCE)
+            }
+            false => u128::MAX,
+        };
+
+        let is_reorganized = block_header.height() < previous.0 || is_fork == Some(true);
+        Self::check_cumulative_weight_transition(
+            previous,
+            (block_header.height(), block_header.cumulative_weight()),
+            maximum_weight_per_block,
+            is_reorganized,
+        )
+    }
+
+    ///
+    /// Returns an error if the cumulative weight has decreased between the given (height, weight) pairs,
+    /// unless the chain was reorganized, or has increased by more than the given maximum weight per block of height gained.
+    ///
+    fn check_cumulative_weight_transition(
+        (previous_height, previous_weight): (u32, u128),
+        (height, weight): (u32, u128),
+        maximum_weight_per_block: u128,
+        is_reorganized: bool,
+    ) -> Result<(), String> {
+        // Ensure the cumulative weight has not decreased, as chains only grow, unless they are reorganized.
+        if weight < previous_weight {
+            if is_reorganized {
+                return Ok(());
+            }
+            return Err(format!(
+                "Advertised cumulative weight decreased from {} to {}",
+                previous_weight, weight
+            ));
+        }
+
+        // Ensure the cumulative weight has not increased by more than the blocks gained can account for.
+        // A reorganization at the same height may still change the weight, so at least one block is allowed.
+        let number_of_blocks = (height.saturating_sub(previous_height) as u128).max(1);
+        let maximum_increase = number_of_blocks.saturating_mul(maximum_weight_per_block);
+        if weight - previous_weight > maximum_increase {
+            return Err(format!(
+                "Advertised cumulative weight jumped implausibly from {} to {} over {} blocks",
+                previous_weight, weight, number_of_blocks
+            ));
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Returns the maximum age of a connection in the given direction before it is recycled, if any.
+    /// Only outbound connections are recycled, as this node is able to reconnect to the peer.
+    /// A random delay of up to a tenth of the maximum age is added, to stagger the reconnections.
+    ///
+    fn maximum_connection_age<R: Rng>(direction: ConnectionDirection, rng: &mut R) -> Option<Duration> {
+        match (E::MAXIMUM_CONNECTION_AGE_IN_SECS, direction) {
+            (0, _) | (_, ConnectionDirection::Inbound) => None,
+            (maximum_age, ConnectionDirection::Outbound) => {
+                let stagger = rng.gen_range(0..=maximum_age / 10);
+                Some(Duration::from_secs(maximum_age.saturating_add(stagger)))
+            }
+        }
+    }
+
+    ///
+    /// Returns a `BlockUnavailable` response if the start of the requested blocks is older
+    /// than `E::PRUNE_DEPTH` blocks from the given latest block height, as it has been pruned.
+    ///
+    fn pruned_block_response(start_block_height: u32, end_block_height: u32, latest_block_height: u32) -> Option<Message<N, E>> {
+        match E::PRUNE_DEPTH > 0 && start_block_height < latest_block_height.saturating_sub(E::PRUNE_DEPTH) {
+            true => Some(Message::BlockUnavailable(start_block_height, end_block_height)),
+            false => None,
+        }
+    }
+
+    ///
+    /// Returns a `BlockResponseEnd` response if the end of the requested blocks is beyond the given latest block height,
+    /// to signal to the peer that this node has no more blocks to send beyond it.
+    ///
+    fn block_response_end(end_block_height: u32, latest_block_height: u32) -> Option<Message<N, E>> {
+        match end_block_height > latest_block_height {
+            true => Some(Message::BlockResponseEnd(latest_block_height)),
+            false => None,
+        }
+    }
+
+    ///
+    /// Returns the requested block range, with the end clamped to the given latest block height,
+    /// or `None` if the request starts beyond the latest block height.
+    ///
+    fn clamp_block_request(start_block_height: u32, end_block_height: u32, latest_block_height: u32) -> Option<(u32, u32)> {
+        match start_block_height <= latest_block_height {
+            true => Some((start_block_height, end_block_height.min(latest_block_height))),
+            false => None,
+        }
+    }
+
+    ///
+    /// Returns `true` if the given start of a block request is more than `E::MAXIMUM_BLOCK_REQUEST` blocks
+    /// beyond the given latest block height, which a peer that is syncing from this node would not request.
+    ///
+    fn is_far_future_block_request(start_block_height: u32, latest_block_height: u32) -> bool {
+        start_block_height > latest_block_height.saturating_add(E::MAXIMUM_BLOCK_REQUEST)
+    }
+
+    ///
+    /// Returns a failure if the given height of an unconfirmed block is more than `E::MAXIMUM_BLOCK_REQUEST` blocks
+    /// beyond the given latest block height, as no peer on the canonical chain could have produced it yet.
+    ///
+    fn check_unconfirmed_block_height(block_height: u32, latest_block_height: u32) -> Result<(), String> {
+        match block_height > latest_block_height.saturating_add(E::MAXIMUM_BLOCK_REQUEST) {
+            true => Err(format!(
+                "Sent unconfirmed block {} beyond block {}",
+                block_height, latest_block_height
+            )),
+            false => Ok(()),
+        }
+    }
+
+    ///
+    /// Returns a failure if the given unconfirmed block does not match the block height and block hash declared for it,
+    /// as the declared fields are used to route the unconfirmed block before it is deserialized.
+    ///
+    fn check_unconfirmed_block(block_height: u32, block_hash: N::BlockHash, block: &Block<N>) -> Result<(), String> {
+        if block.height() != block_height {
+            return Err(format!(
+                "Sent unconfirmed block {} declared as block {}",
+                block.height(),
+                block_height
+            ));
+        }
+        if block.hash() != block_hash {
+            return Err(format!(
+                "Sent unconfirmed block {} ({}) declared as ({})",
+                block_height,
+                block.hash(),
+                block_hash
+            ));
+        }
+        Ok(())
+    }
+
+    ///
+    /// Returns `true` if the given height of an unconfirmed block is more than the given fork depth of the peer
+    /// below the given latest block height, in which case the peer is on a fork too old to be adopted.
+    ///
+    fn is_on_stale_fork(block_height: u32, latest_block_height: u32, fork_depth: u32) -> bool {
+        block_height.saturating_add(fork_depth) < latest_block_height
+    }
+
+    ///
+    /// Returns the given fork depth, capped at `E::MAXIMUM_ADVERTISED_FORK_DEPTH`.
+    ///
+    fn bounded_fork_depth(fork_depth: u32) -> u32 {
+        fork_depth.min(E::MAXIMUM_ADVERTISED_FORK_DEPTH)
+    }
+
+    ///
+    /// Returns `true` if the given address, as shared by a peer in a `PeerResponse`, may be a routable peer.
+    /// Unspecified, multicast, broadcast, and documentation addresses are never valid candidate peers.
+    ///
+    fn is_valid_peer_address(peer_ip: &SocketAddr) -> bool {
+        match peer_ip.ip() {
+            IpAddr::V4(ip) => !(ip.is_unspecified() || ip.is_multicast() || ip.is_broadcast() || ip.is_documentation()),
+            IpAddr::V6(ip) => {
+                // The documentation range for IPv6 is 2001:db8::/32.
+                let is_documentation = ip.segments()[0] == 0x2001 && ip.segments()[1] == 0x0db8;
+                !(ip.is_unspecified() || ip.is_multicast() || is_documentation)
+            }
+        }
+    }
+
+    ///
+    /// Returns the valid addresses of the given `PeerResponse` addresses, and a failure if the peer
+    /// sent more than `E::MAXIMUM_INVALID_PEER_ADDRESSES` invalid addresses.
+    ///
+    fn filter_peer_addresses(peer_ips: Vec<SocketAddr>) -> (Vec<SocketAddr>, Option<String>) {
+        let (valid, invalid): (Vec<_>, Vec<_>) = peer_ips.into_iter().partition(Self::is_valid_peer_address);
+        match invalid.len() > E::MAXIMUM_INVALID_PEER_ADDRESSES {
+            true => (valid, Some(format!("Sent {} invalid peer addresses", invalid.len()))),
+            false => (valid, None),
+        }
+    }
+
+    ///
+    /// Records a block request from this peer that starts beyond the given latest block height, and returns
+    /// a failure if the peer has requested blocks far beyond the latest block height too many times.
+    ///
+    fn record_future_block_request(&mut self, start_block_height: u32, latest_block_height: u32) -> Option<String> {
+        if !Self::is_far_future_block_request(start_block_height, latest_block_height) {
+            return None;
+        }
+        self.future_block_requests = self.future_block_requests.saturating_add(1);
+        match self.future_block_requests > E::MAXIMUM_FUTURE_BLOCK_REQUESTS {
+            true => Some(format!(
+                "Requested block {} beyond block {}",
+                start_block_height, latest_block_height
+            )),
+            false => None,
+        }
+    }
+
+    ///
+    /// Returns `true` if no `Pong` has been received from the peer within `E::RADIO_SILENCE_IN_SECS`,
+    /// since the last `Pong` or, if none has been received yet, since the connection was established.
+    ///
+    fn is_pong_silent(last_pong_at: Option<Instant>, connected_at: Instant, now: Instant) -> bool {
+        let last_pong_at = last_pong_at.unwrap_or(connected_at);
+        now.saturating_duration_since(last_pong_at) > Duration::from_secs(E::RADIO_SILENCE_IN_SECS)
+    }
+
+    /// Returns `true` if the given mempool size of a peer exceeds the relay threshold.
+    fn is_mempool_full(mempool_size: u32) -> bool {
+        mempool_size > E::MAX_PEER_MEMPOOL_SIZE
+    }
+
     /// Returns the IP address of the peer, with the port set to the listener port.
     fn peer_ip(&self) -> SocketAddr {
         self.listener_ip
     }
 
-    /// Sends the given message to this peer.
+    ///
+    /// Sends the given message to this peer. If coalescing is enabled and agreed with the peer, a small message is
+    /// buffered and written together with subsequent messages, at most `E::SEND_COALESCE_INTERVAL_IN_MILLIS` later.
+    ///
     async fn send(&mut self, message: Message<N, E>) -> Result<()> {
         trace!("Sending '{}' to {}", message.name(), self.peer_ip());
-        self.outbound_socket.send(message).await?;
+        let message = message.encode_payloads(self.encoding).await?;
+        let is_batched = self.features.contains(FeatureFlags::BATCHES_MESSAGES);
+        match E::SEND_COALESCE_INTERVAL_IN_MILLIS > 0 && is_batched && message.is_coalescible() {
+            true => {
+                self.outbound_socket.feed(message).await?;
+                let interval = Duration::from_millis(E::SEND_COALESCE_INTERVAL_IN_MILLIS);
+                self.coalesce_deadline.get_or_insert_with(|| Instant::now() + interval);
+            }
+            false => {
+                // Sending the message also writes any coalesced messages ahead of it.
+                self.coalesce_deadline = None;
+                self.outbound_socket.send(message).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes any coalesced outbound messages to the socket.
+    async fn flush(&mut self) -> Result<()> {
+        self.coalesce_deadline = None;
+        self.outbound_socket.flush().await?;
+        Ok(())
+    }
+
+    ///
+    /// Closes the connection with this peer. If a reason is given, the connection is closed gracefully,
+    /// with a `Disconnect` that is flushed before the stream is shut down, so the peer learns why it was dropped.
+    ///
+    async fn close(&mut self, reason: Option<DisconnectReason>) {
+        debug!("Dropping {} (graceful = {})", self.peer_ip(), reason.is_some());
+        self.coalesce_deadline = None;
+        let close_timeout = Duration::from_millis(E::CONNECTION_TIMEOUT_IN_MILLIS);
+        let result = timeout(close_timeout, Self::shutdown(&mut self.outbound_socket, reason)).await;
+        if !matches!(result, Ok(Ok(()))) {
+            trace!("Failed to close the connection to {}", self.peer_ip());
+        }
+    }
+
+    ///
+    /// Sends a `Disconnect` with the given reason on the given socket, if one is given,
+    /// and shuts down the stream once all outbound messages are flushed.
+    ///
+    async fn shutdown<S: AsyncRead + AsyncWrite + Unpin>(
+        socket: &mut Framed<S, MessageCodec<N, E>>,
+        reason: Option<DisconnectReason>,
+    ) -> Result<()> {
+        if let Some(reason) = reason {
+            socket.feed(Message::Disconnect(reason)).await?;
+        }
+        socket.flush().await?;
+        socket.get_mut().shutdown().await?;
         Ok(())
     }
 
-    /// Performs the handshake protocol, returning the listener IP and nonce of the peer upon success.
+    ///
+    /// Defers the given request until the throttle of the peer expires, returning `false` if the request is dropped.
+    /// A request is dropped if an identical request is already deferred, or if `E::MAXIMUM_DEFERRED_REQUESTS` are deferred.
+    ///
+    fn defer_request(deferred_requests: &mut VecDeque<Message<N, E>>, message: Message<N, E>) -> bool {
+        let is_duplicate = deferred_requests.iter().any(|deferred| match (deferred, &message) {
+            (Message::BlockRequest(start, end), Message::BlockRequest(other_start, other_end)) => (start, end) == (other_start, other_end),
+            (Message::TransactionRequest(transaction_ids), Message::TransactionRequest(other_transaction_ids)) => {
+                transaction_ids == other_transaction_ids
+            }
+            (deferred, message) => deferred.id() == message.id() && matches!(message, Message::PeerRequest(..) | Message::MempoolRequest),
+        });
+        if is_duplicate || deferred_requests.len() >= E::MAXIMUM_DEFERRED_REQUESTS {
+            return false;
+        }
+        deferred_requests.push_back(message);
+        true
+    }
+
+    /// Returns the duration until the coalesced outbound messages must be written to the socket.
+    fn time_until_flush(&self) -> Duration {
+        self.coalesce_deadline
+            .map_or(Duration::ZERO, |deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    ///
+    /// Returns the block locators of the current chain tip, serialized for this peer.
+    /// The serialized block locators are served from the given cache, if they are up to date.
+    ///
+    async fn latest_block_locators(
+        &self,
+        ledger_reader: &LedgerReader<N>,
+        block_locator_cache: &RwLock<BlockLocatorCache<N>>,
+    ) -> Data<BlockLocators<N>> {
+        // Serialize the block locators on a miss, and cache them for the other peers using this encoding format.
+        let latest_block_hash = ledger_reader.latest_block_hash();
+        let block_locators =
+            BlockLocatorCache::get_or_load(block_locator_cache, latest_block_hash, || ledger_reader.latest_block_locators()).await;
+        let serialize = || Data::Object((*block_locators).clone()).serialize_with(self.encoding);
+        match BlockLocatorCache::get_or_serialize(block_locator_cache, latest_block_hash, self.encoding, serialize).await {
+            Ok(block_locators) => Data::Buffer(block_locators),
+            Err(error) => {
+                warn!("Failed to serialize the block locators: {}", error);
+                Data::Object((*block_locators).clone())
+            }
+        }
+    }
+
+    /// Returns `true` if both this node and the peer support block locator diffs.
+    fn supports_block_locators_diff(&self) -> bool {
+        NodeCapabilities::from(E::NODE_TYPE).contains(NodeCapabilities::DIFFS_BLOCK_LOCATORS)
+            && self.capabilities.contains(NodeCapabilities::DIFFS_BLOCK_LOCATORS)
+    }
+
+    ///
+    /// Returns the `Pong` message for this peer. If block locator diffs are supported and the peer
+    /// has received block locators before, only the changes since the last `Pong` are sent.
+    ///
+    async fn pong(
+        &mut self,
+        is_fork: Option<bool>,
+        ledger_reader: &LedgerReader<N>,
+        block_locator_cache: &RwLock<BlockLocatorCache<N>>,
+    ) -> Message<N, E> {
+        if self.supports_block_locators_diff() {
+            let latest_block_hash = ledger_reader.latest_block_hash();
+            let block_locators =
+                BlockLocatorCache::get_or_load(block_locator_cache, latest_block_hash, || ledger_reader.latest_block_locators()).await;
+            let diff = self
+                .sent_block_locators
+                .as_ref()
+                .and_then(|base| BlockLocatorsDiff::new(base, &block_locators));
+            self.sent_block_locators = Some(BlockLocatorsDiff::hashes(&block_locators));
+
+            if let Some(diff) = diff {
+                return Message::PongDiff(is_fork, Data::Object(diff));
+            }
+        }
+
+        // If the chain tip changed in the meantime, the next diff will not apply, and the peer requests a resync.
+        Message::Pong(is_fork, self.latest_block_locators(ledger_reader, block_locator_cache).await)
+    }
+
+    ///
+    /// Measures the round-trip time of the last `Ping`, and reports the peer if it has been slow for a sustained window.
+    /// Returns the round-trip time, if a `Ping` was outstanding.
+    ///
+    async fn record_round_trip_time(&mut self, peers_router: &PeersRouter<N, E>) -> Option<Duration> {
+        let rtt = self.last_ping_sent.take()?.elapsed();
+        let threshold = Duration::from_millis(E::SLOW_PEER_RTT_THRESHOLD_IN_MILLIS);
+        let window = Duration::from_secs(E::SLOW_PEER_WINDOW_IN_SECS);
+        if self.slow_peer_detector.update(rtt, Instant::now(), threshold, window) {
+            if let Err(error) = peers_router.send(PeersRequest::SlowPeer(self.peer_ip(), rtt)).await {
+                warn!("[SlowPeer] {}", error);
+            }
+        }
+        Some(rtt)
+    }
+
+    /// Returns the `Pong` request for the ledger, with the given block locators and round-trip time of this peer.
+    fn ledger_pong(&self, is_fork: Option<bool>, block_locators: BlockLocators<N>, rtt: Option<Duration>) -> LedgerRequest<N> {
+        LedgerRequest::Pong(
+            self.peer_ip(),
+            self.node_type,
+            self.capabilities,
+            self.status.get(),
+            is_fork,
+            block_locators,
+            rtt,
+        )
+    }
+
+    ///
+    /// Returns a `Ping` with the latest state of the ledger. The advertised fork depth is the maximum fork depth,
+    /// unless the ledger holds an unconfirmed block on a deeper fork.
+    ///
+    pub(super) fn ping(ledger_reader: &LedgerReader<N>) -> Message<N, E> {
+        Message::Ping(
+            E::MESSAGE_VERSION,
+            Self::bounded_fork_depth(N::ALEO_MAXIMUM_FORK_DEPTH.max(E::orphan_depth().load(Ordering::SeqCst))),
+            E::NODE_TYPE,
+            E::status().get(),
+            ledger_reader.latest_block_hash(),
+            Data::Object(ledger_reader.latest_block_header()),
+            E::memory_pool_size().load(Ordering::SeqCst),
+        )
+    }
+
+    /// Spawns an asynchronous task that sends the next `Ping` to the given peer, after the preset time.
+    fn schedule_ping(peer_ip: SocketAddr, peers_router: &PeersRouter<N, E>, ledger_reader: &LedgerReader<N>) {
+        let peers_router = peers_router.clone();
+        let ledger_reader = ledger_reader.clone();
+        E::tasks().append(task::spawn(async move {
+            // Sleep for the preset time before sending a `Ping` request.
+            tokio::time::sleep(Duration::from_secs(E::PING_SLEEP_IN_SECS)).await;
+
+            // Send a `Ping` request to the peer.
+            let message = Self::ping(&ledger_reader);
+            if let Err(error) = peers_router.send(PeersRequest::MessageSend(peer_ip, message)).await {
+                warn!("[Ping] {}", error);
+            }
+        }));
+    }
+
+    ///
+    /// Exchanges the `Hello` byte with the given features with the peer, and returns the features agreed with the peer.
+    /// The `Hello` is exchanged before the handshake, as the agreed features determine how messages are framed.
+    ///
+    /// A peer that sends its handshake without a `Hello` agrees on no features, and the first byte of its handshake is returned.
+    /// To never send a `Hello` to such a peer, the node accepting the connection only replies to a `Hello`,
+    /// and the connecting node first waits `E::HELLO_GRACE_PERIOD_IN_MILLIS` for a handshake without a `Hello`.
+    ///
+    async fn exchange_hello<S: AsyncRead + AsyncWrite + Unpin>(
+        stream: &mut S,
+        features: FeatureFlags,
+        direction: ConnectionDirection,
+    ) -> Result<(FeatureFlags, Option<u8>)> {
+        // Read the first byte from the peer, if the peer sends one before this node sends its `Hello`.
+        let first_byte = match direction {
+            ConnectionDirection::Inbound => Some(stream.read_u8().await?),
+            ConnectionDirection::Outbound => {
+                match timeout(Duration::from_millis(E::HELLO_GRACE_PERIOD_IN_MILLIS), stream.read_u8()).await {
+                    Ok(byte) => Some(byte?),
+                    Err(_) => None,
+                }
+            }
+        };
+
+        match first_byte {
+            Some(byte) => match FeatureFlags::from_hello(byte) {
+                // The peer sent its `Hello` first, which is only replied to on an inbound connection.
+                Some(peer_features) => {
+                    if direction == ConnectionDirection::Inbound {
+                        stream.write_all(&[features.to_hello()]).await?;
+                    }
+                    Ok((features.negotiate(peer_features), None))
+                }
+                // The peer sent its handshake without a `Hello`.
+                None => Ok((FeatureFlags::empty(), Some(byte))),
+            },
+            // The peer is waiting for the `Hello` of this node.
+            None => {
+                stream.write_all(&[features.to_hello()]).await?;
+                match FeatureFlags::from_hello(stream.read_u8().await?) {
+                    Some(peer_features) => Ok((features.negotiate(peer_features), None)),
+                    None => Err(anyhow!("Expected a 'Hello' in reply to the 'Hello' of this node")),
+                }
+            }
+        }
+    }
+
+    ///
+    /// Returns the socket with the given stream, framed with the agreed features,
+    /// which first reads the given byte of the handshake of the peer, if one was read during the `Hello` exchange.
+    ///
+    fn framed(stream: TcpStream, features: FeatureFlags, first_byte: Option<u8>) -> Framed<TcpStream, MessageCodec<N, E>> {
+        let mut parts = FramedParts::new::<Message<N, E>>(stream, MessageCodec::<N, E>::new(features));
+        if let Some(byte) = first_byte {
+            parts.read_buf.extend_from_slice(&[byte]);
+        }
+        Framed::from_parts(parts)
+    }
+
+    ///
+    /// Opens a connection to the given listener of a peer, and returns `Ok` if the listener
+    /// responds with a `ChallengeRequest` carrying the given nonce of the peer.
+    ///
+    async fn verify_listener(listener_ip: SocketAddr, peer_nonce: u64) -> Result<()> {
+        let verification = async move {
+            let mut stream = TcpStream::connect(listener_ip).await?;
+            let (features, first_byte) = Self::exchange_hello(&mut stream, FeatureFlags::local(), ConnectionDirection::Outbound).await?;
+            let mut socket = Self::framed(stream, features, first_byte);
+
+            match socket.next().await {
+                Some(Ok(Message::ChallengeRequest(.., nonce, _))) => match nonce == peer_nonce {
+                    true => Ok(()),
+                    false => Err(anyhow!("Listener {} responded with the nonce of another node", listener_ip)),
+                },
+                Some(Ok(message)) => Err(anyhow!(
+                    "Expected challenge request, received '{}' from {}",
+                    message.name(),
+                    listener_ip
+                )),
+                Some(Err(error)) => Err(error.into()),
+                None => Err(anyhow!("Dropped prior to challenge request of {}", listener_ip)),
+            }
+        };
+
+        match timeout(Duration::from_millis(E::CONNECTION_TIMEOUT_IN_MILLIS), verification).await {
+            Ok(result) => result,
+            Err(_) => Err(anyhow!("Timed out verifying the listener {}", listener_ip)),
+        }
+    }
+
+    /// Performs the handshake protocol, returning the listener IP, nonce, and advertised state of the peer upon success.
     async fn handshake(
-        outbound_socket: &mut Framed<TcpStream, Message<N, E>>,
+        outbound_socket: &mut Framed<TcpStream, MessageCodec<N, E>>,
         local_ip: SocketAddr,
         local_nonce: u64,
         local_cumulative_weight: u128,
-        connected_nonces: &[u64],
-    ) -> Result<(SocketAddr, u64, NodeType, Status)> {
+        connected_nonces: &[(SocketAddr, u64)],
+    ) -> Result<(SocketAddr, u64, u32, NodeType, NodeCapabilities, Status, u128)> {
         // Get the IP address of the peer.
-        let mut peer_ip = outbound_socket.get_ref().peer_addr()?;
+        let socket_ip = outbound_socket.get_ref().peer_addr()?;
+        let mut peer_ip = socket_ip;
 
         // Retrieve the genesis block header.
         let genesis_header = N::genesis_block().header();
@@ -171,6 +837,7 @@ impl<N: Network, E: Environment> Peer<N, E> {
             E::MESSAGE_VERSION,
             N::ALEO_MAXIMUM_FORK_DEPTH,
             E::NODE_TYPE,
+            E::NODE_CAPABILITIES,
             E::status().get(),
             local_ip.port(),
             local_nonce,
@@ -180,7 +847,7 @@ impl<N: Network, E: Environment> Peer<N, E> {
         outbound_socket.send(message).await?;
 
         // Wait for the counterparty challenge request to come in.
-        let (peer_nonce, node_type, status) = match outbound_socket.next().await {
+        let (peer_nonce, version, node_type, capabilities, status, peer_cumulative_weight) = match outbound_socket.next().await {
             Some(Ok(message)) => {
                 // Process the message.
                 trace!("Received '{}-B' from {}", message.name(), peer_ip);
@@ -189,15 +856,17 @@ impl<N: Network, E: Environment> Peer<N, E> {
                         version,
                         fork_depth,
                         node_type,
+                        capabilities,
                         peer_status,
                         listener_port,
                         peer_nonce,
                         peer_cumulative_weight,
                     ) => {
-                        // Ensure the message protocol version is not outdated.
-                        if version < E::MESSAGE_VERSION {
-                            warn!("Dropping {} on version {} (outdated)", peer_ip, version);
-                            return Err(anyhow!("Dropping {} on version {} (outdated)", peer_ip, version));
+                        // Ensure the message protocol version is supported.
+                        if !Self::is_version_supported(version) {
+                            info!("Dropping {} on version {} (unsupported)", peer_ip, version);
+                            outbound_socket.send(Message::Disconnect(DisconnectReason::VersionMismatch)).await?;
+                            return Err(anyhow!("Dropping {} on version {} (unsupported)", peer_ip, version));
                         }
                         // Ensure the maximum fork depth is correct.
                         if fork_depth != N::ALEO_MAXIMUM_FORK_DEPTH {
@@ -227,25 +896,16 @@ impl<N: Network, E: Environment> Peer<N, E> {
                         if local_nonce == peer_nonce {
                             return Err(anyhow!("Attempted to connect to self (nonce = {})", peer_nonce));
                         }
-                        // Ensure the peer is not already connected to this node.
-                        if connected_nonces.contains(&peer_nonce) {
+                        // Ensure the peer is not already connected to this node, unless it replaces its connection to this listener.
+                        let listener_ip = SocketAddr::new(peer_ip.ip(), listener_port);
+                        if connected_nonces
+                            .iter()
+                            .any(|(ip, nonce)| *nonce == peer_nonce && *ip != listener_ip)
+                        {
                             return Err(anyhow!("Already connected to a peer with nonce {}", peer_nonce));
                         }
-                        // Verify the listener port.
-                        if peer_ip.port() != listener_port {
-                            // Update the peer IP to the listener port.
-                            peer_ip.set_port(listener_port);
-                            // Ensure the claimed listener port is open.
-                            let stream =
-                                match timeout(Duration::from_millis(E::CONNECTION_TIMEOUT_IN_MILLIS), TcpStream::connect(peer_ip)).await {
-                                    Ok(stream) => stream,
-                                    Err(error) => return Err(anyhow!("Unable to reach '{}': '{:?}'", peer_ip, error)),
-                                };
-                            // Error if the stream is not open.
-                            if let Err(error) = stream {
-                                return Err(anyhow!("Unable to reach '{}': '{}'", peer_ip, error));
-                            }
-                        }
+                        // Update the peer IP to the listener port, which is verified after the handshake.
+                        peer_ip.set_port(listener_port);
                         // Send the challenge response.
                         let message = Message::ChallengeResponse(Data::Object(genesis_header.clone()));
                         trace!("Sending '{}-B' to {}", message.name(), peer_ip);
@@ -255,17 +915,22 @@ impl<N: Network, E: Environment> Peer<N, E> {
                         let status = Status::new();
                         status.update(peer_status);
 
-                        (peer_nonce, node_type, status)
+                        (peer_nonce, version, node_type, capabilities, status, peer_cumulative_weight)
+                    }
+                    Message::Disconnect(reason) => {
+                        return Err(anyhow!("{} disconnected prior to challenge request ({:?})", peer_ip, reason));
                     }
                     message => {
-                        return Err(anyhow!(
-                            "Expected challenge request, received '{}' from {}",
-                            message.name(),
-                            peer_ip
-                        ));
+                        let violation = format!("expected challenge request, received '{}'", message.name());
+                        return Err(Self::protocol_violation(outbound_socket, socket_ip, violation).await);
                     }
                 }
             }
+            // The peer sent a message that could not be decoded.
+            Some(Err(error)) if error.kind() == std::io::ErrorKind::InvalidData => {
+                let violation = format!("invalid challenge request ({})", error);
+                return Err(Self::protocol_violation(outbound_socket, socket_ip, violation).await);
+            }
             // An error occurred.
             Some(Err(error)) => return Err(anyhow!("Failed to get challenge request from {}: {:?}", peer_ip, error)),
             // Did not receive anything.
@@ -282,17 +947,30 @@ impl<N: Network, E: Environment> Peer<N, E> {
                         // Perform the deferred non-blocking deserialization of the block header.
                         let block_header = block_header.deserialize().await?;
                         match &block_header == genesis_header {
-                            true => Ok((peer_ip, peer_nonce, node_type, status)),
+                            true => Ok((
+                                peer_ip,
+                                peer_nonce,
+                                version,
+                                node_type,
+                                capabilities,
+                                status,
+                                peer_cumulative_weight,
+                            )),
                             false => Err(anyhow!("Challenge response from {} failed, received '{}'", peer_ip, block_header)),
                         }
                     }
-                    message => Err(anyhow!(
-                        "Expected challenge response, received '{}' from {}",
-                        message.name(),
-                        peer_ip
-                    )),
+                    Message::Disconnect(reason) => Err(anyhow!("{} disconnected prior to challenge response ({:?})", peer_ip, reason)),
+                    message => {
+                        let violation = format!("expected challenge response, received '{}'", message.name());
+                        Err(Self::protocol_violation(outbound_socket, socket_ip, violation).await)
+                    }
                 }
             }
+            // The peer sent a message that could not be decoded.
+            Some(Err(error)) if error.kind() == std::io::ErrorKind::InvalidData => {
+                let violation = format!("invalid challenge response ({})", error);
+                Err(Self::protocol_violation(outbound_socket, socket_ip, violation).await)
+            }
             // An error occurred.
             Some(Err(error)) => Err(anyhow!("Failed to get challenge response from {}: {:?}", peer_ip, error)),
             // Did not receive anything.
@@ -300,6 +978,21 @@ impl<N: Network, E: Environment> Peer<N, E> {
         }
     }
 
+    ///
+    /// Disconnects from a peer that deviated from the handshake protocol, without reading any further messages,
+    /// and returns the protocol violation to penalize the peer with.
+    ///
+    async fn protocol_violation(
+        outbound_socket: &mut Framed<TcpStream, MessageCodec<N, E>>,
+        socket_ip: SocketAddr,
+        violation: String,
+    ) -> anyhow::Error {
+        if let Err(error) = outbound_socket.send(Message::Disconnect(DisconnectReason::ProtocolViolation)).await {
+            trace!("Failed to send a 'Disconnect' to {}: {}", socket_ip, error);
+        }
+        ProtocolViolation(socket_ip, violation).into()
+    }
+
     /// A handler to process an individual peer.
     #[allow(clippy::too_many_arguments)]
     pub(super) async fn handler(
@@ -311,11 +1004,19 @@ impl<N: Network, E: Environment> Peer<N, E> {
         ledger_router: LedgerRouter<N>,
         prover_router: ProverRouter<N>,
         operator_router: OperatorRouter<N>,
-        connected_nonces: Vec<u64>,
+        block_locator_cache: Arc<RwLock<BlockLocatorCache<N>>>,
+        message_histories: MessageHistories,
+        connected_nonces: Vec<(SocketAddr, u64)>,
         connection_result: Option<ConnectionResult>,
     ) {
         let peers_router = peers_router.clone();
 
+        // Only outbound connections report their connection result.
+        let direction = match connection_result.is_some() {
+            true => ConnectionDirection::Outbound,
+            false => ConnectionDirection::Inbound,
+        };
+
         E::tasks().append(task::spawn(async move {
             // Register our peer with state which internally sets up some channels.
             let mut peer = match Peer::new(
@@ -325,6 +1026,7 @@ impl<N: Network, E: Environment> Peer<N, E> {
                 &peers_router,
                 &ledger_reader,
                 &connected_nonces,
+                direction,
             )
                 .await
             {
@@ -339,6 +1041,17 @@ impl<N: Network, E: Environment> Peer<N, E> {
                 }
                 Err(error) => {
                     trace!("{}", error);
+                    // Restrict a peer that deviated from the handshake protocol.
+                    // A peer that connected to this node is restricted by its IP address, as the port of its connection is ephemeral.
+                    if let Some(ProtocolViolation(socket_ip, _)) = error.downcast_ref::<ProtocolViolation>() {
+                        let restricted_ip = match direction {
+                            ConnectionDirection::Outbound => *socket_ip,
+                            ConnectionDirection::Inbound => Peers::<N, E>::inbound_peer_lookup(*socket_ip),
+                        };
+                        if let Err(error) = peers_router.send(PeersRequest::PeerRestricted(restricted_ip)).await {
+                            warn!("[PeerRestricted] {}", error);
+                        }
+                    }
                     // If the optional connection result router is given, report a failed connection result.
                     if let Some(router) = connection_result {
                         if router.send(Err(error)).is_err() {
@@ -353,22 +1066,65 @@ impl<N: Network, E: Environment> Peer<N, E> {
             let peer_ip = peer.peer_ip();
             info!("Connected to {}", peer_ip);
 
+            // Register the message history of this connection, which replaces the history of a recycled connection.
+            let message_history = Arc::new(parking_lot::Mutex::new(MessageHistory::new()));
+            message_histories.write().await.insert(peer_ip, message_history.clone());
+
+            // Request the unconfirmed transactions of the peer, to bootstrap the memory pool.
+            let keeps_memory_pool = |node_type: NodeType| node_type != NodeType::Beacon && node_type != NodeType::Sync;
+            if keeps_memory_pool(E::NODE_TYPE) && keeps_memory_pool(peer.node_type) {
+                if let Err(error) = peer.send(Message::MempoolRequest).await {
+                    warn!("[MempoolRequest] {}", error);
+                }
+            }
+
+            // The result of the new connection that replaces this connection, once it is being recycled.
+            let mut recycle_result: Option<oneshot::Receiver<Result<()>>> = None;
+            // Set to `true` if the connection is closed in order to be recycled.
+            let mut is_recycled = false;
+
             // Process incoming messages until this stream is disconnected.
             loop {
                 tokio::select! {
                     // Message channel is routing a message outbound to the peer.
                     Some(mut message) = peer.outbound_handler.recv() => {
+                        // Recycle the connection if it has exceeded its maximum age, by reconnecting before this connection is closed.
+                        if recycle_result.is_none()
+                            && peer.maximum_connection_age.map_or(false, |maximum_age| peer.connected_at.elapsed() > maximum_age)
+                        {
+                            debug!("Recycling the connection to {} after {} seconds", peer_ip, peer.connected_at.elapsed().as_secs());
+                            let (router, handler) = oneshot::channel();
+                            let request = PeersRequest::PeerRecycled(
+                                peer_ip,
+                                ledger_reader.clone(),
+                                ledger_router.clone(),
+                                operator_router.clone(),
+                                prover_router.clone(),
+                                router,
+                            );
+                            match peers_router.send(request).await {
+                                Ok(()) => recycle_result = Some(handler),
+                                Err(error) => warn!("[PeerRecycled] {}", error),
+                            }
+                        }
                         // Disconnect if the peer has not communicated back within the predefined time.
                         if peer.last_seen.elapsed() > Duration::from_secs(E::RADIO_SILENCE_IN_SECS) {
                             warn!("Peer {} has not communicated in {} seconds", peer_ip, peer.last_seen.elapsed().as_secs());
+                            peer.close(Some(DisconnectReason::NoReasonGiven)).await;
+                            break;
+                        } else if Self::is_pong_silent(peer.last_pong_at, peer.connected_at, Instant::now()) {
+                            warn!("Peer {} has not responded to a 'Ping' in {} seconds", peer_ip, E::RADIO_SILENCE_IN_SECS);
+                            peer.close(Some(DisconnectReason::NoReasonGiven)).await;
                             break;
                         } else {
                             // Ensure sufficient time has passed before needing to send the message.
                             let is_ready_to_send = match message {
-                                Message::Ping(_, _, _, _, _, ref mut data) => {
+                                Message::Ping(_, _, _, _, _, ref mut data, _) => {
                                     // Perform non-blocking serialisation of the block header.
-                                    let serialized_header = Data::serialize(data.clone()).await.expect("Block header serialization is bugged");
+                                    let serialized_header = data.clone().serialize_with(peer.encoding).await.expect("Block header serialization is bugged");
                                     let _ = std::mem::replace(data, Data::Buffer(serialized_header));
+                                    // Update the timestamp of the last ping, to measure the round-trip time.
+                                    peer.last_ping_sent = Some(Instant::now());
 
                                     true
                                 }
@@ -385,7 +1141,7 @@ impl<N: Network, E: Environment> Peer<N, E> {
                                     }
 
                                     // Perform non-blocking serialization of the block (if it hasn't been serialized yet).
-                                    let serialized_block = Data::serialize(data.clone()).await.expect("Block serialization is bugged");
+                                    let serialized_block = data.clone().serialize_with(peer.encoding).await.expect("Block serialization is bugged");
                                     let _ = std::mem::replace(data, Data::Buffer(serialized_block));
 
                                     is_ready_to_send
@@ -400,6 +1156,12 @@ impl<N: Network, E: Environment> Peer<N, E> {
 
                                     // Update the timestamp for the peer and sent transaction.
                                     peer.seen_outbound_transactions.insert(transaction.transaction_id(), SystemTime::now());
+
+                                    // Skip relaying the transaction to a peer that sent it to this node, as it already has it.
+                                    let has_transaction = peer.seen_inbound_transactions.contains_key(&transaction.transaction_id());
+                                    let is_ready_to_send = is_ready_to_send && !has_transaction;
+                                    // Skip relaying the transaction to a peer whose memory pool is likely full.
+                                    let is_ready_to_send = is_ready_to_send && !Self::is_mempool_full(peer.mempool_size);
                                     // Report the unconfirmed block height.
                                     if is_ready_to_send {
                                         trace!(
@@ -411,17 +1173,74 @@ impl<N: Network, E: Environment> Peer<N, E> {
                                     }
                                     is_ready_to_send
                                 }
+                                Message::PoolRegister(..) => {
+                                    // Only register with peers that accept pool registrations.
+                                    let is_ready_to_send = peer.capabilities.contains(NodeCapabilities::ACCEPTS_POOL_REGISTRATIONS);
+                                    if !is_ready_to_send {
+                                        warn!("Skipping 'PoolRegister' to {}, as it does not accept pool registrations", peer_ip);
+                                    }
+                                    is_ready_to_send
+                                }
+                                Message::Disconnect(reason) => {
+                                    // Close the connection once the `Disconnect` is sent, as the peer is dropped.
+                                    peer.close(Some(reason)).await;
+                                    break;
+                                }
+                                Message::BlockRequest(..) | Message::PeerRequest(..) | Message::MempoolRequest | Message::TransactionRequest(..) => {
+                                    // Defer requests to a peer that has asked this node to throttle them, until the throttle expires.
+                                    let is_ready_to_send = !peer.throttle.is_throttled(Instant::now());
+                                    if !is_ready_to_send {
+                                        match Self::defer_request(&mut peer.deferred_requests, message.clone()) {
+                                            true => trace!("Deferring '{}' to {} (throttled)", message.name(), peer_ip),
+                                            false => trace!("Skipping '{}' to {} (throttled)", message.name(), peer_ip),
+                                        }
+                                    }
+                                    is_ready_to_send
+                                }
                                 _ => true,
                             };
                             // Send the message if it is ready.
                             if is_ready_to_send {
                                 // Route a message to the peer.
                                 if let Err(error) = peer.send(message).await {
-                                    warn!("[OutboundRouter] {}", error);
+                                    if let Some(line) = peer.error_log.check(Instant::now(), &format!("[OutboundRouter] {}", error)) {
+                                        warn!("{}", line);
+                                    }
                                 }
                             }
                         }
                     }
+                    // The connection that replaces this connection has been established, or has failed.
+                    result = async { recycle_result.as_mut().unwrap().await }, if recycle_result.is_some() => {
+                        recycle_result = None;
+                        match result {
+                            Ok(Ok(())) => {
+                                is_recycled = true;
+                                break;
+                            }
+                            // Keep this connection, and attempt to recycle it again once another maximum age has passed.
+                            _ => {
+                                debug!("Failed to recycle the connection to {}, keeping the existing connection", peer_ip);
+                                peer.maximum_connection_age = Self::maximum_connection_age(ConnectionDirection::Outbound, &mut thread_rng())
+                                    .map(|maximum_age| peer.connected_at.elapsed() + maximum_age);
+                            }
+                        }
+                    }
+                    // The throttle requested by the peer has expired, so the deferred requests are due to be sent.
+                    _ = tokio::time::sleep(peer.throttle.time_until_expiry(Instant::now())), if !peer.deferred_requests.is_empty() => {
+                        while let Some(message) = peer.deferred_requests.pop_front() {
+                            trace!("Sending deferred '{}' to {}", message.name(), peer_ip);
+                            if let Err(error) = peer.send(message).await {
+                                warn!("[OutboundRouter] {}", error);
+                            }
+                        }
+                    }
+                    // The coalesced outbound messages are due to be written to the peer.
+                    _ = tokio::time::sleep(peer.time_until_flush()), if peer.coalesce_deadline.is_some() => {
+                        if let Err(error) = peer.flush().await {
+                            warn!("[Flush] {}", error);
+                        }
+                    }
                     result = peer.outbound_socket.next() => match result {
                         // Received a message from the peer.
                         Some(Ok(message)) => {
@@ -430,6 +1249,7 @@ impl<N: Network, E: Environment> Peer<N, E> {
                                 true => {
                                     let last_seen = peer.last_seen.elapsed().as_secs();
                                     warn!("Failed to receive a message from {} in {} seconds", peer_ip, last_seen);
+                                    peer.close(Some(DisconnectReason::NoReasonGiven)).await;
                                     break;
                                 },
                                 false => {
@@ -437,10 +1257,26 @@ impl<N: Network, E: Environment> Peer<N, E> {
                                     peer.last_seen = Instant::now();
                                 }
                             }
+                            // Disconnect if the peer has not responded to a `Ping` within the predefined time.
+                            if Self::is_pong_silent(peer.last_pong_at, peer.connected_at, Instant::now()) {
+                                warn!("Failed to receive a 'Pong' from {} in {} seconds", peer_ip, E::RADIO_SILENCE_IN_SECS);
+                                peer.close(Some(DisconnectReason::NoReasonGiven)).await;
+                                break;
+                            }
+                            // Record the message in the message history of the peer.
+                            message_history.lock().push(message.name(), Instant::now());
                             // Process the message.
                             trace!("Received '{}' from {}", message.name(), peer_ip);
                             match message {
                                 Message::BlockRequest(start_block_height, end_block_height) => {
+                                    // If this node is an observer node, notify the peer that it does not serve blocks.
+                                    if E::NODE_TYPE == NodeType::Observer {
+                                        trace!("Declining 'BlockRequest {} to {}' from {}", start_block_height, end_block_height, peer_ip);
+                                        if let Err(error) = peer.send(Message::BlockUnavailable(start_block_height, end_block_height)).await {
+                                            warn!("[BlockUnavailable] {}", error);
+                                        }
+                                        continue;
+                                    }
                                     // Ensure the request is within the accepted limits.
                                     let number_of_blocks = end_block_height.saturating_sub(start_block_height);
                                     if number_of_blocks > E::MAXIMUM_BLOCK_REQUEST {
@@ -451,6 +1287,37 @@ impl<N: Network, E: Environment> Peer<N, E> {
                                         }
                                         continue;
                                     }
+                                    // Ensure the requested blocks exist, and clamp the request to the latest block height.
+                                    let latest_height = ledger_reader.latest_block_height();
+                                    let block_response_end = Self::block_response_end(end_block_height, latest_height);
+                                    let block_range = Self::clamp_block_request(start_block_height, end_block_height, latest_height);
+                                    let (start_block_height, end_block_height) = match block_range {
+                                        Some(block_range) => block_range,
+                                        None => {
+                                            trace!("Ignoring 'BlockRequest' from {} beyond block {}", peer_ip, latest_height);
+                                            // Notify the peer that this node has no blocks beyond the latest block height.
+                                            if let Some(message) = block_response_end {
+                                                if let Err(error) = peer.send(message).await {
+                                                    warn!("[BlockResponseEnd] {}", error);
+                                                }
+                                            }
+                                            // Penalize the peer if it repeatedly requests blocks far beyond the latest block height.
+                                            if let Some(failure) = peer.record_future_block_request(start_block_height, latest_height) {
+                                                if let Err(error) = ledger_router.send(LedgerRequest::Failure(peer_ip, failure)).await {
+                                                    warn!("[Failure] {}", error);
+                                                }
+                                            }
+                                            continue;
+                                        }
+                                    };
+                                    // Ensure the requested blocks have not been pruned, and notify the peer otherwise.
+                                    let response = Self::pruned_block_response(start_block_height, end_block_height, latest_height);
+                                    if let Some(message) = response {
+                                        if let Err(error) = peer.send(message).await {
+                                            warn!("[BlockUnavailable] {}", error);
+                                        }
+                                        continue;
+                                    }
                                     // Retrieve the requested blocks.
                                     let blocks = match ledger_reader.get_blocks(start_block_height, end_block_height) {
                                         Ok(blocks) => blocks,
@@ -465,16 +1332,36 @@ impl<N: Network, E: Environment> Peer<N, E> {
                                     // Send a `BlockResponse` message for each block to the peer.
                                     for block in blocks {
                                         debug!("Sending 'BlockResponse {}' to {}", block.height(), peer_ip);
-                                        if let Err(error) = peer.outbound_socket.send(Message::BlockResponse(Data::Object(block))).await {
+                                        if let Err(error) = peer.send(Message::BlockResponse(Data::Object(block))).await {
                                             warn!("[BlockResponse] {}", error);
                                             break;
                                         }
                                     }
+                                    // Notify the peer if the request extended beyond the latest block height.
+                                    if let Some(message) = block_response_end {
+                                        debug!("Sending 'BlockResponseEnd {}' to {}", latest_height, peer_ip);
+                                        if let Err(error) = peer.send(message).await {
+                                            warn!("[BlockResponseEnd] {}", error);
+                                        }
+                                    }
                                 },
                                 Message::BlockResponse(block) => {
+                                    // Wait for a verification slot, which is prioritized for the canonical sync path.
+                                    let permit = E::block_verifications().acquire_for_sync().await;
                                     // Perform the deferred non-blocking deserialization of the block.
-                                    match block.deserialize().await {
+                                    match block.deserialize_with(peer.encoding).await {
                                         Ok(block) => {
+                                            // Ensure the block meets the expected difficulty target, prior to the expensive full verification.
+                                            // Note: If this ledger can not compute the target yet, the full verification decides.
+                                            let target = ledger_reader.get_expected_difficulty_target(block.height(), block.timestamp());
+                                            if matches!(target, Ok(target) if !verify_difficulty_only(block.header(), target)) {
+                                                let failure = format!("Block {} does not meet the expected difficulty target", block.height());
+                                                if let Err(error) = ledger_router.send(LedgerRequest::Failure(peer_ip, failure)).await {
+                                                    warn!("[Failure] {}", error);
+                                                }
+                                                continue;
+                                            }
+
                                             // TODO (howardwu): TEMPORARY - Remove this after testnet2.
                                             // Sanity check for a V12 ledger.
                                             if N::NETWORK_ID == 2
@@ -482,11 +1369,12 @@ impl<N: Network, E: Environment> Peer<N, E> {
                                                 && block.header().proof().is_hiding()
                                             {
                                                 warn!("Peer {} is not V12-compliant, proceeding to disconnect", peer_ip);
+                                                peer.close(Some(DisconnectReason::VersionMismatch)).await;
                                                 break;
                                             }
 
                                             // Route the `BlockResponse` to the ledger.
-                                            if let Err(error) = ledger_router.send(LedgerRequest::BlockResponse(peer_ip, block, prover_router.clone())).await {
+                                            if let Err(error) = ledger_router.send(LedgerRequest::BlockResponse(peer_ip, block, prover_router.clone(), Some(permit))).await {
                                                 warn!("[BlockResponse] {}", error);
                                             }
                                         },
@@ -499,34 +1387,54 @@ impl<N: Network, E: Environment> Peer<N, E> {
                                 Message::ChallengeRequest(..) | Message::ChallengeResponse(..) => {
                                     // Peer is not following the protocol.
                                     warn!("Peer {} is not following the protocol", peer_ip);
+                                    peer.close(None).await;
                                     break;
                                 },
-                                Message::Disconnect => break,
-                                Message::PeerRequest => {
+                                Message::Disconnect(reason) => {
+                                    debug!("Peer {} disconnected ({:?})", peer_ip, reason);
+                                    break;
+                                }
+                                Message::PeerRequest(listener_ip) => {
+                                    // Reflect the address the peer is reached at, as observed from the connection,
+                                    // along with its self-reported listener port, so that it may detect its external address.
+                                    let port = listener_ip.map(|listener_ip| listener_ip.port()).unwrap_or_else(|| peer_ip.port());
+                                    let requester_ip = SocketAddr::new(peer_ip.ip(), port);
                                     // Send a `PeerResponse` message.
-                                    if let Err(error) = peers_router.send(PeersRequest::SendPeerResponse(peer_ip)).await {
+                                    let request = PeersRequest::SendPeerResponse(peer_ip, Some(requester_ip));
+                                    if let Err(error) = peers_router.send(request).await {
                                         warn!("[PeerRequest] {}", error);
                                     }
                                 }
-                                Message::PeerResponse(peer_ips) => {
+                                Message::PeerResponse(requester_ip, peer_ips) => {
+                                    // Drop the unroutable peer IPs, and route a `Failure` to the ledger if there are too many.
+                                    let (peer_ips, failure) = Self::filter_peer_addresses(peer_ips);
+                                    if let Some(failure) = failure {
+                                        if let Err(error) = ledger_router.send(LedgerRequest::Failure(peer_ip, failure)).await {
+                                            warn!("[Failure] {}", error);
+                                        }
+                                    }
                                     // Adds the given peer IPs to the list of candidate peers.
-                                    if let Err(error) = peers_router.send(PeersRequest::ReceivePeerResponse(peer_ips)).await {
+                                    let reporter = requester_ip.map(|requester_ip| (peer_ip, requester_ip));
+                                    let request = PeersRequest::ReceivePeerResponse(reporter, peer_ips);
+                                    if let Err(error) = peers_router.send(request).await {
                                         warn!("[PeerResponse] {}", error);
                                     }
                                 }
-                                Message::Ping(version, fork_depth, node_type, status, block_hash, block_header) => {
-                                    // Ensure the message protocol version is not outdated.
-                                    if version < E::MESSAGE_VERSION {
-                                        warn!("Dropping {} on version {} (outdated)", peer_ip, version);
+                                Message::Ping(version, fork_depth, node_type, status, block_hash, block_header, mempool_size) => {
+                                    // Ensure the message protocol version is supported.
+                                    if !Self::is_version_supported(version) {
+                                        warn!("Dropping {} on version {} (unsupported)", peer_ip, version);
+                                        peer.close(Some(DisconnectReason::VersionMismatch)).await;
                                         break;
                                     }
-                                    // Ensure the maximum fork depth is correct.
-                                    if fork_depth != N::ALEO_MAXIMUM_FORK_DEPTH {
+                                    // Ensure the fork depth is no less than the maximum fork depth.
+                                    if fork_depth < N::ALEO_MAXIMUM_FORK_DEPTH {
                                         warn!("Dropping {} for an incorrect maximum fork depth of {}", peer_ip, fork_depth);
+                                        peer.close(Some(DisconnectReason::VersionMismatch)).await;
                                         break;
                                     }
                                     // Perform the deferred non-blocking deserialization of the block header.
-                                    match block_header.deserialize().await {
+                                    match block_header.deserialize_with(peer.encoding).await {
                                         Ok(block_header) => {
                                             // If this node is not a sync node and is syncing, the peer is a sync node, and this node is ahead, proceed to disconnect.
                                             if E::NODE_TYPE != NodeType::Sync
@@ -535,6 +1443,7 @@ impl<N: Network, E: Environment> Peer<N, E> {
                                                 && ledger_reader.latest_cumulative_weight() > block_header.cumulative_weight()
                                             {
                                                 trace!("Disconnecting from {} (ahead of sync node)", peer_ip);
+                                                peer.close(Some(DisconnectReason::NoReasonGiven)).await;
                                                 break;
                                             }
 
@@ -545,10 +1454,24 @@ impl<N: Network, E: Environment> Peer<N, E> {
                                                 && block_header.proof().is_hiding()
                                             {
                                                 warn!("Peer {} is not V12-compliant, proceeding to disconnect", peer_ip);
+                                                peer.close(Some(DisconnectReason::VersionMismatch)).await;
                                                 break;
                                             }
 
+                                            // Ensure the cumulative weight advertised by the peer is plausible, given its previous ping or handshake.
+                                            let is_fork = match ledger_reader.get_block_hash(block_header.height()) {
+                                                Ok(expected_block_hash) => Some(expected_block_hash != block_hash),
+                                                Err(_) => None,
+                                            };
+                                            if let Err(failure) = Self::check_cumulative_weight(peer.advertised_weight, &block_header, is_fork, &ledger_reader) {
+                                                warn!("[Ping] {} from {}", failure, peer_ip);
+                                                if let Err(error) = ledger_router.send(LedgerRequest::Failure(peer_ip, failure)).await {
+                                                    warn!("[Failure] {}", error);
+                                                }
+                                            }
+
                                             // Update the block header of the peer.
+                                            peer.advertised_weight = (block_header.height(), block_header.cumulative_weight());
                                             peer.block_header = block_header;
                                         }
                                         Err(error) => warn!("[Ping] {}", error),
@@ -560,22 +1483,38 @@ impl<N: Network, E: Environment> Peer<N, E> {
                                     peer.node_type = node_type;
                                     // Update the status of the peer.
                                     peer.status.update(status);
+                                    // Update the memory pool size of the peer.
+                                    peer.mempool_size = mempool_size;
+                                    // Update the fork depth of the peer, within the bound that this node honors.
+                                    peer.fork_depth = Self::bounded_fork_depth(fork_depth);
 
                                     // Determine if the peer is on a fork (or unknown).
                                     let is_fork = match ledger_reader.get_block_hash(peer.block_header.height()) {
                                         Ok(expected_block_hash) => Some(expected_block_hash != block_hash),
                                         Err(_) => None,
                                     };
-                                    // Send a `Pong` message to the peer.
-                                    if let Err(error) = peer.send(Message::Pong(is_fork, Data::Object(ledger_reader.latest_block_locators()))).await {
+                                    // Send a `Pong` message to the peer, with the block locators (or their diff) of this node.
+                                    let message = peer.pong(is_fork, &ledger_reader, &block_locator_cache).await;
+                                    if let Err(error) = peer.send(message).await {
                                         warn!("[Pong] {}", error);
                                     }
                                 },
                                 Message::Pong(is_fork, block_locators) => {
+                                    // Update the timestamp of the last pong.
+                                    peer.last_pong_at = Some(Instant::now());
+                                    // Measure the round-trip time of the `Ping`.
+                                    let rtt = peer.record_round_trip_time(&peers_router).await;
+
                                     // Perform the deferred non-blocking deserialization of block locators.
-                                    let request = match block_locators.deserialize().await {
-                                        // Route the `Pong` to the ledger.
-                                        Ok(block_locators) => LedgerRequest::Pong(peer_ip, peer.node_type, peer.status.get(), is_fork, block_locators),
+                                    let request = match block_locators.deserialize_with(peer.encoding).await {
+                                        Ok(block_locators) => {
+                                            // Store the block locators, as the base of the next block locators diff from the peer.
+                                            if peer.supports_block_locators_diff() {
+                                                peer.received_block_locators = Some(block_locators.clone());
+                                            }
+                                            // Route the `Pong` to the ledger.
+                                            peer.ledger_pong(is_fork, block_locators, rtt)
+                                        }
                                         // Route the `Failure` to the ledger.
                                         Err(error) => LedgerRequest::Failure(peer_ip, format!("{}", error)),
                                     };
@@ -585,23 +1524,46 @@ impl<N: Network, E: Environment> Peer<N, E> {
                                         warn!("[Pong] {}", error);
                                     }
 
-                                    // Spawn an asynchronous task for the `Ping` request.
-                                    let peers_router = peers_router.clone();
-                                    let ledger_reader = ledger_reader.clone();
-                                    E::tasks().append(task::spawn(async move {
-                                        // Sleep for the preset time before sending a `Ping` request.
-                                        tokio::time::sleep(Duration::from_secs(E::PING_SLEEP_IN_SECS)).await;
+                                    // Schedule the next `Ping` to the peer.
+                                    Self::schedule_ping(peer_ip, &peers_router, &ledger_reader);
+                                }
+                                Message::PongDiff(is_fork, block_locators_diff) => {
+                                    // Update the timestamp of the last pong.
+                                    peer.last_pong_at = Some(Instant::now());
+                                    // Measure the round-trip time of the `Ping`.
+                                    let rtt = peer.record_round_trip_time(&peers_router).await;
 
-                                        // Retrieve the latest ledger state.
-                                        let latest_block_hash = ledger_reader.latest_block_hash();
-                                        let latest_block_header = ledger_reader.latest_block_header();
+                                    // Reconstruct the block locators of the peer, from the block locators last received from it.
+                                    let base = peer.received_block_locators.take();
+                                    let block_locators = match (base, block_locators_diff.deserialize_with(peer.encoding).await) {
+                                        (Some(base), Ok(block_locators_diff)) => block_locators_diff.apply(&base),
+                                        (None, _) => Err(anyhow!("No block locators to apply the diff to")),
+                                        (_, Err(error)) => Err(error),
+                                    };
 
-                                        // Send a `Ping` request to the peer.
-                                        let message = Message::Ping(E::MESSAGE_VERSION, N::ALEO_MAXIMUM_FORK_DEPTH, E::NODE_TYPE, E::status().get(), latest_block_hash, Data::Object(latest_block_header));
-                                        if let Err(error) = peers_router.send(PeersRequest::MessageSend(peer_ip, message)).await {
-                                            warn!("[Ping] {}", error);
+                                    match block_locators {
+                                        Ok(block_locators) => {
+                                            peer.received_block_locators = Some(block_locators.clone());
+                                            // Route the `Pong` to the ledger.
+                                            if let Err(error) = ledger_router.send(peer.ledger_pong(is_fork, block_locators, rtt)).await {
+                                                warn!("[PongDiff] {}", error);
+                                            }
+                                        }
+                                        // Request the full block locators in the next `Pong`, as the states have drifted apart.
+                                        Err(error) => {
+                                            trace!("Requesting the full block locators of {}: {}", peer_ip, error);
+                                            if let Err(error) = peer.send(Message::PongResync).await {
+                                                warn!("[PongResync] {}", error);
+                                            }
                                         }
-                                    }));
+                                    }
+
+                                    // Schedule the next `Ping` to the peer.
+                                    Self::schedule_ping(peer_ip, &peers_router, &ledger_reader);
+                                }
+                                Message::PongResync => {
+                                    // Send the full block locators in the next `Pong`.
+                                    peer.sent_block_locators = None;
                                 }
                                 Message::UnconfirmedBlock(block_height, block_hash, block) => {
                                     // Drop the peer, if they have sent more than 5 unconfirmed blocks in the last 5 seconds.
@@ -612,9 +1574,26 @@ impl<N: Network, E: Environment> Peer<N, E> {
                                         if let Err(error) = peers_router.send(PeersRequest::PeerRestricted(peer_ip)).await {
                                             warn!("[PeerRestricted] {}", error);
                                         }
+                                        peer.close(Some(DisconnectReason::RateLimitExceeded)).await;
                                         break;
                                     }
 
+                                    // Discard an unconfirmed block far beyond the latest block height, before any further processing.
+                                    let latest_block_height = ledger_reader.latest_block_height();
+                                    if let Err(failure) = Self::check_unconfirmed_block_height(block_height, latest_block_height) {
+                                        trace!("Discarding 'UnconfirmedBlock {}' from {}", block_height, peer_ip);
+                                        // Route the `Failure` to the ledger.
+                                        if let Err(error) = ledger_router.send(LedgerRequest::Failure(peer_ip, failure)).await {
+                                            warn!("[Failure] {}", error);
+                                        }
+                                        continue;
+                                    }
+                                    // Discard an unconfirmed block from a peer on a very old fork.
+                                    if Self::is_on_stale_fork(block_height, latest_block_height, peer.fork_depth) {
+                                        trace!("Discarding 'UnconfirmedBlock {}' from {} (stale fork)", block_height, peer_ip);
+                                        continue;
+                                    }
+
                                     // Retrieve the last seen timestamp of the received block.
                                     let last_seen = peer.seen_inbound_blocks.entry(block_hash).or_insert(SystemTime::UNIX_EPOCH);
                                     let is_router_ready = last_seen.elapsed().unwrap().as_secs() > E::RADIO_SILENCE_IN_SECS;
@@ -625,7 +1604,6 @@ impl<N: Network, E: Environment> Peer<N, E> {
                                     // Ensure the unconfirmed block is at least within 2 blocks of the latest block height,
                                     // and no more that 2 blocks ahead of the latest block height.
                                     // If it is stale, skip the routing of this unconfirmed block to the ledger.
-                                    let latest_block_height = ledger_reader.latest_block_height();
                                     let lower_bound = latest_block_height.saturating_sub(2);
                                     let upper_bound = latest_block_height.saturating_add(2);
                                     let is_within_range = block_height >= lower_bound && block_height <= upper_bound;
@@ -637,14 +1615,35 @@ impl<N: Network, E: Environment> Peer<N, E> {
                                     if E::NODE_TYPE == NodeType::Beacon || E::NODE_TYPE == NodeType::Sync || !is_router_ready || !is_within_range || !is_node_ready {
                                         trace!("Skipping 'UnconfirmedBlock {}' from {}", block_height, peer_ip)
                                     } else {
+                                        // Wait for a verification slot, or drop the block if too many are queued.
+                                        let permit = match E::block_verifications().acquire_for_gossip().await {
+                                            Some(permit) => permit,
+                                            None => {
+                                                warn!("Dropping 'UnconfirmedBlock {}' from {} (too many pending verifications)", block_height, peer_ip);
+                                                // Route the `Failure` to the ledger.
+                                                if let Err(error) = ledger_router.send(LedgerRequest::Failure(peer_ip, "Too many pending block verifications".to_string())).await {
+                                                    warn!("[Failure] {}", error);
+                                                }
+                                                // Ask the peer to throttle its requests, as this node is overloaded.
+                                                if let Err(error) = peer.send(Message::Throttle(E::THROTTLE_DURATION_IN_SECS)).await {
+                                                    warn!("[Throttle] {}", error);
+                                                }
+                                                continue;
+                                            }
+                                        };
+
                                         // Perform the deferred non-blocking deserialization of the block.
-                                        let request = match block.deserialize().await {
+                                        let request = match block.deserialize_with(peer.encoding).await {
                                             // Ensure the claimed block height and block hash matches in the deserialized block.
-                                            Ok(block) => match block_height == block.height() && block_hash == block.hash() {
+                                            Ok(block) => match Self::check_unconfirmed_block(block_height, block_hash, &block) {
                                                 // Route the `UnconfirmedBlock` to the ledger.
-                                                true => LedgerRequest::UnconfirmedBlock(peer_ip, block, prover_router.clone()),
-                                                // Route the `Failure` to the ledger.
-                                                false => LedgerRequest::Failure(peer_ip, "Malformed UnconfirmedBlock message".to_string())
+                                                Ok(()) => LedgerRequest::UnconfirmedBlock(peer_ip, block, prover_router.clone(), Some(permit)),
+                                                Err(failure) => {
+                                                    // Forget the declared block hash, so that the block it names is not skipped as already seen.
+                                                    peer.seen_inbound_blocks.remove(&block_hash);
+                                                    // Route the `Failure` to the ledger.
+                                                    LedgerRequest::Failure(peer_ip, failure)
+                                                }
                                             },
                                             // Route the `Failure` to the ledger.
                                             Err(error) => LedgerRequest::Failure(peer_ip, format!("{}", error)),
@@ -665,6 +1664,7 @@ impl<N: Network, E: Environment> Peer<N, E> {
                                         if let Err(error) = peers_router.send(PeersRequest::PeerRestricted(peer_ip)).await {
                                             warn!("[PeerRestricted] {}", error);
                                         }
+                                        peer.close(Some(DisconnectReason::RateLimitExceeded)).await;
                                         break;
                                     }
 
@@ -698,7 +1698,7 @@ impl<N: Network, E: Environment> Peer<N, E> {
                                 Message::PoolRequest(share_difficulty, block_template) => {
                                     if E::NODE_TYPE != NodeType::Prover {
                                         trace!("Skipping 'PoolRequest' from {}", peer_ip);
-                                    } else if let Ok(block_template) = block_template.deserialize().await {
+                                    } else if let Ok(block_template) = block_template.deserialize_with(peer.encoding).await {
                                         if let Err(error) = prover_router.send(ProverRequest::PoolRequest(peer_ip, share_difficulty, block_template)).await {
                                             warn!("[PoolRequest] {}", error);
                                         }
@@ -706,36 +1706,1070 @@ impl<N: Network, E: Environment> Peer<N, E> {
                                         warn!("[PoolRequest] could not deserialize block template");
                                     }
                                 }
+                                Message::PoolRequestMulti(block_templates) => {
+                                    if E::NODE_TYPE != NodeType::Prover {
+                                        trace!("Skipping 'PoolRequestMulti' from {}", peer_ip);
+                                    } else {
+                                        // Perform the deferred non-blocking deserialization of the block templates.
+                                        let num_templates = block_templates.len();
+                                        let mut deserialized_templates = Vec::with_capacity(num_templates);
+                                        for (share_difficulty, block_template) in block_templates {
+                                            if let Ok(block_template) = block_template.deserialize_with(peer.encoding).await {
+                                                deserialized_templates.push((share_difficulty, block_template));
+                                            }
+                                        }
+
+                                        if deserialized_templates.len() != num_templates {
+                                            warn!("[PoolRequestMulti] could not deserialize block templates");
+                                        } else if let Err(error) = prover_router.send(ProverRequest::PoolRequestMulti(peer_ip, deserialized_templates)).await {
+                                            warn!("[PoolRequestMulti] {}", error);
+                                        }
+                                    }
+                                }
                                 Message::PoolResponse(address, nonce, proof) => {
                                     if E::NODE_TYPE != NodeType::Operator {
                                         trace!("Skipping 'PoolResponse' from {}", peer_ip);
-                                    } else if let Ok(proof) = proof.deserialize().await {
-                                        if let Err(error) = operator_router.send(OperatorRequest::PoolResponse(peer_ip, address, nonce, proof)).await {
-                                            warn!("[PoolResponse] {}", error);
+                                    } else {
+                                        match proof.deserialize_with(peer.encoding).await {
+                                            Ok(proof) => {
+                                                if let Err(error) = operator_router.send(OperatorRequest::PoolResponse(peer_ip, address, nonce, proof)).await {
+                                                    warn!("[PoolResponse] {}", error);
+                                                }
+                                            }
+                                            Err(error) => {
+                                                warn!("[PoolResponse] Prover {} ({}) sent a malformed proof", address, peer_ip);
+                                                // Reject the share from the prover.
+                                                if let Err(error) = peer.send(Message::PoolReject(PoolRejectReason::MalformedProof)).await {
+                                                    warn!("[PoolReject] {}", error);
+                                                }
+                                                // Route a `Failure` to the ledger.
+                                                let failure = format!("Prover {} sent a malformed proof: {}", address, error);
+                                                if let Err(error) = ledger_router.send(LedgerRequest::Failure(peer_ip, failure)).await {
+                                                    warn!("[Failure] {}", error);
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                                Message::PoolReject(reason) => {
+                                    if E::NODE_TYPE != NodeType::Prover {
+                                        trace!("Skipping 'PoolReject' from {}", peer_ip);
+                                    } else {
+                                        warn!("Operator {} rejected a share ({:?})", peer_ip, reason);
+                                    }
+                                }
+                                Message::MempoolRequest => {
+                                    // Ensure the peer does not request the memory pool more frequently than allowed.
+                                    let is_rate_limited = peer
+                                        .last_mempool_request
+                                        .map(|last_request| last_request.elapsed() < Duration::from_secs(E::MEMPOOL_REQUEST_INTERVAL_IN_SECS))
+                                        .unwrap_or(false);
+
+                                    if matches!(E::NODE_TYPE, NodeType::Beacon | NodeType::Sync | NodeType::Observer) {
+                                        trace!("Skipping 'MempoolRequest' from {}", peer_ip);
+                                    } else if is_rate_limited {
+                                        // Route a `Failure` to the ledger.
+                                        if let Err(error) = ledger_router.send(LedgerRequest::Failure(peer_ip, "Too frequent mempool requests".to_string())).await {
+                                            warn!("[Failure] {}", error);
                                         }
                                     } else {
-                                        warn!("[PoolResponse] could not deserialize proof");
+                                        peer.last_mempool_request = Some(Instant::now());
+                                        // Route the `MempoolRequest` to the prover.
+                                        if let Err(error) = prover_router.send(ProverRequest::MempoolRequest(peer_ip)).await {
+                                            warn!("[MempoolRequest] {}", error);
+                                        }
+                                    }
+                                }
+                                Message::MempoolResponse(transaction_ids) => {
+                                    if E::NODE_TYPE == NodeType::Beacon || E::NODE_TYPE == NodeType::Sync {
+                                        trace!("Skipping 'MempoolResponse' from {}", peer_ip);
+                                    } else if let Err(error) = prover_router.send(ProverRequest::MempoolResponse(peer_ip, transaction_ids)).await {
+                                        warn!("[MempoolResponse] {}", error);
+                                    }
+                                }
+                                Message::TransactionRequest(transaction_ids) => {
+                                    if matches!(E::NODE_TYPE, NodeType::Beacon | NodeType::Sync | NodeType::Observer) {
+                                        trace!("Skipping 'TransactionRequest' from {}", peer_ip);
+                                    } else if let Err(error) = prover_router.send(ProverRequest::TransactionRequest(peer_ip, transaction_ids)).await {
+                                        warn!("[TransactionRequest] {}", error);
+                                    }
+                                }
+                                Message::Throttle(duration_in_secs) => {
+                                    let duration = Duration::from_secs(duration_in_secs as u64);
+                                    let maximum_duration = Duration::from_secs(E::MAXIMUM_THROTTLE_DURATION_IN_SECS as u64);
+                                    // Honor the throttle for at most the maximum duration, so the peer can not silence this node.
+                                    match peer.throttle.request(Instant::now(), duration, maximum_duration) {
+                                        Some(duration) => debug!("Throttling requests to {} for {} seconds", peer_ip, duration.as_secs()),
+                                        None => trace!("Ignoring 'Throttle' from {}", peer_ip),
+                                    }
+                                }
+                                Message::BlockUnavailable(start_block_height, end_block_height) => {
+                                    debug!("Blocks {} to {} are unavailable from {}", start_block_height, end_block_height, peer_ip);
+                                    // Route the unavailable blocks to the ledger, to request them from a different peer.
+                                    let request = LedgerRequest::BlockUnavailable(peer_ip, start_block_height, end_block_height);
+                                    if let Err(error) = ledger_router.send(request).await {
+                                        warn!("[BlockUnavailable] {}", error);
+                                    }
+                                }
+                                Message::BlockResponseEnd(block_height) => {
+                                    debug!("No blocks beyond {} are available from {}", block_height, peer_ip);
+                                    // Route the highest available block height to the ledger, to complete the block requests beyond it.
+                                    if let Err(error) = ledger_router.send(LedgerRequest::BlockResponseEnd(peer_ip, block_height)).await {
+                                        warn!("[BlockResponseEnd] {}", error);
                                     }
                                 }
-                                Message::Unused(_) => break, // Peer is not following the protocol.
+                                Message::Unused(_) => {
+                                    // Peer is not following the protocol.
+                                    peer.close(None).await;
+                                    break;
+                                }
                             }
                         }
                         // An error occurred.
-                        Some(Err(error)) => error!("Failed to read message from {}: {}", peer_ip, error),
+                        Some(Err(error)) => {
+                            // Penalize a peer that relays an oversized transaction, which was rejected before deserialization.
+                            if let Some(oversized) = error.get_ref().and_then(|error| error.downcast_ref::<OversizedTransaction>()) {
+                                let failure = format!("Relayed an oversized transaction ({} bytes)", oversized.0);
+                                if let Err(error) = ledger_router.send(LedgerRequest::Failure(peer_ip, failure)).await {
+                                    warn!("[Failure] {}", error);
+                                }
+                            }
+                            let error = format!("Failed to read message from {}: {}", peer_ip, error);
+                            if let Some(line) = peer.error_log.check(Instant::now(), &error) {
+                                error!("{}", line);
+                            }
+                        }
                         // The stream has been disconnected.
                         None => break,
                     },
                 }
             }
 
-            // When this is reached, it means the peer has disconnected.
-            // Route a `Disconnect` to the ledger.
-            if let Err(error) = ledger_router
-                .send(LedgerRequest::Disconnect(peer_ip, "peer has disconnected".to_string()))
-                .await
-            {
-                warn!("[Peer::Disconnect] {}", error);
+            // Report the errors that were suppressed since they were last logged.
+            for line in peer.error_log.drain(Instant::now()) {
+                warn!("{}", line);
+            }
+
+            // Write any coalesced outbound messages before closing the connection.
+            if peer.coalesce_deadline.is_some() {
+                let _ = timeout(Duration::from_millis(E::CONNECTION_TIMEOUT_IN_MILLIS), peer.flush()).await;
+            }
+
+            // Close a recycled connection once the peer has had time to register the connection that replaces it.
+            if is_recycled {
+                tokio::time::sleep(Duration::from_millis(E::CONNECTION_TIMEOUT_IN_MILLIS)).await;
+                peer.close(Some(DisconnectReason::NoReasonGiven)).await;
+            }
+
+            // When this is reached, it means the connection is closed.
+            // Route a `PeerClosed` to the peers, which disconnects the peer, unless this connection was replaced.
+            let request = PeersRequest::PeerClosed(peer_ip, peer.outbound_router.clone(), ledger_router, operator_router);
+            if let Err(error) = peers_router.send(request).await {
+                warn!("[PeerClosed] {}", error);
             }
         }));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        environment::{Client, Observer},
+        helpers::DefaultFeePolicy,
+    };
+    use snarkos_storage::{storage::rocksdb::RocksDB, LedgerState};
+    use snarkvm::{dpc::testnet2::Testnet2, prelude::UniformRand};
+
+    use std::sync::atomic::AtomicBool;
+
+    #[test]
+    fn test_is_mempool_full() {
+        type P = Peer<Testnet2, Client<Testnet2>>;
+
+        // Ensure a peer reporting a memory pool within the threshold is relayed to.
+        assert!(!P::is_mempool_full(0));
+        assert!(!P::is_mempool_full(Client::<Testnet2>::MAX_PEER_MEMPOOL_SIZE));
+
+        // Ensure a peer reporting a memory pool above the threshold is not relayed to.
+        assert!(P::is_mempool_full(Client::<Testnet2>::MAX_PEER_MEMPOOL_SIZE + 1));
+    }
+
+    #[test]
+    fn test_defer_request() {
+        type P = Peer<Testnet2, Client<Testnet2>>;
+        let mut deferred_requests = VecDeque::new();
+
+        // Ensure the requests to a throttled peer are deferred, rather than dropped.
+        assert!(P::defer_request(&mut deferred_requests, Message::BlockRequest(0, 249)));
+        assert!(P::defer_request(&mut deferred_requests, Message::BlockRequest(250, 499)));
+        assert!(P::defer_request(&mut deferred_requests, Message::PeerRequest(None)));
+
+        // Ensure an identical request is only deferred once.
+        assert!(!P::defer_request(&mut deferred_requests, Message::BlockRequest(0, 249)));
+        assert!(!P::defer_request(&mut deferred_requests, Message::PeerRequest(None)));
+        assert_eq!(3, deferred_requests.len());
+
+        // Ensure the number of deferred requests is bounded.
+        for block_height in 0..Client::<Testnet2>::MAXIMUM_DEFERRED_REQUESTS as u32 {
+            P::defer_request(
+                &mut deferred_requests,
+                Message::BlockRequest(1000 + block_height, 1000 + block_height),
+            );
+        }
+        assert_eq!(Client::<Testnet2>::MAXIMUM_DEFERRED_REQUESTS, deferred_requests.len());
+    }
+
+    #[derive(Clone, Debug, Default)]
+    struct PrunedNode;
+
+    #[rustfmt::skip]
+    impl Environment for PrunedNode {
+        type Network = Testnet2;
+        type FeePolicy = DefaultFeePolicy;
+        const NODE_TYPE: NodeType = NodeType::Client;
+        const PRUNE_DEPTH: u32 = 100;
+        const MINIMUM_NUMBER_OF_PEERS: usize = 2;
+        const MAXIMUM_NUMBER_OF_PEERS: usize = 21;
+    }
+
+    #[test]
+    fn test_pruned_block_response() {
+        // Ensure a node at height 200 with a prune depth of 100 reports the pruned blocks as unavailable.
+        match Peer::<Testnet2, PrunedNode>::pruned_block_response(0, 50, 200) {
+            Some(Message::BlockUnavailable(start_block_height, end_block_height)) => {
+                assert_eq!((0, 50), (start_block_height, end_block_height))
+            }
+            _ => panic!("Expected a 'BlockUnavailable' response"),
+        }
+
+        // Ensure the blocks within the prune depth are served.
+        assert!(Peer::<Testnet2, PrunedNode>::pruned_block_response(100, 150, 200).is_none());
+        assert!(Peer::<Testnet2, PrunedNode>::pruned_block_response(0, 50, 100).is_none());
+
+        // Ensure a node without pruning serves all blocks.
+        assert!(Peer::<Testnet2, Client<Testnet2>>::pruned_block_response(0, 50, 200).is_none());
+    }
+
+    #[derive(Clone, Debug, Default)]
+    struct RecyclingNode;
+
+    #[rustfmt::skip]
+    impl Environment for RecyclingNode {
+        type Network = Testnet2;
+        type FeePolicy = DefaultFeePolicy;
+        const NODE_TYPE: NodeType = NodeType::Client;
+        const MAXIMUM_CONNECTION_AGE_IN_SECS: u64 = 3600;
+        const MINIMUM_NUMBER_OF_PEERS: usize = 2;
+        const MAXIMUM_NUMBER_OF_PEERS: usize = 21;
+    }
+
+    #[test]
+    fn test_maximum_connection_age() {
+        let rng = &mut thread_rng();
+
+        for _ in 0..100 {
+            // Ensure outbound connections are recycled within the staggered age range.
+            let maximum_age = Peer::<Testnet2, RecyclingNode>::maximum_connection_age(ConnectionDirection::Outbound, rng).unwrap();
+            assert!(maximum_age >= Duration::from_secs(3600));
+            assert!(maximum_age <= Duration::from_secs(3960));
+        }
+
+        // Ensure inbound connections are never recycled.
+        assert!(Peer::<Testnet2, RecyclingNode>::maximum_connection_age(ConnectionDirection::Inbound, rng).is_none());
+        // Ensure connections are never recycled by default.
+        assert!(Peer::<Testnet2, Client<Testnet2>>::maximum_connection_age(ConnectionDirection::Outbound, rng).is_none());
+    }
+
+    #[test]
+    fn test_pong_silence() {
+        type P = Peer<Testnet2, Client<Testnet2>>;
+
+        let radio_silence = Duration::from_secs(Client::<Testnet2>::RADIO_SILENCE_IN_SECS);
+        let connected_at = Instant::now();
+        let after_radio_silence = connected_at + radio_silence + Duration::from_secs(1);
+
+        // Ensure a peer is not silent within the radio silence period, even before its first `Pong`.
+        assert!(!P::is_pong_silent(None, connected_at, connected_at + radio_silence));
+        // Ensure a peer that responds to all messages except `Pong` is silent past the radio silence period.
+        assert!(P::is_pong_silent(None, connected_at, after_radio_silence));
+
+        // Ensure a peer that sent a recent `Pong` is not silent.
+        let last_pong_at = connected_at + Duration::from_secs(10);
+        assert!(!P::is_pong_silent(Some(last_pong_at), connected_at, after_radio_silence));
+        // Ensure a peer whose last `Pong` predates the radio silence period is silent.
+        let after_pong_silence = last_pong_at + radio_silence + Duration::from_secs(1);
+        assert!(P::is_pong_silent(Some(last_pong_at), connected_at, after_pong_silence));
+    }
+
+    #[test]
+    fn test_future_block_request() {
+        type P = Peer<Testnet2, Client<Testnet2>>;
+
+        // Ensure a request within the latest block height is served as is.
+        assert_eq!(Some((50, 100)), P::clamp_block_request(50, 100, 200));
+        // Ensure a request past the latest block height is clamped to it.
+        assert_eq!(Some((150, 200)), P::clamp_block_request(150, 250, 200));
+        // Ensure a request starting beyond the latest block height yields no blocks.
+        assert_eq!(None, P::clamp_block_request(201, 250, 200));
+        assert_eq!(None, P::clamp_block_request(100_000, 100_050, 200));
+
+        // Ensure only a request far beyond the latest block height is penalized.
+        let maximum_block_request = Client::<Testnet2>::MAXIMUM_BLOCK_REQUEST;
+        assert!(!P::is_far_future_block_request(201, 200));
+        assert!(!P::is_far_future_block_request(200 + maximum_block_request, 200));
+        assert!(P::is_far_future_block_request(201 + maximum_block_request, 200));
+    }
+
+    #[test]
+    fn test_block_response_end() {
+        type P = Peer<Testnet2, Client<Testnet2>>;
+
+        // Ensure a request ending at or before the latest block height is not terminated early.
+        assert!(P::block_response_end(100, 200).is_none());
+        assert!(P::block_response_end(200, 200).is_none());
+
+        // Ensure a request ending just past the latest block height reports the latest block height.
+        match P::block_response_end(201, 200) {
+            Some(Message::BlockResponseEnd(block_height)) => assert_eq!(200, block_height),
+            _ => panic!("Expected a 'BlockResponseEnd' response"),
+        }
+        // Ensure a request starting beyond the latest block height reports the latest block height.
+        match P::block_response_end(250, 200) {
+            Some(Message::BlockResponseEnd(block_height)) => assert_eq!(200, block_height),
+            _ => panic!("Expected a 'BlockResponseEnd' response"),
+        }
+    }
+
+    #[test]
+    fn test_unconfirmed_block_height() {
+        type P = Peer<Testnet2, Client<Testnet2>>;
+
+        // Ensure an unconfirmed block far beyond the latest block height is a failure.
+        assert!(P::check_unconfirmed_block_height(u32::MAX, 100).is_err());
+        let maximum_block_request = Client::<Testnet2>::MAXIMUM_BLOCK_REQUEST;
+        assert!(P::check_unconfirmed_block_height(101 + maximum_block_request, 100).is_err());
+        assert!(P::check_unconfirmed_block_height(100 + maximum_block_request, 100).is_ok());
+        assert!(P::check_unconfirmed_block_height(101, 100).is_ok());
+
+        // Ensure only an unconfirmed block beyond the maximum fork depth is on a stale fork.
+        let maximum_fork_depth = <Testnet2 as Network>::ALEO_MAXIMUM_FORK_DEPTH;
+        let latest_block_height = maximum_fork_depth + 100;
+        assert!(!P::is_on_stale_fork(latest_block_height, latest_block_height, maximum_fork_depth));
+        assert!(!P::is_on_stale_fork(100, latest_block_height, maximum_fork_depth));
+        assert!(P::is_on_stale_fork(99, latest_block_height, maximum_fork_depth));
+        assert!(!P::is_on_stale_fork(0, 100, maximum_fork_depth));
+
+        // Ensure a peer advertising a deeper fork depth has its deeper forks tolerated.
+        assert!(!P::is_on_stale_fork(99, latest_block_height, maximum_fork_depth + 1));
+        assert!(P::is_on_stale_fork(98, latest_block_height, maximum_fork_depth + 1));
+
+        // Ensure a peer advertising an unbounded fork depth still has its stale forks filtered.
+        let fork_depth = P::bounded_fork_depth(u32::MAX);
+        assert_eq!(Client::<Testnet2>::MAXIMUM_ADVERTISED_FORK_DEPTH, fork_depth);
+        assert!(P::is_on_stale_fork(0, u32::MAX, fork_depth));
+        assert_eq!(maximum_fork_depth, P::bounded_fork_depth(maximum_fork_depth));
+    }
+
+    #[test]
+    fn test_unconfirmed_block_mismatch() {
+        type P = Peer<Testnet2, Client<Testnet2>>;
+
+        let block = Testnet2::genesis_block();
+        assert!(P::check_unconfirmed_block(block.height(), block.hash(), block).is_ok());
+
+        // Ensure a block with a different declared block hash is a failure.
+        let block_hash = <Testnet2 as Network>::BlockHash::rand(&mut thread_rng());
+        let failure = P::check_unconfirmed_block(block.height(), block_hash, block).unwrap_err();
+        assert!(failure.contains(&block_hash.to_string()));
+
+        // Ensure a block with a different declared block height is a failure.
+        assert!(P::check_unconfirmed_block(block.height() + 1, block.hash(), block).is_err());
+    }
+
+    #[test]
+    fn test_peer_address_filter() {
+        type P = Peer<Testnet2, Client<Testnet2>>;
+        let is_valid = |addr: &str| P::is_valid_peer_address(&addr.parse().unwrap());
+
+        // Ensure routable addresses, including loopback addresses for local networks, are valid.
+        assert!(is_valid("144.126.219.193:4132"));
+        assert!(is_valid("127.0.0.1:4132"));
+        assert!(is_valid("[2606:4700::1111]:4132"));
+
+        // Ensure unspecified addresses are filtered.
+        assert!(!is_valid("0.0.0.0:4132"));
+        assert!(!is_valid("[::]:4132"));
+
+        // Ensure multicast addresses are filtered.
+        assert!(!is_valid("224.0.0.1:4132"));
+        assert!(!is_valid("239.255.255.250:4132"));
+        assert!(!is_valid("[ff02::1]:4132"));
+
+        // Ensure the broadcast address is filtered.
+        assert!(!is_valid("255.255.255.255:4132"));
+
+        // Ensure documentation addresses are filtered.
+        assert!(!is_valid("192.0.2.1:4132"));
+        assert!(!is_valid("198.51.100.1:4132"));
+        assert!(!is_valid("203.0.113.1:4132"));
+        assert!(!is_valid("[2001:db8::1]:4132"));
+    }
+
+    #[test]
+    fn test_filter_peer_addresses() {
+        type P = Peer<Testnet2, Client<Testnet2>>;
+        let valid: SocketAddr = "144.126.219.193:4132".parse().unwrap();
+        let invalid: SocketAddr = "0.0.0.0:4132".parse().unwrap();
+
+        // Ensure invalid addresses are dropped, and a few are tolerated.
+        let maximum_invalid = Client::<Testnet2>::MAXIMUM_INVALID_PEER_ADDRESSES;
+        let mut peer_ips = vec![invalid; maximum_invalid];
+        peer_ips.push(valid);
+        assert_eq!((vec![valid], None), P::filter_peer_addresses(peer_ips));
+
+        // Ensure a peer that sends too many invalid addresses is penalized.
+        let mut peer_ips = vec![invalid; maximum_invalid + 1];
+        peer_ips.push(valid);
+        let (peer_ips, failure) = P::filter_peer_addresses(peer_ips);
+        assert_eq!(vec![valid], peer_ips);
+        assert!(failure.is_some());
+    }
+
+    #[derive(Clone, Debug, Default)]
+    struct MinimumVersionNode;
+
+    #[rustfmt::skip]
+    impl Environment for MinimumVersionNode {
+        type Network = Testnet2;
+        type FeePolicy = DefaultFeePolicy;
+        const NODE_TYPE: NodeType = NodeType::Client;
+        const MESSAGE_VERSION: u32 = 14;
+        const MINIMUM_PEER_PROTOCOL_VERSION: u32 = 12;
+        const MINIMUM_NUMBER_OF_PEERS: usize = 2;
+        const MAXIMUM_NUMBER_OF_PEERS: usize = 21;
+    }
+
+    #[test]
+    fn test_minimum_peer_protocol_version() {
+        type P = Peer<Testnet2, MinimumVersionNode>;
+
+        // Ensure a peer advertising a version below the minimum is rejected.
+        assert!(!P::is_version_supported(11));
+        // Ensure a peer advertising the minimum version, or a newer one, is accepted,
+        // even if it is older than the version of this node.
+        assert!(P::is_version_supported(12));
+        assert!(P::is_version_supported(13));
+        assert!(P::is_version_supported(15));
+
+        // Ensure a peer on an outdated version is rejected by default.
+        type D = Peer<Testnet2, Client<Testnet2>>;
+        assert!(!D::is_version_supported(Client::<Testnet2>::MESSAGE_VERSION - 1));
+        assert!(D::is_version_supported(Client::<Testnet2>::MESSAGE_VERSION));
+    }
+
+    #[test]
+    fn test_cumulative_weight_decreasing() {
+        type P = Peer<Testnet2, Client<Testnet2>>;
+
+        // Ensure a growing sequence of cumulative weights is accepted.
+        let pings = [(1, 100), (2, 200), (2, 200), (5, 500)];
+        for window in pings.windows(2) {
+            assert!(P::check_cumulative_weight_transition(window[0], window[1], 100, false).is_ok());
+        }
+
+        // Ensure a decrease in the cumulative weight is flagged, even without a bound on the weight per block.
+        let pings = [(1, 100), (2, 200), (3, 150)];
+        let failures = pings
+            .windows(2)
+            .filter(|window| P::check_cumulative_weight_transition(window[0], window[1], u128::MAX, false).is_err())
+            .count();
+        assert_eq!(1, failures);
+
+        // Ensure a decrease in the cumulative weight is accepted, if the chain of the peer was reorganized.
+        assert!(P::check_cumulative_weight_transition((3, 300), (3, 250), 100, true).is_ok());
+    }
+
+    #[test]
+    fn test_cumulative_weight_baseline_and_reorganization() {
+        type P = Peer<Testnet2, Client<Testnet2>>;
+
+        let ledger_reader: LedgerReader<Testnet2> = Arc::new(genesis_ledger());
+        let genesis_header = Testnet2::genesis_block().header().clone();
+        let genesis_weight = genesis_header.cumulative_weight();
+
+        // Ensure the first ping is checked against the cumulative weight of the handshake, rather than the genesis block.
+        let handshake_weight = genesis_weight + 1;
+        assert!(P::check_cumulative_weight((0, handshake_weight), &genesis_header, Some(false), &ledger_reader).is_err());
+        assert!(P::check_cumulative_weight((0, genesis_weight), &genesis_header, Some(false), &ledger_reader).is_ok());
+
+        // Ensure a decrease is accepted, if the block header of the peer is on a fork, or below its previous height.
+        assert!(P::check_cumulative_weight((0, handshake_weight), &genesis_header, Some(true), &ledger_reader).is_ok());
+        assert!(P::check_cumulative_weight((1, handshake_weight), &genesis_header, Some(false), &ledger_reader).is_ok());
+        assert!(P::check_cumulative_weight((0, handshake_weight), &genesis_header, None, &ledger_reader).is_err());
+    }
+
+    #[test]
+    fn test_cumulative_weight_implausible_jump() {
+        type P = Peer<Testnet2, Client<Testnet2>>;
+
+        // Bound the weight per block as a synced node at the genesis difficulty would.
+        let genesis_header = Testnet2::genesis_block().header();
+        let genesis_weight = u64::MAX / genesis_header.difficulty_target();
+        let maximum = genesis_weight as u128 * Client::<Testnet2>::CUMULATIVE_WEIGHT_TOLERANCE;
+        assert!(maximum < u64::MAX as u128);
+
+        // Ensure an increase of the maximum weight per block gained is accepted.
+        assert!(P::check_cumulative_weight_transition((1, 0), (2, maximum), maximum, false).is_ok());
+        assert!(P::check_cumulative_weight_transition((1, 0), (11, 10 * maximum), maximum, false).is_ok());
+
+        // Ensure an increase beyond the maximum weight per block gained is flagged.
+        assert!(P::check_cumulative_weight_transition((1, 0), (2, maximum + 1), maximum, false).is_err());
+        assert!(P::check_cumulative_weight_transition((1, 0), (11, 10 * maximum + 1), maximum, false).is_err());
+        // Ensure a weight increase with a lower height is bounded as a single block.
+        assert!(P::check_cumulative_weight_transition((10, 0), (5, maximum + 1), maximum, false).is_err());
+        // Ensure a peer advertising the largest possible weight per block is flagged.
+        assert!(P::check_cumulative_weight_transition((1, 0), (2, u64::MAX as u128), maximum, false).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_exchange_hello() {
+        type P = Peer<Testnet2, Client<Testnet2>>;
+
+        let all = FeatureFlags::COMPRESSES_ZSTD | FeatureFlags::BATCHES_MESSAGES;
+
+        let (outbound, inbound) = (ConnectionDirection::Outbound, ConnectionDirection::Inbound);
+
+        // Ensure two compression-capable nodes agree on compression.
+        let (mut a, mut b) = tokio::io::duplex(16);
+        let (features_a, features_b) = tokio::join!(P::exchange_hello(&mut a, all, outbound), P::exchange_hello(&mut b, all, inbound));
+        assert_eq!((all, None), features_a.unwrap());
+        assert_eq!((all, None), features_b.unwrap());
+
+        // Ensure a node that lacks compression leaves the connection uncompressed on both ends.
+        let (mut a, mut b) = tokio::io::duplex(16);
+        let (features_a, features_b) = tokio::join!(
+            P::exchange_hello(&mut a, all, outbound),
+            P::exchange_hello(&mut b, FeatureFlags::BATCHES_MESSAGES, inbound)
+        );
+        assert_eq!((FeatureFlags::BATCHES_MESSAGES, None), features_a.unwrap());
+        assert_eq!((FeatureFlags::BATCHES_MESSAGES, None), features_b.unwrap());
+
+        // Ensure a peer that sends its handshake without a `Hello` agrees on no features, and is sent no `Hello`.
+        for direction in [outbound, inbound] {
+            let (mut a, mut b) = tokio::io::duplex(16);
+            b.write_all(&[0x26]).await.unwrap();
+            assert_eq!(
+                (FeatureFlags::empty(), Some(0x26)),
+                P::exchange_hello(&mut a, all, direction).await.unwrap()
+            );
+            drop(a);
+            assert_eq!(0, b.read(&mut [0u8; 1]).await.unwrap());
+        }
+
+        // Ensure a peer that closes the connection before its `Hello` is rejected.
+        let (mut a, b) = tokio::io::duplex(16);
+        drop(b);
+        assert!(P::exchange_hello(&mut a, all, outbound).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown() {
+        type P = Peer<Testnet2, Client<Testnet2>>;
+
+        /// Reads the messages on the given stream until EOF, and returns the last one.
+        async fn last_message(stream: tokio::io::DuplexStream) -> Option<Message<Testnet2, Client<Testnet2>>> {
+            let mut socket = Framed::new(stream, MessageCodec::<Testnet2, Client<Testnet2>>::default());
+            let mut last_message = None;
+            while let Some(message) = socket.next().await {
+                last_message = Some(message.expect("Failed to decode the message"));
+            }
+            last_message
+        }
+
+        // Ensure a graceful drop sends a `Disconnect` after the pending messages, as the last frame before EOF.
+        let (a, b) = tokio::io::duplex(1024);
+        let mut socket = Framed::new(a, MessageCodec::<Testnet2, Client<Testnet2>>::default());
+        socket.feed(Message::PongResync).await.unwrap();
+        P::shutdown(&mut socket, Some(DisconnectReason::VersionMismatch)).await.unwrap();
+        match last_message(b).await {
+            Some(Message::Disconnect(reason)) => assert_eq!(DisconnectReason::VersionMismatch, reason),
+            _ => panic!("Expected a 'Disconnect' before EOF"),
+        }
+
+        // Ensure a forced drop is an EOF without a frame.
+        let (a, b) = tokio::io::duplex(1024);
+        let mut socket = Framed::new(a, MessageCodec::<Testnet2, Client<Testnet2>>::default());
+        P::shutdown(&mut socket, None).await.unwrap();
+        assert!(last_message(b).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_handshake_protocol_violation() {
+        type P = Peer<Testnet2, Client<Testnet2>>;
+
+        /// Performs the handshake with a peer that answers the challenge request with the given frame, and returns
+        /// the result of the handshake and the reason of the last `Disconnect` received by the peer before EOF.
+        async fn handshake_with(frame: Vec<u8>) -> (Result<()>, Option<DisconnectReason>) {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let peer_stream = TcpStream::connect(listener.local_addr().unwrap()).await.unwrap();
+            let (node_stream, _) = listener.accept().await.unwrap();
+
+            let mut node_socket = Framed::new(node_stream, MessageCodec::<Testnet2, Client<Testnet2>>::default());
+            let node = async move {
+                let local_ip = "127.0.0.1:4130".parse().unwrap();
+                P::handshake(&mut node_socket, local_ip, 0, 0, &[]).await.map(|_| ())
+            };
+
+            let mut peer_socket = Framed::new(peer_stream, MessageCodec::<Testnet2, Client<Testnet2>>::default());
+            let peer = async move {
+                assert!(matches!(peer_socket.next().await, Some(Ok(Message::ChallengeRequest(..)))));
+                let mut bytes = (frame.len() as u32).to_le_bytes().to_vec();
+                bytes.extend_from_slice(&frame);
+                peer_socket.get_mut().write_all(&bytes).await.unwrap();
+
+                let mut last_message = None;
+                while let Some(Ok(message)) = peer_socket.next().await {
+                    last_message = Some(message);
+                }
+                match last_message {
+                    Some(Message::Disconnect(reason)) => Some(reason),
+                    _ => None,
+                }
+            };
+
+            // Ensure the handshake ends immediately, without waiting for further messages.
+            match timeout(Duration::from_secs(1), async { tokio::join!(node, peer) }).await {
+                Ok(result) => result,
+                Err(_) => panic!("The handshake did not end immediately"),
+            }
+        }
+
+        /// Returns the frame of the given message.
+        fn frame(message: Message<Testnet2, Client<Testnet2>>) -> Vec<u8> {
+            let mut frame = Vec::new();
+            message.serialize_into(&mut frame).unwrap();
+            frame
+        }
+
+        // Ensure an unknown message ID as the first frame is a protocol violation.
+        let (result, reason) = handshake_with(u16::MAX.to_le_bytes().to_vec()).await;
+        assert!(result.unwrap_err().downcast_ref::<ProtocolViolation>().is_some());
+        assert_eq!(Some(DisconnectReason::ProtocolViolation), reason);
+
+        // Ensure a valid message out of the handshake sequence is a protocol violation.
+        let (result, reason) = handshake_with(frame(Message::PeerRequest(None))).await;
+        assert!(result.unwrap_err().downcast_ref::<ProtocolViolation>().is_some());
+        assert_eq!(Some(DisconnectReason::ProtocolViolation), reason);
+
+        // Ensure a peer that disconnects during the handshake is not penalized.
+        let (result, reason) = handshake_with(frame(Message::Disconnect(DisconnectReason::TooManyPeers))).await;
+        assert!(result.unwrap_err().downcast_ref::<ProtocolViolation>().is_none());
+        assert_eq!(None, reason);
+    }
+
+    /// Initializes a new ledger at the genesis block.
+    fn genesis_ledger() -> LedgerState<Testnet2> {
+        let path = tempfile::tempdir().expect("Failed to open temporary directory").into_path();
+        LedgerState::open_writer::<RocksDB, _>(path).expect("Failed to initialize ledger")
+    }
+
+    /// Returns a `Ping` from a peer at the genesis block, with the given memory pool size.
+    fn genesis_ping<E: Environment>(mempool_size: u32) -> Message<Testnet2, E> {
+        let genesis_block = Testnet2::genesis_block();
+        Message::Ping(
+            E::MESSAGE_VERSION,
+            Testnet2::ALEO_MAXIMUM_FORK_DEPTH,
+            NodeType::Client,
+            State::Ready,
+            genesis_block.hash(),
+            Data::Object(genesis_block.header().clone()),
+            mempool_size,
+        )
+    }
+
+    ///
+    /// A peer that is connected to the `Peer` handler of a node, along with the requests that the handler routes.
+    ///
+    struct TestPeer<E: Environment> {
+        /// The socket of the peer, which supports the connection features agreed in its `Hello`, if any.
+        socket: Framed<TcpStream, MessageCodec<Testnet2, E>>,
+        /// The router of the handler, which routes messages outbound to the peer.
+        outbound_router: OutboundRouter<Testnet2, E>,
+        /// The requests that the handler routes to the peers.
+        _peers_handler: mpsc::Receiver<PeersRequest<Testnet2, E>>,
+        /// The requests that the handler routes to the ledger.
+        ledger_handler: mpsc::Receiver<LedgerRequest<Testnet2>>,
+        /// The requests that the handler routes to the prover.
+        _prover_handler: mpsc::Receiver<ProverRequest<Testnet2>>,
+        /// The requests that the handler routes to the operator.
+        _operator_handler: mpsc::Receiver<OperatorRequest<Testnet2>>,
+    }
+
+    impl<E: Environment> TestPeer<E> {
+        /// Connects to a new `Peer` handler of a node with the given ledger, and completes the handshake with it.
+        async fn connect(ledger_reader: LedgerReader<Testnet2>) -> Self {
+            Self::connect_with_features(ledger_reader, FeatureFlags::empty()).await
+        }
+
+        ///
+        /// Connects to a new `Peer` handler of a node with the given ledger, and completes the handshake with it.
+        /// If any connection features are given, they are agreed with the handler in a `Hello` before the handshake.
+        ///
+        async fn connect_with_features(ledger_reader: LedgerReader<Testnet2>, features: FeatureFlags) -> Self {
+            let (peers_router, mut peers_handler) = mpsc::channel(1024);
+            let (ledger_router, ledger_handler) = mpsc::channel(1024);
+            let (prover_router, prover_handler) = mpsc::channel(1024);
+            let (operator_router, operator_handler) = mpsc::channel(1024);
+
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let mut peer_stream = TcpStream::connect(listener.local_addr().unwrap()).await.unwrap();
+            let (node_stream, _) = listener.accept().await.unwrap();
+
+            Peer::<Testnet2, E>::handler(
+                node_stream,
+                "127.0.0.1:4130".parse().unwrap(),
+                0,
+                &peers_router,
+                ledger_reader,
+                ledger_router,
+                prover_router,
+                operator_router,
+                Default::default(),
+                Default::default(),
+                vec![],
+                None,
+            )
+            .await;
+
+            // Agree on the connection features with the handler, if any are given.
+            let features = match features == FeatureFlags::empty() {
+                true => features,
+                false => {
+                    peer_stream.write_all(&[features.to_hello()]).await.unwrap();
+                    let node_features = FeatureFlags::from_hello(peer_stream.read_u8().await.unwrap()).unwrap();
+                    features.negotiate(node_features)
+                }
+            };
+
+            // Perform the handshake without capabilities, so that the payloads are encoded with bincode.
+            let mut socket = Framed::new(peer_stream, MessageCodec::<Testnet2, E>::new(features));
+            let challenge_request = Message::ChallengeRequest(
+                E::MESSAGE_VERSION,
+                Testnet2::ALEO_MAXIMUM_FORK_DEPTH,
+                NodeType::Client,
+                NodeCapabilities::empty(),
+                State::Ready,
+                4131,
+                1,
+                0,
+            );
+            socket.send(challenge_request).await.unwrap();
+            assert!(matches!(socket.next().await, Some(Ok(Message::ChallengeRequest(..)))));
+            let genesis_header = Testnet2::genesis_block().header().clone();
+            socket.send(Message::ChallengeResponse(Data::Object(genesis_header))).await.unwrap();
+            assert!(matches!(socket.next().await, Some(Ok(Message::ChallengeResponse(..)))));
+
+            // Retrieve the outbound router of the handler, once the peer is connected.
+            let outbound_router = match peers_handler.recv().await {
+                Some(PeersRequest::PeerConnected(.., outbound_router)) => outbound_router,
+                _ => panic!("Expected a 'PeerConnected' request"),
+            };
+
+            Self {
+                socket,
+                outbound_router,
+                _peers_handler: peers_handler,
+                ledger_handler,
+                _prover_handler: prover_handler,
+                _operator_handler: operator_handler,
+            }
+        }
+
+        /// Returns the messages received from the handler, up to and including the first one that matches the given predicate.
+        async fn receive_until(&mut self, predicate: impl Fn(&Message<Testnet2, E>) -> bool) -> Vec<Message<Testnet2, E>> {
+            let mut messages = Vec::new();
+            loop {
+                match timeout(Duration::from_secs(5), self.socket.next()).await {
+                    Ok(Some(Ok(message))) => {
+                        let is_match = predicate(&message);
+                        messages.push(message);
+                        if is_match {
+                            return messages;
+                        }
+                    }
+                    _ => panic!("Expected a message from the handler"),
+                }
+            }
+        }
+
+        /// Sends a `Ping` and waits for its `Pong`, by which the handler has processed every message sent before the `Ping`.
+        async fn sync(&mut self) -> Vec<Message<Testnet2, E>> {
+            self.socket.send(genesis_ping(0)).await.unwrap();
+            self.receive_until(|message| matches!(message, Message::Pong(..) | Message::PongDiff(..)))
+                .await
+        }
+
+        ///
+        /// Disconnects the handler, once it has processed every message sent before, and returns
+        /// the messages received from the handler until EOF, along with the requests that it routed to the ledger.
+        ///
+        async fn disconnect(mut self) -> (Vec<Message<Testnet2, E>>, Vec<LedgerRequest<Testnet2>>) {
+            let mut messages = self.sync().await;
+            self.outbound_router
+                .send(Message::Disconnect(DisconnectReason::NoReasonGiven))
+                .await
+                .unwrap();
+            while let Some(Ok(message)) = self.socket.next().await {
+                messages.push(message);
+            }
+
+            let mut ledger_requests = Vec::new();
+            while let Ok(request) = self.ledger_handler.try_recv() {
+                ledger_requests.push(request);
+            }
+            (messages, ledger_requests)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unconfirmed_block_far_beyond_latest_block() {
+        let mut peer = TestPeer::<Client<Testnet2>>::connect(Arc::new(genesis_ledger())).await;
+
+        // Announce an unconfirmed block far beyond the latest block height of the node.
+        let genesis_block = Testnet2::genesis_block();
+        let message = Message::UnconfirmedBlock(u32::MAX, genesis_block.hash(), Data::Object(genesis_block.clone()));
+        peer.socket.send(message).await.unwrap();
+
+        // Ensure the block is discarded with exactly one failure, before it is routed to the ledger.
+        let (_, ledger_requests) = peer.disconnect().await;
+        assert_eq!(1, ledger_requests.len());
+        match &ledger_requests[0] {
+            LedgerRequest::Failure(_, failure) => assert!(failure.contains(&u32::MAX.to_string())),
+            request => panic!("Unexpected ledger request: {:?}", request),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_future_block_request_handler() {
+        type E = Client<Testnet2>;
+        let mut peer = TestPeer::<E>::connect(Arc::new(genesis_ledger())).await;
+
+        // Request blocks far beyond the latest block height, up to the number of requests that is tolerated.
+        let start_block_height = 1000 + E::MAXIMUM_BLOCK_REQUEST;
+        for _ in 0..E::MAXIMUM_FUTURE_BLOCK_REQUESTS {
+            peer.socket
+                .send(Message::BlockRequest(start_block_height, start_block_height + 10))
+                .await
+                .unwrap();
+
+            // Ensure each request is answered with the latest block height, and no blocks.
+            let messages = peer.receive_until(|message| matches!(message, Message::BlockResponseEnd(..))).await;
+            assert!(!messages.iter().any(|message| matches!(message, Message::BlockResponse(..))));
+            assert!(matches!(messages.last(), Some(Message::BlockResponseEnd(0))));
+        }
+        peer.sync().await;
+        assert!(peer.ledger_handler.try_recv().is_err());
+
+        // Ensure a peer that keeps requesting blocks far beyond the latest block height is penalized once per request.
+        peer.socket
+            .send(Message::BlockRequest(start_block_height, start_block_height + 10))
+            .await
+            .unwrap();
+        let (messages, ledger_requests) = peer.disconnect().await;
+        assert!(matches!(messages.first(), Some(Message::BlockResponseEnd(0))));
+        assert_eq!(1, ledger_requests.len());
+        assert!(matches!(ledger_requests[0], LedgerRequest::Failure(..)));
+    }
+
+    #[derive(Clone, Debug, Default)]
+    struct ShallowPrunedNode;
+
+    #[rustfmt::skip]
+    impl Environment for ShallowPrunedNode {
+        type Network = Testnet2;
+        type FeePolicy = DefaultFeePolicy;
+        const NODE_TYPE: NodeType = NodeType::Client;
+        const PRUNE_DEPTH: u32 = 1;
+        const MINIMUM_NUMBER_OF_PEERS: usize = 2;
+        const MAXIMUM_NUMBER_OF_PEERS: usize = 21;
+    }
+
+    #[tokio::test]
+    async fn test_pruned_block_request_handler() {
+        let rng = &mut thread_rng();
+        let terminator = AtomicBool::new(false);
+
+        // Mine 2 blocks, so that the genesis block is beyond the prune depth of the node.
+        let ledger = genesis_ledger();
+        let address = Account::<Testnet2>::new(rng).address();
+        for _ in 0..2 {
+            let (block, _) = ledger
+                .mine_next_block(address, true, &[], &terminator, rng)
+                .expect("Failed to mine");
+            ledger.add_next_block(&block).expect("Failed to add next block to ledger");
+        }
+        let mut peer = TestPeer::<ShallowPrunedNode>::connect(Arc::new(ledger)).await;
+
+        // Ensure a request for pruned blocks is answered with a `BlockUnavailable` for the requested range.
+        peer.socket.send(Message::BlockRequest(0, 1)).await.unwrap();
+        let messages = peer.receive_until(|message| matches!(message, Message::BlockUnavailable(..))).await;
+        assert!(!messages.iter().any(|message| matches!(message, Message::BlockResponse(..))));
+        assert!(matches!(messages.last(), Some(Message::BlockUnavailable(0, 1))));
+
+        // Ensure a request within the prune depth is served.
+        peer.socket.send(Message::BlockRequest(1, 2)).await.unwrap();
+        let (messages, ledger_requests) = peer.disconnect().await;
+        let number_of_blocks = messages
+            .iter()
+            .filter(|message| matches!(message, Message::BlockResponse(..)))
+            .count();
+        assert_eq!(2, number_of_blocks);
+        assert!(ledger_requests.is_empty());
+    }
+
+    #[derive(Clone, Debug, Default)]
+    struct CoalescingNode;
+
+    #[rustfmt::skip]
+    impl Environment for CoalescingNode {
+        type Network = Testnet2;
+        type FeePolicy = DefaultFeePolicy;
+        const NODE_TYPE: NodeType = NodeType::Client;
+        const SEND_COALESCE_INTERVAL_IN_MILLIS: u64 = 500;
+        const MINIMUM_NUMBER_OF_PEERS: usize = 2;
+        const MAXIMUM_NUMBER_OF_PEERS: usize = 21;
+    }
+
+    #[tokio::test]
+    async fn test_send_coalescing() {
+        type E = CoalescingNode;
+        let interval = Duration::from_millis(E::SEND_COALESCE_INTERVAL_IN_MILLIS);
+        let ledger_reader: LedgerReader<Testnet2> = Arc::new(genesis_ledger());
+        let mut peer = TestPeer::<E>::connect_with_features(ledger_reader, FeatureFlags::BATCHES_MESSAGES).await;
+        assert!(Message::<Testnet2, E>::PeerRequest(None).is_coalescible());
+
+        // Ensure small messages are held back to be coalesced, and written within the coalesce interval.
+        let start = Instant::now();
+        for _ in 0..2 {
+            peer.outbound_router.send(Message::PeerRequest(None)).await.unwrap();
+        }
+        peer.receive_until(|message| matches!(message, Message::PeerRequest(..))).await;
+        let latency = start.elapsed();
+        assert!(latency >= interval / 2, "Expected the message to be held back, sent after {:?}", latency);
+        assert!(latency <= interval * 2, "Expected the message within the coalesce interval, sent after {:?}", latency);
+        peer.receive_until(|message| matches!(message, Message::PeerRequest(..))).await;
+
+        // Ensure a coalesced message is written ahead of the `Disconnect` that closes the connection, without waiting.
+        let start = Instant::now();
+        peer.outbound_router.send(Message::PeerRequest(None)).await.unwrap();
+        peer.outbound_router
+            .send(Message::Disconnect(DisconnectReason::NoReasonGiven))
+            .await
+            .unwrap();
+        let messages = peer.receive_until(|message| matches!(message, Message::Disconnect(..))).await;
+        assert!(start.elapsed() < interval, "Expected the close to flush the message, sent after {:?}", start.elapsed());
+        assert!(matches!(messages.as_slice(), [.., Message::PeerRequest(..), Message::Disconnect(..)]));
+    }
+
+    #[tokio::test]
+    async fn test_observer_block_request() {
+        let mut peer = TestPeer::<Observer<Testnet2>>::connect(Arc::new(genesis_ledger())).await;
+
+        // Ensure an observer declines a block request with a `BlockUnavailable` for the requested range.
+        peer.socket.send(Message::BlockRequest(0, 0)).await.unwrap();
+        let messages = peer.receive_until(|message| matches!(message, Message::BlockUnavailable(..))).await;
+        assert!(!messages.iter().any(|message| matches!(message, Message::BlockResponse(..))));
+        assert!(matches!(messages.last(), Some(Message::BlockUnavailable(0, 0))));
+    }
+
+    #[tokio::test]
+    async fn test_full_mempool_relay() {
+        ///
+        /// Routes the given transaction to a peer that reported the given memory pool size in its `Ping`,
+        /// and returns `true` if the transaction was relayed to the peer.
+        ///
+        async fn is_relayed(ledger_reader: LedgerReader<Testnet2>, mempool_size: u32, transaction: Transaction<Testnet2>) -> bool {
+            let mut peer = TestPeer::<Client<Testnet2>>::connect(ledger_reader).await;
+            peer.socket.send(genesis_ping(mempool_size)).await.unwrap();
+            peer.receive_until(|message| matches!(message, Message::Pong(..) | Message::PongDiff(..)))
+                .await;
+
+            peer.outbound_router
+                .send(Message::UnconfirmedTransaction(transaction))
+                .await
+                .unwrap();
+            peer.outbound_router
+                .send(Message::Disconnect(DisconnectReason::NoReasonGiven))
+                .await
+                .unwrap();
+            let messages = peer.receive_until(|message| matches!(message, Message::Disconnect(..))).await;
+            messages
+                .iter()
+                .any(|message| matches!(message, Message::UnconfirmedTransaction(..)))
+        }
+
+        let ledger_reader: LedgerReader<Testnet2> = Arc::new(genesis_ledger());
+        let transaction = Testnet2::genesis_block().transactions().first().unwrap().clone();
+        let maximum_mempool_size = Client::<Testnet2>::MAX_PEER_MEMPOOL_SIZE;
+
+        // Ensure a transaction is relayed to a peer whose memory pool is within the relay threshold.
+        assert!(is_relayed(ledger_reader.clone(), maximum_mempool_size, transaction.clone()).await);
+        // Ensure a transaction is not relayed to a peer whose memory pool is likely full.
+        assert!(!is_relayed(ledger_reader, maximum_mempool_size + 1, transaction).await);
+    }
+
+    #[derive(Clone, Debug, Default)]
+    struct SilentNode;
+
+    #[rustfmt::skip]
+    impl Environment for SilentNode {
+        type Network = Testnet2;
+        type FeePolicy = DefaultFeePolicy;
+        const NODE_TYPE: NodeType = NodeType::Client;
+        const RADIO_SILENCE_IN_SECS: u64 = 1;
+        const MINIMUM_NUMBER_OF_PEERS: usize = 2;
+        const MAXIMUM_NUMBER_OF_PEERS: usize = 21;
+    }
+
+    #[tokio::test]
+    async fn test_pong_silence_handler() {
+        ///
+        /// Sends a `Ping` to the handler every 200 milliseconds, along with a `Pong` if `sends_pong` is `true`,
+        /// for 3 times the radio silence period, and returns the reason of the `Disconnect` sent by the handler, if any.
+        ///
+        async fn keep_alive(ledger_reader: LedgerReader<Testnet2>, sends_pong: bool) -> Option<DisconnectReason> {
+            let mut peer = TestPeer::<SilentNode>::connect(ledger_reader.clone()).await;
+            let deadline = Instant::now() + 3 * Duration::from_secs(SilentNode::RADIO_SILENCE_IN_SECS);
+            let mut interval = tokio::time::interval(Duration::from_millis(200));
+            while Instant::now() < deadline {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        peer.socket.send(genesis_ping(0)).await.unwrap();
+                        if sends_pong {
+                            let block_locators = Data::Object(ledger_reader.latest_block_locators());
+                            peer.socket.send(Message::Pong(Some(false), block_locators)).await.unwrap();
+                        }
+                    }
+                    message = peer.socket.next() => match message {
+                        Some(Ok(Message::Disconnect(reason))) => return Some(reason),
+                        Some(Ok(_)) => (),
+                        _ => panic!("The handler closed the connection without a 'Disconnect'"),
+                    },
+                }
+            }
+            None
+        }
+
+        let ledger_reader: LedgerReader<Testnet2> = Arc::new(genesis_ledger());
+
+        // Ensure a peer that sends all messages except `Pong` is disconnected once the radio silence period has passed,
+        // even though it communicates within the radio silence period.
+        assert_eq!(
+            Some(DisconnectReason::NoReasonGiven),
+            keep_alive(ledger_reader.clone(), false).await
+        );
+        // Ensure a peer that also sends `Pong` stays connected.
+        assert_eq!(None, keep_alive(ledger_reader, true).await);
+    }
+}
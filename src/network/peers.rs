@@ -14,23 +14,43 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Data, Environment, LedgerReader, LedgerRouter, Message, OperatorRouter, OutboundRouter, Peer, ProverRouter};
+use crate::{
+    helpers::{BlockLocatorCache, EncodingFormat, Event, Events, FeatureFlags, MessageHistory, NodeType, Reputation, ReputationStore},
+    Data,
+    DisconnectReason,
+    Environment,
+    LedgerReader,
+    LedgerRequest,
+    LedgerRouter,
+    Message,
+    MessageCodec,
+    OperatorRequest,
+    OperatorRouter,
+    OutboundRouter,
+    Peer,
+    ProverRouter,
+};
 use snarkvm::dpc::prelude::*;
 
 use anyhow::Result;
+use futures::SinkExt;
 use rand::{prelude::IteratorRandom, rngs::OsRng, thread_rng, Rng};
 use std::{
-    collections::{HashMap, HashSet},
-    net::SocketAddr,
+    collections::{HashMap, HashSet, VecDeque},
+    io,
+    net::{IpAddr, SocketAddr},
+    path::PathBuf,
     sync::Arc,
     time::{Duration, Instant, SystemTime},
 };
 use tokio::{
+    io::AsyncWriteExt,
     net::TcpStream,
     sync::{mpsc, oneshot, RwLock},
     task,
     time::timeout,
 };
+use tokio_util::codec::Framed;
 
 /// Shorthand for the parent half of the `Peers` message channel.
 pub(crate) type PeersRouter<N, E> = mpsc::Sender<PeersRequest<N, E>>;
@@ -40,6 +60,17 @@ type PeersHandler<N, E> = mpsc::Receiver<PeersRequest<N, E>>;
 
 /// Shorthand for the parent half of the connection result channel.
 pub(crate) type ConnectionResult = oneshot::Sender<Result<()>>;
+/// Shorthand for the map of connected peers to their message history, which each connection updates under its own lock.
+pub(crate) type MessageHistories = Arc<RwLock<HashMap<SocketAddr, Arc<parking_lot::Mutex<MessageHistory>>>>>;
+
+/// The direction of a connection with a peer, as determined when the connection was established.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ConnectionDirection {
+    /// The peer connected to this node.
+    Inbound,
+    /// This node connected to the peer.
+    Outbound,
+}
 
 ///
 /// An enum of requests that the `Peers` struct processes.
@@ -70,16 +101,33 @@ pub enum PeersRequest<N: Network, E: Environment> {
         OperatorRouter<N>,
         ProverRouter<N>,
     ),
-    /// PeerConnected := (peer_ip, peer_nonce, outbound_router)
-    PeerConnected(SocketAddr, u64, OutboundRouter<N, E>),
+    /// PeerConnected := (peer_ip, peer_nonce, version, node_type, direction, cumulative_weight, outbound_router)
+    PeerConnected(SocketAddr, u64, u32, NodeType, ConnectionDirection, u128, OutboundRouter<N, E>),
+    /// PeerClosed := (peer_ip, outbound_router, ledger_router, operator_router)
+    PeerClosed(SocketAddr, OutboundRouter<N, E>, LedgerRouter<N>, OperatorRouter<N>),
     /// PeerDisconnected := (peer_ip)
     PeerDisconnected(SocketAddr),
+    /// PeerRecycled := (peer_ip, ledger_reader, ledger_router, operator_router, prover_router, connection_result)
+    PeerRecycled(
+        SocketAddr,
+        LedgerReader<N>,
+        LedgerRouter<N>,
+        OperatorRouter<N>,
+        ProverRouter<N>,
+        ConnectionResult,
+    ),
+    /// PeerBanned := (peer_ip)
+    PeerBanned(SocketAddr),
     /// PeerRestricted := (peer_ip)
     PeerRestricted(SocketAddr),
-    /// SendPeerResponse := (peer_ip)
-    SendPeerResponse(SocketAddr),
-    /// ReceivePeerResponse := (\[peer_ip\])
-    ReceivePeerResponse(Vec<SocketAddr>),
+    /// PeerVerified := (peer_ip)
+    PeerVerified(SocketAddr),
+    /// SendPeerResponse := (peer_ip, requester_ip)
+    SendPeerResponse(SocketAddr, Option<SocketAddr>),
+    /// ReceivePeerResponse := (Option<(peer_ip, requester_ip)>, \[peer_ip\])
+    ReceivePeerResponse(Option<(SocketAddr, SocketAddr)>, Vec<SocketAddr>),
+    /// SlowPeer := (peer_ip, rtt)
+    SlowPeer(SocketAddr, Duration),
 }
 
 ///
@@ -92,23 +140,39 @@ pub struct Peers<N: Network, E: Environment> {
     local_ip: SocketAddr,
     /// The local nonce for this node session.
     local_nonce: u64,
-    /// The map connected peer IPs to their nonce and outbound message router.
-    connected_peers: RwLock<HashMap<SocketAddr, (u64, OutboundRouter<N, E>)>>,
+    /// The map connected peer IPs to their nonce, node type, connection direction, and outbound message router.
+    connected_peers: RwLock<HashMap<SocketAddr, (u64, NodeType, ConnectionDirection, OutboundRouter<N, E>)>>,
+    /// The set of connected peer IPs whose listener was verified to accept connections.
+    verified_peers: RwLock<HashSet<SocketAddr>>,
     /// The set of candidate peer IPs.
     candidate_peers: RwLock<HashSet<SocketAddr>>,
-    /// The set of restricted peer IPs.
+    /// The set of restricted peer IPs, and the expiry of their restriction.
     restricted_peers: RwLock<HashMap<SocketAddr, Instant>>,
+    /// The set of peer IPs banned for being on an incompatible chain, and the timestamp of their ban.
+    banned_peers: RwLock<HashMap<SocketAddr, Instant>>,
     /// The map of peers to their first-seen port number, number of attempts, and timestamp of the last inbound connection request.
     seen_inbound_connections: RwLock<HashMap<SocketAddr, ((u16, u32), SystemTime)>>,
     /// The map of peers to the timestamp of their last outbound connection request.
     seen_outbound_connections: RwLock<HashMap<SocketAddr, SystemTime>>,
+    /// The external address of this node, as reported by peers, if it differs from the local address.
+    detected_external_addr: RwLock<Option<SocketAddr>>,
+    /// The addresses of this node most recently reported by distinct peers, as (peer_ip, reported_addr), oldest first.
+    external_addr_reports: RwLock<VecDeque<(IpAddr, SocketAddr)>>,
+    /// The cache of the serialized block locators of the current chain tip, served to peers in `Pong` messages.
+    block_locator_cache: Arc<RwLock<BlockLocatorCache<N>>>,
+    /// The map of connected peers to the types of the most recent messages received from them.
+    message_histories: MessageHistories,
+    /// The event channel of the node.
+    events: Events<N>,
+    /// The store of the reputation of each peer, which is persisted across restarts.
+    reputations: RwLock<ReputationStore>,
 }
 
 impl<N: Network, E: Environment> Peers<N, E> {
     ///
     /// Initializes a new instance of `Peers`.
     ///
-    pub(crate) async fn new(local_ip: SocketAddr, local_nonce: Option<u64>) -> Arc<Self> {
+    pub(crate) async fn new(local_ip: SocketAddr, local_nonce: Option<u64>, reputation_store_path: Option<PathBuf>) -> Arc<Self> {
         // Initialize an mpsc channel for sending requests to the `Peers` struct.
         let (peers_router, mut peers_handler) = mpsc::channel(1024);
 
@@ -118,18 +182,46 @@ impl<N: Network, E: Environment> Peers<N, E> {
             None => thread_rng().gen(),
         };
 
+        // Load the reputation of the peers, and restore the peers that are still restricted.
+        let now = chrono::Utc::now().timestamp();
+        let is_persisted = reputation_store_path.is_some();
+        let reputations = ReputationStore::load(reputation_store_path, E::PEER_REPUTATION_MAXIMUM_AGE_IN_SECS as i64, now);
+        let restricted_peers = reputations
+            .restricted_peers(E::RADIO_SILENCE_IN_SECS, E::MAXIMUM_RESTRICTION_DURATION_IN_SECS, now)
+            .map(|(peer_ip, remaining_in_secs)| (peer_ip, Instant::now() + Duration::from_secs(remaining_in_secs)))
+            .collect();
+
         // Initialize the peers.
         let peers = Arc::new(Self {
             peers_router,
             local_ip,
             local_nonce,
             connected_peers: Default::default(),
+            verified_peers: Default::default(),
             candidate_peers: Default::default(),
-            restricted_peers: Default::default(),
+            restricted_peers: RwLock::new(restricted_peers),
+            banned_peers: Default::default(),
             seen_inbound_connections: Default::default(),
             seen_outbound_connections: Default::default(),
+            detected_external_addr: Default::default(),
+            external_addr_reports: Default::default(),
+            block_locator_cache: Default::default(),
+            message_histories: Default::default(),
+            events: Events::new(E::MAXIMUM_BUFFERED_EVENTS),
+            reputations: RwLock::new(reputations),
         });
 
+        // Initialize the process for periodically saving the reputation of the peers.
+        if is_persisted {
+            let peers = peers.clone();
+            E::tasks().append(task::spawn(async move {
+                loop {
+                    tokio::time::sleep(Duration::from_secs(E::PEER_REPUTATION_FLUSH_IN_SECS)).await;
+                    peers.save_reputations().await;
+                }
+            }));
+        }
+
         // Initialize the peers router process.
         {
             let peers = peers.clone();
@@ -159,6 +251,28 @@ impl<N: Network, E: Environment> Peers<N, E> {
         self.peers_router.clone()
     }
 
+    /// Returns a handle to the event channel of the node.
+    pub fn events(&self) -> Events<N> {
+        self.events.clone()
+    }
+
+    ///
+    /// Returns the reputation of the given peer, if it is known.
+    ///
+    pub async fn reputation(&self, ip: SocketAddr) -> Option<Reputation> {
+        self.reputations.read().await.get(&ip).copied()
+    }
+
+    ///
+    /// Saves the reputation of the peers to disk, if the reputation store is persisted.
+    ///
+    pub async fn save_reputations(&self) {
+        let reputations = self.reputations.read().await.clone();
+        if let Err(error) = reputations.save() {
+            warn!("Failed to save the peer reputation store: {}", error);
+        }
+    }
+
     ///
     /// Returns `true` if the node is connected to the given IP.
     ///
@@ -170,8 +284,19 @@ impl<N: Network, E: Environment> Peers<N, E> {
     /// Returns `true` if the given IP is restricted.
     ///
     pub async fn is_restricted(&self, ip: SocketAddr) -> bool {
-        match self.restricted_peers.read().await.get(&ip) {
-            Some(timestamp) => timestamp.elapsed().as_secs() < E::RADIO_SILENCE_IN_SECS,
+        let is_restricted = match self.restricted_peers.read().await.get(&ip) {
+            Some(expiry) => Instant::now() < *expiry,
+            None => false,
+        };
+        is_restricted || self.is_banned(ip).await
+    }
+
+    ///
+    /// Returns `true` if the given IP is banned for being on an incompatible chain.
+    ///
+    pub async fn is_banned(&self, ip: SocketAddr) -> bool {
+        match self.banned_peers.read().await.get(&ip) {
+            Some(timestamp) => timestamp.elapsed().as_secs() < E::INCOMPATIBLE_FORK_BAN_IN_SECS,
             None => false,
         }
     }
@@ -190,6 +315,13 @@ impl<N: Network, E: Environment> Peers<N, E> {
         self.candidate_peers.read().await.clone()
     }
 
+    ///
+    /// Returns the external address of this node as reported by peers, if it differs from the local address.
+    ///
+    pub async fn detected_external_addr(&self) -> Option<SocketAddr> {
+        *self.detected_external_addr.read().await
+    }
+
     ///
     /// TODO (howardwu): Make this operation more efficient.
     /// Returns the number of connected sync nodes.
@@ -215,6 +347,55 @@ impl<N: Network, E: Environment> Peers<N, E> {
         self.connected_peers.read().await.len()
     }
 
+    ///
+    /// Returns the number of peers that connected to this node.
+    ///
+    pub async fn number_of_inbound_peers(&self) -> usize {
+        self.connected_peers
+            .read()
+            .await
+            .values()
+            .filter(|(_, _, direction, _)| *direction == ConnectionDirection::Inbound)
+            .count()
+    }
+
+    ///
+    /// Returns the node type and connection direction of each connected peer.
+    ///
+    pub async fn connected_peer_kinds(&self) -> Vec<(NodeType, ConnectionDirection)> {
+        self.connected_peers
+            .read()
+            .await
+            .values()
+            .map(|(_, node_type, direction, _)| (*node_type, *direction))
+            .collect()
+    }
+
+    ///
+    /// Returns the IP address, node type, and connection direction of each connected peer.
+    ///
+    pub async fn connected_peers_with_kinds(&self) -> Vec<(SocketAddr, NodeType, ConnectionDirection)> {
+        self.connected_peers
+            .read()
+            .await
+            .iter()
+            .map(|(peer_ip, (_, node_type, direction, _))| (*peer_ip, *node_type, *direction))
+            .collect()
+    }
+
+    ///
+    /// Returns the types of the most recent messages received from the given peer, from oldest to newest,
+    /// with the UNIX timestamp in milliseconds at which each message was received.
+    ///
+    pub async fn message_history(&self, peer_ip: SocketAddr) -> Vec<(String, u64)> {
+        self.message_histories
+            .read()
+            .await
+            .get(&peer_ip)
+            .map(|history| history.lock().to_vec())
+            .unwrap_or_default()
+    }
+
     ///
     /// Returns the number of candidate peers.
     ///
@@ -223,14 +404,14 @@ impl<N: Network, E: Environment> Peers<N, E> {
     }
 
     ///
-    /// Returns the list of nonces for the connected peers.
+    /// Returns the list of nonces for the connected peers, with the IP address of each peer.
     ///
-    pub(crate) async fn connected_nonces(&self) -> Vec<u64> {
+    pub(crate) async fn connected_nonces(&self) -> Vec<(SocketAddr, u64)> {
         self.connected_peers
             .read()
             .await
-            .values()
-            .map(|(peer_nonce, _)| *peer_nonce)
+            .iter()
+            .map(|(peer_ip, (peer_nonce, ..))| (*peer_ip, *peer_nonce))
             .collect()
     }
 
@@ -278,8 +459,8 @@ impl<N: Network, E: Environment> Peers<N, E> {
                         drop(seen_outbound_connections);
 
                         // Initialize the peer handler.
-                        match timeout(Duration::from_millis(E::CONNECTION_TIMEOUT_IN_MILLIS), TcpStream::connect(peer_ip)).await {
-                            Ok(stream) => match stream {
+                        match Self::connect_with_timeout(peer_ip).await {
+                            Some(stream) => match stream {
                                 Ok(stream) => {
                                     Peer::handler(
                                         stream,
@@ -290,6 +471,8 @@ impl<N: Network, E: Environment> Peers<N, E> {
                                         ledger_router,
                                         prover_router,
                                         operator_router,
+                                        self.block_locator_cache.clone(),
+                                        self.message_histories.clone(),
                                         self.connected_nonces().await,
                                         Some(connection_result),
                                     )
@@ -300,8 +483,10 @@ impl<N: Network, E: Environment> Peers<N, E> {
                                     self.candidate_peers.write().await.remove(&peer_ip);
                                 }
                             },
-                            Err(error) => {
-                                error!("Unable to reach '{}': '{:?}'", peer_ip, error);
+                            None => {
+                                warn!("Timed out connecting to {} after {} ms", peer_ip, E::CONNECTION_TIMEOUT_IN_MILLIS);
+                                // A timeout is not a protocol error, so the peer is only removed from the in-flight connections.
+                                self.seen_outbound_connections.write().await.remove(&peer_ip);
                                 self.candidate_peers.write().await.remove(&peer_ip);
                             }
                         };
@@ -309,6 +494,19 @@ impl<N: Network, E: Environment> Peers<N, E> {
                 }
             }
             PeersRequest::Heartbeat(ledger_reader, ledger_router, operator_router, prover_router) => {
+                // Precompute the block locators of the current chain tip, to serve them in `Pong` messages.
+                self.refresh_block_locator_cache(&ledger_reader).await;
+
+                // Remove the restrictions that have expired, so that the restricted peers remain bounded.
+                let now = Instant::now();
+                self.restricted_peers.write().await.retain(|_, expiry| now < *expiry);
+                // Remove the reputations that have not been updated recently, so that the reputation store remains bounded.
+                let timestamp = chrono::Utc::now().timestamp();
+                self.reputations
+                    .write()
+                    .await
+                    .prune(E::PEER_REPUTATION_MAXIMUM_AGE_IN_SECS as i64, timestamp);
+
                 // Obtain the number of connected peers.
                 let number_of_connected_peers = self.number_of_connected_peers().await;
                 // Ensure the number of connected peers is below the maximum threshold.
@@ -330,9 +528,9 @@ impl<N: Network, E: Environment> Peers<N, E> {
                     // Proceed to send disconnect requests to these peers.
                     for peer_ip in peer_ips_to_disconnect {
                         info!("Disconnecting from {} (exceeded maximum connections)", peer_ip);
-                        self.send(peer_ip, Message::Disconnect).await;
+                        self.send(peer_ip, Message::Disconnect(DisconnectReason::TooManyPeers)).await;
                         // Add an entry for this `Peer` in the restricted peers.
-                        self.restricted_peers.write().await.insert(peer_ip, Instant::now());
+                        self.add_restriction(peer_ip, Duration::from_secs(E::RADIO_SILENCE_IN_SECS)).await;
                     }
                 }
 
@@ -351,19 +549,19 @@ impl<N: Network, E: Environment> Peers<N, E> {
                         .choose_multiple(&mut OsRng::default(), num_excess_sync_nodes)
                     {
                         info!("Disconnecting from {} (exceeded maximum connections)", peer_ip);
-                        self.send(peer_ip, Message::Disconnect).await;
+                        self.send(peer_ip, Message::Disconnect(DisconnectReason::TooManyPeers)).await;
                         // Add an entry for this `Peer` in the restricted peers.
-                        self.restricted_peers.write().await.insert(peer_ip, Instant::now());
+                        self.add_restriction(peer_ip, Duration::from_secs(E::RADIO_SILENCE_IN_SECS)).await;
                     }
                 }
 
                 // Skip if the number of connected peers is above the minimum threshold.
-                match number_of_connected_peers < E::MINIMUM_NUMBER_OF_PEERS {
+                match number_of_connected_peers < Self::minimum_number_of_peers() {
                     true => {
                         trace!("Sending request for more peer connections");
                         // Request more peers if the number of connected peers is below the threshold.
                         for peer_ip in self.connected_peers().await.iter().choose_multiple(&mut OsRng::default(), 3) {
-                            self.send(*peer_ip, Message::PeerRequest).await;
+                            self.send(*peer_ip, Message::PeerRequest(Some(self.local_ip))).await;
                         }
                     }
                     false => return,
@@ -379,7 +577,7 @@ impl<N: Network, E: Environment> Peers<N, E> {
 
                 // Attempt to connect to more peers if the number of connected peers is below the minimum threshold.
                 // Select the peers randomly from the list of candidate peers.
-                let midpoint_number_of_peers = E::MINIMUM_NUMBER_OF_PEERS.saturating_add(E::MAXIMUM_NUMBER_OF_PEERS) / 2;
+                let midpoint_number_of_peers = Self::minimum_number_of_peers().saturating_add(E::MAXIMUM_NUMBER_OF_PEERS) / 2;
                 for peer_ip in self
                     .candidate_peers()
                     .await
@@ -432,23 +630,23 @@ impl<N: Network, E: Environment> Peers<N, E> {
                 else if self.number_of_connected_peers().await >= E::MAXIMUM_NUMBER_OF_PEERS {
                     debug!("Dropping connection request from {} (maximum peers reached)", peer_ip);
                 }
+                // Ensure the node does not surpass the maximum number of inbound connections.
+                else if self.number_of_inbound_peers().await >= E::MAX_INBOUND_CONNECTIONS {
+                    debug!("Dropping connection request from {} (maximum inbound connections reached)", peer_ip);
+                    Self::reject_connection(stream, DisconnectReason::TooManyPeers);
+                }
                 // Ensure the node is not already connected to this peer.
                 else if self.is_connected_to(peer_ip).await {
                     debug!("Dropping connection request from {} (already connected)", peer_ip);
                 }
-                // Ensure the peer is not restricted.
-                else if self.is_restricted(peer_ip).await {
+                // Ensure the peer is not restricted, including by its IP address.
+                else if self.is_restricted(peer_ip).await || self.is_restricted(Self::inbound_peer_lookup(peer_ip)).await {
                     debug!("Dropping connection request from {} (restricted)", peer_ip);
                 }
                 // Spawn a handler to be run asynchronously.
                 else {
                     // Sanitize the port from the peer, if it is a remote IP address.
-                    let (peer_lookup, peer_port) = match peer_ip.ip().is_loopback() {
-                        // Loopback case - Do not sanitize, merely pass through.
-                        true => (peer_ip, peer_ip.port()),
-                        // Remote case - Sanitize, storing u16::MAX for the peer IP address to dedup the peer next time.
-                        false => (SocketAddr::new(peer_ip.ip(), u16::MAX), peer_ip.port()),
-                    };
+                    let (peer_lookup, peer_port) = (Self::inbound_peer_lookup(peer_ip), peer_ip.port());
 
                     // Lock seen_inbound_connections for further processing.
                     let mut seen_inbound_connections = self.seen_inbound_connections.write().await;
@@ -473,7 +671,7 @@ impl<N: Network, E: Environment> Peers<N, E> {
                     if *initial_port < peer_port && *num_attempts > E::MAXIMUM_CONNECTION_FAILURES {
                         trace!("Dropping connection request from {} (tried {} secs ago)", peer_ip, elapsed);
                         // Add an entry for this `Peer` in the restricted peers.
-                        self.restricted_peers.write().await.insert(peer_ip, Instant::now());
+                        self.add_restriction(peer_ip, Duration::from_secs(E::RADIO_SILENCE_IN_SECS)).await;
                     } else {
                         debug!("Received a connection request from {}", peer_ip);
                         // Update the number of attempts for this peer.
@@ -492,6 +690,8 @@ impl<N: Network, E: Environment> Peers<N, E> {
                             ledger_router,
                             prover_router,
                             operator_router,
+                            self.block_locator_cache.clone(),
+                            self.message_histories.clone(),
                             self.connected_nonces().await,
                             None,
                         )
@@ -499,35 +699,194 @@ impl<N: Network, E: Environment> Peers<N, E> {
                     }
                 }
             }
-            PeersRequest::PeerConnected(peer_ip, peer_nonce, outbound) => {
+            PeersRequest::PeerConnected(peer_ip, peer_nonce, version, node_type, direction, cumulative_weight, outbound) => {
                 // Add an entry for this `Peer` in the connected peers.
-                self.connected_peers.write().await.insert(peer_ip, (peer_nonce, outbound));
+                let previous = self
+                    .connected_peers
+                    .write()
+                    .await
+                    .insert(peer_ip, (peer_nonce, node_type, direction, outbound));
                 // Remove an entry for this `Peer` in the candidate peers, if it exists.
                 self.candidate_peers.write().await.remove(&peer_ip);
+                // Emit the `PeerConnected` event, preceded by a `PeerDisconnected` for a replaced connection.
+                if previous.is_some() {
+                    self.events.emit(Event::PeerDisconnected(peer_ip));
+                }
+                self.events
+                    .emit(Event::PeerConnected(peer_ip, version, node_type, direction, cumulative_weight));
             }
             PeersRequest::PeerDisconnected(peer_ip) => {
                 // Remove an entry for this `Peer` in the connected peers, if it exists.
-                self.connected_peers.write().await.remove(&peer_ip);
+                self.remove_connected_peer(peer_ip).await;
+                // Remove an entry for this `Peer` in the verified peers, if it exists.
+                self.verified_peers.write().await.remove(&peer_ip);
+                // Remove the message history of this `Peer`, if it exists.
+                self.message_histories.write().await.remove(&peer_ip);
                 // Add an entry for this `Peer` in the candidate peers.
                 self.candidate_peers.write().await.insert(peer_ip);
             }
-            PeersRequest::PeerRestricted(peer_ip) => {
-                // Remove an entry for this `Peer` in the connected peers, if it exists.
-                self.connected_peers.write().await.remove(&peer_ip);
-                // Add an entry for this `Peer` in the restricted peers.
-                self.restricted_peers.write().await.insert(peer_ip, Instant::now());
+            PeersRequest::PeerClosed(peer_ip, outbound_router, ledger_router, operator_router) => {
+                // Skip a connection that was replaced by a newer connection to the same peer, such as a recycled connection.
+                let is_replaced = self
+                    .connected_peers
+                    .read()
+                    .await
+                    .get(&peer_ip)
+                    .map_or(false, |(.., router)| !router.same_channel(&outbound_router));
+                if is_replaced {
+                    debug!("Closed the replaced connection to {}", peer_ip);
+                    return;
+                }
+
+                if E::NODE_TYPE == NodeType::Operator {
+                    // Route a `PoolDisconnect` to the operator, as a prover may have been on this connection.
+                    if let Err(error) = operator_router.send(OperatorRequest::PoolDisconnect(peer_ip)).await {
+                        warn!("[PoolDisconnect] {}", error);
+                    }
+                }
+                // Route a `Disconnect` to the ledger.
+                if let Err(error) = ledger_router
+                    .send(LedgerRequest::Disconnect(peer_ip, "peer has disconnected".to_string()))
+                    .await
+                {
+                    warn!("[PeerClosed] {}", error);
+                }
             }
-            PeersRequest::SendPeerResponse(recipient) => {
+            PeersRequest::PeerRecycled(peer_ip, ledger_reader, ledger_router, operator_router, prover_router, connection_result) => {
+                debug!("Reconnecting to {} (recycled connection)", peer_ip);
+                // The existing connection stays open until the new connection replaces it in the connected peers,
+                // so the connection limits and the connection frequency limit do not apply.
+                match Self::connect_with_timeout(peer_ip).await {
+                    Some(Ok(stream)) => {
+                        Peer::handler(
+                            stream,
+                            self.local_ip,
+                            self.local_nonce,
+                            &self.peers_router,
+                            ledger_reader,
+                            ledger_router,
+                            prover_router,
+                            operator_router,
+                            self.block_locator_cache.clone(),
+                            self.message_histories.clone(),
+                            self.connected_nonces().await,
+                            Some(connection_result),
+                        )
+                        .await
+                    }
+                    // Dropping the connection result reports the failure, so the existing connection is kept.
+                    Some(Err(error)) => debug!("Failed to reconnect to {}: {}", peer_ip, error),
+                    None => debug!("Timed out reconnecting to {} after {} ms", peer_ip, E::CONNECTION_TIMEOUT_IN_MILLIS),
+                }
+            }
+            PeersRequest::PeerBanned(peer_ip) => {
+                // Add an entry for this `Peer` in the banned peers.
+                self.banned_peers.write().await.insert(peer_ip, Instant::now());
+                // Restrict this `Peer`, which also removes it from the connected peers.
+                self.restrict_peer(peer_ip).await;
+            }
+            PeersRequest::PeerRestricted(peer_ip) => self.restrict_peer(peer_ip).await,
+            PeersRequest::PeerVerified(peer_ip) => {
+                // Add an entry for this `Peer` in the verified peers, if it is still connected.
+                if self.is_connected_to(peer_ip).await {
+                    self.verified_peers.write().await.insert(peer_ip);
+                }
+            }
+            PeersRequest::SendPeerResponse(recipient, requester_ip) => {
                 // Send a `PeerResponse` message.
-                let connected_peers = self.connected_peers().await;
-                self.send(recipient, Message::PeerResponse(connected_peers)).await;
+                let peer_ips = self.peer_response().await;
+                self.send(recipient, Message::PeerResponse(requester_ip, peer_ips)).await;
             }
-            PeersRequest::ReceivePeerResponse(peer_ips) => {
+            PeersRequest::ReceivePeerResponse(reporter, peer_ips) => {
+                if let Some((peer_ip, requester_ip)) = reporter {
+                    self.update_external_addr(peer_ip, requester_ip).await;
+                }
                 self.add_candidate_peers(peer_ips.iter()).await;
             }
+            PeersRequest::SlowPeer(peer_ip, rtt) => {
+                warn!(
+                    "Peer {} has been slow for over {} seconds (round-trip time of {} ms)",
+                    peer_ip,
+                    E::SLOW_PEER_WINDOW_IN_SECS,
+                    rtt.as_millis()
+                );
+                #[cfg(feature = "prometheus")]
+                snarkos_metrics::increment_counter!(snarkos_metrics::SLOW_PEERS_TOTAL);
+                self.events.emit(Event::SlowPeer(peer_ip, rtt));
+            }
         }
     }
 
+    ///
+    /// Records the address of this node, as reported by the given peer, and updates the external address of this node
+    /// once a majority of the most recent reports, from at least `E::MINIMUM_EXTERNAL_ADDR_REPORTERS` distinct peers,
+    /// agree on it. The external address differs from the local address when this node is behind a NAT.
+    ///
+    /// Each peer IP address holds a single report, so that a peer cannot outvote the others by reporting repeatedly.
+    ///
+    async fn update_external_addr(&self, peer_ip: SocketAddr, reported_addr: SocketAddr) {
+        let agreed_addr = {
+            let mut reports = self.external_addr_reports.write().await;
+            reports.retain(|(reporter, _)| *reporter != peer_ip.ip());
+            reports.push_back((peer_ip.ip(), reported_addr));
+            while reports.len() > E::EXTERNAL_ADDR_REPORT_WINDOW {
+                reports.pop_front();
+            }
+
+            let agreement = reports.iter().filter(|(_, addr)| *addr == reported_addr).count();
+            match agreement >= E::MINIMUM_EXTERNAL_ADDR_REPORTERS && 2 * agreement > reports.len() {
+                true => reported_addr,
+                false => return,
+            }
+        };
+
+        // A majority reporting the local address indicates this node is no longer behind a NAT.
+        let external_addr = Some(agreed_addr).filter(|addr| *addr != self.local_ip);
+        let mut detected_external_addr = self.detected_external_addr.write().await;
+        if *detected_external_addr != external_addr {
+            if let Some(external_addr) = external_addr {
+                info!(
+                    "Peers report an external address of {}, which differs from the local address {}",
+                    external_addr, self.local_ip
+                );
+            }
+            *detected_external_addr = external_addr;
+        }
+    }
+
+    ///
+    /// Serializes the block locators of the current chain tip into the block locator cache,
+    /// if they are not cached yet. Block locators in other encoding formats are cached on demand.
+    ///
+    async fn refresh_block_locator_cache(&self, ledger_reader: &LedgerReader<N>) {
+        let latest_block_hash = ledger_reader.latest_block_hash();
+        let serialize = || Data::Object(ledger_reader.latest_block_locators()).serialize_with(EncodingFormat::Bincode);
+        let cache = &self.block_locator_cache;
+        if let Err(error) = BlockLocatorCache::get_or_serialize(cache, latest_block_hash, EncodingFormat::Bincode, serialize).await {
+            warn!("Failed to serialize the block locators: {}", error);
+        }
+    }
+
+    ///
+    /// Returns the list of peer IPs to share in a `PeerResponse` message.
+    /// Only the connected peers whose listener was verified to accept connections are shared.
+    ///
+    /// If `E::SELF_ADVERTISE_IN_PEER_RESPONSE` is set, the listener address of this node is
+    /// prepended, so that it is re-advertised to others as an entry point to the network.
+    ///
+    pub async fn peer_response(&self) -> Vec<SocketAddr> {
+        let connected_peers = self.connected_peers().await;
+        let verified_peers = self.verified_peers.read().await;
+        let mut peer_ips: Vec<_> = connected_peers
+            .into_iter()
+            .filter(|peer_ip| verified_peers.contains(peer_ip))
+            .collect();
+        if E::SELF_ADVERTISE_IN_PEER_RESPONSE && !self.local_ip.ip().is_unspecified() {
+            peer_ips.insert(0, self.local_ip);
+        }
+        peer_ips
+    }
+
     ///
     /// Adds the given peer IPs to the set of candidate peers.
     ///
@@ -549,43 +908,142 @@ impl<N: Network, E: Environment> Peers<N, E> {
         }
     }
 
+    ///
+    /// Returns the minimum number of connected peers that the node seeks, which includes the peers required to mine.
+    ///
+    fn minimum_number_of_peers() -> usize {
+        E::MINIMUM_NUMBER_OF_PEERS.max(E::MINIMUM_PEERS_TO_MINE)
+    }
+
+    ///
+    /// Returns the address under which an inbound connection from the given address is tracked.
+    /// The ephemeral port of a remote address is sanitized to u16::MAX, so that the peer is deduped by its IP address.
+    ///
+    pub(crate) fn inbound_peer_lookup(peer_ip: SocketAddr) -> SocketAddr {
+        match peer_ip.ip().is_loopback() {
+            // Loopback case - Do not sanitize, merely pass through.
+            true => peer_ip,
+            // Remote case - Sanitize, storing u16::MAX for the peer IP address to dedup the peer next time.
+            false => SocketAddr::new(peer_ip.ip(), u16::MAX),
+        }
+    }
+
+    ///
+    /// Removes the given peer from the connected peers, and emits a `PeerDisconnected` event if it was connected.
+    ///
+    async fn remove_connected_peer(&self, peer_ip: SocketAddr) {
+        if self.connected_peers.write().await.remove(&peer_ip).is_some() {
+            self.events.emit(Event::PeerDisconnected(peer_ip));
+        }
+    }
+
+    ///
+    /// Removes the given peer from the connected and verified peers, and restricts it.
+    ///
+    async fn restrict_peer(&self, peer_ip: SocketAddr) {
+        // Remove an entry for this `Peer` in the connected peers, if it exists.
+        self.remove_connected_peer(peer_ip).await;
+        // Remove an entry for this `Peer` in the verified peers, if it exists.
+        self.verified_peers.write().await.remove(&peer_ip);
+        // Record the restriction in the reputation of this `Peer`, which lengthens its repeated restrictions.
+        let now = chrono::Utc::now().timestamp();
+        let reputation = self.reputations.write().await.record_restriction(peer_ip, now);
+        let duration = reputation.restriction_duration_in_secs(E::RADIO_SILENCE_IN_SECS, E::MAXIMUM_RESTRICTION_DURATION_IN_SECS);
+        // Add an entry for this `Peer` in the restricted peers.
+        self.add_restriction(peer_ip, Duration::from_secs(duration)).await;
+        // Remove the message history of this `Peer`, if it exists.
+        self.message_histories.write().await.remove(&peer_ip);
+    }
+
+    ///
+    /// Restricts the given peer for the given duration, unless it is already restricted for longer.
+    ///
+    async fn add_restriction(&self, peer_ip: SocketAddr, duration: Duration) {
+        let expiry = Instant::now() + duration;
+        let mut restricted_peers = self.restricted_peers.write().await;
+        let entry = restricted_peers.entry(peer_ip).or_insert(expiry);
+        *entry = (*entry).max(expiry);
+    }
+
     ///
     /// Sends the given message to specified peer.
     ///
     async fn send(&self, peer: SocketAddr, message: Message<N, E>) {
         let target_peer = self.connected_peers.read().await.get(&peer).cloned();
         match target_peer {
-            Some((_, outbound)) => {
+            Some((.., outbound)) => {
                 if let Err(error) = outbound.send(message).await {
                     trace!("Outbound channel failed: {}", error);
-                    self.connected_peers.write().await.remove(&peer);
+                    self.remove_connected_peer(peer).await;
                 }
             }
             None => warn!("Attempted to send to a non-connected peer {}", peer),
         }
     }
 
+    ///
+    /// Sends a `Disconnect` with the given reason on the given stream, before the handshake, and closes the stream.
+    /// The `Disconnect` follows an empty `Hello`, so that it is read as an uncompressed frame by the peer.
+    ///
+    fn reject_connection(mut stream: TcpStream, reason: DisconnectReason) {
+        E::tasks().append(task::spawn(async move {
+            let send_timeout = Duration::from_millis(E::CONNECTION_TIMEOUT_IN_MILLIS);
+            let result = timeout(send_timeout, async move {
+                stream.write_all(&[FeatureFlags::empty().to_hello()]).await?;
+                let mut outbound_socket = Framed::new(stream, MessageCodec::<N, E>::default());
+                outbound_socket.send(Message::Disconnect(reason)).await
+            })
+            .await;
+            if !matches!(result, Ok(Ok(()))) {
+                trace!("Failed to send a disconnect ({:?})", reason);
+            }
+        }));
+    }
+
     ///
     /// Sends the given message to every connected peer, excluding the sender.
+    /// Returns the number of peers the message was dispatched to.
     ///
-    async fn propagate(&self, sender: SocketAddr, mut message: Message<N, E>) {
+    async fn propagate(&self, sender: SocketAddr, mut message: Message<N, E>) -> usize {
         // Perform ahead-of-time, non-blocking serialization just once for applicable objects.
-        if let Message::UnconfirmedBlock(_, _, ref mut data) = message {
-            let serialized_block = Data::serialize(data.clone()).await.expect("Block serialization is bugged");
-            let _ = std::mem::replace(data, Data::Buffer(serialized_block));
+        // With the postcard backend, the encoding is negotiated per peer, so serialization is deferred to each peer.
+        if !cfg!(feature = "postcard") {
+            if let Message::UnconfirmedBlock(_, _, ref mut data) = message {
+                let serialized_block = Data::serialize(data.clone()).await.expect("Block serialization is bugged");
+                let _ = std::mem::replace(data, Data::Buffer(serialized_block));
+            }
         }
 
         // Iterate through all peers that are not the sender, sync node, or beacon node.
-        for peer in self
+        let peers = self
             .connected_peers()
             .await
             .iter()
             .filter(|peer_ip| *peer_ip != &sender && !E::sync_nodes().contains(peer_ip) && !E::beacon_nodes().contains(peer_ip))
             .copied()
-            .collect::<Vec<_>>()
-        {
-            self.send(peer, message.clone()).await;
+            .collect::<Vec<_>>();
+        for peer in &peers {
+            self.send(*peer, message.clone()).await;
         }
+        peers.len()
+    }
+
+    ///
+    /// Sends the given message from this node to every connected peer, as in `propagate`,
+    /// and returns the number of peers the message was dispatched to.
+    /// Each peer applies its relay filters before sending, and skips a message it has already seen.
+    ///
+    pub(crate) async fn rebroadcast(&self, message: Message<N, E>) -> usize {
+        self.propagate(self.local_ip, message).await
+    }
+
+    ///
+    /// Opens a TCP stream to the given peer, returning `None` if the connection timeout elapses.
+    ///
+    async fn connect_with_timeout(peer_ip: SocketAddr) -> Option<io::Result<TcpStream>> {
+        timeout(Duration::from_millis(E::CONNECTION_TIMEOUT_IN_MILLIS), TcpStream::connect(peer_ip))
+            .await
+            .ok()
     }
 
     ///
@@ -595,7 +1053,478 @@ impl<N: Network, E: Environment> Peers<N, E> {
     pub async fn reset_known_peers(&self) {
         self.candidate_peers.write().await.clear();
         self.restricted_peers.write().await.clear();
+        self.banned_peers.write().await.clear();
         self.seen_inbound_connections.write().await.clear();
         self.seen_outbound_connections.write().await.clear();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        environment::{Client, Miner, SyncNode},
+        helpers::DefaultFeePolicy,
+    };
+    use snarkvm::dpc::testnet2::Testnet2;
+
+    use snarkos_storage::{storage::rocksdb::RocksDB, LedgerState};
+    use tokio::{
+        io::AsyncReadExt,
+        net::{TcpListener, TcpSocket},
+        time::Instant as TokioInstant,
+    };
+    use tokio_stream::StreamExt;
+
+    #[derive(Clone, Debug, Default)]
+    struct InboundLimitedNode;
+
+    #[rustfmt::skip]
+    impl Environment for InboundLimitedNode {
+        type Network = Testnet2;
+        type FeePolicy = DefaultFeePolicy;
+        const NODE_TYPE: NodeType = NodeType::Client;
+        const MINIMUM_NUMBER_OF_PEERS: usize = 1;
+        const MAXIMUM_NUMBER_OF_PEERS: usize = 3;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_connect_with_timeout() {
+        // Initialize a listener that never accepts connections.
+        let socket = TcpSocket::new_v4().unwrap();
+        socket.bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        let listener = socket.listen(1).unwrap();
+        let peer_ip = listener.local_addr().unwrap();
+
+        // Fill the backlog of the listener, so that further connections are left pending.
+        let mut backlog = Vec::new();
+        for _ in 0..16 {
+            match std::net::TcpStream::connect_timeout(&peer_ip, Duration::from_millis(100)) {
+                Ok(stream) => backlog.push(stream),
+                Err(_) => break,
+            }
+        }
+
+        // Ensure the connector gives up once the connection timeout elapses.
+        let start = TokioInstant::now();
+        assert!(Peers::<Testnet2, Client<Testnet2>>::connect_with_timeout(peer_ip).await.is_none());
+        let elapsed = start.elapsed().as_millis() as i128;
+        let expected = Client::<Testnet2>::CONNECTION_TIMEOUT_IN_MILLIS as i128;
+        assert!((elapsed - expected).abs() <= 50, "Timed out after {} ms", elapsed);
+    }
+
+    /// Connects to a recipient and a verified peer, and returns the peer IPs of the `PeerResponse` messages
+    /// that the recipient receives for the given number of `SendPeerResponse` requests.
+    async fn sent_peer_responses<E: Environment>(
+        local_ip: SocketAddr,
+        verified_ip: SocketAddr,
+        num_responses: usize,
+    ) -> Vec<Vec<SocketAddr>> {
+        let peers = Peers::<Testnet2, E>::new(local_ip, None, None).await;
+        let recipient_ip: SocketAddr = "127.0.0.1:4131".parse().unwrap();
+        let direction = ConnectionDirection::Inbound;
+
+        let (recipient_router, mut recipient_handler) = mpsc::channel(1024);
+        let request = PeersRequest::PeerConnected(recipient_ip, 1, 0, NodeType::Client, direction, 0, recipient_router);
+        peers.update(request).await;
+        let (verified_router, _verified_handler) = mpsc::channel(1024);
+        let request = PeersRequest::PeerConnected(verified_ip, 2, 0, NodeType::Client, direction, 0, verified_router);
+        peers.update(request).await;
+        peers.update(PeersRequest::PeerVerified(verified_ip)).await;
+
+        let mut responses = Vec::with_capacity(num_responses);
+        for _ in 0..num_responses {
+            peers.update(PeersRequest::SendPeerResponse(recipient_ip, None)).await;
+            match recipient_handler.try_recv() {
+                Ok(Message::PeerResponse(None, peer_ips)) => responses.push(peer_ips),
+                message => panic!("Expected a 'PeerResponse', found {:?}", message),
+            }
+        }
+        responses
+    }
+
+    #[tokio::test]
+    async fn test_sync_node_self_advertises_in_peer_response() {
+        let local_ip: SocketAddr = "127.0.0.1:4135".parse().unwrap();
+        let verified_ip: SocketAddr = "127.0.0.1:4132".parse().unwrap();
+        let num_responses = 10;
+
+        // Ensure a sync node includes its own address in the `PeerResponse` messages it sends, ahead of its verified peers.
+        let responses = sent_peer_responses::<SyncNode<Testnet2>>(local_ip, verified_ip, num_responses).await;
+        assert_eq!(num_responses, responses.len());
+        let num_self_advertised = responses.iter().filter(|peer_ips| peer_ips.first() == Some(&local_ip)).count();
+        assert!(num_self_advertised * 2 >= num_responses);
+        assert!(responses.iter().all(|peer_ips| peer_ips.contains(&verified_ip)));
+
+        // Ensure a client only shares its verified peers, and not its own address.
+        let responses = sent_peer_responses::<Client<Testnet2>>(local_ip, verified_ip, num_responses).await;
+        assert!(responses.iter().all(|peer_ips| peer_ips == &vec![verified_ip]));
+    }
+
+    #[tokio::test]
+    async fn test_peer_response_only_includes_verified_peers() {
+        let peers = Peers::<Testnet2, Client<Testnet2>>::new("127.0.0.1:4130".parse().unwrap(), None, None).await;
+
+        // Connect two inbound peers.
+        let direction = ConnectionDirection::Inbound;
+        let verified_ip: SocketAddr = "127.0.0.1:4131".parse().unwrap();
+        let unverified_ip: SocketAddr = "127.0.0.1:4132".parse().unwrap();
+        for (nonce, peer_ip) in [verified_ip, unverified_ip].iter().enumerate() {
+            let (outbound_router, _outbound_handler) = mpsc::channel(1);
+            let request = PeersRequest::PeerConnected(*peer_ip, nonce as u64, 0, NodeType::Client, direction, 0, outbound_router);
+            peers.update(request).await;
+        }
+        assert!(peers.peer_response().await.is_empty());
+
+        // Ensure only the peer with a verified listener is shared.
+        peers.update(PeersRequest::PeerVerified(verified_ip)).await;
+        assert_eq!(vec![verified_ip], peers.peer_response().await);
+
+        // Ensure a disconnected peer is no longer shared, even if it reconnects unverified.
+        peers.update(PeersRequest::PeerDisconnected(verified_ip)).await;
+        let (outbound_router, _outbound_handler) = mpsc::channel(1);
+        let request = PeersRequest::PeerConnected(verified_ip, 2, 0, NodeType::Client, direction, 0, outbound_router);
+        peers.update(request).await;
+        assert!(peers.peer_response().await.is_empty());
+
+        // Ensure a peer that is not connected is not marked as verified.
+        let stranger_ip: SocketAddr = "127.0.0.1:4133".parse().unwrap();
+        peers.update(PeersRequest::PeerVerified(stranger_ip)).await;
+        assert!(peers.peer_response().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_peer_connection_events() {
+        let peers = Peers::<Testnet2, Client<Testnet2>>::new("127.0.0.1:4130".parse().unwrap(), None, None).await;
+        let mut events = peers.events().subscribe();
+
+        // Ensure a connected peer is announced with its connection metadata.
+        let peer_ip: SocketAddr = "127.0.0.1:4131".parse().unwrap();
+        let direction = ConnectionDirection::Outbound;
+        let (outbound_router, _outbound_handler) = mpsc::channel(1);
+        let request = PeersRequest::PeerConnected(peer_ip, 1, 12, NodeType::Sync, direction, 1234, outbound_router);
+        peers.update(request).await;
+        assert_eq!(
+            Event::PeerConnected(peer_ip, 12, NodeType::Sync, direction, 1234),
+            events.recv().await.unwrap()
+        );
+
+        // Ensure a disconnected peer is announced once, even if it is removed again.
+        peers.update(PeersRequest::PeerDisconnected(peer_ip)).await;
+        peers.update(PeersRequest::PeerRestricted(peer_ip)).await;
+        assert_eq!(Event::PeerDisconnected(peer_ip), events.recv().await.unwrap());
+        assert!(events.try_recv().is_err());
+
+        // Ensure a restricted peer is announced as disconnected.
+        let (outbound_router, _outbound_handler) = mpsc::channel(1);
+        let request = PeersRequest::PeerConnected(peer_ip, 2, 12, NodeType::Sync, direction, 1234, outbound_router);
+        peers.update(request).await;
+        peers.update(PeersRequest::PeerRestricted(peer_ip)).await;
+        assert!(matches!(events.recv().await.unwrap(), Event::PeerConnected(..)));
+        assert_eq!(Event::PeerDisconnected(peer_ip), events.recv().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_replaced_connection_is_closed_without_disconnect() {
+        let peers = Peers::<Testnet2, Client<Testnet2>>::new("127.0.0.1:4130".parse().unwrap(), None, None).await;
+        let (ledger_router, mut ledger_handler) = mpsc::channel(1);
+        let (operator_router, _operator_handler) = mpsc::channel(1);
+
+        // Replace the connection to the peer, as when the connection is recycled.
+        let peer_ip: SocketAddr = "127.0.0.1:4131".parse().unwrap();
+        let direction = ConnectionDirection::Outbound;
+        let (recycled_router, _recycled_handler) = mpsc::channel(1);
+        let request = PeersRequest::PeerConnected(peer_ip, 1, 0, NodeType::Client, direction, 0, recycled_router.clone());
+        peers.update(request).await;
+        let (outbound_router, _outbound_handler) = mpsc::channel(1);
+        let request = PeersRequest::PeerConnected(peer_ip, 1, 0, NodeType::Client, direction, 0, outbound_router.clone());
+        peers.update(request).await;
+        assert_eq!(vec![(peer_ip, 1)], peers.connected_nonces().await);
+
+        // Ensure closing the replaced connection keeps the peer connected.
+        peers
+            .update(PeersRequest::PeerClosed(peer_ip, recycled_router, ledger_router.clone(), operator_router.clone()))
+            .await;
+        assert!(peers.is_connected_to(peer_ip).await);
+        assert!(ledger_handler.try_recv().is_err());
+
+        // Ensure closing the current connection disconnects the peer in the ledger.
+        peers
+            .update(PeersRequest::PeerClosed(peer_ip, outbound_router, ledger_router, operator_router))
+            .await;
+        assert!(matches!(ledger_handler.try_recv(), Ok(LedgerRequest::Disconnect(ip, _)) if ip == peer_ip));
+    }
+
+    #[test]
+    fn test_inbound_peer_lookup() {
+        type P = Peers<Testnet2, Client<Testnet2>>;
+
+        // Ensure inbound connections from the ephemeral ports of a remote host are tracked as one peer.
+        let lookup = P::inbound_peer_lookup("203.0.113.1:50001".parse().unwrap());
+        assert_eq!(lookup, P::inbound_peer_lookup("203.0.113.1:50002".parse().unwrap()));
+        assert_eq!("203.0.113.1:65535".parse::<SocketAddr>().unwrap(), lookup);
+
+        // Ensure loopback addresses are passed through, as local nodes connect from distinct ports.
+        let loopback: SocketAddr = "127.0.0.1:50001".parse().unwrap();
+        assert_eq!(loopback, P::inbound_peer_lookup(loopback));
+    }
+
+    #[tokio::test]
+    async fn test_slow_peer_event() {
+        let peers = Peers::<Testnet2, Client<Testnet2>>::new("127.0.0.1:4130".parse().unwrap(), None, None).await;
+        let mut events = peers.events().subscribe();
+
+        // Ensure a slow peer is announced with its round-trip time.
+        let peer_ip: SocketAddr = "127.0.0.1:4131".parse().unwrap();
+        let rtt = Duration::from_millis(Client::<Testnet2>::SLOW_PEER_RTT_THRESHOLD_IN_MILLIS + 1);
+        peers.update(PeersRequest::SlowPeer(peer_ip, rtt)).await;
+        assert_eq!(Event::SlowPeer(peer_ip, rtt), events.recv().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_seeks_peers_to_mine() {
+        type E = Miner<Testnet2>;
+        assert!(E::MINIMUM_NUMBER_OF_PEERS < E::MINIMUM_PEERS_TO_MINE);
+        let peers = Peers::<Testnet2, E>::new("127.0.0.1:4130".parse().unwrap(), None, None).await;
+
+        // Connect to the minimum number of peers, which is fewer than the number required to mine.
+        let peer_ip: SocketAddr = "127.0.0.1:4131".parse().unwrap();
+        let (outbound_router, mut outbound_handler) = mpsc::channel(1024);
+        let request = PeersRequest::PeerConnected(peer_ip, 1, 0, NodeType::Miner, ConnectionDirection::Outbound, 0, outbound_router);
+        peers.update(request).await;
+        assert_eq!(E::MINIMUM_NUMBER_OF_PEERS, peers.number_of_connected_peers().await);
+
+        // Ensure the heartbeat keeps requesting peers, so that the miner can reach the number required to mine.
+        let ledger_reader = Arc::new(LedgerState::open_writer::<RocksDB, _>(tempfile::tempdir().unwrap().into_path()).unwrap());
+        let (ledger_router, _ledger_handler) = mpsc::channel(1024);
+        let (operator_router, _operator_handler) = mpsc::channel(1024);
+        let (prover_router, _prover_handler) = mpsc::channel(1024);
+        peers
+            .update(PeersRequest::Heartbeat(ledger_reader, ledger_router, operator_router, prover_router))
+            .await;
+        let mut messages = std::iter::from_fn(|| outbound_handler.try_recv().ok());
+        assert!(messages.any(|message| matches!(message, Message::PeerRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_peer_reputation() {
+        let peers = Peers::<Testnet2, Client<Testnet2>>::new("127.0.0.1:4130".parse().unwrap(), None, None).await;
+        let peer_ip: SocketAddr = "127.0.0.1:4131".parse().unwrap();
+        assert!(peers.reputation(peer_ip).await.is_none());
+
+        // Ensure the restrictions of a peer are recorded in its reputation.
+        peers.update(PeersRequest::PeerRestricted(peer_ip)).await;
+        let reputation = peers.reputation(peer_ip).await.unwrap();
+        assert_eq!(1, reputation.restrictions);
+        assert!(reputation.last_restricted.is_some());
+
+        // Ensure a repeated restriction lasts longer than the first one.
+        let radio_silence = Duration::from_secs(Client::<Testnet2>::RADIO_SILENCE_IN_SECS);
+        peers.update(PeersRequest::PeerRestricted(peer_ip)).await;
+        assert_eq!(2, peers.reputation(peer_ip).await.unwrap().restrictions);
+        let expiry = *peers.restricted_peers.read().await.get(&peer_ip).unwrap();
+        assert!(expiry > Instant::now() + radio_silence);
+        assert!(peers.is_restricted(peer_ip).await);
+    }
+
+    #[tokio::test]
+    async fn test_message_history_removed_on_restriction() {
+        let peers = Peers::<Testnet2, Client<Testnet2>>::new("127.0.0.1:4130".parse().unwrap(), None, None).await;
+        let peer_ip: SocketAddr = "127.0.0.1:4131".parse().unwrap();
+
+        // Record a message in the history of the peer, as its connection would.
+        let message_history = Arc::new(parking_lot::Mutex::new(MessageHistory::new()));
+        peers.message_histories.write().await.insert(peer_ip, message_history.clone());
+        message_history.lock().push("Ping", Instant::now());
+        assert_eq!(1, peers.message_history(peer_ip).await.len());
+
+        // Ensure the history of a restricted peer is removed.
+        peers.update(PeersRequest::PeerRestricted(peer_ip)).await;
+        assert!(peers.message_history(peer_ip).await.is_empty());
+        assert!(peers.message_histories.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_peer_banned() {
+        let peers = Peers::<Testnet2, Client<Testnet2>>::new("127.0.0.1:4130".parse().unwrap(), None, None).await;
+        let peer_ip: SocketAddr = "127.0.0.1:4131".parse().unwrap();
+
+        // Ensure a restricted peer is not banned.
+        peers.update(PeersRequest::PeerRestricted(peer_ip)).await;
+        assert!(peers.is_restricted(peer_ip).await);
+        assert!(!peers.is_banned(peer_ip).await);
+
+        // Ensure a banned peer is disconnected, restricted, and banned.
+        let (outbound_router, _outbound_handler) = mpsc::channel(1);
+        let request = PeersRequest::PeerConnected(peer_ip, 1, 0, NodeType::Client, ConnectionDirection::Inbound, 0, outbound_router);
+        peers.update(request).await;
+        peers.update(PeersRequest::PeerBanned(peer_ip)).await;
+        assert!(!peers.is_connected_to(peer_ip).await);
+        assert!(peers.is_restricted(peer_ip).await);
+        assert!(peers.is_banned(peer_ip).await);
+        assert_eq!(2, peers.reputation(peer_ip).await.unwrap().restrictions);
+    }
+
+    #[tokio::test]
+    async fn test_connected_peer_kinds() {
+        let peers = Peers::<Testnet2, Client<Testnet2>>::new("127.0.0.1:4130".parse().unwrap(), None, None).await;
+
+        // Connect one inbound and two outbound peers of known types.
+        let connections = [
+            ("127.0.0.1:4131", NodeType::Client, ConnectionDirection::Inbound),
+            ("127.0.0.1:4132", NodeType::Sync, ConnectionDirection::Outbound),
+            ("127.0.0.1:4133", NodeType::Prover, ConnectionDirection::Outbound),
+        ];
+        for (nonce, (peer_ip, node_type, direction)) in connections.iter().enumerate() {
+            let (outbound_router, _outbound_handler) = mpsc::channel(1);
+            let request = PeersRequest::PeerConnected(
+                peer_ip.parse().unwrap(),
+                nonce as u64,
+                0,
+                *node_type,
+                *direction,
+                0,
+                outbound_router,
+            );
+            peers.update(request).await;
+        }
+
+        // Ensure the node type and direction of each connection is recorded.
+        let mut peer_kinds = peers.connected_peer_kinds().await;
+        peer_kinds.sort_by_key(|(node_type, _)| node_type.to_string());
+        let mut expected: Vec<_> = connections
+            .iter()
+            .map(|(_, node_type, direction)| (*node_type, *direction))
+            .collect();
+        expected.sort_by_key(|(node_type, _)| node_type.to_string());
+        assert_eq!(expected, peer_kinds);
+
+        // Ensure a disconnected peer is no longer counted.
+        peers
+            .update(PeersRequest::PeerDisconnected("127.0.0.1:4131".parse().unwrap()))
+            .await;
+        let peer_kinds = peers.connected_peer_kinds().await;
+        assert_eq!(2, peer_kinds.len());
+        assert!(peer_kinds.iter().all(|(_, direction)| *direction == ConnectionDirection::Outbound));
+    }
+
+    #[tokio::test]
+    async fn test_detected_external_addr() {
+        // Initialize a node behind a NAT, which only knows its local address.
+        let local_ip: SocketAddr = "192.168.1.10:4132".parse().unwrap();
+        let peers = Peers::<Testnet2, Client<Testnet2>>::new(local_ip, None, None).await;
+        assert_eq!(None, peers.detected_external_addr().await);
+
+        let reporter = |index: u8| SocketAddr::from(([198, 51, 100, index], 4132));
+
+        // Ensure peers reporting the local address do not change the external address.
+        for index in 0..3 {
+            peers.update(PeersRequest::ReceivePeerResponse(Some((reporter(index), local_ip)), vec![])).await;
+        }
+        assert_eq!(None, peers.detected_external_addr().await);
+
+        // Ensure the translated address is only detected as the external address once it is reported by a majority.
+        let external_ip: SocketAddr = "203.0.113.7:4132".parse().unwrap();
+        for index in 3..6 {
+            assert_eq!(None, peers.detected_external_addr().await);
+            peers.update(PeersRequest::ReceivePeerResponse(Some((reporter(index), external_ip)), vec![])).await;
+        }
+        assert_eq!(None, peers.detected_external_addr().await);
+        peers.update(PeersRequest::ReceivePeerResponse(Some((reporter(6), external_ip)), vec![])).await;
+        assert_eq!(Some(external_ip), peers.detected_external_addr().await);
+
+        // Ensure a response without a reported address keeps the detected external address.
+        peers.update(PeersRequest::ReceivePeerResponse(None, vec![])).await;
+        assert_eq!(Some(external_ip), peers.detected_external_addr().await);
+    }
+
+    #[tokio::test]
+    async fn test_detected_external_addr_requires_distinct_peers() {
+        let local_ip: SocketAddr = "192.168.1.10:4132".parse().unwrap();
+        let peers = Peers::<Testnet2, Client<Testnet2>>::new(local_ip, None, None).await;
+
+        // Ensure a single peer cannot set the external address, from a single connection or from several ports.
+        let spoofed_ip: SocketAddr = "203.0.113.66:4132".parse().unwrap();
+        for port in 0..(2 * Client::<Testnet2>::EXTERNAL_ADDR_REPORT_WINDOW as u16) {
+            let reporter = SocketAddr::from(([198, 51, 100, 1], 4000 + port));
+            peers.update(PeersRequest::ReceivePeerResponse(Some((reporter, spoofed_ip)), vec![])).await;
+        }
+        assert_eq!(None, peers.detected_external_addr().await);
+
+        // Ensure distinct peers agreeing on the external address override it.
+        let external_ip: SocketAddr = "203.0.113.7:4132".parse().unwrap();
+        for index in 2..(2 + Client::<Testnet2>::MINIMUM_EXTERNAL_ADDR_REPORTERS as u8) {
+            let reporter = SocketAddr::from(([198, 51, 100, index], 4132));
+            peers.update(PeersRequest::ReceivePeerResponse(Some((reporter, external_ip)), vec![])).await;
+        }
+        assert_eq!(Some(external_ip), peers.detected_external_addr().await);
+
+        // Ensure the detected external address survives a lone dissenting peer.
+        let reporter = SocketAddr::from(([198, 51, 100, 1], 4132));
+        peers.update(PeersRequest::ReceivePeerResponse(Some((reporter, spoofed_ip)), vec![])).await;
+        assert_eq!(Some(external_ip), peers.detected_external_addr().await);
+    }
+
+    #[tokio::test]
+    async fn test_max_inbound_connections() {
+        type E = InboundLimitedNode;
+        assert_eq!(2, E::MAX_INBOUND_CONNECTIONS);
+
+        let peers = Peers::<Testnet2, E>::new("127.0.0.1:4130".parse().unwrap(), None, None).await;
+
+        // Fill the inbound slots.
+        for (nonce, peer_ip) in ["127.0.0.1:4131", "127.0.0.1:4132"].iter().enumerate() {
+            let (outbound_router, _outbound_handler) = mpsc::channel(1);
+            let request = PeersRequest::PeerConnected(
+                peer_ip.parse().unwrap(),
+                nonce as u64,
+                0,
+                NodeType::Client,
+                ConnectionDirection::Inbound,
+                0,
+                outbound_router,
+            );
+            peers.update(request).await;
+        }
+        assert_eq!(E::MAX_INBOUND_CONNECTIONS, peers.number_of_inbound_peers().await);
+
+        // Attempt one more inbound connection.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).await.unwrap();
+        let (stream, peer_ip) = listener.accept().await.unwrap();
+
+        let ledger_reader = Arc::new(LedgerState::open_writer::<RocksDB, _>(tempfile::tempdir().unwrap().into_path()).unwrap());
+        let request = PeersRequest::PeerConnecting(
+            stream,
+            peer_ip,
+            ledger_reader,
+            mpsc::channel(1).0,
+            mpsc::channel(1).0,
+            mpsc::channel(1).0,
+        );
+        peers.update(request).await;
+
+        // Ensure the inbound connection is rejected with a disconnect, after an empty `Hello`.
+        assert_eq!(FeatureFlags::empty().to_hello(), client.read_u8().await.unwrap());
+        let mut client = Framed::new(client, MessageCodec::<Testnet2, E>::default());
+        match client.next().await {
+            Some(Ok(Message::Disconnect(reason))) => assert_eq!(DisconnectReason::TooManyPeers, reason),
+            _ => panic!("Expected a 'Disconnect' message"),
+        }
+        assert_eq!(E::MAX_INBOUND_CONNECTIONS, peers.number_of_inbound_peers().await);
+
+        // Ensure an outbound connection still fills the remaining peer slot.
+        let (outbound_router, _outbound_handler) = mpsc::channel(1);
+        let request = PeersRequest::PeerConnected(
+            "127.0.0.1:4133".parse().unwrap(),
+            3,
+            0,
+            NodeType::Client,
+            ConnectionDirection::Outbound,
+            0,
+            outbound_router,
+        );
+        peers.update(request).await;
+        assert_eq!(E::MAXIMUM_NUMBER_OF_PEERS, peers.number_of_connected_peers().await);
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use snarkvm::dpc::{posw::PoSWProof, prelude::*};
+
+use rand::thread_rng;
+use std::{fmt::Debug, sync::atomic::AtomicBool};
+
+///
+/// A backend that computes the proof of succinct work for a block template.
+///
+/// The prover dispatches proving attempts to its backend, so alternative implementations
+/// (for example, a GPU-accelerated one) may be swapped in without changing the prover.
+///
+pub trait ProofBackend<N: Network>: Debug + Send + Sync {
+    ///
+    /// Attempts to prove the given block template once, returning the nonce and proof on success.
+    /// Returns `None` if the attempt failed, or if it was interrupted by the terminator.
+    ///
+    fn prove(&self, template: &BlockTemplate<N>, terminator: &AtomicBool) -> Option<(N::PoSWNonce, PoSWProof<N>)>;
+}
+
+///
+/// A proof backend that computes proofs on the CPU, using the threads of the current thread pool.
+///
+#[derive(Clone, Debug, Default)]
+pub struct CpuProofBackend;
+
+impl<N: Network> ProofBackend<N> for CpuProofBackend {
+    fn prove(&self, template: &BlockTemplate<N>, terminator: &AtomicBool) -> Option<(N::PoSWNonce, PoSWProof<N>)> {
+        match BlockHeader::mine_once_unchecked(template, terminator, &mut thread_rng()) {
+            Ok(block_header) => Some((block_header.nonce(), block_header.proof().clone())),
+            Err(error) => {
+                trace!("Failed to prove block template {}: {}", template.block_height(), error);
+                None
+            }
+        }
+    }
+}
@@ -15,7 +15,8 @@
 // along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-    helpers::{NodeType, State},
+    helpers::{Event, Events, NodeType, State},
+    CpuProofBackend,
     Data,
     Environment,
     LedgerReader,
@@ -24,17 +25,23 @@ use crate::{
     Message,
     PeersRequest,
     PeersRouter,
+    ProofBackend,
 };
-use snarkos_storage::{storage::Storage, ProverState};
+use snarkos_storage::{storage::Storage, CoinbaseSplit, ProverState};
 use snarkvm::dpc::{posw::PoSWProof, prelude::*};
 
 use anyhow::{anyhow, Result};
 use rand::thread_rng;
+use rayon::prelude::*;
 use std::{
+    collections::{HashMap, HashSet},
     net::SocketAddr,
     path::Path,
-    sync::{atomic::Ordering, Arc},
-    time::Duration,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
 };
 use tokio::{
     sync::{mpsc, oneshot, RwLock},
@@ -47,6 +54,9 @@ pub(crate) type ProverRouter<N> = mpsc::Sender<ProverRequest<N>>;
 /// Shorthand for the child half of the `Prover` message channel.
 type ProverHandler<N> = mpsc::Receiver<ProverRequest<N>>;
 
+/// Shorthand for the map of unconfirmed transaction IDs to the timestamp at which they entered the memory pool.
+pub(crate) type MemoryPoolTimestamps<N> = Arc<RwLock<HashMap<<N as Network>::TransactionID, i64>>>;
+
 /// The miner heartbeat in seconds.
 const MINER_HEARTBEAT_IN_SECONDS: Duration = Duration::from_secs(2);
 
@@ -57,8 +67,16 @@ const MINER_HEARTBEAT_IN_SECONDS: Duration = Duration::from_secs(2);
 pub enum ProverRequest<N: Network> {
     /// PoolRequest := (peer_ip, share_difficulty, block_template)
     PoolRequest(SocketAddr, u64, BlockTemplate<N>),
+    /// PoolRequestMulti := (peer_ip, \[(share_difficulty, block_template)\])
+    PoolRequestMulti(SocketAddr, Vec<(u64, BlockTemplate<N>)>),
     /// MemoryPoolClear := (block)
     MemoryPoolClear(Option<Block<N>>),
+    /// MempoolRequest := (peer_ip)
+    MempoolRequest(SocketAddr),
+    /// MempoolResponse := (peer_ip, \[transaction_id\])
+    MempoolResponse(SocketAddr, Vec<N::TransactionID>),
+    /// TransactionRequest := (peer_ip, \[transaction_id\])
+    TransactionRequest(SocketAddr, Vec<N::TransactionID>),
     /// UnconfirmedTransaction := (peer_ip, transaction)
     UnconfirmedTransaction(SocketAddr, Transaction<N>),
 }
@@ -72,30 +90,70 @@ pub struct Prover<N: Network, E: Environment> {
     state: Arc<ProverState<N>>,
     /// The Aleo address of the prover.
     address: Option<Address<N>>,
+    /// The coinbase split of the miner, if the coinbase reward is split across multiple addresses.
+    coinbase_split: Option<CoinbaseSplit<N>>,
     /// The IP address of the connected pool.
     pool: Option<SocketAddr>,
     /// The prover router of the node.
     prover_router: ProverRouter<N>,
     /// The pool of unconfirmed transactions.
     memory_pool: Arc<RwLock<MemoryPool<N>>>,
+    /// The timestamp at which each unconfirmed transaction entered the memory pool.
+    memory_pool_timestamps: MemoryPoolTimestamps<N>,
     /// The peers router of the node.
     peers_router: PeersRouter<N, E>,
     /// The ledger state of the node.
     ledger_reader: LedgerReader<N>,
     /// The ledger router of the node.
     ledger_router: LedgerRouter<N>,
+    /// The backend that computes the proofs of the prover.
+    backend: Arc<dyn ProofBackend<N>>,
+    /// The event channel of the node.
+    events: Events<N>,
 }
 
 impl<N: Network, E: Environment> Prover<N, E> {
     /// Initializes a new instance of the prover.
+    #[allow(clippy::too_many_arguments)]
     pub async fn open<S: Storage, P: AsRef<Path> + Copy>(
         path: P,
         address: Option<Address<N>>,
+        coinbase_split: Option<CoinbaseSplit<N>>,
+        local_ip: SocketAddr,
+        pool_ip: Option<SocketAddr>,
+        peers_router: PeersRouter<N, E>,
+        ledger_reader: LedgerReader<N>,
+        ledger_router: LedgerRouter<N>,
+        events: Events<N>,
+    ) -> Result<Arc<Self>> {
+        Self::with_backend::<S, P, _>(
+            path,
+            address,
+            coinbase_split,
+            local_ip,
+            pool_ip,
+            peers_router,
+            ledger_reader,
+            ledger_router,
+            events,
+            CpuProofBackend,
+        )
+        .await
+    }
+
+    /// Initializes a new instance of the prover, which computes its proofs with the given backend.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn with_backend<S: Storage, P: AsRef<Path> + Copy, B: ProofBackend<N> + 'static>(
+        path: P,
+        address: Option<Address<N>>,
+        coinbase_split: Option<CoinbaseSplit<N>>,
         local_ip: SocketAddr,
         pool_ip: Option<SocketAddr>,
         peers_router: PeersRouter<N, E>,
         ledger_reader: LedgerReader<N>,
         ledger_router: LedgerRouter<N>,
+        events: Events<N>,
+        backend: B,
     ) -> Result<Arc<Self>> {
         // Initialize an mpsc channel for sending requests to the `Prover` struct.
         let (prover_router, mut prover_handler) = mpsc::channel(1024);
@@ -103,12 +161,16 @@ impl<N: Network, E: Environment> Prover<N, E> {
         let prover = Arc::new(Self {
             state: Arc::new(ProverState::open_writer::<S, P>(path)?),
             address,
+            coinbase_split,
             pool: pool_ip,
             prover_router,
             memory_pool: Arc::new(RwLock::new(MemoryPool::new())),
+            memory_pool_timestamps: Default::default(),
             peers_router,
             ledger_reader,
             ledger_router,
+            backend: Arc::new(backend),
+            events,
         });
 
         // Initialize the handler for the prover.
@@ -169,6 +231,11 @@ impl<N: Network, E: Environment> Prover<N, E> {
         self.memory_pool.clone()
     }
 
+    /// Returns an instance of the timestamps at which the unconfirmed transactions entered the memory pool.
+    pub(crate) fn memory_pool_timestamps(&self) -> MemoryPoolTimestamps<N> {
+        self.memory_pool_timestamps.clone()
+    }
+
     /// Returns all coinbase records in storage.
     pub fn to_coinbase_records(&self) -> Vec<(u32, Record<N>)> {
         self.state.to_coinbase_records()
@@ -182,12 +249,67 @@ impl<N: Network, E: Environment> Prover<N, E> {
         match request {
             ProverRequest::PoolRequest(operator_ip, share_difficulty, block_template) => {
                 // Process the pool request message.
-                self.process_pool_request(operator_ip, share_difficulty, block_template).await;
+                self.process_pool_request(operator_ip, vec![(share_difficulty, block_template)])
+                    .await;
+            }
+            ProverRequest::PoolRequestMulti(operator_ip, block_templates) => {
+                // Process the pool request message, in order of priority of the block templates.
+                self.process_pool_request(operator_ip, block_templates).await;
+            }
+            ProverRequest::MemoryPoolClear(block) => {
+                let mut memory_pool = self.memory_pool.write().await;
+                match block {
+                    Some(block) => memory_pool.remove_transactions(block.transactions()),
+                    None => *memory_pool = MemoryPool::new(),
+                }
+                // Remove the timestamps of the transactions that have left the memory pool.
+                let remaining_transaction_ids: HashSet<_> = memory_pool
+                    .transactions()
+                    .iter()
+                    .map(|transaction| transaction.transaction_id())
+                    .collect();
+                self.memory_pool_timestamps
+                    .write()
+                    .await
+                    .retain(|transaction_id, _| remaining_transaction_ids.contains(transaction_id));
+                // Update the memory pool size, as advertised to peers.
+                E::memory_pool_size().store(memory_pool.transactions().len() as u32, Ordering::SeqCst);
+            }
+            ProverRequest::MempoolRequest(peer_ip) => {
+                // Share the IDs of the unconfirmed transactions, up to the maximum response size.
+                let transaction_ids = self
+                    .memory_pool
+                    .read()
+                    .await
+                    .transactions()
+                    .iter()
+                    .take(E::MAXIMUM_MEMPOOL_RESPONSE_SIZE)
+                    .map(|transaction| transaction.transaction_id())
+                    .collect();
+                self.send(peer_ip, Message::MempoolResponse(transaction_ids)).await;
+            }
+            ProverRequest::MempoolResponse(peer_ip, transaction_ids) => {
+                // Request the unconfirmed transactions that are not yet known to this node.
+                let known_transaction_ids = self.memory_pool_transaction_ids().await;
+                let unknown_transaction_ids: Vec<_> = transaction_ids
+                    .into_iter()
+                    .filter(|transaction_id| !known_transaction_ids.contains(transaction_id))
+                    .filter(|transaction_id| matches!(self.ledger_reader.contains_transaction(transaction_id), Ok(false)))
+                    .collect();
+                if !unknown_transaction_ids.is_empty() {
+                    self.send(peer_ip, Message::TransactionRequest(unknown_transaction_ids)).await;
+                }
+            }
+            ProverRequest::TransactionRequest(peer_ip, transaction_ids) => {
+                // Send the requested unconfirmed transactions that are in the memory pool.
+                let transaction_ids: HashSet<_> = transaction_ids.into_iter().collect();
+                let transactions = self.memory_pool.read().await.transactions();
+                for transaction in transactions {
+                    if transaction_ids.contains(&transaction.transaction_id()) {
+                        self.send(peer_ip, Message::UnconfirmedTransaction(transaction)).await;
+                    }
+                }
             }
-            ProverRequest::MemoryPoolClear(block) => match block {
-                Some(block) => self.memory_pool.write().await.remove_transactions(block.transactions()),
-                None => *self.memory_pool.write().await = MemoryPool::new(),
-            },
             ProverRequest::UnconfirmedTransaction(peer_ip, transaction) => {
                 // Ensure the node is not peering.
                 if !E::status().is_peering() {
@@ -198,6 +320,29 @@ impl<N: Network, E: Environment> Prover<N, E> {
         }
     }
 
+    ///
+    /// Sends the given message to the given peer.
+    ///
+    async fn send(&self, peer_ip: SocketAddr, message: Message<N, E>) {
+        let name = message.name().to_string();
+        if let Err(error) = self.peers_router.send(PeersRequest::MessageSend(peer_ip, message)).await {
+            warn!("[{}] {}", name, error);
+        }
+    }
+
+    ///
+    /// Returns the IDs of the unconfirmed transactions in the memory pool.
+    ///
+    async fn memory_pool_transaction_ids(&self) -> HashSet<N::TransactionID> {
+        self.memory_pool
+            .read()
+            .await
+            .transactions()
+            .iter()
+            .map(|transaction| transaction.transaction_id())
+            .collect()
+    }
+
     ///
     /// Sends a `PoolRegister` message to the pool IP address.
     ///
@@ -220,9 +365,9 @@ impl<N: Network, E: Environment> Prover<N, E> {
     }
 
     ///
-    /// Processes a `PoolRequest` message from a pool operator.
+    /// Processes a `PoolRequest` or `PoolRequestMulti` message from a pool operator.
     ///
-    async fn process_pool_request(&self, operator_ip: SocketAddr, share_difficulty: u64, block_template: BlockTemplate<N>) {
+    async fn process_pool_request(&self, operator_ip: SocketAddr, block_templates: Vec<(u64, BlockTemplate<N>)>) {
         if E::NODE_TYPE == NodeType::Prover {
             if let Some(recipient) = self.address {
                 if let Some(pool_ip) = self.pool {
@@ -231,43 +376,36 @@ impl<N: Network, E: Environment> Prover<N, E> {
                         // If `terminator` is `false` and the status is not `Peering` or `Mining`
                         // already, mine the next block.
                         if !E::terminator().load(Ordering::SeqCst) && !E::status().is_peering() && !E::status().is_mining() {
+                            // Ensure there is at least one block template.
+                            if block_templates.is_empty() {
+                                warn!("[PoolRequest] Received no block templates from {}", operator_ip);
+                                return;
+                            }
+
                             // Set the status to `Mining`.
                             E::status().update(State::Mining);
+                            E::proving_progress().start(Instant::now());
 
-                            let block_height = block_template.block_height();
-                            let block_template = block_template.clone();
+                            let block_heights = block_templates
+                                .iter()
+                                .map(|(_, block_template)| block_template.block_height())
+                                .collect::<Vec<_>>();
+                            E::prover_stats().record_template(block_heights[0], Instant::now());
 
+                            let backend = self.backend.clone();
                             let result = task::spawn_blocking(move || {
-                                E::thread_pool().install(move || {
-                                    loop {
-                                        let block_header =
-                                            BlockHeader::mine_once_unchecked(&block_template, E::terminator(), &mut thread_rng())?;
-
-                                        // Ensure the share difficulty target is met.
-                                        if N::posw().verify(
-                                            block_header.height(),
-                                            share_difficulty,
-                                            &[*block_header.to_header_root().unwrap(), *block_header.nonce()],
-                                            block_header.proof(),
-                                        ) {
-                                            return Ok::<(N::PoSWNonce, PoSWProof<N>, u64), anyhow::Error>((
-                                                block_header.nonce(),
-                                                block_header.proof().clone(),
-                                                block_header.proof().to_proof_difficulty()?,
-                                            ));
-                                        }
-                                    }
-                                })
+                                E::thread_pool().install(move || Self::solve_block_templates(&*backend, &block_templates))
                             })
                             .await;
 
+                            E::proving_progress().finish(Instant::now(), matches!(result, Ok(Ok(_))));
                             E::status().update(State::Ready);
 
                             match result {
-                                Ok(Ok((nonce, proof, proof_difficulty))) => {
+                                Ok(Ok((index, nonce, proof, proof_difficulty))) => {
                                     info!(
                                         "Prover successfully mined a share for unconfirmed block {} with proof difficulty of {}",
-                                        block_height, proof_difficulty
+                                        block_heights[index], proof_difficulty
                                     );
 
                                     // Send a `PoolResponse` to the operator.
@@ -290,6 +428,67 @@ impl<N: Network, E: Environment> Prover<N, E> {
         }
     }
 
+    ///
+    /// Attempts to solve the given block templates in parallel, until one of them meets its share difficulty.
+    /// Returns the index of the solved block template, along with its nonce, proof, and proof difficulty.
+    /// If multiple block templates are solved at once, the one with the highest priority (lowest index) is returned.
+    ///
+    fn solve_block_templates(
+        backend: &dyn ProofBackend<N>,
+        block_templates: &[(u64, BlockTemplate<N>)],
+    ) -> Result<(usize, N::PoSWNonce, PoSWProof<N>, u64)> {
+        // Once a block template is solved, the remaining attempts are stopped.
+        let is_solved = AtomicBool::new(false);
+
+        let solutions = block_templates
+            .par_iter()
+            .enumerate()
+            .map(|(index, (share_difficulty, block_template))| {
+                let header_root = block_template.to_header_root()?;
+                while !is_solved.load(Ordering::SeqCst) {
+                    // Stop attempting the block template, if the backend fails to prove it.
+                    let (nonce, proof) = match backend.prove(block_template, E::terminator()) {
+                        Some(solution) => solution,
+                        None => return Ok(None),
+                    };
+                    E::prover_stats().record_attempts(1, Instant::now());
+
+                    // Ensure the share difficulty target is met.
+                    if N::posw().verify(block_template.block_height(), *share_difficulty, &[*header_root, *nonce], &proof) {
+                        is_solved.store(true, Ordering::SeqCst);
+                        let proof_difficulty = proof.to_proof_difficulty()?;
+                        return Ok(Some((index, nonce, proof, proof_difficulty)));
+                    }
+                }
+                Ok(None)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        solutions
+            .into_iter()
+            .flatten()
+            .next()
+            .ok_or_else(|| anyhow!("Failed to solve any of the {} block templates", block_templates.len()))
+    }
+
+    ///
+    /// Mines the next block with the given function, and records the time taken to find a valid block.
+    ///
+    fn mine_and_record_duration<T>(mine: impl FnOnce() -> Result<T>) -> Result<T> {
+        #[cfg(feature = "prometheus")]
+        let start = Instant::now();
+
+        let result = mine();
+
+        // Record the time taken to find a valid block.
+        #[cfg(feature = "prometheus")]
+        if result.is_ok() {
+            snarkos_metrics::histogram!(snarkos_metrics::BLOCK_MINING_DURATION_SECONDS, start.elapsed().as_secs_f64());
+        }
+
+        result
+    }
+
     ///
     /// Adds the given unconfirmed transaction to the memory pool.
     ///
@@ -298,10 +497,31 @@ impl<N: Network, E: Environment> Prover<N, E> {
         trace!("Received unconfirmed transaction {} from {}", transaction.transaction_id(), peer_ip);
         // Ensure the unconfirmed transaction is new.
         if let Ok(false) = self.ledger_reader.contains_transaction(&transaction.transaction_id()) {
+            // Ensure the fee of the unconfirmed transaction is acceptable to the network.
+            if !E::FeePolicy::is_acceptable_transaction(&transaction) {
+                debug!("Rejecting unconfirmed transaction {} for its fee", transaction.transaction_id());
+                return;
+            }
+
             debug!("Adding unconfirmed transaction {} to memory pool", transaction.transaction_id());
             // Attempt to add the unconfirmed transaction to the memory pool.
             match self.memory_pool.write().await.add_transaction(&transaction) {
                 Ok(()) => {
+                    // Record the timestamp at which the transaction entered the memory pool.
+                    self.memory_pool_timestamps
+                        .write()
+                        .await
+                        .insert(transaction.transaction_id(), chrono::Utc::now().timestamp());
+                    // Update the memory pool size, as advertised to peers.
+                    E::memory_pool_size().fetch_add(1, Ordering::SeqCst);
+                    // Notify the subscribers of the accepted transaction.
+                    self.events.emit(Event::TransactionAccepted(transaction.transaction_id()));
+
+                    // Observer nodes only follow the network, and never relay transactions.
+                    if E::NODE_TYPE == NodeType::Observer {
+                        return;
+                    }
+
                     // Upon success, propagate the unconfirmed transaction to the connected peers.
                     let request = PeersRequest::MessagePropagate(peer_ip, Message::UnconfirmedTransaction(transaction));
                     if let Err(error) = self.peers_router.send(request).await {
@@ -320,6 +540,9 @@ impl<N: Network, E: Environment> Prover<N, E> {
         // Initialize a new instance of the miner.
         if E::NODE_TYPE == NodeType::Miner && prover.pool.is_none() {
             if let Some(recipient) = prover.address {
+                // Split the coinbase reward across the specified addresses, or pay it to the miner address.
+                let coinbase_split = prover.coinbase_split.clone().unwrap_or_else(|| CoinbaseSplit::from(recipient));
+
                 // Initialize the prover process.
                 let prover = prover.clone();
                 let (router, handler) = oneshot::channel();
@@ -331,43 +554,51 @@ impl<N: Network, E: Environment> Prover<N, E> {
                         if !E::terminator().load(Ordering::SeqCst) && !E::status().is_peering() && !E::status().is_mining() {
                             // Set the status to `Mining`.
                             E::status().update(State::Mining);
+                            E::proving_progress().start(Instant::now());
 
                             // Prepare the unconfirmed transactions and dependent objects.
                             let state = prover.state.clone();
                             let canon = prover.ledger_reader.clone(); // This is *safe* as the ledger only reads.
+                            E::prover_stats().record_template(canon.latest_block_height().saturating_add(1), Instant::now());
+                            E::prover_stats().record_attempts(1, Instant::now());
                             let unconfirmed_transactions = prover.memory_pool.read().await.transactions();
                             let ledger_router = prover.ledger_router.clone();
                             let prover_router = prover.prover_router.clone();
+                            let coinbase_split = coinbase_split.clone();
 
                             E::tasks().append(task::spawn(async move {
                                 // Mine the next block.
                                 let result = task::spawn_blocking(move || {
                                     E::thread_pool().install(move || {
-                                        canon.mine_next_block(
-                                            recipient,
-                                            E::COINBASE_IS_PUBLIC,
-                                            &unconfirmed_transactions,
-                                            E::terminator(),
-                                            &mut thread_rng(),
-                                        )
+                                        Self::mine_and_record_duration(|| {
+                                            canon.mine_next_block_with_coinbase_split(
+                                                &coinbase_split,
+                                                E::COINBASE_IS_PUBLIC,
+                                                &unconfirmed_transactions,
+                                                E::terminator(),
+                                                &mut thread_rng(),
+                                            )
+                                        })
                                     })
                                 })
                                 .await
                                 .map_err(|e| e.into());
 
                                 // Set the status to `Ready`.
+                                E::proving_progress().finish(Instant::now(), matches!(result, Ok(Ok(_))));
                                 E::status().update(State::Ready);
 
                                 match result {
                                     Ok(Ok((block, coinbase_record))) => {
                                         debug!("Miner has found unconfirmed block {} ({})", block.height(), block.hash());
+                                        E::prover_stats().record_block_found(Instant::now());
                                         // Store the coinbase record.
                                         if let Err(error) = state.add_coinbase_record(block.height(), coinbase_record) {
                                             warn!("[Miner] Failed to store coinbase record - {}", error);
                                         }
 
                                         // Broadcast the next block.
-                                        let request = LedgerRequest::UnconfirmedBlock(local_ip, block, prover_router.clone());
+                                        let request = LedgerRequest::UnconfirmedBlock(local_ip, block, prover_router.clone(), None);
                                         if let Err(error) = ledger_router.send(request).await {
                                             warn!("Failed to broadcast mined block - {}", error);
                                         }
@@ -388,3 +619,183 @@ impl<N: Network, E: Environment> Prover<N, E> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environment::Client;
+    use snarkos_storage::{storage::rocksdb::RocksDB, LedgerState};
+    use snarkvm::dpc::testnet2::Testnet2;
+
+    /// A proof backend that always returns the same nonce and proof.
+    #[derive(Debug)]
+    struct MockProofBackend {
+        nonce: <Testnet2 as Network>::PoSWNonce,
+        proof: PoSWProof<Testnet2>,
+    }
+
+    impl ProofBackend<Testnet2> for MockProofBackend {
+        fn prove(
+            &self,
+            _template: &BlockTemplate<Testnet2>,
+            _terminator: &AtomicBool,
+        ) -> Option<(<Testnet2 as Network>::PoSWNonce, PoSWProof<Testnet2>)> {
+            Some((self.nonce, self.proof.clone()))
+        }
+    }
+
+    fn temp_dir() -> std::path::PathBuf {
+        tempfile::tempdir().expect("Failed to open temporary directory").into_path()
+    }
+
+    #[test]
+    fn test_solve_block_templates() {
+        let rng = &mut thread_rng();
+
+        // Initialize two distinct block templates.
+        let ledger = LedgerState::<Testnet2>::open_writer::<RocksDB, _>(temp_dir()).expect("Failed to initialize ledger");
+        let block_templates = (0..2)
+            .map(|_| {
+                let recipient = Account::<Testnet2>::new(rng).address();
+                let block_template = ledger.get_block_template(recipient, true, &[], rng).unwrap();
+                (u64::MAX, block_template)
+            })
+            .collect::<Vec<_>>();
+
+        // Message the prover with both block templates.
+        let message = Message::<Testnet2, Client<Testnet2>>::PoolRequestMulti(
+            block_templates
+                .iter()
+                .map(|(share_difficulty, block_template)| (*share_difficulty, Data::Object(block_template.clone())))
+                .collect(),
+        );
+        let mut buffer = Vec::new();
+        message.serialize_into(&mut buffer).unwrap();
+        let block_templates = match Message::<Testnet2, Client<Testnet2>>::deserialize(&buffer).unwrap() {
+            Message::PoolRequestMulti(block_templates) => block_templates
+                .into_iter()
+                .map(|(share_difficulty, block_template)| (share_difficulty, block_template.deserialize_blocking().unwrap()))
+                .collect::<Vec<_>>(),
+            message => panic!("Expected a 'PoolRequestMulti', found '{}'", message.name()),
+        };
+        assert_eq!(2, block_templates.len());
+
+        // Solve the block templates.
+        let (index, nonce, proof, _) =
+            Prover::<Testnet2, Client<Testnet2>>::solve_block_templates(&CpuProofBackend, &block_templates).unwrap();
+        assert!(index < block_templates.len());
+
+        // Ensure the solution is for the solved block template, and not the other one.
+        let (share_difficulty, block_template) = &block_templates[index];
+        let (_, other_block_template) = &block_templates[1 - index];
+        assert!(Testnet2::posw().verify(
+            block_template.block_height(),
+            *share_difficulty,
+            &[*block_template.to_header_root().unwrap(), *nonce],
+            &proof,
+        ));
+        assert!(!Testnet2::posw().verify(
+            other_block_template.block_height(),
+            *share_difficulty,
+            &[*other_block_template.to_header_root().unwrap(), *nonce],
+            &proof,
+        ));
+
+        // Ensure the solution forms a block for the solved block template.
+        let block_header = BlockHeader::<Testnet2>::from(
+            block_template.previous_ledger_root(),
+            block_template.transactions().transactions_root(),
+            BlockHeaderMetadata::new(block_template),
+            nonce,
+            proof,
+        )
+        .unwrap();
+        let block = Block::from(
+            block_template.previous_block_hash(),
+            block_header,
+            block_template.transactions().clone(),
+        )
+        .unwrap();
+        assert_eq!(block_template.previous_block_hash(), block.previous_block_hash());
+        assert_eq!(block_template.block_height(), block.height());
+    }
+
+    #[tokio::test]
+    async fn test_prover_submits_backend_proof() {
+        type E = crate::environment::Prover<Testnet2>;
+        let rng = &mut thread_rng();
+
+        // Initialize a block template, and a fixed solution for it.
+        let ledger = Arc::new(LedgerState::<Testnet2>::open_writer::<RocksDB, _>(temp_dir()).expect("Failed to initialize ledger"));
+        let recipient = Account::<Testnet2>::new(rng).address();
+        let block_template = ledger.get_block_template(recipient, true, &[], rng).unwrap();
+        let (nonce, proof) = CpuProofBackend.prove(&block_template, &AtomicBool::new(false)).unwrap();
+
+        // Initialize a prover with the mock backend, registered to a pool.
+        let pool_ip: SocketAddr = "127.0.0.1:4132".parse().unwrap();
+        let (peers_router, mut peers_handler) = mpsc::channel(1024);
+        let (ledger_router, _ledger_handler) = mpsc::channel(1024);
+        let backend = MockProofBackend {
+            nonce,
+            proof: proof.clone(),
+        };
+        let prover = Prover::<Testnet2, E>::with_backend::<RocksDB, _, _>(
+            &temp_dir(),
+            Some(recipient),
+            None,
+            "127.0.0.1:4133".parse().unwrap(),
+            Some(pool_ip),
+            peers_router,
+            ledger,
+            ledger_router,
+            Events::new(16),
+            backend,
+        )
+        .await
+        .expect("Failed to initialize prover");
+
+        // Process a pool request from the pool.
+        E::status().update(State::Ready);
+        prover.update(ProverRequest::PoolRequest(pool_ip, u64::MAX, block_template)).await;
+
+        // Ensure the prover submits the solution of the backend to the pool.
+        loop {
+            match peers_handler.recv().await.expect("Failed to receive a peers request") {
+                PeersRequest::MessageSend(peer_ip, Message::PoolResponse(address, candidate_nonce, Data::Object(candidate_proof))) => {
+                    assert_eq!(pool_ip, peer_ip);
+                    assert_eq!(recipient, address);
+                    assert_eq!(nonce, candidate_nonce);
+                    assert_eq!(proof, candidate_proof);
+                    break;
+                }
+                // Skip the pool registration of the prover.
+                PeersRequest::MessageSend(_, Message::PoolRegister(..)) => continue,
+                request => panic!("Unexpected peers request: {:?}", request),
+            }
+        }
+    }
+
+    #[cfg(feature = "prometheus")]
+    #[test]
+    fn test_block_mining_duration() {
+        type P = Prover<Testnet2, crate::environment::Miner<Testnet2>>;
+        let handle = snarkos_metrics::initialize_recorder();
+        let count = |rendered: &str| {
+            let prefix = format!("{}_count ", snarkos_metrics::BLOCK_MINING_DURATION_SECONDS);
+            rendered.lines().find_map(|line| line.strip_prefix(&prefix).map(str::to_string))
+        };
+
+        // Mine a fake block, with a mock PoSW that succeeds on the first attempt.
+        assert!(P::mine_and_record_duration(|| Ok(())).is_ok());
+
+        // Ensure the miner records one observation, in the smallest bucket.
+        let rendered = handle.render();
+        let line = format!("{}_bucket{{le=\"1\"}} 1", snarkos_metrics::BLOCK_MINING_DURATION_SECONDS);
+        assert!(rendered.contains(&line), "missing '{}' in:\n{}", line, rendered);
+        assert_eq!(Some("1".to_string()), count(&rendered));
+
+        // Ensure a failed attempt to mine a block is not recorded.
+        assert!(P::mine_and_record_duration::<()>(|| Err(anyhow!("Failed to mine"))).is_err());
+        assert_eq!(Some("1".to_string()), count(&handle.render()));
+    }
+}
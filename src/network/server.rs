@@ -17,22 +17,24 @@
 use crate::{
     display::notification_message,
     environment::Environment,
-    helpers::{NodeType, State},
-    ledger::{Ledger, LedgerRequest, LedgerRouter},
+    helpers::{Event, NodeType, State},
+    ledger::{Ledger, LedgerRequest, LedgerRouter, PeersStateReader, SyncProgressReader, UnconfirmedBlocksReader},
     operator::{Operator, OperatorRouter},
     peers::{Peers, PeersRequest, PeersRouter},
-    prover::{Prover, ProverRouter},
-    rpc::initialize_rpc_server,
+    prover::{MemoryPoolTimestamps, Prover, ProverRouter},
+    rpc::{initialize_rpc_server, RpcMethodFilter},
     Node,
 };
+#[cfg(unix)]
+use crate::rpc::initialize_rpc_socket_server;
 use snarkos_storage::{storage::rocksdb::RocksDB, LedgerState};
 use snarkvm::prelude::*;
 
 use anyhow::Result;
-use std::{net::SocketAddr, sync::Arc, time::Duration};
+use std::{collections::HashMap, net::SocketAddr, sync::Arc, time::Duration};
 use tokio::{
     net::TcpListener,
-    sync::{oneshot, RwLock},
+    sync::{broadcast, oneshot, RwLock},
     task,
 };
 
@@ -61,6 +63,9 @@ impl<N: Network, E: Environment> Server<N, E> {
     ///
     #[inline]
     pub async fn initialize(node: &Node, address: Option<Address<N>>, pool_ip: Option<SocketAddr>) -> Result<Self> {
+        // Initialize the coinbase split of the miner, if one is specified.
+        let coinbase_split = node.coinbase_split::<N>()?;
+
         // Initialize a new TCP listener at the given IP.
         let (local_ip, listener) = match TcpListener::bind(node.node).await {
             Ok(listener) => (listener.local_addr().expect("Failed to fetch the local IP"), listener),
@@ -73,20 +78,24 @@ impl<N: Network, E: Environment> Server<N, E> {
         let operator_storage_path = node.operator_storage_path(local_ip);
         // Initialize the prover storage path.
         let prover_storage_path = node.prover_storage_path(local_ip);
+        // Initialize the peer reputation storage path.
+        let peer_reputation_storage_path = node.peer_reputation_storage_path(local_ip);
 
         // Initialize a new instance for managing peers.
-        let peers = Peers::new(local_ip, None).await;
+        let peers = Peers::new(local_ip, None, Some(peer_reputation_storage_path)).await;
         // Initialize a new instance for managing the ledger.
-        let ledger = Ledger::<N, E>::open::<RocksDB, _>(&ledger_storage_path, peers.router()).await?;
+        let ledger = Ledger::<N, E>::open::<RocksDB, _>(&ledger_storage_path, peers.router(), peers.events()).await?;
         // Initialize a new instance for managing the prover.
         let prover = Prover::open::<RocksDB, _>(
             &prover_storage_path,
             address,
+            coinbase_split,
             local_ip,
             pool_ip,
             peers.router(),
             ledger.reader(),
             ledger.router(),
+            ledger.events(),
         )
         .await?;
         // Initialize a new instance for managing the operator.
@@ -154,16 +163,35 @@ impl<N: Network, E: Environment> Server<N, E> {
             prover.router(),
         )
         .await;
+        // Initialize a new instance of the DNS seeder.
+        Self::initialize_dns_seeder(peers.router()).await;
         // Initialize a new instance of the heartbeat.
         Self::initialize_heartbeat(peers.router(), ledger.reader(), ledger.router(), operator.router(), prover.router()).await;
         // Initialize a new instance of the RPC server.
-        Self::initialize_rpc(node, address, &peers, ledger.reader(), prover.router(), prover.memory_pool()).await;
+        Self::initialize_rpc(
+            node,
+            address,
+            &peers,
+            ledger.reader(),
+            ledger.peers_state(),
+            ledger.sync_progress(),
+            ledger.unconfirmed_blocks(),
+            operator.clone(),
+            prover.router(),
+            prover.memory_pool(),
+            prover.memory_pool_timestamps(),
+        )
+        .await;
         // Initialize a new instance of the notification.
         Self::initialize_notification(ledger.reader(), prover.clone(), address).await;
 
-        // Initialise the metrics exporter.
-        #[cfg(feature = "prometheus")]
-        Self::initialize_metrics();
+        // Initialize the metrics endpoint, if it is enabled.
+        if let Some(metrics_addr) = node.metrics_addr::<E>() {
+            #[cfg(feature = "prometheus")]
+            Self::initialize_metrics(metrics_addr, peers.clone(), ledger.reader()).await;
+            #[cfg(not(feature = "prometheus"))]
+            warn!("The metrics endpoint at {} requires the node to be built with the 'prometheus' feature", metrics_addr);
+        }
 
         Ok(Self {
             local_ip,
@@ -184,6 +212,11 @@ impl<N: Network, E: Environment> Server<N, E> {
         self.peers.clone()
     }
 
+    /// Returns a new subscription to the event channel of this node.
+    pub fn events(&self) -> broadcast::Receiver<Event<N>> {
+        self.ledger.events().subscribe()
+    }
+
     ///
     /// Sends a connection request to the given IP address.
     ///
@@ -222,15 +255,22 @@ impl<N: Network, E: Environment> Server<N, E> {
         trace!("Proceeding to shut down the ledger...");
         let (canon_lock, block_requests_lock, storage_map_lock) = self.ledger.shut_down().await;
 
-        // Acquire the locks for ledger.
+        // Save the reputation of the peers, so that it carries over to the next start.
+        self.peers.save_reputations().await;
+
+        // Flush the tasks, forcibly aborting the ones that do not complete in time.
+        // The ledger locks are not held yet, so that a task waiting on them can still complete.
+        trace!("Proceeding to flush tasks...");
+        let number_of_aborted_tasks = E::tasks().flush_with_timeout(Duration::from_secs(10)).await;
+        if number_of_aborted_tasks > 0 {
+            warn!("Forcibly aborted {} tasks that did not complete within 10 seconds", number_of_aborted_tasks);
+        }
+
+        // Acquire the locks for ledger, to ensure no write to storage is still in progress.
         trace!("Proceeding to lock the ledger...");
         let _block_requests_lock = block_requests_lock.lock().await;
         let _canon_lock = canon_lock.lock().await;
         let _storage_map_lock = storage_map_lock.write();
-        trace!("Ledger has shut down, proceeding to flush tasks...");
-
-        // Flush the tasks.
-        E::tasks().flush();
         trace!("Node has shut down.");
     }
 
@@ -325,33 +365,121 @@ impl<N: Network, E: Environment> Server<N, E> {
         let _ = handler.await;
     }
 
+    ///
+    /// Initialize a new instance of the DNS seeder, which periodically resolves
+    /// the DNS seed hostnames and routes the resolved IPs to the candidate peers.
+    ///
+    #[inline]
+    async fn initialize_dns_seeder(peers_router: PeersRouter<N, E>) {
+        // Skip the DNS seeder if no seed hostnames are configured.
+        if E::DNS_SEEDS.is_empty() {
+            return;
+        }
+
+        // Initialize the DNS seeder process.
+        let (router, handler) = oneshot::channel();
+        E::tasks().append(task::spawn(async move {
+            // Notify the outer function that the task is ready.
+            let _ = router.send(());
+            // The last successfully-resolved peer IPs of each seed hostname.
+            let mut resolved_peers: HashMap<&'static str, Vec<SocketAddr>> = HashMap::with_capacity(E::DNS_SEEDS.len());
+            loop {
+                for seed in E::DNS_SEEDS {
+                    match tokio::net::lookup_host(*seed).await {
+                        Ok(peer_ips) => {
+                            let peer_ips: Vec<SocketAddr> = peer_ips.collect();
+                            debug!("Resolved {} peers from DNS seed {}", peer_ips.len(), seed);
+                            resolved_peers.insert(*seed, peer_ips);
+                        }
+                        // Keep the previously-resolved peer IPs for this seed hostname.
+                        Err(error) => warn!("Failed to resolve DNS seed {}: {}", seed, error),
+                    }
+                }
+
+                // Route the deduplicated peer IPs to the candidate peers.
+                let mut peer_ips: Vec<SocketAddr> = resolved_peers.values().flatten().copied().collect();
+                peer_ips.sort_unstable();
+                peer_ips.dedup();
+                if let Err(error) = peers_router.send(PeersRequest::ReceivePeerResponse(None, peer_ips)).await {
+                    error!("Failed to send DNS seeds to peers: {}", error)
+                }
+
+                // Sleep for `E::DNS_SEED_REFRESH_IN_SECS` seconds.
+                tokio::time::sleep(Duration::from_secs(E::DNS_SEED_REFRESH_IN_SECS)).await;
+            }
+        }));
+        // Wait until the DNS seeder task is ready.
+        let _ = handler.await;
+    }
+
     ///
     /// Initialize a new instance of the RPC server.
     ///
     #[inline]
+    #[allow(clippy::too_many_arguments)]
     async fn initialize_rpc(
         node: &Node,
         address: Option<Address<N>>,
         peers: &Arc<Peers<N, E>>,
         ledger_reader: LedgerReader<N>,
+        peers_state: PeersStateReader<N>,
+        sync_progress: SyncProgressReader,
+        unconfirmed_blocks: UnconfirmedBlocksReader<N>,
+        operator: Arc<Operator<N, E>>,
         prover_router: ProverRouter<N>,
         memory_pool: Arc<RwLock<MemoryPool<N>>>,
+        memory_pool_timestamps: MemoryPoolTimestamps<N>,
     ) {
         if !node.norpc {
-            // Initialize a new instance of the RPC server.
-            E::tasks().append(
-                initialize_rpc_server::<N, E>(
-                    node.rpc,
-                    node.rpc_username.clone(),
-                    node.rpc_password.clone(),
-                    address,
-                    peers,
-                    ledger_reader,
-                    prover_router,
-                    memory_pool,
-                )
-                .await,
-            );
+            // Initialize the filter of the exposed RPC methods.
+            let methods = RpcMethodFilter::new(node.rpc_enable.clone(), node.rpc_disable.clone());
+
+            // Initialize a new instance of the RPC server on the Unix domain socket, if one is specified.
+            #[cfg(unix)]
+            if let Some(rpc_socket) = &node.rpc_socket {
+                E::tasks().append(
+                    initialize_rpc_socket_server::<N, E>(
+                        rpc_socket.clone(),
+                        node.rpc_username.clone(),
+                        node.rpc_password.clone(),
+                        address,
+                        peers,
+                        ledger_reader.clone(),
+                        peers_state.clone(),
+                        sync_progress.clone(),
+                        unconfirmed_blocks.clone(),
+                        operator.clone(),
+                        prover_router.clone(),
+                        memory_pool.clone(),
+                        memory_pool_timestamps.clone(),
+                        methods.clone(),
+                    )
+                    .await,
+                );
+            }
+
+            // Initialize a new instance of the RPC server on the TCP address, unless only the socket is enabled.
+            if !node.rpc_socket_only {
+                E::tasks().append(
+                    initialize_rpc_server::<N, E>(
+                        node.rpc,
+                        node.rpc_username.clone(),
+                        node.rpc_password.clone(),
+                        address,
+                        peers,
+                        ledger_reader,
+                        peers_state,
+                        sync_progress,
+                        unconfirmed_blocks,
+                        operator,
+                        prover_router,
+                        memory_pool,
+                        memory_pool_timestamps,
+                        methods,
+                    )
+                    .await,
+                );
+            }
         }
     }
 
@@ -409,8 +537,33 @@ impl<N: Network, E: Environment> Server<N, E> {
         let _ = handler.await;
     }
 
+    ///
+    /// Initialize a new instance of the metrics endpoint, on its own port.
+    /// The endpoint only renders the recorded metrics, which are sampled in the background,
+    /// so that a scrape neither modifies nor waits on the state of the node.
+    ///
     #[cfg(feature = "prometheus")]
-    fn initialize_metrics() {
-        E::tasks().append(snarkos_metrics::initialize().expect("couldn't initialise the metrics"));
+    async fn initialize_metrics(listen_address: SocketAddr, peers: Arc<Peers<N, E>>, ledger: LedgerReader<N>) {
+        E::tasks().append(snarkos_metrics::initialize(listen_address).expect("couldn't initialise the metrics"));
+        info!("Listening for metrics scrapes at {}", listen_address);
+
+        // Initialize the sampling process for the gauges.
+        let (router, handler) = oneshot::channel();
+        E::tasks().append(task::spawn(async move {
+            // Notify the outer function that the task is ready.
+            let _ = router.send(());
+            loop {
+                let number_of_connected_peers = peers.number_of_connected_peers().await;
+                snarkos_metrics::gauge!(snarkos_metrics::CONNECTED_PEERS, number_of_connected_peers as f64);
+                snarkos_metrics::gauge!(snarkos_metrics::LATEST_BLOCK_HEIGHT, ledger.latest_block_height() as f64);
+                let memory_pool_size = E::memory_pool_size().load(std::sync::atomic::Ordering::SeqCst);
+                snarkos_metrics::gauge!(snarkos_metrics::MEMORY_POOL_SIZE, memory_pool_size as f64);
+
+                // Sleep for `E::HEARTBEAT_IN_SECS` seconds.
+                tokio::time::sleep(Duration::from_secs(E::HEARTBEAT_IN_SECS)).await;
+            }
+        }));
+        // Wait until the sampling task is ready.
+        let _ = handler.await;
     }
 }
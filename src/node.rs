@@ -15,12 +15,12 @@
 // along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-    environment::{Client, ClientTrial, Environment, Miner, MinerTrial, Operator, OperatorTrial, Prover, ProverTrial, SyncNode},
+    environment::{Client, ClientTrial, Environment, Miner, MinerTrial, Observer, Operator, OperatorTrial, Prover, ProverTrial, SyncNode},
     helpers::{NodeType, Updater},
     network::Server,
     Display,
 };
-use snarkos_storage::storage::rocksdb::RocksDB;
+use snarkos_storage::{storage::rocksdb::RocksDB, CoinbaseSplit};
 use snarkvm::dpc::{prelude::*, testnet2::Testnet2};
 
 use anyhow::{anyhow, Result};
@@ -40,6 +40,9 @@ pub struct Node {
     /// Specify this as a mining node, with the given miner address.
     #[structopt(long = "miner")]
     pub miner: Option<String>,
+    /// Specify the coinbase split of a mining node, as a comma-separated list of `address:weight` pairs.
+    #[structopt(long = "coinbase-split")]
+    pub coinbase_split: Option<String>,
     /// Specify this as an operating node, with the given operator address.
     #[structopt(long = "operator")]
     pub operator: Option<String>,
@@ -58,12 +61,27 @@ pub struct Node {
     /// Specify the IP address and port for the RPC server.
     #[structopt(parse(try_from_str), default_value = "0.0.0.0:3032", long = "rpc")]
     pub rpc: SocketAddr,
+    /// Specify the path of a Unix domain socket for the RPC server.
+    #[structopt(parse(from_os_str), long = "rpc-socket")]
+    pub rpc_socket: Option<PathBuf>,
+    /// If the flag is set, the RPC server will only bind to the Unix domain socket, and not to the TCP address.
+    #[structopt(long = "rpc-socket-only", requires = "rpc-socket")]
+    pub rpc_socket_only: bool,
+    /// Specify a comma-separated list of RPC methods to enable, including those disabled by default on the TCP address.
+    #[structopt(long = "rpc-enable", use_delimiter = true)]
+    pub rpc_enable: Vec<String>,
+    /// Specify a comma-separated list of RPC methods to disable.
+    #[structopt(long = "rpc-disable", use_delimiter = true)]
+    pub rpc_disable: Vec<String>,
     /// Specify the username for the RPC server.
     #[structopt(default_value = "root", long = "username")]
     pub rpc_username: String,
     /// Specify the password for the RPC server.
     #[structopt(default_value = "pass", long = "password")]
     pub rpc_password: String,
+    /// Specify the IP address and port for the metrics endpoint, which enables it, when built with the `prometheus` feature.
+    #[structopt(parse(try_from_str), long = "metrics")]
+    pub metrics: Option<SocketAddr>,
     /// Specify the verbosity of the node [options: 0, 1, 2, 3]
     #[structopt(default_value = "2", long = "verbosity")]
     pub verbosity: u8,
@@ -76,6 +94,12 @@ pub struct Node {
     /// If the flag is set, the node will not initialize the RPC server.
     #[structopt(long)]
     pub norpc: bool,
+    /// If the flag is set, the node will not initialize the metrics endpoint.
+    #[structopt(long, conflicts_with = "metrics")]
+    pub nometrics: bool,
+    /// If the flag is set, the node will follow the network as a read-only observer, without relaying.
+    #[structopt(long)]
+    pub observer: bool,
     #[structopt(hidden = true, long)]
     pub trial: bool,
     #[structopt(hidden = true, long)]
@@ -104,6 +128,7 @@ impl Node {
                 (NodeType::Operator, true) => self.start_server::<Testnet2, OperatorTrial<Testnet2>>(&self.operator).await,
                 (NodeType::Prover, true) => self.start_server::<Testnet2, ProverTrial<Testnet2>>(&self.prover).await,
                 (NodeType::Sync, _) => self.start_server::<Testnet2, SyncNode<Testnet2>>(&None).await,
+                (NodeType::Observer, false) => self.start_server::<Testnet2, Observer<Testnet2>>(&None).await,
                 _ => panic!("Unsupported node configuration"),
             },
         }
@@ -111,12 +136,13 @@ impl Node {
 
     fn get_node_type(&self) -> (NodeType, bool) {
         (
-            match (self.network, &self.miner, &self.operator, &self.prover, self.sync) {
-                (2, None, None, None, false) => NodeType::Client,
-                (2, Some(_), None, None, false) => NodeType::Miner,
-                (2, None, Some(_), None, false) => NodeType::Operator,
-                (2, None, None, Some(_), false) => NodeType::Prover,
-                (2, None, None, None, true) => NodeType::Sync,
+            match (self.network, &self.miner, &self.operator, &self.prover, self.sync, self.observer) {
+                (2, None, None, None, false, false) => NodeType::Client,
+                (2, Some(_), None, None, false, false) => NodeType::Miner,
+                (2, None, Some(_), None, false, false) => NodeType::Operator,
+                (2, None, None, Some(_), false, false) => NodeType::Prover,
+                (2, None, None, None, true, false) => NodeType::Sync,
+                (2, None, None, None, false, true) => NodeType::Observer,
                 _ => panic!("Unsupported node configuration"),
             },
             self.trial,
@@ -136,6 +162,20 @@ impl Node {
         }
     }
 
+    /// Returns the storage path of the peer reputation store.
+    pub(crate) fn peer_reputation_storage_path(&self, _local_ip: SocketAddr) -> PathBuf {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "test")] {
+                // Tests may use any available ports, and removes the storage artifacts afterwards,
+                // so that there is no need to adhere to a specific number assignment logic.
+                PathBuf::from(format!("/tmp/snarkos-test-peers-{}", _local_ip.port()))
+            } else {
+                // The store is kept next to the ledger, so that development nodes do not share it.
+                aleo_std::aleo_ledger_dir(self.network, self.dev).with_extension("peers")
+            }
+        }
+    }
+
     /// Returns the storage path of the operator.
     pub(crate) fn operator_storage_path(&self, _local_ip: SocketAddr) -> PathBuf {
         cfg_if::cfg_if! {
@@ -162,6 +202,29 @@ impl Node {
         }
     }
 
+    /// Returns the coinbase split of the miner, if one is specified.
+    pub(crate) fn coinbase_split<N: Network>(&self) -> Result<Option<CoinbaseSplit<N>>> {
+        match &self.coinbase_split {
+            Some(coinbase_split) => Ok(Some(CoinbaseSplit::from_str(coinbase_split)?)),
+            None => Ok(None),
+        }
+    }
+
+    ///
+    /// Returns the address of the metrics endpoint, if it is enabled. The endpoint is enabled by the environment,
+    /// on localhost, unless the command-line options enable it on a given address, or disable it.
+    ///
+    pub(crate) fn metrics_addr<E: Environment>(&self) -> Option<SocketAddr> {
+        match (self.nometrics, self.metrics) {
+            (true, _) => None,
+            (false, Some(metrics_addr)) => Some(metrics_addr),
+            (false, None) => match E::ENABLE_METRICS_ENDPOINT {
+                true => Some(SocketAddr::from(([127, 0, 0, 1], E::METRICS_ENDPOINT_PORT))),
+                false => None,
+            },
+        }
+    }
+
     async fn start_server<N: Network, E: Environment>(&self, address: &Option<String>) -> Result<()> {
         println!("{}", crate::display::welcome_message());
 
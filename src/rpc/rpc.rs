@@ -17,11 +17,16 @@
 //! Logic for instantiating the RPC server.
 
 use crate::{
-    rpc::{rpc_impl::RpcImpl, rpc_trait::RpcFunctions},
+    operator::Operator,
+    rpc::{rpc_impl::RpcImpl, rpc_trait::RpcFunctions, rpc_types::TransactionSelection},
     Environment,
     LedgerReader,
+    MemoryPoolTimestamps,
     Peers,
+    PeersStateReader,
     ProverRouter,
+    SyncProgressReader,
+    UnconfirmedBlocksReader,
 };
 use snarkvm::dpc::{Address, MemoryPool, Network};
 
@@ -33,9 +38,16 @@ use hyper::{
 use json_rpc_types as jrt;
 use jsonrpc_core::{Metadata, Params};
 use serde::{Deserialize, Serialize};
-use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+use std::{collections::HashSet, convert::Infallible, net::SocketAddr, sync::Arc};
 use tokio::sync::{oneshot, RwLock};
 
+#[cfg(unix)]
+use hyper::server::conn::Http;
+#[cfg(unix)]
+use std::{io, path::PathBuf};
+#[cfg(unix)]
+use tokio::net::UnixListener;
+
 /// Defines the authentication format for accessing private endpoints on the RPC server.
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct RpcCredentials {
@@ -54,19 +66,30 @@ pub struct Meta {
 
 impl Metadata for Meta {}
 
-const METHODS_EXPECTING_PARAMS: [&str; 12] = [
+const METHODS_EXPECTING_PARAMS: [&str; 23] = [
     // public
+    "decoderawtransaction",
+    "decoderecord",
+    "decoderecords",
+    "estimatefee",
     "getblock",
     "getblocks",
+    "getblocksbyminer",
     "getblockheight",
     "getblockhash",
     "getblockhashes",
     "getblockheader",
+    "getblockstats",
     "getblocktransactions",
+    "getblocktransactionids",
     "getciphertext",
     "getledgerproof",
+    "getpeerchaininfo",
+    "getpeermessagehistory",
+    "getrawtransaction",
     "gettransaction",
     "gettransition",
+    "resendtransaction",
     "sendtransaction",
     // // private
     // "createtransaction",
@@ -75,8 +98,57 @@ const METHODS_EXPECTING_PARAMS: [&str; 12] = [
     // "connect",
 ];
 
+/// The methods that are disabled on the RPC server at the TCP address, unless they are explicitly enabled.
+pub const GUARDED_METHODS: [&str; 8] = [
+    "getblocktemplate",
+    "getpeermessagehistory",
+    "gettaskstatus",
+    "pausepool",
+    "rebuildminerindex",
+    "resendtransaction",
+    "resumepool",
+    "sendtransaction",
+];
+
+/// Determines which RPC methods are exposed by the RPC server.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RpcMethodFilter {
+    /// The methods that are explicitly enabled.
+    enabled: HashSet<String>,
+    /// The methods that are disabled.
+    disabled: HashSet<String>,
+}
+
+impl RpcMethodFilter {
+    /// Initializes a new filter from the given lists of enabled and disabled methods.
+    /// If a method is both enabled and disabled, it is disabled.
+    pub fn new<I: IntoIterator<Item = String>>(enabled: I, disabled: I) -> Self {
+        Self {
+            enabled: enabled.into_iter().collect(),
+            disabled: disabled.into_iter().collect(),
+        }
+    }
+
+    /// Returns the filter for a public RPC server, which additionally disables
+    /// the `GUARDED_METHODS` that are not explicitly enabled.
+    pub fn guarded(mut self) -> Self {
+        for method in GUARDED_METHODS {
+            if !self.enabled.contains(method) {
+                self.disabled.insert(method.to_string());
+            }
+        }
+        self
+    }
+
+    /// Returns `true` if the given method is exposed by the RPC server.
+    pub fn is_enabled(&self, method: &str) -> bool {
+        !self.disabled.contains(method)
+    }
+}
+
 /// Starts a local RPC HTTP server at `rpc_port` in a dedicated `tokio` task.
 /// RPC failures do not affect the rest of the node.
+/// As the server is reachable over the network, the `GUARDED_METHODS` are disabled unless explicitly enabled.
 #[allow(clippy::too_many_arguments)]
 pub async fn initialize_rpc_server<N: Network, E: Environment>(
     rpc_addr: SocketAddr,
@@ -85,11 +157,30 @@ pub async fn initialize_rpc_server<N: Network, E: Environment>(
     address: Option<Address<N>>,
     peers: &Arc<Peers<N, E>>,
     ledger: LedgerReader<N>,
+    peers_state: PeersStateReader<N>,
+    sync_progress: SyncProgressReader,
+    unconfirmed_blocks: UnconfirmedBlocksReader<N>,
+    operator: Arc<Operator<N, E>>,
     prover_router: ProverRouter<N>,
     memory_pool: Arc<RwLock<MemoryPool<N>>>,
+    memory_pool_timestamps: MemoryPoolTimestamps<N>,
+    methods: RpcMethodFilter,
 ) -> tokio::task::JoinHandle<()> {
     let credentials = RpcCredentials { username, password };
-    let rpc = RpcImpl::new(credentials, address, peers.clone(), ledger, prover_router, memory_pool);
+    let rpc = RpcImpl::new(
+        credentials,
+        address,
+        peers.clone(),
+        ledger,
+        peers_state,
+        sync_progress,
+        unconfirmed_blocks,
+        operator,
+        prover_router,
+        memory_pool,
+        memory_pool_timestamps,
+        methods.guarded(),
+    );
 
     let service = make_service_fn(move |conn: &AddrStream| {
         let caller = conn.remote_addr();
@@ -111,6 +202,108 @@ pub async fn initialize_rpc_server<N: Network, E: Environment>(
     task
 }
 
+/// Starts a local RPC HTTP server on the Unix domain socket at `socket_path` in a dedicated `tokio` task.
+/// RPC failures do not affect the rest of the node.
+#[cfg(unix)]
+#[allow(clippy::too_many_arguments)]
+pub async fn initialize_rpc_socket_server<N: Network, E: Environment>(
+    socket_path: PathBuf,
+    username: String,
+    password: String,
+    address: Option<Address<N>>,
+    peers: &Arc<Peers<N, E>>,
+    ledger: LedgerReader<N>,
+    peers_state: PeersStateReader<N>,
+    sync_progress: SyncProgressReader,
+    unconfirmed_blocks: UnconfirmedBlocksReader<N>,
+    operator: Arc<Operator<N, E>>,
+    prover_router: ProverRouter<N>,
+    memory_pool: Arc<RwLock<MemoryPool<N>>>,
+    memory_pool_timestamps: MemoryPoolTimestamps<N>,
+    methods: RpcMethodFilter,
+) -> tokio::task::JoinHandle<()> {
+    let credentials = RpcCredentials { username, password };
+    let rpc = RpcImpl::new(
+        credentials,
+        address,
+        peers.clone(),
+        ledger,
+        peers_state,
+        sync_progress,
+        unconfirmed_blocks,
+        operator,
+        prover_router,
+        memory_pool,
+        memory_pool_timestamps,
+        methods,
+    );
+
+    serve_rpc_socket(socket_path, rpc)
+        .await
+        .expect("Failed to start the RPC socket server")
+}
+
+/// Binds the Unix domain socket at `socket_path`, restricted to the current user, and serves the given RPC on it.
+#[cfg(unix)]
+async fn serve_rpc_socket<N: Network, E: Environment>(socket_path: PathBuf, rpc: RpcImpl<N, E>) -> io::Result<tokio::task::JoinHandle<()>> {
+    use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
+
+    // Remove a stale socket file, left behind by a previous instance of the node.
+    if let Ok(metadata) = std::fs::symlink_metadata(&socket_path) {
+        if !metadata.file_type().is_socket() {
+            let error = format!("{} exists and is not a socket", socket_path.display());
+            return Err(io::Error::new(io::ErrorKind::AlreadyExists, error));
+        }
+        std::fs::remove_file(&socket_path)?;
+    }
+
+    // Bind the socket inside a private directory first, so that it is never reachable by other users
+    // before its permissions are restricted, and then move it into place.
+    let file_name = socket_path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("{} is not a file path", socket_path.display())))?;
+    let private_directory = socket_path.with_file_name(format!(".{}.{}", file_name.to_string_lossy(), std::process::id()));
+    let _ = std::fs::remove_dir_all(&private_directory);
+    std::fs::DirBuilder::new().mode(0o700).create(&private_directory)?;
+
+    let private_socket_path = private_directory.join(file_name);
+    let listener = UnixListener::bind(&private_socket_path).and_then(|listener| {
+        // Ensure only the current user may connect to the socket.
+        std::fs::set_permissions(&private_socket_path, std::fs::Permissions::from_mode(0o600))?;
+        std::fs::rename(&private_socket_path, &socket_path)?;
+        Ok(listener)
+    });
+    let _ = std::fs::remove_dir_all(&private_directory);
+    let listener = listener?;
+
+    // Unix domain socket peers have no IP address, so use the unspecified address for the caller.
+    let caller = SocketAddr::from(([0, 0, 0, 0], 0));
+
+    let (router, handler) = oneshot::channel();
+    let task = tokio::spawn(async move {
+        // Notify the outer function that the task is ready.
+        let _ = router.send(());
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    let rpc = rpc.clone();
+                    tokio::spawn(async move {
+                        let service = service_fn(move |req| handle_rpc::<N, E>(caller, rpc.clone(), req));
+                        if let Err(error) = Http::new().serve_connection(stream, service).await {
+                            warn!("[RPC] {}", error);
+                        }
+                    });
+                }
+                Err(error) => error!("Failed to accept a connection on the RPC socket: {}", error),
+            }
+        }
+    });
+    // Wait until the spawned task is ready.
+    let _ = handler.await;
+
+    Ok(task)
+}
+
 async fn handle_rpc<N: Network, E: Environment>(
     caller: SocketAddr,
     rpc: RpcImpl<N, E>,
@@ -169,6 +362,15 @@ async fn handle_rpc<N: Network, E: Environment>(
 
     debug!("Received '{}' RPC request from {}: {:?}", &*req.method, caller, headers);
 
+    // Ensure the request method is exposed by the RPC server.
+    if !rpc.is_method_enabled(&req.method) {
+        let error = jrt::Error::with_custom_msg(jrt::ErrorCode::MethodNotFound, "method disabled").set_data(req.method.to_string());
+        let resp = jrt::Response::<(), String>::error(jrt::Version::V2, error, req.id.clone());
+        let body = serde_json::to_vec(&resp).unwrap_or_default();
+
+        return Ok(hyper::Response::new(body.into()));
+    }
+
     // Read the request params.
     let mut params = match read_params(&req) {
         Ok(params) => params,
@@ -211,6 +413,25 @@ async fn handle_rpc<N: Network, E: Environment>(
             let result = rpc.latest_ledger_root().await.map_err(convert_crate_err);
             result_to_response(&req, result)
         }
+        "decoderecord" => {
+            // The expected owner is optional.
+            let expected_owner = params.get(1).and_then(|owner| owner.as_str()).map(|owner| owner.to_string());
+            let result = rpc
+                .decode_record(params[0].as_str().unwrap_or("").into(), expected_owner)
+                .await
+                .map_err(convert_crate_err);
+            result_to_response(&req, result)
+        }
+        "decoderecords" => match serde_json::from_value::<Vec<String>>(params.remove(0)) {
+            Ok(records) => {
+                let result = rpc.decode_records(records).await.map_err(convert_crate_err);
+                result_to_response(&req, result)
+            }
+            Err(_) => {
+                let err = jrt::Error::with_custom_msg(jrt::ErrorCode::ParseError, "Invalid records!");
+                jrt::Response::error(jrt::Version::V2, err, req.id.clone())
+            }
+        },
         "getblock" => match serde_json::from_value::<u32>(params.remove(0)) {
             Ok(height) => {
                 let result = rpc.get_block(height).await.map_err(convert_crate_err);
@@ -239,6 +460,22 @@ async fn handle_rpc<N: Network, E: Environment>(
                 }
             }
         }
+        "getblocksbyminer" => {
+            let result = rpc.get_blocks_by_miner(params.remove(0)).await.map_err(convert_crate_err);
+            result_to_response(&req, result)
+        }
+        "getblockchaininfo" => {
+            let result = rpc.get_blockchain_info().await.map_err(convert_crate_err);
+            result_to_response(&req, result)
+        }
+        "getblockcount" => {
+            let result = rpc.get_block_count().await.map_err(convert_crate_err);
+            result_to_response(&req, result)
+        }
+        "getchaintips" => {
+            let result = rpc.get_chain_tips().await.map_err(convert_crate_err);
+            result_to_response(&req, result)
+        }
         "getblockheight" => {
             let result = rpc.get_block_height(params.remove(0)).await.map_err(convert_crate_err);
             result_to_response(&req, result)
@@ -281,9 +518,32 @@ async fn handle_rpc<N: Network, E: Environment>(
                 jrt::Response::error(jrt::Version::V2, err, req.id.clone())
             }
         },
+        "getblockstats" => match serde_json::from_value::<u32>(params.remove(0)) {
+            Ok(height) => {
+                let result = rpc.get_block_stats(height).await.map_err(convert_crate_err);
+                result_to_response(&req, result)
+            }
+            Err(_) => {
+                let err = jrt::Error::with_custom_msg(jrt::ErrorCode::ParseError, "Invalid block height!");
+                jrt::Response::error(jrt::Version::V2, err, req.id.clone())
+            }
+        },
         "getblocktemplate" => {
-            let result = rpc.get_block_template().await.map_err(convert_crate_err);
-            result_to_response(&req, result)
+            // The transaction selection is optional, and defaults to all transactions in the memory pool.
+            let selection = match &req.params {
+                Some(Params::Array(params)) => params.get(0).filter(|selection| !selection.is_null()).cloned(),
+                _ => None,
+            };
+            match selection.map(serde_json::from_value::<TransactionSelection>).transpose() {
+                Ok(selection) => {
+                    let result = rpc.get_block_template(selection).await.map_err(convert_crate_err);
+                    result_to_response(&req, result)
+                }
+                Err(_) => {
+                    let err = jrt::Error::with_custom_msg(jrt::ErrorCode::InvalidParams, "Invalid transaction selection!");
+                    jrt::Response::error(jrt::Version::V2, err, req.id.clone())
+                }
+            }
         }
         "getblocktransactions" => match serde_json::from_value::<u32>(params.remove(0)) {
             Ok(height) => {
@@ -295,6 +555,10 @@ async fn handle_rpc<N: Network, E: Environment>(
                 jrt::Response::error(jrt::Version::V2, err, req.id.clone())
             }
         },
+        "getblocktransactionids" => {
+            let result = rpc.get_block_transaction_ids(params.remove(0)).await.map_err(convert_crate_err);
+            result_to_response(&req, result)
+        }
         "getciphertext" => {
             let result = rpc.get_ciphertext(params.remove(0)).await.map_err(convert_crate_err);
             result_to_response(&req, result)
@@ -307,22 +571,122 @@ async fn handle_rpc<N: Network, E: Environment>(
             let result = rpc.get_memory_pool().await.map_err(convert_crate_err);
             result_to_response(&req, result)
         }
+        "getmininginfo" => {
+            let result = rpc.get_mining_info().await.map_err(convert_crate_err);
+            result_to_response(&req, result)
+        }
+        "getpoolinfo" => {
+            let result = rpc.get_pool_info().await.map_err(convert_crate_err);
+            result_to_response(&req, result)
+        }
+        "getrawmempool" => {
+            // The `verbose` flag is optional, and defaults to `false`.
+            let verbose = match &req.params {
+                Some(Params::Array(params)) => params.get(0).and_then(|verbose| verbose.as_bool()).unwrap_or(false),
+                _ => false,
+            };
+            let result = rpc.get_raw_mempool(verbose).await.map_err(convert_crate_err);
+            result_to_response(&req, result)
+        }
         "gettransaction" => {
             let result = rpc.get_transaction(params.remove(0)).await.map_err(convert_crate_err);
             result_to_response(&req, result)
         }
+        "getrawtransaction" => {
+            let result = rpc.get_raw_transaction(params.remove(0)).await.map_err(convert_crate_err);
+            result_to_response(&req, result)
+        }
+        "decoderawtransaction" => {
+            let result = rpc
+                .decode_raw_transaction(params[0].as_str().unwrap_or("").into())
+                .await
+                .map_err(convert_crate_err);
+            result_to_response(&req, result)
+        }
         "gettransition" => {
             let result = rpc.get_transition(params.remove(0)).await.map_err(convert_crate_err);
             result_to_response(&req, result)
         }
+        "getconnectioncount" => {
+            let result = rpc.get_connection_count().await.map_err(convert_crate_err);
+            result_to_response(&req, result)
+        }
         "getconnectedpeers" => {
             let result = rpc.get_connected_peers().await.map_err(convert_crate_err);
             result_to_response(&req, result)
         }
+        "getpeerchaininfo" => {
+            let result = rpc.get_peer_chain_info(params.remove(0)).await.map_err(convert_crate_err);
+            result_to_response(&req, result)
+        }
+        "getpeerinfo" => {
+            let result = rpc.get_peer_info().await.map_err(convert_crate_err);
+            result_to_response(&req, result)
+        }
+        "listpeers" => {
+            let result = rpc.list_peers().await.map_err(convert_crate_err);
+            result_to_response(&req, result)
+        }
+        "getpeermessagehistory" => {
+            let result = rpc.get_peer_message_history(params.remove(0)).await.map_err(convert_crate_err);
+            result_to_response(&req, result)
+        }
+        "getsyncstatus" => {
+            // The target block height is optional, and defaults to the median block height advertised by the peers.
+            let target_block_height = match &req.params {
+                Some(Params::Array(params)) => params.get(0).filter(|target| !target.is_null()).cloned(),
+                _ => None,
+            };
+            match target_block_height.map(serde_json::from_value::<u32>).transpose() {
+                Ok(target_block_height) => {
+                    let result = rpc.get_sync_status(target_block_height).await.map_err(convert_crate_err);
+                    result_to_response(&req, result)
+                }
+                Err(_) => {
+                    let err = jrt::Error::with_custom_msg(jrt::ErrorCode::InvalidParams, "Invalid target block height!");
+                    jrt::Response::error(jrt::Version::V2, err, req.id.clone())
+                }
+            }
+        }
+        "gettaskstatus" => {
+            let result = rpc.get_task_status().await.map_err(convert_crate_err);
+            result_to_response(&req, result)
+        }
         "getnodestate" => {
             let result = rpc.get_node_state().await.map_err(convert_crate_err);
             result_to_response(&req, result)
         }
+        "ping" => {
+            let result = rpc.ping().await.map_err(convert_crate_err);
+            result_to_response(&req, result)
+        }
+        "rebuildminerindex" => {
+            let result = rpc.rebuild_miner_index().await.map_err(convert_crate_err);
+            result_to_response(&req, result)
+        }
+        "pausepool" => {
+            let result = rpc.pause_pool().await.map_err(convert_crate_err);
+            result_to_response(&req, result)
+        }
+        "resumepool" => {
+            let result = rpc.resume_pool().await.map_err(convert_crate_err);
+            result_to_response(&req, result)
+        }
+        "estimatefee" => {
+            match (
+                serde_json::from_value::<usize>(params.remove(0)),
+                serde_json::from_value::<u32>(params.remove(0)),
+            ) {
+                (Ok(transaction_size), Ok(target_blocks)) => {
+                    let result = rpc.estimate_fee(transaction_size, target_blocks).await.map_err(convert_crate_err);
+                    result_to_response(&req, result)
+                }
+                (Err(_), _) | (_, Err(_)) => {
+                    let err = jrt::Error::with_custom_msg(jrt::ErrorCode::ParseError, "Invalid transaction size or target blocks!");
+                    jrt::Response::error(jrt::Version::V2, err, req.id.clone())
+                }
+            }
+        }
         "sendtransaction" => {
             let result = rpc
                 .send_transaction(params[0].as_str().unwrap_or("").into())
@@ -330,6 +694,10 @@ async fn handle_rpc<N: Network, E: Environment>(
                 .map_err(convert_crate_err);
             result_to_response(&req, result)
         }
+        "resendtransaction" => {
+            let result = rpc.resend_transaction(params.remove(0)).await.map_err(convert_crate_err);
+            result_to_response(&req, result)
+        }
         // // private
         // "createtransaction" => {
         //     let result = rpc
@@ -394,7 +762,10 @@ fn read_params(req: &jrt::Request<Params>) -> Result<Vec<serde_json::Value>, jrt
 
 /// Converts the crate's RpcError into a jrt::RpcError
 fn convert_crate_err(err: crate::rpc::rpc_impl::RpcError) -> jrt::Error<String> {
-    let error = jrt::Error::with_custom_msg(jrt::ErrorCode::ServerError(-32000), "internal error");
+    let error = match &err {
+        crate::rpc::rpc_impl::RpcError::InvalidInput(_) => jrt::Error::with_custom_msg(jrt::ErrorCode::InvalidParams, "invalid input"),
+        _ => jrt::Error::with_custom_msg(jrt::ErrorCode::ServerError(-32000), "internal error"),
+    };
     error.set_data(err.to_string())
 }
 
@@ -421,13 +792,45 @@ fn result_to_response<T: Serialize>(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{environment::Client, helpers::State, ledger::Ledger, network::Prover};
+    use crate::{
+        environment::{Client, Miner},
+        helpers::{DefaultFeePolicy, FeePolicy, NodeCapabilities, NodeType, State, SyncEstimate},
+        ledger::Ledger,
+        network::{ConnectionDirection, Prover},
+        rpc::{
+            rpc_impl::MAXIMUM_DECODE_RECORDS,
+            rpc_types::{
+                BlockCountInfo,
+                BlockStats,
+                BlockchainInfo,
+                ChainTip,
+                ConnectionCount,
+                DecodedRecord,
+                MiningInfo,
+                PEER_LIST_VERSION,
+                PeerChainInfo,
+                PeerInfo,
+                PeerList,
+                PeerListEntry,
+                PingInfo,
+                PoolInfo,
+                RawMempool,
+                RawTransaction,
+                RecordInfo,
+                ResendTransactionResponse,
+                SendTransactionResponse,
+                SyncStatus,
+                TaskStatus,
+                TransactionInfo,
+            },
+        },
+    };
     use snarkos_storage::{
         storage::{rocksdb::RocksDB, Storage},
         LedgerState,
     };
     use snarkvm::{
-        dpc::{testnet2::Testnet2, AccountScheme, AleoAmount, Transaction, Transactions, Transition},
+        dpc::{testnet2::Testnet2, AccountScheme, AleoAmount, Blocks, Transaction, Transactions, Transition},
         prelude::{Account, Block, BlockHeader},
         utilities::ToBytes,
     };
@@ -437,6 +840,7 @@ mod tests {
     use rand_chacha::ChaChaRng;
     use snarkvm::dpc::Record;
     use std::{
+        collections::HashMap,
         path::{Path, PathBuf},
         sync::atomic::AtomicBool,
     };
@@ -460,6 +864,14 @@ mod tests {
 
     /// Initializes a new instance of the rpc.
     async fn new_rpc<N: Network, E: Environment, S: Storage, P: AsRef<Path>>(path: Option<P>) -> RpcImpl<N, E> {
+        new_rpc_with_methods::<N, E, S, P>(path, RpcMethodFilter::default()).await
+    }
+
+    /// Initializes a new instance of the rpc, which exposes the methods allowed by the given filter.
+    async fn new_rpc_with_methods<N: Network, E: Environment, S: Storage, P: AsRef<Path>>(
+        path: Option<P>,
+        methods: RpcMethodFilter,
+    ) -> RpcImpl<N, E> {
         let credentials = RpcCredentials {
             username: "root".to_string(),
             password: "pass".to_string(),
@@ -478,9 +890,9 @@ mod tests {
         E::status().update(State::Ready);
 
         // Initialize a new instance for managing peers.
-        let peers = Peers::new(local_ip, None).await;
+        let peers = Peers::new(local_ip, None, None).await;
         // Initialize a new instance for managing the ledger.
-        let ledger = Ledger::<N, E>::open::<S, _>(&ledger_path, peers.router())
+        let ledger = Ledger::<N, E>::open::<S, _>(&ledger_path, peers.router(), peers.events())
             .await
             .expect("Failed to initialize ledger");
 
@@ -488,16 +900,45 @@ mod tests {
         let prover = Prover::open::<S, _>(
             &prover_path,
             None,
+            None,
             local_ip,
             Some(local_ip),
             peers.router(),
             ledger.reader(),
             ledger.router(),
+            ledger.events(),
         )
         .await
         .expect("Failed to initialize prover");
 
-        RpcImpl::<N, E>::new(credentials, None, peers, ledger.reader(), prover.router(), prover.memory_pool())
+        // Initialize a new instance for managing the operator.
+        let operator = Operator::open::<S, _>(
+            &temp_dir(),
+            None,
+            local_ip,
+            prover.memory_pool(),
+            peers.router(),
+            ledger.reader(),
+            ledger.router(),
+            prover.router(),
+        )
+        .await
+        .expect("Failed to initialize operator");
+
+        RpcImpl::<N, E>::new(
+            credentials,
+            None,
+            peers,
+            ledger.reader(),
+            ledger.peers_state(),
+            ledger.sync_progress(),
+            ledger.unconfirmed_blocks(),
+            operator,
+            prover.router(),
+            prover.memory_pool(),
+            prover.memory_pool_timestamps(),
+            methods,
+        )
     }
 
     /// Initializes a new instance of the rpc.
@@ -515,9 +956,9 @@ mod tests {
         E::status().update(State::Ready);
 
         // Initialize a new instance for managing peers.
-        let peers = Peers::new(local_ip, None).await;
+        let peers = Peers::new(local_ip, None, None).await;
         // Initialize a new instance for managing the ledger.
-        let ledger = Ledger::<N, E>::open::<S, _>(&ledger_path, peers.router())
+        let ledger = Ledger::<N, E>::open::<S, _>(&ledger_path, peers.router(), peers.events())
             .await
             .expect("Failed to initialize ledger");
 
@@ -525,15 +966,31 @@ mod tests {
         let prover = Prover::open::<S, _>(
             &prover_path,
             None,
+            None,
             local_ip,
             Some(local_ip),
             peers.router(),
             ledger.reader(),
             ledger.router(),
+            ledger.events(),
         )
         .await
         .expect("Failed to initialize prover");
 
+        // Initialize a new instance for managing the operator.
+        let operator = Operator::open::<S, _>(
+            &temp_dir(),
+            None,
+            local_ip,
+            prover.memory_pool(),
+            peers.router(),
+            ledger.reader(),
+            ledger.router(),
+            prover.router(),
+        )
+        .await
+        .expect("Failed to initialize operator");
+
         E::tasks().append(
             initialize_rpc_server(
                 local_ip,
@@ -542,8 +999,14 @@ mod tests {
                 None,
                 &peers,
                 ledger.reader(),
+                ledger.peers_state(),
+                ledger.sync_progress(),
+                ledger.unconfirmed_blocks(),
+                operator,
                 prover.router(),
                 prover.memory_pool(),
+                prover.memory_pool_timestamps(),
+                RpcMethodFilter::new(vec!["sendtransaction".to_string()], vec![]),
             )
             .await,
         );
@@ -575,6 +1038,102 @@ mod tests {
         assert!(response.unwrap().status().is_success());
     }
 
+    #[test]
+    fn test_rpc_method_filter() {
+        // Ensure all methods are enabled by default.
+        let methods = RpcMethodFilter::default();
+        assert!(GUARDED_METHODS.iter().all(|method| methods.is_enabled(method)));
+
+        // Ensure the guarded methods are disabled on a public server, unless explicitly enabled.
+        let methods = RpcMethodFilter::default().guarded();
+        assert!(GUARDED_METHODS.iter().all(|method| !methods.is_enabled(method)));
+        assert!(methods.is_enabled("latestblockheight"));
+
+        let methods = RpcMethodFilter::new(vec!["sendtransaction".to_string()], vec![]).guarded();
+        assert!(methods.is_enabled("sendtransaction"));
+        assert!(!methods.is_enabled("getblocktemplate"));
+
+        // Ensure a disabled method takes precedence over an enabled one.
+        let methods = RpcMethodFilter::new(vec!["getblock".to_string()], vec!["getblock".to_string()]);
+        assert!(!methods.is_enabled("getblock"));
+    }
+
+    #[tokio::test]
+    async fn test_disabled_method() {
+        // Initialize a new RPC, with a disabled method.
+        let methods = RpcMethodFilter::new(vec![], vec!["latestblockheight".to_string()]);
+        let rpc = new_rpc_with_methods::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None, methods).await;
+
+        // Initialize a new request that calls the `latestblockheight` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc":"2.0",
+	"id": "1",
+	"method": "latestblockheight"
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Ensure the method is reported as disabled.
+        let response_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let response_json: jrt::Response<serde_json::Value, String> = serde_json::from_slice(&response_bytes).unwrap();
+        let error = response_json.payload.expect_err("Expected the method to be disabled");
+        assert_eq!(jrt::ErrorCode::MethodNotFound, error.code);
+        assert_eq!(Some("latestblockheight".to_string()), error.data);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_rpc_socket_server() {
+        use std::os::unix::fs::PermissionsExt;
+        use tokio::{
+            io::{AsyncReadExt, AsyncWriteExt},
+            net::UnixStream,
+        };
+
+        // Leave a stale socket file behind, as a previous instance of the node would.
+        let socket_path = temp_dir().join("rpc.sock");
+        drop(std::os::unix::net::UnixListener::bind(&socket_path).unwrap());
+        assert!(socket_path.exists());
+
+        // Initialize a new RPC on the Unix domain socket.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+        let _server = serve_rpc_socket(socket_path.clone(), rpc).await.unwrap();
+
+        // Ensure only the current user may connect to the socket.
+        let permissions = std::fs::metadata(&socket_path).unwrap().permissions();
+        assert_eq!(0o600, permissions.mode() & 0o777);
+        // Ensure the private directory the socket was bound in is removed.
+        assert_eq!(1, std::fs::read_dir(socket_path.parent().unwrap()).unwrap().count());
+
+        // Send a request that calls the `latestblockheight` endpoint over the socket.
+        let body = r#"{"jsonrpc":"2.0","id":"1","method":"latestblockheight"}"#;
+        let request = format!(
+            "POST / HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let mut stream = UnixStream::connect(&socket_path).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        // Check the block height in the response.
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains(r#""result":0"#));
+
+        // Ensure a path that is not a socket is not removed.
+        let file_path = temp_dir().join("rpc.sock");
+        std::fs::write(&file_path, b"").unwrap();
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+        assert!(serve_rpc_socket(file_path.clone(), rpc).await.is_err());
+        assert!(file_path.exists());
+    }
+
     #[tokio::test]
     async fn test_latest_block() {
         // Initialize a new RPC.
@@ -825,63 +1384,264 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_get_block_height() {
-        // Initialize a new RPC.
-        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+    async fn test_get_blocks_by_miner() {
+        let mut rng = ChaChaRng::seed_from_u64(123456789);
+        let terminator = AtomicBool::new(false);
 
-        // Get the genesis block hash.
-        let block_hash = Testnet2::genesis_block().hash().to_string();
+        // Initialize a new temporary directory.
+        let directory = temp_dir();
 
-        // Initialize a new request that calls the `getblockheight` endpoint.
-        let request = Request::new(Body::from(format!(
-            "{{
-	\"jsonrpc\": \"2.0\",
-	\"id\": \"1\",
-	\"method\": \"getblockheight\",
-	\"params\": [
-        \"{}\"
-    ]
-}}",
-            block_hash
-        )));
+        // Initialize a new ledger state at the temporary directory.
+        let ledger_state = new_ledger_state::<Testnet2, RocksDB, PathBuf>(Some(directory.clone()));
 
-        // Send the request to the RPC.
-        let response = handle_rpc(caller(), rpc, request)
-            .await
-            .expect("Test RPC failed to process request");
+        // Mine 3 blocks, with the first and last attributed to the first miner.
+        let miners = (0..2).map(|_| Account::<Testnet2>::new(&mut rng).address()).collect::<Vec<_>>();
+        for miner in [miners[0], miners[1], miners[0]] {
+            let (block, _) = ledger_state
+                .mine_next_block(miner, true, &[], &terminator, &mut rng)
+                .expect("Failed to mine");
+            ledger_state.add_next_block(&block).expect("Failed to add next block to ledger");
+        }
 
-        // Process the response into a block height.
-        let actual: u32 = process_response(response).await;
+        // Drop the handle to ledger_state. Note this does not remove the blocks in the temporary directory.
+        drop(ledger_state);
 
-        // Check the block height.
-        let expected = Testnet2::genesis_block().height();
-        assert_eq!(expected, actual);
-    }
+        // Initialize a new RPC with the ledger state containing the mined blocks.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(Some(directory)).await;
 
-    #[tokio::test]
-    async fn test_get_block_hash() {
-        // Initialize a new RPC.
-        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+        let get_blocks_by_miner = |rpc: RpcImpl<Testnet2, Client<Testnet2>>, miner: Address<Testnet2>| async move {
+            // Initialize a new request that calls the `getblocksbyminer` endpoint.
+            let request = Request::new(Body::from(format!(
+                r#"{{"jsonrpc": "2.0", "id": "1", "method": "getblocksbyminer", "params": ["{}"]}}"#,
+                miner
+            )));
 
-        // Initialize a new request that calls the `getblockhash` endpoint.
+            // Send the request to the RPC.
+            let response = handle_rpc(caller(), rpc, request)
+                .await
+                .expect("Test RPC failed to process request");
+
+            // Process the response into block heights.
+            let actual: Vec<u32> = process_response(response).await;
+            actual
+        };
+
+        // Check the blocks of each miner.
+        assert_eq!(vec![1, 3], get_blocks_by_miner(rpc.clone(), miners[0]).await);
+        assert_eq!(vec![2], get_blocks_by_miner(rpc.clone(), miners[1]).await);
+
+        // Initialize a new request that calls the `rebuildminerindex` endpoint.
         let request = Request::new(Body::from(
             r#"{
-	"jsonrpc": "2.0",
+	"jsonrpc":"2.0",
 	"id": "1",
-	"method": "getblockhash",
-	"params": [
-        0
-    ]
+	"method": "rebuildminerindex"
 }"#,
         ));
 
         // Send the request to the RPC.
-        let response = handle_rpc(caller(), rpc, request)
+        let response = handle_rpc(caller(), rpc.clone(), request)
             .await
             .expect("Test RPC failed to process request");
+        process_response::<()>(response).await;
 
-        // Process the response into a block hash.
-        let actual: <Testnet2 as Network>::BlockHash = process_response(response).await;
+        // Check the blocks of each miner are unchanged by the rebuild.
+        assert_eq!(vec![1, 3], get_blocks_by_miner(rpc.clone(), miners[0]).await);
+        assert_eq!(vec![2], get_blocks_by_miner(rpc, miners[1]).await);
+    }
+
+    #[tokio::test]
+    async fn test_get_blockchain_info() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Initialize a new request that calls the `getblockchaininfo` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc":"2.0",
+	"id": "1",
+	"method": "getblockchaininfo"
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc.clone(), request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into the blockchain info.
+        let actual: BlockchainInfo = process_response(response).await;
+
+        // Check the blockchain info.
+        assert_eq!(Testnet2::NETWORK_ID, actual.network_id);
+        assert_eq!(rpc.latest_block_height().await.unwrap(), actual.chain_height);
+        assert_eq!(Testnet2::genesis_block().hash().to_string(), actual.best_block_hash);
+        assert_eq!(0, actual.connected_peers);
+        assert!(actual.is_synced);
+        assert_eq!(100.0, actual.sync_progress_percent);
+        assert_eq!(Client::<Testnet2>::NODE_TYPE.to_string(), actual.node_type);
+    }
+
+    #[tokio::test]
+    async fn test_get_chain_tips() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+        let genesis_block = Testnet2::genesis_block();
+
+        // Add an unconfirmed block on a fork, as the ledger would retain it.
+        rpc.unconfirmed_blocks
+            .write()
+            .await
+            .insert(genesis_block.previous_block_hash(), genesis_block.clone());
+
+        // Initialize a new request that calls the `getchaintips` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc":"2.0",
+	"id": "1",
+	"method": "getchaintips"
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into the chain tips.
+        let actual: Vec<ChainTip> = process_response(response).await;
+
+        // Check the tip of the canonical chain is listed first, followed by the retained fork branch.
+        let chain_tip = |branch_length, status: &str| ChainTip {
+            height: 0,
+            hash: genesis_block.hash().to_string(),
+            branch_length,
+            status: status.to_string(),
+        };
+        assert_eq!(vec![chain_tip(0, "active"), chain_tip(1, "fork")], actual);
+    }
+
+    #[tokio::test]
+    async fn test_get_block_count() {
+        let rng = &mut thread_rng();
+        let terminator = AtomicBool::new(false);
+
+        // Initialize a new temporary directory.
+        let directory = temp_dir();
+
+        // Initialize a new ledger state at the temporary directory.
+        let ledger_state = new_ledger_state::<Testnet2, RocksDB, PathBuf>(Some(directory.clone()));
+        assert_eq!(0, ledger_state.latest_block_height());
+
+        // Initialize a new account.
+        let account = Account::<Testnet2>::new(&mut thread_rng());
+        let address = account.address();
+
+        // Mine three blocks with 2, 3, and 5 transactions, including their coinbase transactions.
+        for number_of_transactions in [2, 3, 5] {
+            let transactions = (1..number_of_transactions)
+                .map(|_| {
+                    let (transaction, _) = Transaction::<Testnet2>::new_coinbase(address, AleoAmount(0), true, rng)
+                        .expect("Failed to create a coinbase transaction");
+                    transaction
+                })
+                .collect::<Vec<_>>();
+
+            let (block, _) = ledger_state
+                .mine_next_block(address, true, &transactions, &terminator, rng)
+                .expect("Failed to mine");
+            assert_eq!(number_of_transactions, block.transactions().len());
+            ledger_state.add_next_block(&block).expect("Failed to add next block to ledger");
+        }
+        assert_eq!(3, ledger_state.latest_block_height());
+
+        // Drop the handle to ledger_state. Note this does not remove the blocks in the temporary directory.
+        drop(ledger_state);
+
+        // Initialize a new RPC with the ledger state containing the genesis block and the three blocks.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(Some(directory)).await;
+
+        // Initialize a new request that calls the `getblockcount` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc":"2.0",
+	"id": "1",
+	"method": "getblockcount"
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into the block count info.
+        let actual: BlockCountInfo = process_response(response).await;
+
+        // Check the block count info, which also counts the transactions of the genesis block.
+        let genesis_transactions_total = Testnet2::genesis_block().transactions().len() as u64;
+        assert_eq!(4, actual.block_count);
+        assert_eq!(genesis_transactions_total + 10, actual.transactions_total);
+    }
+
+    #[tokio::test]
+    async fn test_get_block_height() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Get the genesis block hash.
+        let block_hash = Testnet2::genesis_block().hash().to_string();
+
+        // Initialize a new request that calls the `getblockheight` endpoint.
+        let request = Request::new(Body::from(format!(
+            "{{
+	\"jsonrpc\": \"2.0\",
+	\"id\": \"1\",
+	\"method\": \"getblockheight\",
+	\"params\": [
+        \"{}\"
+    ]
+}}",
+            block_hash
+        )));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into a block height.
+        let actual: u32 = process_response(response).await;
+
+        // Check the block height.
+        let expected = Testnet2::genesis_block().height();
+        assert_eq!(expected, actual);
+    }
+
+    #[tokio::test]
+    async fn test_get_block_hash() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Initialize a new request that calls the `getblockhash` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc": "2.0",
+	"id": "1",
+	"method": "getblockhash",
+	"params": [
+        0
+    ]
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into a block hash.
+        let actual: <Testnet2 as Network>::BlockHash = process_response(response).await;
 
         // Check the block hash.
         let expected = Testnet2::genesis_block().hash();
@@ -975,123 +1735,469 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_get_block_template() {
+    async fn test_decode_record() {
+        let mut rng = ChaChaRng::seed_from_u64(123456789);
+
+        // Initialize a new record, owned by a new account.
+        let account = Account::<Testnet2>::new(&mut rng);
+        let (_, record) = Transaction::<Testnet2>::new_coinbase(account.address(), AleoAmount(1234), true, &mut rng)
+            .expect("Failed to create a coinbase transaction");
+        let record_hex = hex::encode(record.to_bytes_le().unwrap());
+
         // Initialize a new RPC.
         let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
 
-        // Initialize the expected block template values.
-        let expected_previous_block_hash = Testnet2::genesis_block().hash().to_string();
-        let expected_block_height = 1;
-        let expected_ledger_root = rpc.latest_ledger_root().await.unwrap().to_string();
-        let expected_transactions = Vec::<serde_json::Value>::new();
-        let expected_block_reward = Block::<Testnet2>::block_reward(1).0;
-
-        // Initialize a new request that calls the `getblocktemplate` endpoint.
-        let request = Request::new(Body::from(
-            r#"{
-	"jsonrpc":"2.0",
-	"id": "1",
-	"method": "getblocktemplate"
-}"#,
-        ));
+        // Initialize a new request that calls the `decoderecord` endpoint with the matching owner.
+        let request = Request::new(Body::from(format!(
+            "{{
+	\"jsonrpc\": \"2.0\",
+	\"id\": \"1\",
+	\"method\": \"decoderecord\",
+	\"params\": [
+        \"{}\",
+        \"{}\"
+    ]
+}}",
+            record_hex,
+            account.address()
+        )));
 
         // Send the request to the RPC.
-        let response = handle_rpc(caller(), rpc, request)
+        let response = handle_rpc(caller(), rpc.clone(), request)
             .await
             .expect("Test RPC failed to process request");
 
-        // Process the response into a ledger root.
-        let actual: serde_json::Value = process_response(response).await;
-
-        // Check the block template state.
-        assert_eq!(expected_previous_block_hash, actual["previous_block_hash"]);
-        assert_eq!(expected_block_height, actual["block_height"]);
-        assert_eq!(expected_ledger_root, actual["ledger_root"].as_str().unwrap());
-        assert_eq!(&expected_transactions, actual["transactions"].as_array().unwrap());
-        assert_eq!(expected_block_reward, actual["coinbase_reward"].as_i64().unwrap());
-    }
+        // Process the response into the record fields.
+        let actual: RecordInfo = process_response(response).await;
 
-    #[tokio::test]
-    async fn test_get_block_transactions() {
-        // Initialize a new RPC.
-        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+        // Check the record fields.
+        assert_eq!(account.address().to_string(), actual.owner);
+        assert_eq!(1234, actual.value);
+        assert_eq!(record.commitment().to_string(), actual.commitment);
 
-        // Initialize a new request that calls the `getblocktransactions` endpoint.
-        let request = Request::new(Body::from(
-            r#"{
-	"jsonrpc": "2.0",
-	"id": "1",
-	"method": "getblocktransactions",
-	"params": [
-        0
+        // Initialize a new request that calls the `decoderecord` endpoint with a mismatching owner.
+        let other_account = Account::<Testnet2>::new(&mut rng);
+        let request = Request::new(Body::from(format!(
+            "{{
+	\"jsonrpc\": \"2.0\",
+	\"id\": \"1\",
+	\"method\": \"decoderecord\",
+	\"params\": [
+        \"{}\",
+        \"{}\"
     ]
-}"#,
-        ));
+}}",
+            record_hex,
+            other_account.address()
+        )));
 
         // Send the request to the RPC.
         let response = handle_rpc(caller(), rpc, request)
             .await
             .expect("Test RPC failed to process request");
 
-        // Process the response into transactions.
-        let actual: Transactions<Testnet2> = process_response(response).await;
-
-        // Check the transactions.
-        let expected = Testnet2::genesis_block().transactions();
-        assert_eq!(*expected, actual);
+        // Ensure the record is rejected as invalid input.
+        let response_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let response_json: jrt::Response<serde_json::Value, String> = serde_json::from_slice(&response_bytes).unwrap();
+        let error = response_json.payload.expect_err("Expected the record to be rejected");
+        assert_eq!(jrt::ErrorCode::InvalidParams, error.code);
+        assert!(error.data.unwrap_or_default().contains(&other_account.address().to_string()));
     }
 
     #[tokio::test]
-    async fn test_get_ciphertext() {
+    async fn test_decode_records() {
+        let mut rng = ChaChaRng::seed_from_u64(123456789);
+
+        // Initialize two new records, owned by a new account.
+        let account = Account::<Testnet2>::new(&mut rng);
+        let (_, record_1) = Transaction::<Testnet2>::new_coinbase(account.address(), AleoAmount(1234), true, &mut rng)
+            .expect("Failed to create a coinbase transaction");
+        let (_, record_2) = Transaction::<Testnet2>::new_coinbase(account.address(), AleoAmount(5678), true, &mut rng)
+            .expect("Failed to create a coinbase transaction");
+
         // Initialize a new RPC.
         let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
 
-        // Get the commitment from the genesis coinbase transaction.
-        let commitment = Testnet2::genesis_block().to_coinbase_transaction().unwrap().transitions()[0]
-            .commitments()
-            .next()
-            .unwrap()
-            .to_string();
-
-        // Initialize a new request that calls the `getciphertext` endpoint.
+        // Initialize a new request that calls the `decoderecords` endpoint, with a malformed record in between.
         let request = Request::new(Body::from(format!(
             "{{
 	\"jsonrpc\": \"2.0\",
 	\"id\": \"1\",
-	\"method\": \"getciphertext\",
+	\"method\": \"decoderecords\",
 	\"params\": [
-        \"{}\"
+        [\"{}\", \"deadbeef\", \"{}\"]
     ]
 }}",
-            commitment
+            hex::encode(record_1.to_bytes_le().unwrap()),
+            hex::encode(record_2.to_bytes_le().unwrap())
         )));
 
         // Send the request to the RPC.
-        let response = handle_rpc(caller(), rpc, request)
+        let response = handle_rpc(caller(), rpc.clone(), request)
             .await
             .expect("Test RPC failed to process request");
 
-        // Process the response into a ciphertext.
-        let actual: <Testnet2 as Network>::RecordCiphertext = process_response(response).await;
+        // Process the response into the decoded records.
+        let actual: Vec<DecodedRecord> = process_response(response).await;
+        assert_eq!(3, actual.len());
+
+        // Ensure the valid records are decoded in order, and only the malformed record fails.
+        match (&actual[0], &actual[1], &actual[2]) {
+            (DecodedRecord::Info(info_1), DecodedRecord::Error(_), DecodedRecord::Info(info_2)) => {
+                assert_eq!(record_1.commitment().to_string(), info_1.commitment);
+                assert_eq!(1234, info_1.value);
+                assert_eq!(record_2.commitment().to_string(), info_2.commitment);
+                assert_eq!(5678, info_2.value);
+            }
+            _ => panic!("Expected only the malformed record to fail to decode"),
+        }
 
-        // Check the ciphertext.
-        assert!(
-            Testnet2::genesis_block()
-                .transactions()
-                .first()
-                .unwrap()
-                .ciphertexts()
-                .any(|expected| *expected == actual)
-        );
+        // Initialize a new request that calls the `decoderecords` endpoint with too many records.
+        let records = vec!["deadbeef"; MAXIMUM_DECODE_RECORDS + 1];
+        let request = Request::new(Body::from(
+            serde_json::json!({ "jsonrpc": "2.0", "id": "1", "method": "decoderecords", "params": [records] }).to_string(),
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Ensure the batch is rejected as invalid input.
+        let response_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let response_json: jrt::Response<serde_json::Value, String> = serde_json::from_slice(&response_bytes).unwrap();
+        let error = response_json.payload.expect_err("Expected the batch to be rejected");
+        assert_eq!(jrt::ErrorCode::InvalidParams, error.code);
     }
 
     #[tokio::test]
-    async fn test_get_ledger_proof() {
-        let mut rng = ChaChaRng::seed_from_u64(123456789);
-        let terminator = AtomicBool::new(false);
-
-        // Initialize a new temporary directory.
-        let directory = temp_dir();
+    async fn test_get_block_stats() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Initialize a new request that calls the `getblockstats` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc": "2.0",
+	"id": "1",
+	"method": "getblockstats",
+	"params": [
+        0
+    ]
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into block stats.
+        let actual: BlockStats = process_response(response).await;
+
+        // Check the block stats, where the genesis block only contains the coinbase transaction.
+        let genesis_block = Testnet2::genesis_block();
+        assert_eq!(0, actual.height);
+        assert_eq!(genesis_block.hash().to_string(), actual.block_hash);
+        assert_eq!(genesis_block.transactions().len(), actual.number_of_transactions);
+        assert_eq!(genesis_block.to_bytes_le().unwrap().len(), actual.size_in_bytes);
+        assert_eq!(0, actual.total_fees);
+        assert_eq!(None, actual.min_fee);
+        assert_eq!(None, actual.max_fee);
+        assert_eq!(None, actual.median_fee);
+        assert_eq!(1, actual.number_of_excluded_transactions);
+        assert_eq!(genesis_block.serial_numbers().count(), actual.number_of_inputs);
+        assert_eq!(genesis_block.commitments().count(), actual.number_of_outputs);
+    }
+
+    #[tokio::test]
+    async fn test_get_block_template() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Initialize the expected block template values.
+        let expected_previous_block_hash = Testnet2::genesis_block().hash().to_string();
+        let expected_block_height = 1;
+        let expected_ledger_root = rpc.latest_ledger_root().await.unwrap().to_string();
+        let expected_transactions = Vec::<serde_json::Value>::new();
+        let expected_block_reward = Block::<Testnet2>::block_reward(1).0;
+
+        // Initialize a new request that calls the `getblocktemplate` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc":"2.0",
+	"id": "1",
+	"method": "getblocktemplate"
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into a ledger root.
+        let actual: serde_json::Value = process_response(response).await;
+
+        // Check the block template state.
+        assert_eq!(expected_previous_block_hash, actual["previous_block_hash"]);
+        assert_eq!(expected_block_height, actual["block_height"]);
+        assert_eq!(expected_ledger_root, actual["ledger_root"].as_str().unwrap());
+        assert_eq!(&expected_transactions, actual["transactions"].as_array().unwrap());
+        assert_eq!(expected_block_reward, actual["coinbase_reward"].as_i64().unwrap());
+
+        // Check the difficulty adjustment inputs.
+        let genesis_header = Testnet2::genesis_block().header();
+        let block_timestamp = actual["time"].as_i64().unwrap();
+        let expected_difficulty_target = Blocks::<Testnet2>::compute_difficulty_target(genesis_header, block_timestamp, 1);
+        assert_eq!(expected_difficulty_target, actual["difficulty_target"].as_u64().unwrap());
+        let time_delta = block_timestamp - genesis_header.timestamp();
+        assert_eq!(genesis_header.difficulty_target(), actual["previous_difficulty_target"]);
+        assert_eq!(0, actual["anchor_block_height"]);
+        assert_eq!(time_delta, actual["anchor_time_delta"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_block_template_with_selection() {
+        let mut rng = ChaChaRng::seed_from_u64(123456789);
+
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Initialize a new account.
+        let account = Account::<Testnet2>::new(&mut rng);
+        let address = account.address();
+
+        // Send two transactions to the node.
+        let mut transactions = Vec::with_capacity(2);
+        for _ in 0..2 {
+            let (transaction, _) = Transaction::<Testnet2>::new_coinbase(address, AleoAmount(0), true, &mut rng)
+                .expect("Failed to create a coinbase transaction");
+
+            // Initialize a new request that calls the `sendtransaction` endpoint.
+            let request = Request::new(Body::from(format!(
+                "{{
+	\"jsonrpc\": \"2.0\",
+	\"id\": \"1\",
+	\"method\": \"sendtransaction\",
+	\"params\": [
+        \"{}\"
+    ]
+}}",
+                hex::encode(transaction.to_bytes_le().unwrap())
+            )));
+
+            // Send the request to the RPC.
+            let _response = handle_rpc(caller(), rpc.clone(), request)
+                .await
+                .expect("Test RPC failed to process request");
+
+            transactions.push(transaction);
+        }
+
+        // Give the node some time to process the transactions.
+        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+
+        // Initialize a new request that calls the `getblocktemplate` endpoint with the given selection.
+        let request = |selection: &str| {
+            Request::new(Body::from(format!(
+                "{{
+	\"jsonrpc\": \"2.0\",
+	\"id\": \"1\",
+	\"method\": \"getblocktemplate\",
+	\"params\": [
+        {}
+    ]
+}}",
+                selection
+            )))
+        };
+
+        // Ensure a malformed selection is rejected.
+        let response = handle_rpc(caller(), rpc.clone(), request("{ \"prioritize\": \"abc\" }"))
+            .await
+            .expect("Test RPC failed to process request");
+        let response_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let response_json: jrt::Response<serde_json::Value, String> = serde_json::from_slice(&response_bytes).unwrap();
+        let error = response_json.payload.expect_err("Expected the selection to be rejected");
+        assert_eq!(jrt::ErrorCode::InvalidParams, error.code);
+
+        // Ensure a selection with a transaction that is not in the memory pool is rejected.
+        let unknown_transaction_id = Testnet2::genesis_block().to_coinbase_transaction().unwrap().transaction_id();
+        let selection = format!("{{ \"prioritize\": [\"{}\"] }}", unknown_transaction_id);
+        let response = handle_rpc(caller(), rpc.clone(), request(&selection))
+            .await
+            .expect("Test RPC failed to process request");
+        let response_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let response_json: jrt::Response<serde_json::Value, String> = serde_json::from_slice(&response_bytes).unwrap();
+        let error = response_json.payload.expect_err("Expected the selection to be rejected");
+        assert_eq!(jrt::ErrorCode::InvalidParams, error.code);
+
+        // Ensure a selection that prioritizes and excludes the same transaction is rejected.
+        let transaction_id = transactions[0].transaction_id();
+        let selection = format!(
+            "{{ \"prioritize\": [\"{}\"], \"exclude\": [\"{}\"] }}",
+            transaction_id, transaction_id
+        );
+        let response = handle_rpc(caller(), rpc.clone(), request(&selection))
+            .await
+            .expect("Test RPC failed to process request");
+        let response_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let response_json: jrt::Response<serde_json::Value, String> = serde_json::from_slice(&response_bytes).unwrap();
+        let error = response_json.payload.expect_err("Expected the selection to be rejected");
+        assert_eq!(jrt::ErrorCode::InvalidParams, error.code);
+
+        // Ensure the template includes the prioritized transaction and leaves out the excluded one.
+        let selection = format!(
+            "{{ \"prioritize\": [\"{}\"], \"exclude\": [\"{}\"] }}",
+            transactions[1].transaction_id(),
+            transactions[0].transaction_id()
+        );
+        let response = handle_rpc(caller(), rpc, request(&selection))
+            .await
+            .expect("Test RPC failed to process request");
+        let actual: serde_json::Value = process_response(response).await;
+
+        let expected_transactions = vec![serde_json::Value::String(transactions[1].to_string())];
+        assert_eq!(&expected_transactions, actual["transactions"].as_array().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_get_block_transactions() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Initialize a new request that calls the `getblocktransactions` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc": "2.0",
+	"id": "1",
+	"method": "getblocktransactions",
+	"params": [
+        0
+    ]
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into transactions.
+        let actual: Transactions<Testnet2> = process_response(response).await;
+
+        // Check the transactions.
+        let expected = Testnet2::genesis_block().transactions();
+        assert_eq!(*expected, actual);
+    }
+
+    #[tokio::test]
+    async fn test_get_block_transaction_ids() {
+        let rng = &mut thread_rng();
+        let terminator = AtomicBool::new(false);
+
+        // Initialize a new temporary directory.
+        let directory = temp_dir();
+
+        // Initialize a new ledger state at the temporary directory.
+        let ledger_state = new_ledger_state::<Testnet2, RocksDB, PathBuf>(Some(directory.clone()));
+        assert_eq!(0, ledger_state.latest_block_height());
+
+        // Initialize a new account.
+        let account = Account::<Testnet2>::new(&mut thread_rng());
+        let address = account.address();
+
+        // Mine the next block.
+        let (block_1, _) = ledger_state
+            .mine_next_block(address, true, &[], &terminator, rng)
+            .expect("Failed to mine");
+        ledger_state.add_next_block(&block_1).expect("Failed to add next block to ledger");
+        assert_eq!(1, ledger_state.latest_block_height());
+
+        // Drop the handle to ledger_state. Note this does not remove the blocks in the temporary directory.
+        drop(ledger_state);
+
+        // Initialize a new RPC with the ledger state containing the genesis block and block_1.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(Some(directory.clone())).await;
+
+        // Initialize a new request that calls the `getblocktransactionids` endpoint.
+        let request = Request::new(Body::from(format!(
+            "{{
+	\"jsonrpc\": \"2.0\",
+	\"id\": \"1\",
+	\"method\": \"getblocktransactionids\",
+	\"params\": [
+        \"{}\"
+    ]
+}}",
+            block_1.hash()
+        )));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into transaction IDs.
+        let actual: Vec<<Testnet2 as Network>::TransactionID> = process_response(response).await;
+
+        // Check the transaction IDs.
+        let expected = block_1.transactions().transaction_ids().collect::<Vec<_>>();
+        assert_eq!(expected, actual);
+    }
+
+    #[tokio::test]
+    async fn test_get_ciphertext() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Get the commitment from the genesis coinbase transaction.
+        let commitment = Testnet2::genesis_block().to_coinbase_transaction().unwrap().transitions()[0]
+            .commitments()
+            .next()
+            .unwrap()
+            .to_string();
+
+        // Initialize a new request that calls the `getciphertext` endpoint.
+        let request = Request::new(Body::from(format!(
+            "{{
+	\"jsonrpc\": \"2.0\",
+	\"id\": \"1\",
+	\"method\": \"getciphertext\",
+	\"params\": [
+        \"{}\"
+    ]
+}}",
+            commitment
+        )));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into a ciphertext.
+        let actual: <Testnet2 as Network>::RecordCiphertext = process_response(response).await;
+
+        // Check the ciphertext.
+        assert!(
+            Testnet2::genesis_block()
+                .transactions()
+                .first()
+                .unwrap()
+                .ciphertexts()
+                .any(|expected| *expected == actual)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_ledger_proof() {
+        let mut rng = ChaChaRng::seed_from_u64(123456789);
+        let terminator = AtomicBool::new(false);
+
+        // Initialize a new temporary directory.
+        let directory = temp_dir();
 
         // Initialize a new ledger state at the temporary directory.
         let ledger_state = new_ledger_state::<Testnet2, RocksDB, PathBuf>(Some(directory.clone()));
@@ -1153,30 +2259,500 @@ mod tests {
         // Initialize a new RPC.
         let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
 
-        // Declare the expected node state.
-        let expected = serde_json::json!({
-            "address": Option::<Address<Testnet2>>::None,
-            "candidate_peers": Vec::<SocketAddr>::new(),
-            "connected_peers": Vec::<SocketAddr>::new(),
-            "latest_block_hash": Testnet2::genesis_block().hash(),
-            "latest_block_height": 0,
-            "latest_cumulative_weight": 0,
-            "launched": format!("{} minutes ago", 0),
-            "number_of_candidate_peers": 0,
-            "number_of_connected_peers": 0,
-            "number_of_connected_sync_nodes": 0,
-            "software": format!("snarkOS {}", env!("CARGO_PKG_VERSION")),
-            "status": Client::<Testnet2>::status().to_string(),
-            "type": Client::<Testnet2>::NODE_TYPE,
-            "version": Client::<Testnet2>::MESSAGE_VERSION,
-        });
+        // Declare the expected node state.
+        let expected = serde_json::json!({
+            "address": Option::<Address<Testnet2>>::None,
+            "candidate_peers": Vec::<SocketAddr>::new(),
+            "connected_peers": Vec::<SocketAddr>::new(),
+            "detected_external_addr": Option::<SocketAddr>::None,
+            "latest_block_hash": Testnet2::genesis_block().hash(),
+            "latest_block_height": 0,
+            "latest_cumulative_weight": 0,
+            "launched": format!("{} minutes ago", 0),
+            "number_of_candidate_peers": 0,
+            "number_of_connected_peers": 0,
+            "number_of_connected_sync_nodes": 0,
+            "software": format!("snarkOS {}", env!("CARGO_PKG_VERSION")),
+            "status": Client::<Testnet2>::status().to_string(),
+            "type": Client::<Testnet2>::NODE_TYPE,
+            "version": Client::<Testnet2>::MESSAGE_VERSION,
+        });
+
+        // Initialize a new request that calls the `getnodestate` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc":"2.0",
+	"id": "1",
+	"method": "getnodestate"
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into a ledger root.
+        let actual: serde_json::Value = process_response(response).await;
+
+        println!("get_node_state: {:?}", actual);
+
+        // Check the node state.
+        assert_eq!(expected, actual);
+    }
+
+    #[tokio::test]
+    async fn test_ping() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Initialize a new request that calls the `ping` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc":"2.0",
+	"id": "1",
+	"method": "ping"
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into the ping info.
+        let actual: PingInfo = process_response(response).await;
+
+        // Check the ping info.
+        assert_eq!(0, actual.connected_peers);
+        assert_eq!(Testnet2::genesis_block().height(), actual.latest_block_height);
+    }
+
+    #[tokio::test]
+    async fn test_get_pool_info() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Initialize a new request that calls the `getpoolinfo` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc":"2.0",
+	"id": "1",
+	"method": "getpoolinfo"
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into the pool info.
+        let actual: PoolInfo = process_response(response).await;
+
+        // Check that no provers are known to the pool, and that the pool is not paused.
+        assert!(actual.provers.is_empty());
+        assert!(!actual.pool_paused);
+        assert_eq!(0, actual.queued_responses);
+    }
+
+    #[tokio::test]
+    async fn test_pause_and_resume_pool() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Sends a request that calls the given endpoint without parameters, and returns the pool info.
+        let call = |rpc, method: &'static str| async move {
+            let request = Request::new(Body::from(format!(r#"{{"jsonrpc": "2.0", "id": "1", "method": "{}"}}"#, method)));
+            let response = handle_rpc(caller(), rpc, request)
+                .await
+                .expect("Test RPC failed to process request");
+            process_response::<serde_json::Value>(response).await
+        };
+
+        // Check that the pool is reported as paused once it is paused.
+        assert_eq!(serde_json::Value::Null, call(rpc.clone(), "pausepool").await);
+        let pool_info: PoolInfo = serde_json::from_value(call(rpc.clone(), "getpoolinfo").await).unwrap();
+        assert!(pool_info.pool_paused);
+        assert_eq!(0, pool_info.queued_responses);
+
+        // Check that the pool is no longer reported as paused once it is resumed.
+        assert_eq!(serde_json::Value::Null, call(rpc.clone(), "resumepool").await);
+        let pool_info: PoolInfo = serde_json::from_value(call(rpc, "getpoolinfo").await).unwrap();
+        assert!(!pool_info.pool_paused);
+    }
+
+    #[tokio::test]
+    async fn test_get_mining_info() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Miner<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Initialize a new request that calls the `getmininginfo` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc":"2.0",
+	"id": "1",
+	"method": "getmininginfo"
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into the mining info.
+        let actual: MiningInfo = process_response(response).await;
+
+        // Check the mining info.
+        assert!(!actual.is_mining_paused);
+        assert_eq!(Testnet2::genesis_block().height(), actual.latest_block_height);
+        assert_eq!(0, actual.memory_pool_size);
+    }
+
+    #[tokio::test]
+    async fn test_get_mining_info_while_proving() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Miner<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Start a proof attempt, and let it run for a second.
+        Miner::<Testnet2>::proving_progress().start(std::time::Instant::now());
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+        // Initialize a new request that calls the `getmininginfo` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc":"2.0",
+	"id": "1",
+	"method": "getmininginfo"
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into the mining info.
+        let actual: MiningInfo = process_response(response).await;
+        Miner::<Testnet2>::proving_progress().finish(std::time::Instant::now(), false);
+
+        // Check the progress of the proof, which has no estimate without any prior proofs.
+        assert!(actual.elapsed_ms.unwrap() >= 1000);
+        assert!(actual.estimated_total_ms.map(|estimate| estimate > 0).unwrap_or(true));
+    }
+
+    #[tokio::test]
+    async fn test_get_mining_info_with_blocks_found() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Miner<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Mock a miner that has found 3 blocks.
+        for _ in 0..3 {
+            Miner::<Testnet2>::prover_stats().record_block_found(std::time::Instant::now());
+        }
+
+        // Initialize a new request that calls the `getmininginfo` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc":"2.0",
+	"id": "1",
+	"method": "getmininginfo"
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into the mining info.
+        let actual: MiningInfo = process_response(response).await;
+
+        // Check the blocks found by the miner.
+        assert_eq!(3, actual.blocks_mined);
+        assert!(actual.last_block_found_secs_ago.is_some());
+        assert!(!actual.connected);
+    }
+
+    #[tokio::test]
+    async fn test_get_mining_info_on_client() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Initialize a new request that calls the `getmininginfo` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc":"2.0",
+	"id": "1",
+	"method": "getmininginfo"
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Ensure a client, which does not mine, has no mining info.
+        let response_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let response_json: jrt::Response<serde_json::Value, String> = serde_json::from_slice(&response_bytes).unwrap();
+        let error = response_json.payload.expect_err("Expected the method to be unavailable");
+        assert!(error.data.unwrap_or_default().contains("do not mine"));
+    }
+
+    #[tokio::test]
+    async fn test_get_peer_chain_info() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Initialize a new request that calls the `getpeerchaininfo` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc":"2.0",
+	"id": "1",
+	"method": "getpeerchaininfo",
+	"params": ["127.0.0.1:4132"]
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into the peer chain info.
+        let actual: PeerChainInfo = process_response(response).await;
+
+        // Check that the peer has no recent data, as it has never sent a ping.
+        let expected = PeerChainInfo::no_recent_data("127.0.0.1:4132".parse().unwrap());
+        assert_eq!(expected, actual);
+        assert!(!actual.has_recent_data);
+    }
+
+    #[tokio::test]
+    async fn test_get_peer_info() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Track one peer at block height 50, one peer at block height 100, and one peer without a chain state.
+        let peer_1: SocketAddr = "127.0.0.1:4131".parse().unwrap();
+        let peer_2: SocketAddr = "127.0.0.1:4132".parse().unwrap();
+        let peer_3: SocketAddr = "127.0.0.1:4133".parse().unwrap();
+        let capabilities = NodeCapabilities::from(NodeType::Client);
+        let peer_state = |height, status| Some((NodeType::Client, capabilities, status, Some(false), height, Default::default()));
+        {
+            let mut peers_state = rpc.peers_state.write().await;
+            peers_state.insert(peer_1, peer_state(50, State::Syncing));
+            peers_state.insert(peer_2, peer_state(100, State::Ready));
+            peers_state.insert(peer_3, None);
+        }
+
+        // Sends a request that calls the `getpeerinfo` endpoint, and returns the peer info.
+        let get_peer_info = |rpc| async move {
+            let request = Request::new(Body::from(
+                r#"{
+	"jsonrpc": "2.0",
+	"id": "1",
+	"method": "getpeerinfo",
+	"params": []
+}"#,
+            ));
+            let response = handle_rpc(caller(), rpc, request)
+                .await
+                .expect("Test RPC failed to process request");
+            process_response::<Vec<PeerInfo>>(response).await
+        };
+
+        // Check that the sync height and syncing flag of each peer match its advertised chain state.
+        let peer_info = |peer_ip, node_type, sync_height, is_syncing| PeerInfo {
+            peer_ip,
+            node_type,
+            sync_height,
+            is_syncing,
+        };
+        let expected = vec![
+            peer_info(peer_1, Some(NodeType::Client), Some(50), true),
+            peer_info(peer_2, Some(NodeType::Client), Some(100), false),
+            peer_info(peer_3, None, None, false),
+        ];
+        assert_eq!(expected, get_peer_info(rpc.clone()).await);
+
+        // Check that a peer is no longer syncing once it advertises that it is ready.
+        rpc.peers_state.write().await.insert(peer_1, peer_state(60, State::Ready));
+        let actual = get_peer_info(rpc).await;
+        assert_eq!(peer_info(peer_1, Some(NodeType::Client), Some(60), false), actual[0]);
+    }
+
+    #[tokio::test]
+    async fn test_list_peers() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Initialize a new request that calls the `listpeers` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc": "2.0",
+	"id": "1",
+	"method": "listpeers",
+	"params": []
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Check that the versioned list is empty, as the node has no connected peers.
+        let actual: PeerList = process_response(response).await;
+        let expected = PeerList {
+            version: PEER_LIST_VERSION,
+            peers: vec![],
+        };
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_peer_list_schema() {
+        // Check that each field of an entry keeps its name and type, as tooling relies on this schema.
+        let peer_ip = "127.0.0.1:4132".parse().unwrap();
+        let entry = PeerListEntry::new(peer_ip, NodeType::Client, ConnectionDirection::Outbound, Some(50));
+        let expected = serde_json::json!({
+            "address": "127.0.0.1:4132",
+            "direction": "outbound",
+            "node_type": "Client",
+            "height": 50,
+        });
+        assert_eq!(expected, serde_json::to_value(&entry).unwrap());
+
+        // Check that a peer without a chain state has a `null` height.
+        let entry = PeerListEntry::new(peer_ip, NodeType::Prover, ConnectionDirection::Inbound, None);
+        assert_eq!("inbound", entry.direction);
+        assert_eq!(serde_json::Value::Null, serde_json::to_value(&entry).unwrap()["height"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_peer_message_history() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Initialize a new request that calls the `getpeermessagehistory` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc": "2.0",
+	"id": "1",
+	"method": "getpeermessagehistory",
+	"params": ["127.0.0.1:4132"]
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into the message history.
+        let actual: Vec<(String, u64)> = process_response(response).await;
+
+        // Check that the history is empty, as the peer is not connected.
+        assert!(actual.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_sync_status() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Track a peer that is ahead of this node.
+        let capabilities = NodeCapabilities::from(NodeType::Client);
+        let peer_state = Some((NodeType::Client, capabilities, State::Ready, Some(false), 100, Default::default()));
+        rpc.peers_state.write().await.insert("127.0.0.1:4131".parse().unwrap(), peer_state);
+
+        // Initialize a new request that calls the `getsyncstatus` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc": "2.0",
+	"id": "1",
+	"method": "getsyncstatus",
+	"params": []
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc.clone(), request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into the sync status.
+        let actual: SyncStatus = process_response(response).await;
+
+        // Check that the node reports a stall, as it has not added any blocks.
+        assert_eq!(0, actual.latest_block_height);
+        assert_eq!(100, actual.target_block_height);
+        assert_eq!(0.0, actual.blocks_per_second);
+        assert_eq!(SyncEstimate::Stalled, actual.estimated_time_to_sync);
+
+        // Initialize a new request that calls the `getsyncstatus` endpoint with the given target block height.
+        let request = |target_block_height: &str| {
+            Request::new(Body::from(format!(
+                r#"{{
+	"jsonrpc": "2.0",
+	"id": "1",
+	"method": "getsyncstatus",
+	"params": [{}]
+}}"#,
+                target_block_height
+            )))
+        };
+
+        // Ensure a target block height within the advertised block heights is used.
+        let response = handle_rpc(caller(), rpc.clone(), request("50"))
+            .await
+            .expect("Test RPC failed to process request");
+        let actual: SyncStatus = process_response(response).await;
+        assert_eq!(50, actual.target_block_height);
+
+        // Ensure a zero, out-of-range, or malformed target block height is rejected.
+        for target_block_height in ["0", "101", "-1", "\"abc\""] {
+            let response = handle_rpc(caller(), rpc.clone(), request(target_block_height))
+                .await
+                .expect("Test RPC failed to process request");
+            let response_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            let response_json: jrt::Response<serde_json::Value, String> = serde_json::from_slice(&response_bytes).unwrap();
+            let error = response_json.payload.expect_err("Expected the target block height to be rejected");
+            assert_eq!(jrt::ErrorCode::InvalidParams, error.code);
+        }
+
+        // Ensure a peer advertising an inflated block height does not move the target block height.
+        let inflated_state = Some((NodeType::Client, capabilities, State::Ready, Some(false), u32::MAX, Default::default()));
+        rpc.peers_state.write().await.insert("127.0.0.1:4132".parse().unwrap(), inflated_state.clone());
+        let response = handle_rpc(caller(), rpc.clone(), request("")).await.expect("Test RPC failed to process request");
+        let actual: SyncStatus = process_response(response).await;
+        assert_eq!(100, actual.target_block_height);
+
+        // Ensure the inflated block height is used once it is advertised by a majority of the peers.
+        rpc.peers_state.write().await.insert("127.0.0.1:4133".parse().unwrap(), inflated_state);
+        let response = handle_rpc(caller(), rpc.clone(), request("")).await.expect("Test RPC failed to process request");
+        let actual: SyncStatus = process_response(response).await;
+        assert_eq!(u32::MAX, actual.target_block_height);
+    }
+
+    #[tokio::test]
+    async fn test_get_task_status() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Spawn a background task that keeps running for the duration of the test.
+        Client::<Testnet2>::tasks().append(tokio::spawn(futures::future::pending::<()>()));
 
-        // Initialize a new request that calls the `getnodestate` endpoint.
+        // Initialize a new request that calls the `gettaskstatus` endpoint.
         let request = Request::new(Body::from(
             r#"{
-	"jsonrpc":"2.0",
+	"jsonrpc": "2.0",
 	"id": "1",
-	"method": "getnodestate"
+	"method": "gettaskstatus",
+	"params": []
 }"#,
         ));
 
@@ -1185,13 +2761,11 @@ mod tests {
             .await
             .expect("Test RPC failed to process request");
 
-        // Process the response into a ledger root.
-        let actual: serde_json::Value = process_response(response).await;
-
-        println!("get_node_state: {:?}", actual);
+        // Process the response into the task status.
+        let actual: TaskStatus = process_response(response).await;
 
-        // Check the node state.
-        assert_eq!(expected, actual);
+        // Check that the background tasks of the node, including the spawned task, are running.
+        assert!(actual.running_tasks >= 1);
     }
 
     #[tokio::test]
@@ -1247,6 +2821,62 @@ mod tests {
         assert_eq!(expected_decrypted_records, actual.decrypted_records)
     }
 
+    #[tokio::test]
+    async fn test_get_raw_transaction() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Get the genesis coinbase transaction.
+        let transaction = Testnet2::genesis_block().to_coinbase_transaction().unwrap();
+
+        // Initialize a new request that calls the `getrawtransaction` endpoint.
+        let request = Request::new(Body::from(format!(
+            "{{
+	\"jsonrpc\": \"2.0\",
+	\"id\": \"1\",
+	\"method\": \"getrawtransaction\",
+	\"params\": [
+        \"{}\"
+    ]
+}}",
+            transaction.transaction_id()
+        )));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc.clone(), request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into a raw transaction.
+        let raw_transaction: RawTransaction = process_response(response).await;
+        assert_eq!(transaction.transaction_id().to_string(), raw_transaction.transaction_id);
+        assert_eq!(hex::encode(transaction.to_bytes_le().unwrap()), raw_transaction.hex);
+
+        // Initialize a new request that calls the `decoderawtransaction` endpoint with the raw transaction.
+        let request = Request::new(Body::from(format!(
+            "{{
+	\"jsonrpc\": \"2.0\",
+	\"id\": \"1\",
+	\"method\": \"decoderawtransaction\",
+	\"params\": [
+        \"{}\"
+    ]
+}}",
+            raw_transaction.hex
+        )));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Ensure the decoded transaction ID matches the ID of the raw transaction.
+        let transaction_info: TransactionInfo = process_response(response).await;
+        assert_eq!(raw_transaction.transaction_id, transaction_info.transaction_id);
+        assert_eq!(transaction.ledger_root().to_string(), transaction_info.ledger_root);
+        assert_eq!(transaction.transitions().len(), transaction_info.transition_ids.len());
+    }
+
     #[tokio::test]
     async fn test_get_transition() {
         // Initialize a new RPC.
@@ -1317,6 +2947,81 @@ mod tests {
         assert_eq!(actual, Vec::<String>::new());
     }
 
+    #[tokio::test]
+    async fn test_get_connection_count() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Initialize a new request that calls the `getconnectioncount` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc": "2.0",
+	"id": "1",
+	"method": "getconnectioncount",
+	"params": []
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into a connection count.
+        let actual: ConnectionCount = process_response(response).await;
+
+        // Check the connection count.
+        assert_eq!(ConnectionCount::default(), actual);
+    }
+
+    #[test]
+    fn test_connection_count() {
+        // Count one inbound and two outbound peers of known types.
+        let connections = vec![
+            (NodeType::Client, ConnectionDirection::Inbound),
+            (NodeType::Sync, ConnectionDirection::Outbound),
+            (NodeType::Client, ConnectionDirection::Outbound),
+        ];
+        let connection_count: ConnectionCount = connections.into_iter().collect();
+
+        // Check the connection count.
+        assert_eq!(3, connection_count.total);
+        assert_eq!(1, connection_count.inbound);
+        assert_eq!(2, connection_count.outbound);
+        assert_eq!(2, connection_count.by_type.len());
+        assert_eq!(Some(&2), connection_count.by_type.get(&NodeType::Client.to_string()));
+        assert_eq!(Some(&1), connection_count.by_type.get(&NodeType::Sync.to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_estimate_fee() {
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Initialize a new request that calls the `estimatefee` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc": "2.0",
+	"id": "1",
+	"method": "estimatefee",
+	"params": [
+        1024, 1
+    ]
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into a fee.
+        let actual: u64 = process_response(response).await;
+
+        // Check the fee estimated by the default fee policy.
+        assert_eq!(DefaultFeePolicy::estimate_fee(1024, 1), actual);
+    }
+
     #[tokio::test]
     async fn test_send_transaction() {
         let mut rng = ChaChaRng::seed_from_u64(123456789);
@@ -1351,11 +3056,78 @@ mod tests {
             .expect("Test RPC failed to process request");
 
         // Process the response into a ciphertext.
-        let actual: <Testnet2 as Network>::TransactionID = process_response(response).await;
+        let actual: SendTransactionResponse = process_response(response).await;
 
         // Check the transaction id.
         let expected = transaction.transaction_id();
-        assert_eq!(expected, actual);
+        assert_eq!(expected.to_string(), actual.transaction_id);
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_with_zeroed_proof() {
+        let mut rng = ChaChaRng::seed_from_u64(123456789);
+
+        // Initialize a new account.
+        let account = Account::<Testnet2>::new(&mut rng);
+        let address = account.address();
+
+        // Initialize a new transaction, and zero out the bytes of its proof.
+        let (transaction, _) = Transaction::<Testnet2>::new_coinbase(address, AleoAmount(1234), true, &mut rng)
+            .expect("Failed to create a coinbase transaction");
+        let mut transaction_bytes = transaction.to_bytes_le().unwrap();
+        let proof_bytes = transaction.transitions()[0].proof().to_bytes_le().unwrap();
+        let proof_index = transaction_bytes
+            .windows(proof_bytes.len())
+            .position(|window| window == &proof_bytes[..])
+            .expect("Failed to find the proof in the transaction");
+        transaction_bytes[proof_index..proof_index + proof_bytes.len()].fill(0);
+
+        // Initialize a new rpc.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Initialize a new request that calls the `sendtransaction` endpoint.
+        let request = Request::new(Body::from(format!(
+            "{{
+	\"jsonrpc\": \"2.0\",
+	\"id\": \"1\",
+	\"method\": \"sendtransaction\",
+	\"params\": [
+        \"{}\"
+    ]
+}}",
+            hex::encode(transaction_bytes)
+        )));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc.clone(), request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Ensure the transaction is rejected as invalid input.
+        let response_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let response_json: jrt::Response<serde_json::Value, String> = serde_json::from_slice(&response_bytes).unwrap();
+        let error = response_json.payload.expect_err("Expected the transaction to be rejected");
+        assert_eq!(jrt::ErrorCode::InvalidParams, error.code);
+        assert!(!error.data.unwrap_or_default().is_empty());
+
+        // Give the node some time to process any transaction.
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+        // Initialize a new request that calls the `getmemorypool` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc":"2.0",
+	"id": "1",
+	"method": "getmemorypool"
+}"#,
+        ));
+
+        // Ensure the transaction was not broadcast.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+        let actual: Vec<Transaction<Testnet2>> = process_response(response).await;
+        assert!(actual.is_empty());
     }
 
     #[tokio::test]
@@ -1393,11 +3165,83 @@ mod tests {
         let response = result.expect("Test RPC failed to process request");
 
         // Process the response into a transaction ID.
-        let actual: <Testnet2 as Network>::TransactionID =
+        let actual: SendTransactionResponse =
             serde_json::from_value(response["result"].clone()).expect("Failed to deserialize response from send_transaction");
 
         // Check the transaction id.
-        assert_eq!("at1yh7l65ege8kgzx5fsyuwldtsyk6k73m95pf7cr5tlqt7s2yvpcyssemtwd", actual.to_string());
+        assert_eq!(
+            "at1yh7l65ege8kgzx5fsyuwldtsyk6k73m95pf7cr5tlqt7s2yvpcyssemtwd",
+            actual.transaction_id
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resend_transaction() {
+        let mut rng = ChaChaRng::seed_from_u64(123456789);
+
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Initialize a new transaction.
+        let account = Account::<Testnet2>::new(&mut rng);
+        let (transaction, _) = Transaction::<Testnet2>::new_coinbase(account.address(), AleoAmount(0), true, &mut rng)
+            .expect("Failed to create a coinbase transaction");
+
+        // Initialize a new request that calls the `resendtransaction` endpoint.
+        let resend_request = || {
+            Request::new(Body::from(format!(
+                "{{
+	\"jsonrpc\": \"2.0\",
+	\"id\": \"1\",
+	\"method\": \"resendtransaction\",
+	\"params\": [
+        \"{}\"
+    ]
+}}",
+                transaction.transaction_id()
+            )))
+        };
+
+        // Ensure a transaction that is not in the memory pool is rejected.
+        let response = handle_rpc(caller(), rpc.clone(), resend_request())
+            .await
+            .expect("Test RPC failed to process request");
+        let response_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let response_json: jrt::Response<serde_json::Value, String> = serde_json::from_slice(&response_bytes).unwrap();
+        let error = response_json.payload.expect_err("Expected the transaction to be rejected");
+        assert_eq!(jrt::ErrorCode::InvalidParams, error.code);
+        assert!(error.data.unwrap_or_default().contains("is not in the memory pool"));
+
+        // Initialize a new request that calls the `sendtransaction` endpoint.
+        let request = Request::new(Body::from(format!(
+            "{{
+	\"jsonrpc\": \"2.0\",
+	\"id\": \"1\",
+	\"method\": \"sendtransaction\",
+	\"params\": [
+        \"{}\"
+    ]
+}}",
+            hex::encode(transaction.to_bytes_le().unwrap())
+        )));
+
+        // Send the transaction to the node.
+        let _response = handle_rpc(caller(), rpc.clone(), request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Give the node some time to add the transaction to the memory pool.
+        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, resend_request())
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response, as the node has no connected peers to send the transaction to.
+        let actual: ResendTransactionResponse = process_response(response).await;
+        assert_eq!(transaction.transaction_id().to_string(), actual.transaction_id);
+        assert_eq!(0, actual.dispatched_to_peers);
     }
 
     #[tokio::test]
@@ -1461,4 +3305,108 @@ mod tests {
         let expected = vec![transaction];
         assert_eq!(*expected, actual);
     }
+
+    #[tokio::test]
+    async fn test_get_raw_mempool() {
+        let mut rng = ChaChaRng::seed_from_u64(123456789);
+
+        // Initialize a new RPC.
+        let rpc = new_rpc::<Testnet2, Client<Testnet2>, RocksDB, PathBuf>(None).await;
+
+        // Ensure an empty memory pool returns an empty list.
+        assert!(rpc.get_raw_mempool(false).await.unwrap().is_empty());
+
+        // Send 5 transactions to the node.
+        let mut expected = Vec::with_capacity(5);
+        let mut expected_fees = HashMap::with_capacity(5);
+        for amount in 0..5 {
+            // Initialize a new account.
+            let account = Account::<Testnet2>::new(&mut rng);
+
+            // Initialize a new transaction, which mints the given amount.
+            let (transaction, _) = Transaction::<Testnet2>::new_coinbase(account.address(), AleoAmount(amount), true, &mut rng)
+                .expect("Failed to create a coinbase transaction");
+            expected.push(transaction.transaction_id().to_string());
+            // A coinbase transaction mints its amount, and so has a value balance of at most 0, and pays no fee.
+            assert!(transaction.value_balance().0 <= 0);
+            expected_fees.insert(transaction.transaction_id().to_string(), 0);
+
+            // Initialize a new request that calls the `sendtransaction` endpoint.
+            let request = Request::new(Body::from(format!(
+                "{{
+	\"jsonrpc\": \"2.0\",
+	\"id\": \"1\",
+	\"method\": \"sendtransaction\",
+	\"params\": [
+        \"{}\"
+    ]
+}}",
+                hex::encode(transaction.to_bytes_le().unwrap())
+            )));
+
+            // Send the request to the RPC.
+            let _response = handle_rpc(caller(), rpc.clone(), request)
+                .await
+                .expect("Test RPC failed to process request");
+        }
+
+        // Give the node some time to process the transactions.
+        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+
+        // Initialize a new request that calls the `getrawmempool` endpoint.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc":"2.0",
+	"id": "1",
+	"method": "getrawmempool",
+	"params": [false]
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc.clone(), request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into transaction IDs.
+        let actual: RawMempool = process_response(response).await;
+        match actual {
+            RawMempool::TransactionIds(mut transaction_ids) => {
+                transaction_ids.sort();
+                expected.sort();
+                assert_eq!(expected, transaction_ids);
+            }
+            RawMempool::Entries(_) => panic!("Expected transaction IDs"),
+        }
+
+        // Initialize a new request that calls the `getrawmempool` endpoint in verbose mode.
+        let request = Request::new(Body::from(
+            r#"{
+	"jsonrpc":"2.0",
+	"id": "1",
+	"method": "getrawmempool",
+	"params": [true]
+}"#,
+        ));
+
+        // Send the request to the RPC.
+        let response = handle_rpc(caller(), rpc, request)
+            .await
+            .expect("Test RPC failed to process request");
+
+        // Process the response into transaction entries.
+        let actual: RawMempool = process_response(response).await;
+        match actual {
+            RawMempool::Entries(entries) => {
+                assert_eq!(5, entries.len());
+                for entry in entries {
+                    assert!(expected.contains(&entry.transaction_id));
+                    assert!(entry.size_in_bytes > 0);
+                    assert_eq!(Some(&entry.fee), expected_fees.get(&entry.transaction_id));
+                    assert!(entry.time_received.is_some());
+                }
+            }
+            RawMempool::TransactionIds(_) => panic!("Expected transaction entries"),
+        }
+    }
 }
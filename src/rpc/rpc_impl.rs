@@ -19,12 +19,50 @@
 //! See [RpcFunctions](../trait.RpcFunctions.html) for documentation of public endpoints.
 
 use crate::{
-    rpc::{rpc::*, rpc_trait::RpcFunctions},
+    helpers::{NodeType, State},
+    ledger::Ledger,
+    operator::Operator,
+    rpc::{
+        rpc::*,
+        rpc_trait::RpcFunctions,
+        rpc_types::{
+            BlockCountInfo,
+            BlockStats,
+            BlockchainInfo,
+            ChainTip,
+            ConnectionCount,
+            DecodedRecord,
+            MempoolEntry,
+            MiningInfo,
+            PEER_LIST_VERSION,
+            PeerChainInfo,
+            PeerInfo,
+            PeerList,
+            PeerListEntry,
+            PingInfo,
+            PoolInfo,
+            ProverEntry,
+            RawMempool,
+            RawTransaction,
+            RecordInfo,
+            ResendTransactionResponse,
+            SendTransactionResponse,
+            SyncStatus,
+            TaskStatus,
+            TransactionInfo,
+            TransactionSelection,
+        },
+    },
     Environment,
     LedgerReader,
+    MemoryPoolTimestamps,
+    Message,
     Peers,
+    PeersStateReader,
     ProverRequest,
     ProverRouter,
+    SyncProgressReader,
+    UnconfirmedBlocksReader,
 };
 use snarkos_storage::Metadata;
 use snarkvm::{
@@ -34,9 +72,14 @@ use snarkvm::{
 
 use jsonrpc_core::Value;
 use snarkvm::{dpc::Record, utilities::ToBytes};
-use std::{cmp::max, net::SocketAddr, ops::Deref, sync::Arc, time::Instant};
+use std::{cmp::max, collections::HashSet, net::SocketAddr, ops::Deref, sync::Arc, time::Instant};
 use tokio::sync::RwLock;
 
+/// The maximum number of transactions returned by `getrawmempool`.
+const MAXIMUM_RAW_MEMPOOL_TRANSACTIONS: usize = 10_000;
+/// The maximum number of records that may be decoded in one `decoderecords` call.
+pub(crate) const MAXIMUM_DECODE_RECORDS: usize = 256;
+
 #[derive(Debug, Error)]
 pub enum RpcError {
     #[error("{}", _0)]
@@ -46,6 +89,8 @@ pub enum RpcError {
     #[error("{}", _0)]
     FromHexError(#[from] hex::FromHexError),
     #[error("{}", _0)]
+    InvalidInput(String),
+    #[error("{}", _0)]
     Message(String),
     #[error("{}", _0)]
     ParseIntError(#[from] std::num::ParseIntError),
@@ -66,11 +111,18 @@ pub struct RpcInner<N: Network, E: Environment> {
     address: Option<Address<N>>,
     peers: Arc<Peers<N, E>>,
     ledger: LedgerReader<N>,
+    pub(crate) peers_state: PeersStateReader<N>,
+    sync_progress: SyncProgressReader,
+    pub(crate) unconfirmed_blocks: UnconfirmedBlocksReader<N>,
+    operator: Arc<Operator<N, E>>,
     prover_router: ProverRouter<N>,
     memory_pool: Arc<RwLock<MemoryPool<N>>>,
+    memory_pool_timestamps: MemoryPoolTimestamps<N>,
     /// RPC credentials for accessing guarded endpoints
     #[allow(unused)]
     pub(crate) credentials: RpcCredentials,
+    /// The filter of the RPC methods exposed by the server.
+    methods: RpcMethodFilter,
     launched: Instant,
 }
 
@@ -88,24 +140,143 @@ impl<N: Network, E: Environment> Deref for RpcImpl<N, E> {
 
 impl<N: Network, E: Environment> RpcImpl<N, E> {
     /// Creates a new struct for calling public and private RPC endpoints.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         credentials: RpcCredentials,
         address: Option<Address<N>>,
         peers: Arc<Peers<N, E>>,
         ledger: LedgerReader<N>,
+        peers_state: PeersStateReader<N>,
+        sync_progress: SyncProgressReader,
+        unconfirmed_blocks: UnconfirmedBlocksReader<N>,
+        operator: Arc<Operator<N, E>>,
         prover_router: ProverRouter<N>,
         memory_pool: Arc<RwLock<MemoryPool<N>>>,
+        memory_pool_timestamps: MemoryPoolTimestamps<N>,
+        methods: RpcMethodFilter,
     ) -> Self {
         Self(Arc::new(RpcInner {
             address,
             peers,
             ledger,
+            peers_state,
+            sync_progress,
+            unconfirmed_blocks,
+            operator,
             prover_router,
             memory_pool,
+            memory_pool_timestamps,
             credentials,
+            methods,
             launched: Instant::now(),
         }))
     }
+
+    /// Returns `true` if the given RPC method is exposed by the server.
+    pub(crate) fn is_method_enabled(&self, method: &str) -> bool {
+        self.methods.is_enabled(method)
+    }
+
+    /// Returns the fee of the given transaction, or `None` if it has a negative value balance (i.e. a coinbase transaction).
+    fn transaction_fee(transaction: &Transaction<N>) -> Option<i64> {
+        match transaction.value_balance().0 {
+            fee if fee >= 0 => Some(fee),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the given transaction does not spend or create a record already in the ledger.
+    fn is_valid_for_next_block(&self, transaction: &Transaction<N>) -> bool {
+        for serial_number in transaction.serial_numbers() {
+            if let Ok(true) = self.ledger.contains_serial_number(serial_number) {
+                return false;
+            }
+        }
+
+        for commitment in transaction.commitments() {
+            if let Ok(true) = self.ledger.contains_commitment(commitment) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    ///
+    /// Returns the given memory pool transactions, with the prioritized transactions first and without the excluded ones.
+    /// Returns an error if a selected transaction is not in the memory pool, is selected in conflicting ways,
+    /// or if a prioritized transaction is invalid or conflicts with another prioritized transaction.
+    ///
+    fn select_transactions(
+        &self,
+        transactions: Vec<Transaction<N>>,
+        selection: &TransactionSelection,
+    ) -> Result<Vec<Transaction<N>>, RpcError> {
+        // Ensure no transaction is both prioritized and excluded.
+        if let Some(transaction_id) = selection.prioritize.iter().find(|id| selection.exclude.contains(id)) {
+            return Err(RpcError::InvalidInput(format!(
+                "Transaction {} cannot be both prioritized and excluded",
+                transaction_id
+            )));
+        }
+
+        // Ensure the selected transactions are in the memory pool.
+        let find = |transaction_id: &String| {
+            transactions
+                .iter()
+                .find(|transaction| transaction.transaction_id().to_string() == *transaction_id)
+        };
+        for transaction_id in selection.prioritize.iter().chain(selection.exclude.iter()) {
+            if find(transaction_id).is_none() {
+                return Err(RpcError::InvalidInput(format!(
+                    "Transaction {} is not in the memory pool",
+                    transaction_id
+                )));
+            }
+        }
+
+        // Include the prioritized transactions first, in the given order.
+        let mut selected_transactions: Vec<Transaction<N>> = Vec::with_capacity(transactions.len());
+        let mut serial_numbers = HashSet::new();
+        for transaction in selection.prioritize.iter().filter_map(find) {
+            if selected_transactions.contains(transaction) {
+                continue;
+            }
+            if !self.is_valid_for_next_block(transaction) {
+                return Err(RpcError::InvalidInput(format!(
+                    "Transaction {} is not valid for the next block",
+                    transaction.transaction_id()
+                )));
+            }
+            if !transaction
+                .serial_numbers()
+                .all(|serial_number| serial_numbers.insert(serial_number))
+            {
+                return Err(RpcError::InvalidInput(format!(
+                    "Transaction {} conflicts with another prioritized transaction",
+                    transaction.transaction_id()
+                )));
+            }
+            selected_transactions.push(transaction.clone());
+        }
+
+        // Include the remaining transactions, except the excluded ones and those that conflict with a prioritized one.
+        for transaction in transactions.iter() {
+            let transaction_id = transaction.transaction_id().to_string();
+            if selection.prioritize.contains(&transaction_id) || selection.exclude.contains(&transaction_id) {
+                continue;
+            }
+            if transaction
+                .serial_numbers()
+                .any(|serial_number| serial_numbers.contains(serial_number))
+            {
+                continue;
+            }
+            selected_transactions.push(transaction.clone());
+        }
+
+        Ok(selected_transactions)
+    }
 }
 
 #[async_trait::async_trait]
@@ -145,6 +316,58 @@ impl<N: Network, E: Environment> RpcFunctions<N> for RpcImpl<N, E> {
         Ok(self.ledger.latest_ledger_root())
     }
 
+    /// Returns the fields of the given record, ensuring it belongs to the expected owner, if one is given.
+    async fn decode_record(&self, record_bytes: String, expected_owner: Option<String>) -> Result<RecordInfo, RpcError> {
+        let record: Record<N> = match FromBytes::from_bytes_le(&hex::decode(record_bytes)?) {
+            Ok(record) => record,
+            Err(error) => return Err(RpcError::InvalidInput(format!("Failed to deserialize the record: {}", error))),
+        };
+
+        // Ensure the record belongs to the expected owner, so it is not mistaken for a record of another address.
+        if let Some(expected_owner) = expected_owner {
+            let expected_owner: Address<N> = match expected_owner.parse() {
+                Ok(address) => address,
+                Err(error) => return Err(RpcError::InvalidInput(format!("Invalid expected owner: {}", error))),
+            };
+            if record.owner() != expected_owner {
+                return Err(RpcError::InvalidInput(format!(
+                    "Record {} is owned by {}, not the expected owner {}",
+                    record.commitment(),
+                    record.owner(),
+                    expected_owner
+                )));
+            }
+        }
+
+        Ok(RecordInfo {
+            owner: record.owner().to_string(),
+            value: record.value().0,
+            payload: hex::encode(record.payload().to_bytes_le()?),
+            program_id: record.program_id().to_string(),
+            commitment: record.commitment().to_string(),
+        })
+    }
+
+    /// Returns the fields of each of the given records, in order, or the reason each record failed to decode.
+    async fn decode_records(&self, records: Vec<String>) -> Result<Vec<DecodedRecord>, RpcError> {
+        if records.len() > MAXIMUM_DECODE_RECORDS {
+            return Err(RpcError::InvalidInput(format!(
+                "Requested {} records, but at most {} may be decoded at once",
+                records.len(),
+                MAXIMUM_DECODE_RECORDS
+            )));
+        }
+
+        let mut decoded_records = Vec::with_capacity(records.len());
+        for record_bytes in records {
+            decoded_records.push(match self.decode_record(record_bytes, None).await {
+                Ok(record_info) => DecodedRecord::Info(record_info),
+                Err(error) => DecodedRecord::Error(error.to_string()),
+            });
+        }
+        Ok(decoded_records)
+    }
+
     /// Returns the block given the block height.
     async fn get_block(&self, block_height: u32) -> Result<Block<N>, RpcError> {
         Ok(self.ledger.get_block(block_height)?)
@@ -156,6 +379,80 @@ impl<N: Network, E: Environment> RpcFunctions<N> for RpcImpl<N, E> {
         Ok(self.ledger.get_blocks(safe_start_height, end_block_height)?)
     }
 
+    /// Returns the heights of the canonical blocks mined by the given address, in increasing order.
+    async fn get_blocks_by_miner(&self, address: serde_json::Value) -> Result<Vec<u32>, RpcError> {
+        let address: Address<N> = serde_json::from_value(address)?;
+        Ok(self.ledger.get_blocks_by_miner(&address)?)
+    }
+
+    /// Returns a summary of the canonical chain and the sync state of this node.
+    async fn get_blockchain_info(&self) -> Result<BlockchainInfo, RpcError> {
+        let latest_block_height = self.ledger.latest_block_height();
+        let latest_block_timestamp = self.ledger.latest_block_timestamp();
+
+        // The node is considered synced once it is no longer peering or syncing.
+        let is_synced = !E::status().is_peering() && !E::status().is_syncing();
+
+        // Estimate the sync progress from the timestamp of the latest block,
+        // relative to the time elapsed since the genesis block.
+        let sync_progress_percent = match is_synced {
+            true => 100.0,
+            false => {
+                let genesis_timestamp = N::genesis_block().timestamp();
+                let elapsed = chrono::Utc::now().timestamp().saturating_sub(genesis_timestamp);
+                match elapsed > 0 {
+                    true => {
+                        let progress = latest_block_timestamp.saturating_sub(genesis_timestamp) as f64 / elapsed as f64;
+                        (progress * 100.0).clamp(0.0, 100.0)
+                    }
+                    false => 100.0,
+                }
+            }
+        };
+
+        Ok(BlockchainInfo {
+            network_id: N::NETWORK_ID,
+            chain_height: latest_block_height,
+            best_block_hash: self.ledger.latest_block_hash().to_string(),
+            difficulty_target: self.ledger.latest_block_difficulty_target(),
+            cumulative_weight: self.ledger.latest_cumulative_weight(),
+            is_synced,
+            sync_progress_percent,
+            connected_peers: self.peers.number_of_connected_peers().await,
+            node_type: E::NODE_TYPE.to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        })
+    }
+
+    /// Returns the number of blocks and the total number of transactions in the canonical chain.
+    async fn get_block_count(&self) -> Result<BlockCountInfo, RpcError> {
+        let latest_block_height = self.ledger.latest_block_height();
+        Ok(BlockCountInfo {
+            block_count: latest_block_height.saturating_add(1),
+            transactions_total: self.ledger.get_transactions_total(latest_block_height)?,
+        })
+    }
+
+    /// Returns the tip of the canonical chain, followed by the tips of the retained fork branches, from the highest.
+    async fn get_chain_tips(&self) -> Result<Vec<ChainTip>, RpcError> {
+        let mut chain_tips = vec![ChainTip {
+            height: self.ledger.latest_block_height(),
+            hash: self.ledger.latest_block_hash().to_string(),
+            branch_length: 0,
+            status: "active".to_string(),
+        }];
+
+        let mut branches = Ledger::<N, E>::fork_branches(&*self.unconfirmed_blocks.read().await);
+        branches.sort_by_key(|branch| std::cmp::Reverse(branch.tip_block_height));
+        chain_tips.extend(branches.into_iter().map(|branch| ChainTip {
+            height: branch.tip_block_height,
+            hash: branch.tip_block_hash.to_string(),
+            branch_length: branch.len(),
+            status: "fork".to_string(),
+        }));
+        Ok(chain_tips)
+    }
+
     /// Returns the block height for the given the block hash.
     async fn get_block_height(&self, block_hash: serde_json::Value) -> Result<u32, RpcError> {
         let block_hash: N::BlockHash = serde_json::from_value(block_hash)?;
@@ -178,8 +475,47 @@ impl<N: Network, E: Environment> RpcFunctions<N> for RpcImpl<N, E> {
         Ok(self.ledger.get_block_header(block_height)?)
     }
 
-    /// Returns the block template for the next mined block
-    async fn get_block_template(&self) -> Result<Value, RpcError> {
+    /// Returns aggregate statistics on the transactions of the block at the given block height.
+    /// Transactions with a negative value balance (i.e. coinbase transactions) do not pay a fee,
+    /// and are excluded from the fee statistics.
+    async fn get_block_stats(&self, block_height: u32) -> Result<BlockStats, RpcError> {
+        let block = self.ledger.get_block(block_height)?;
+
+        // Collect the fees of the transactions that pay one.
+        let mut fees = Vec::with_capacity(block.transactions().len());
+        let mut number_of_excluded_transactions = 0;
+        for transaction in block.transactions().iter() {
+            match Self::transaction_fee(transaction) {
+                Some(fee) => fees.push(fee),
+                None => number_of_excluded_transactions += 1,
+            }
+        }
+        fees.sort_unstable();
+
+        // Compute the median fee, averaging the two middle fees for an even number of fees.
+        let median_fee = match fees.len() {
+            0 => None,
+            len if len % 2 == 0 => Some((fees[len / 2 - 1] + fees[len / 2]) / 2),
+            len => Some(fees[len / 2]),
+        };
+
+        Ok(BlockStats {
+            height: block.height(),
+            block_hash: block.hash().to_string(),
+            number_of_transactions: block.transactions().len(),
+            size_in_bytes: block.to_bytes_le()?.len(),
+            total_fees: fees.iter().sum(),
+            min_fee: fees.first().copied(),
+            max_fee: fees.last().copied(),
+            median_fee,
+            number_of_excluded_transactions,
+            number_of_inputs: block.serial_numbers().count(),
+            number_of_outputs: block.commitments().count(),
+        })
+    }
+
+    /// Returns the block template for the next mined block, with the given selection of the memory pool transactions.
+    async fn get_block_template(&self, selection: Option<TransactionSelection>) -> Result<Value, RpcError> {
         // Fetch the latest state from the ledger.
         let latest_block = self.ledger.latest_block();
         let ledger_root = self.ledger.latest_ledger_root();
@@ -189,16 +525,19 @@ impl<N: Network, E: Environment> RpcFunctions<N> for RpcImpl<N, E> {
         let block_height = self.ledger.latest_block_height() + 1;
         let block_timestamp = chrono::Utc::now().timestamp();
 
-        // Compute the block difficulty target.
-        let difficulty_target = if N::NETWORK_ID == 2 && block_height <= snarkvm::dpc::testnet2::V12_UPGRADE_BLOCK_HEIGHT {
-            Blocks::<N>::compute_difficulty_target(latest_block.header(), block_timestamp, block_height)
+        // Select the block header that anchors the difficulty adjustment.
+        let anchor_block_header = if N::NETWORK_ID == 2 && block_height <= snarkvm::dpc::testnet2::V12_UPGRADE_BLOCK_HEIGHT {
+            latest_block.header().clone()
         } else if N::NETWORK_ID == 2 {
-            let anchor_block_header = self.ledger.get_block_header(snarkvm::dpc::testnet2::V12_UPGRADE_BLOCK_HEIGHT)?;
-            Blocks::<N>::compute_difficulty_target(&anchor_block_header, block_timestamp, block_height)
+            self.ledger.get_block_header(snarkvm::dpc::testnet2::V12_UPGRADE_BLOCK_HEIGHT)?
         } else {
-            Blocks::<N>::compute_difficulty_target(N::genesis_block().header(), block_timestamp, block_height)
+            N::genesis_block().header().clone()
         };
 
+        // Compute the block difficulty target.
+        let difficulty_target = Blocks::<N>::compute_difficulty_target(&anchor_block_header, block_timestamp, block_height);
+        let anchor_time_delta = block_timestamp.saturating_sub(anchor_block_header.timestamp());
+
         // Compute the cumulative weight.
         let cumulative_weight = latest_block
             .cumulative_weight()
@@ -208,30 +547,21 @@ impl<N: Network, E: Environment> RpcFunctions<N> for RpcImpl<N, E> {
         let mut coinbase_reward = Block::<N>::block_reward(block_height);
         let mut transaction_fees = AleoAmount::ZERO;
 
-        // Get and filter the transactions from the mempool.
-        let transactions: Vec<String> = self
-            .memory_pool
-            .read()
-            .await
-            .transactions()
-            .iter()
-            .filter(|transaction| {
-                for serial_number in transaction.serial_numbers() {
-                    if let Ok(true) = self.ledger.contains_serial_number(serial_number) {
-                        return false;
-                    }
-                }
-
-                for commitment in transaction.commitments() {
-                    if let Ok(true) = self.ledger.contains_commitment(commitment) {
-                        return false;
-                    }
-                }
+        // Get the transactions from the mempool, in the order of the given selection.
+        let transactions = self.memory_pool.read().await.transactions();
+        let transactions = match selection {
+            Some(selection) => self.select_transactions(transactions, &selection)?,
+            None => transactions,
+        };
 
+        // Filter the transactions that are invalid for the next block.
+        let transactions: Vec<String> = transactions
+            .iter()
+            .filter(|transaction| self.is_valid_for_next_block(transaction))
+            .map(|transaction| {
                 transaction_fees = transaction_fees.add(transaction.value_balance());
-                true
+                transaction.to_string()
             })
-            .map(|tx| tx.to_string())
             .collect();
 
         // Enforce that the transaction fee is positive or zero.
@@ -247,6 +577,9 @@ impl<N: Network, E: Environment> RpcFunctions<N> for RpcImpl<N, E> {
             "block_height": block_height,
             "time": block_timestamp,
             "difficulty_target": difficulty_target,
+            "previous_difficulty_target": latest_block.difficulty_target(),
+            "anchor_block_height": anchor_block_header.height(),
+            "anchor_time_delta": anchor_time_delta,
             "cumulative_weight": cumulative_weight,
             "ledger_root": ledger_root,
             "transactions": transactions,
@@ -259,6 +592,12 @@ impl<N: Network, E: Environment> RpcFunctions<N> for RpcImpl<N, E> {
         Ok(self.ledger.get_block_transactions(block_height)?)
     }
 
+    /// Returns the transaction IDs from the block of the given block hash, without loading the transactions.
+    async fn get_block_transaction_ids(&self, block_hash: serde_json::Value) -> Result<Vec<N::TransactionID>, RpcError> {
+        let block_hash: N::BlockHash = serde_json::from_value(block_hash)?;
+        Ok(self.ledger.get_block_transaction_ids(&block_hash)?)
+    }
+
     /// Returns the ciphertext given the commitment.
     async fn get_ciphertext(&self, commitment: serde_json::Value) -> Result<N::RecordCiphertext, RpcError> {
         let commitment: N::Commitment = serde_json::from_value(commitment)?;
@@ -277,6 +616,85 @@ impl<N: Network, E: Environment> RpcFunctions<N> for RpcImpl<N, E> {
         Ok(self.memory_pool.read().await.transactions())
     }
 
+    /// Returns the provers contributing to the pool of this node, including their share-submission anomalies.
+    async fn get_pool_info(&self) -> Result<PoolInfo, RpcError> {
+        let provers = self
+            .operator
+            .provers()
+            .await
+            .into_iter()
+            .map(|(prover, share_difficulty, variance_ms, is_flagged)| ProverEntry {
+                address: prover.to_string(),
+                share_difficulty,
+                shares: self.operator.get_shares_for_prover(&prover),
+                submission_interval_variance_ms: variance_ms,
+                is_flagged,
+            })
+            .collect();
+
+        Ok(PoolInfo {
+            provers,
+            pool_paused: self.operator.is_paused(),
+            queued_responses: self.operator.number_of_queued_responses().await as u32,
+        })
+    }
+
+    /// Returns the mining state of this node, including whether mining is paused by the operator.
+    async fn get_mining_info(&self) -> Result<MiningInfo, RpcError> {
+        // Only miners and provers attempt proofs, so the other node types have no mining state.
+        if !matches!(E::NODE_TYPE, NodeType::Miner | NodeType::Prover) {
+            return Err(RpcError::Message(format!("{} nodes do not mine", E::NODE_TYPE)));
+        }
+
+        let now = Instant::now();
+        let proving_progress = E::proving_progress();
+        let prover_stats = E::prover_stats();
+        let (current_template_height, template_age) = prover_stats.current_template(now).unwrap_or_default();
+        Ok(MiningInfo {
+            status: E::status().get(),
+            is_mining: E::status().is_mining(),
+            is_mining_paused: E::is_mining_paused(),
+            latest_block_height: self.ledger.latest_block_height(),
+            memory_pool_size: self.memory_pool.read().await.transactions().len(),
+            elapsed_ms: proving_progress.elapsed(Instant::now()).map(|elapsed| elapsed.as_millis() as u64),
+            estimated_total_ms: proving_progress.estimated_total().map(|estimate| estimate.as_millis() as u64),
+            connected: self.peers.number_of_connected_peers().await > 0,
+            current_template_height,
+            template_age_ms: template_age.as_millis() as u64,
+            hashrate_estimate: prover_stats.hashrate_estimate(now),
+            proof_attempts_total: prover_stats.proof_attempts_total(),
+            blocks_mined: prover_stats.blocks_mined(),
+            last_block_found_secs_ago: prover_stats.last_block_found(now).map(|elapsed| elapsed.as_secs()),
+        })
+    }
+
+    /// Returns up to `MAXIMUM_RAW_MEMPOOL_TRANSACTIONS` transaction IDs from the memory pool,
+    /// or their metadata if `verbose` is `true`. Only the verbose form serializes the transactions.
+    async fn get_raw_mempool(&self, verbose: bool) -> Result<RawMempool, RpcError> {
+        let transactions = self.memory_pool.read().await.transactions();
+        let transactions = transactions.iter().take(MAXIMUM_RAW_MEMPOOL_TRANSACTIONS);
+
+        match verbose {
+            true => {
+                let memory_pool_timestamps = self.memory_pool_timestamps.read().await;
+                let mut entries = Vec::with_capacity(transactions.len());
+                for transaction in transactions {
+                    entries.push(MempoolEntry {
+                        transaction_id: transaction.transaction_id().to_string(),
+                        size_in_bytes: transaction.to_bytes_le()?.len(),
+                        fee: Self::transaction_fee(transaction).unwrap_or(0),
+                        number_of_transitions: transaction.transitions().len(),
+                        time_received: memory_pool_timestamps.get(&transaction.transaction_id()).copied(),
+                    });
+                }
+                Ok(RawMempool::Entries(entries))
+            }
+            false => Ok(RawMempool::TransactionIds(
+                transactions.map(|transaction| transaction.transaction_id().to_string()).collect(),
+            )),
+        }
+    }
+
     /// Returns a transaction with metadata and decrypted records given the transaction ID.
     async fn get_transaction(&self, transaction_id: serde_json::Value) -> Result<Value, RpcError> {
         let transaction_id: N::TransactionID = serde_json::from_value(transaction_id)?;
@@ -286,17 +704,188 @@ impl<N: Network, E: Environment> RpcFunctions<N> for RpcImpl<N, E> {
         Ok(serde_json::json!({ "transaction": transaction, "metadata": metadata, "decrypted_records": decrypted_records }))
     }
 
+    /// Returns the hex-encoded bytes and the ID of a transaction in the ledger or the memory pool, given the transaction ID.
+    async fn get_raw_transaction(&self, transaction_id: serde_json::Value) -> Result<RawTransaction, RpcError> {
+        let transaction_id: N::TransactionID = serde_json::from_value(transaction_id)?;
+        let transaction = match self.ledger.get_transaction(&transaction_id) {
+            Ok(transaction) => transaction,
+            // Retrieve the transaction from the memory pool, if it is not in the ledger.
+            Err(error) => {
+                let transaction = self
+                    .memory_pool
+                    .read()
+                    .await
+                    .transactions()
+                    .into_iter()
+                    .find(|transaction| transaction.transaction_id() == transaction_id);
+                match transaction {
+                    Some(transaction) => transaction,
+                    None => return Err(error.into()),
+                }
+            }
+        };
+
+        Ok(RawTransaction {
+            hex: hex::encode(transaction.to_bytes_le()?),
+            transaction_id: transaction_id.to_string(),
+        })
+    }
+
+    /// Returns a summary of the transaction given its hex-encoded bytes.
+    async fn decode_raw_transaction(&self, transaction_hex: String) -> Result<TransactionInfo, RpcError> {
+        let transaction: Transaction<N> = match FromBytes::from_bytes_le(&hex::decode(transaction_hex)?) {
+            Ok(transaction) => transaction,
+            Err(error) => return Err(RpcError::InvalidInput(format!("Failed to deserialize the transaction: {}", error))),
+        };
+
+        Ok(TransactionInfo {
+            transaction_id: transaction.transaction_id().to_string(),
+            ledger_root: transaction.ledger_root().to_string(),
+            transition_ids: transaction
+                .transitions()
+                .iter()
+                .map(|transition| transition.transition_id().to_string())
+                .collect(),
+        })
+    }
+
     /// Returns a transition given the transition ID.
     async fn get_transition(&self, transition_id: serde_json::Value) -> Result<Transition<N>, RpcError> {
         let transition_id: N::TransitionID = serde_json::from_value(transition_id)?;
         Ok(self.ledger.get_transition(&transition_id)?)
     }
 
+    /// Returns the number of connected peers, by connection direction and by node type.
+    async fn get_connection_count(&self) -> Result<ConnectionCount, RpcError> {
+        Ok(self.peers.connected_peer_kinds().await.into_iter().collect())
+    }
+
     /// Returns the peers currently connected to this node.
     async fn get_connected_peers(&self) -> Result<Vec<SocketAddr>, RpcError> {
         Ok(self.peers.connected_peers().await)
     }
 
+    /// Returns the chain state last advertised by the given peer, as used by the fork-choice logic.
+    /// If the peer has not advertised its chain state yet, the response indicates there is no recent data.
+    async fn get_peer_chain_info(&self, peer_ip: serde_json::Value) -> Result<PeerChainInfo, RpcError> {
+        let peer_ip: SocketAddr = serde_json::from_value(peer_ip)?;
+
+        match self.peers_state.read().await.get(&peer_ip) {
+            Some(Some((node_type, _, status, is_fork, block_height, block_locators))) => Ok(PeerChainInfo {
+                peer_ip,
+                has_recent_data: true,
+                node_type: Some(*node_type),
+                status: Some(*status),
+                latest_block_height: Some(*block_height),
+                latest_block_hash: block_locators.get(block_height).map(|(block_hash, _)| block_hash.to_string()),
+                cumulative_weight: block_locators.get_cumulative_weight(*block_height),
+                is_fork: *is_fork,
+            }),
+            _ => Ok(PeerChainInfo::no_recent_data(peer_ip)),
+        }
+    }
+
+    /// Returns the types of the most recent messages received from the given peer, from oldest to newest,
+    /// with the UNIX timestamp in milliseconds at which each message was received.
+    async fn get_peer_message_history(&self, peer_ip: serde_json::Value) -> Result<Vec<(String, u64)>, RpcError> {
+        let peer_ip: SocketAddr = serde_json::from_value(peer_ip)?;
+        Ok(self.peers.message_history(peer_ip).await)
+    }
+
+    /// Returns the peers tracked by the ledger, with the block height each peer last advertised, ordered by IP address.
+    async fn get_peer_info(&self) -> Result<Vec<PeerInfo>, RpcError> {
+        let mut peer_info = self
+            .peers_state
+            .read()
+            .await
+            .iter()
+            .map(|(peer_ip, peer_state)| PeerInfo {
+                peer_ip: *peer_ip,
+                node_type: peer_state.as_ref().map(|(node_type, ..)| *node_type),
+                sync_height: peer_state.as_ref().map(|(_, _, _, _, block_height, _)| *block_height),
+                is_syncing: matches!(peer_state, Some((_, _, State::Syncing, ..))),
+            })
+            .collect::<Vec<_>>();
+        peer_info.sort_by_key(|peer| peer.peer_ip);
+        Ok(peer_info)
+    }
+
+    /// Returns the connected peers of this node in a compact, stable schema, ordered by IP address.
+    async fn list_peers(&self) -> Result<PeerList, RpcError> {
+        let peers_state = self.peers_state.read().await;
+        let mut peers = self
+            .peers
+            .connected_peers_with_kinds()
+            .await
+            .into_iter()
+            .map(|(peer_ip, node_type, direction)| {
+                let height = match peers_state.get(&peer_ip) {
+                    Some(Some((_, _, _, _, block_height, _))) => Some(*block_height),
+                    _ => None,
+                };
+                PeerListEntry::new(peer_ip, node_type, direction, height)
+            })
+            .collect::<Vec<_>>();
+        peers.sort_by_key(|peer| peer.address);
+
+        Ok(PeerList {
+            version: PEER_LIST_VERSION,
+            peers,
+        })
+    }
+
+    /// Returns the sync status of this node, with the estimated time remaining to reach the given
+    /// target block height, or the median block height advertised by the connected peers.
+    /// The median is used, so that a minority of peers cannot inflate the target block height.
+    async fn get_sync_status(&self, target_block_height: Option<u32>) -> Result<SyncStatus, RpcError> {
+        let latest_block_height = self.ledger.latest_block_height();
+        let mut block_heights = self
+            .peers_state
+            .read()
+            .await
+            .values()
+            .filter_map(|peer_state| peer_state.as_ref().map(|(_, _, _, _, block_height, _)| *block_height))
+            .collect::<Vec<_>>();
+        block_heights.sort_unstable();
+
+        // Use the lower median for an even number of peers, as the higher block height is not backed by a majority.
+        let known_block_height = match block_heights.len() {
+            0 => latest_block_height,
+            num_peers => max(latest_block_height, block_heights[(num_peers - 1) / 2]),
+        };
+
+        // Ensure the given target block height is one that this node can sync to.
+        let target_block_height = match target_block_height {
+            Some(0) => return Err(RpcError::InvalidInput("The target block height must be greater than 0".to_string())),
+            Some(target_block_height) if target_block_height > known_block_height => {
+                return Err(RpcError::InvalidInput(format!(
+                    "The target block height {} exceeds the known block height {}",
+                    target_block_height, known_block_height
+                )));
+            }
+            Some(target_block_height) => target_block_height,
+            None => known_block_height,
+        };
+
+        let now = Instant::now();
+        let sync_progress = self.sync_progress.read().await;
+
+        Ok(SyncStatus {
+            latest_block_height,
+            target_block_height,
+            blocks_per_second: sync_progress.blocks_per_second(now),
+            estimated_time_to_sync: sync_progress.estimate(now, latest_block_height, target_block_height),
+        })
+    }
+
+    /// Returns the number of running and completed background tasks of this node.
+    async fn get_task_status(&self) -> Result<TaskStatus, RpcError> {
+        Ok(TaskStatus {
+            running_tasks: E::tasks().running_count().await,
+            completed_tasks: E::tasks().completed_count().await,
+        })
+    }
+
     /// Returns the current state of this node.
     async fn get_node_state(&self) -> Result<Value, RpcError> {
         let candidate_peers = self.peers.candidate_peers().await;
@@ -304,6 +893,7 @@ impl<N: Network, E: Environment> RpcFunctions<N> for RpcImpl<N, E> {
         let number_of_candidate_peers = candidate_peers.len();
         let number_of_connected_peers = connected_peers.len();
         let number_of_connected_sync_nodes = self.peers.number_of_connected_sync_nodes().await;
+        let detected_external_addr = self.peers.detected_external_addr().await;
 
         let latest_block_hash = self.ledger.latest_block_hash();
         let latest_block_height = self.ledger.latest_block_height();
@@ -313,6 +903,7 @@ impl<N: Network, E: Environment> RpcFunctions<N> for RpcImpl<N, E> {
             "address": self.address,
             "candidate_peers": candidate_peers,
             "connected_peers": connected_peers,
+            "detected_external_addr": detected_external_addr,
             "latest_block_hash": latest_block_hash,
             "latest_block_height": latest_block_height,
             "latest_cumulative_weight": latest_cumulative_weight,
@@ -327,14 +918,123 @@ impl<N: Network, E: Environment> RpcFunctions<N> for RpcImpl<N, E> {
         }))
     }
 
+    /// Returns the uptime, status, number of connected peers, and latest block height of this node.
+    /// This only briefly reads the latest block and the connected peers, rather than the ledger state that is
+    /// locked while a block is added, so it responds promptly even while the node is syncing.
+    async fn ping(&self) -> Result<PingInfo, RpcError> {
+        Ok(PingInfo {
+            uptime_in_secs: self.launched.elapsed().as_secs(),
+            status: E::status().get(),
+            connected_peers: self.peers.number_of_connected_peers().await,
+            latest_block_height: self.ledger.latest_block_height(),
+        })
+    }
+
+    /// Rebuilds the index of miners to their blocks from the canonical chain.
+    async fn rebuild_miner_index(&self) -> Result<(), RpcError> {
+        Ok(self.ledger.rebuild_miner_index()?)
+    }
+
+    /// Pauses the pool for maintenance, and queues the shares received until it resumes.
+    async fn pause_pool(&self) -> Result<(), RpcError> {
+        Ok(self.operator.pause()?)
+    }
+
+    /// Resumes the pool, and processes the shares queued while it was paused.
+    async fn resume_pool(&self) -> Result<(), RpcError> {
+        Ok(self.operator.resume().await?)
+    }
+
+    /// Returns the fee expected to confirm a transaction of the given size within the given number of blocks.
+    async fn estimate_fee(&self, transaction_size: usize, target_blocks: u32) -> Result<u64, RpcError> {
+        Ok(E::FeePolicy::estimate_fee(transaction_size, target_blocks))
+    }
+
     /// Returns the transaction ID. If the given transaction is valid, it is added to the memory pool and propagated to all peers.
-    async fn send_transaction(&self, transaction_hex: String) -> Result<N::TransactionID, RpcError> {
-        let transaction: Transaction<N> = FromBytes::from_bytes_le(&hex::decode(transaction_hex)?)?;
+    async fn send_transaction(&self, transaction_hex: String) -> Result<SendTransactionResponse, RpcError> {
+        let start = Instant::now();
+
+        // Validate the transaction against the current state, before broadcasting it.
+        let transaction: Transaction<N> = match FromBytes::from_bytes_le(&hex::decode(transaction_hex)?) {
+            Ok(transaction) => transaction,
+            Err(error) => return Err(RpcError::InvalidInput(format!("Failed to deserialize the transaction: {}", error))),
+        };
+        if !transaction.is_valid() {
+            return Err(RpcError::InvalidInput(format!(
+                "Transaction {} has an invalid proof",
+                transaction.transaction_id()
+            )));
+        }
+        if !E::FeePolicy::is_acceptable_transaction(&transaction) {
+            return Err(RpcError::InvalidInput(format!(
+                "Transaction {} has an unacceptable fee",
+                transaction.transaction_id()
+            )));
+        }
+        for serial_number in transaction.serial_numbers() {
+            if let Ok(true) = self.ledger.contains_serial_number(serial_number) {
+                return Err(RpcError::InvalidInput(format!(
+                    "Transaction {} spends an existing serial number {}",
+                    transaction.transaction_id(),
+                    serial_number
+                )));
+            }
+        }
+        for commitment in transaction.commitments() {
+            if let Ok(true) = self.ledger.contains_commitment(commitment) {
+                return Err(RpcError::InvalidInput(format!(
+                    "Transaction {} contains an existing commitment {}",
+                    transaction.transaction_id(),
+                    commitment
+                )));
+            }
+        }
+        let validation_time_ms = start.elapsed().as_millis() as u64;
+
         // Route an `UnconfirmedTransaction` to the prover.
         let request = ProverRequest::UnconfirmedTransaction("0.0.0.0:3032".parse().unwrap(), transaction.clone());
         if let Err(error) = self.prover_router.send(request).await {
             warn!("[UnconfirmedTransaction] {}", error);
         }
-        Ok(transaction.transaction_id())
+        Ok(SendTransactionResponse {
+            transaction_id: transaction.transaction_id().to_string(),
+            validation_time_ms,
+        })
+    }
+
+    /// Rebroadcasts the given transaction from the memory pool to the connected peers.
+    async fn resend_transaction(&self, transaction_id: serde_json::Value) -> Result<ResendTransactionResponse, RpcError> {
+        let transaction_id: N::TransactionID = serde_json::from_value(transaction_id)?;
+
+        // Retrieve the transaction from the memory pool.
+        let transaction = self
+            .memory_pool
+            .read()
+            .await
+            .transactions()
+            .into_iter()
+            .find(|transaction| transaction.transaction_id() == transaction_id);
+        let transaction = match transaction {
+            Some(transaction) => transaction,
+            None => {
+                return Err(RpcError::InvalidInput(format!(
+                    "Transaction {} is not in the memory pool",
+                    transaction_id
+                )));
+            }
+        };
+
+        // Observer nodes only follow the network, and never relay transactions.
+        if E::NODE_TYPE == NodeType::Observer {
+            return Err(RpcError::Message(format!("{} nodes do not relay transactions", E::NODE_TYPE)));
+        }
+
+        // Dispatch the transaction to the connected peers, which skip it if they have already seen it.
+        let dispatched_to_peers = self.peers.rebroadcast(Message::UnconfirmedTransaction(transaction)).await;
+
+        Ok(ResendTransactionResponse {
+            transaction_id: transaction_id.to_string(),
+            dispatched_to_peers,
+        })
     }
 }
@@ -16,7 +16,32 @@
 
 //! Definition of the public and private RPC endpoints.
 
-use crate::rpc::rpc_impl::RpcError;
+use crate::rpc::{
+    rpc_impl::RpcError,
+    rpc_types::{
+        BlockCountInfo,
+        BlockStats,
+        BlockchainInfo,
+        ChainTip,
+        ConnectionCount,
+        DecodedRecord,
+        MiningInfo,
+        PeerChainInfo,
+        PeerInfo,
+        PeerList,
+        PingInfo,
+        PoolInfo,
+        RawMempool,
+        RawTransaction,
+        RecordInfo,
+        ResendTransactionResponse,
+        SendTransactionResponse,
+        SyncStatus,
+        TaskStatus,
+        TransactionInfo,
+        TransactionSelection,
+    },
+};
 use snarkvm::dpc::{Block, BlockHeader, Network, Transaction, Transactions, Transition};
 
 use std::net::SocketAddr;
@@ -45,12 +70,30 @@ pub trait RpcFunctions<N: Network> {
     #[doc = include_str!("./documentation/public_endpoints/latestledgerroot.md")]
     async fn latest_ledger_root(&self) -> Result<N::LedgerRoot, RpcError>;
 
+    #[doc = include_str!("./documentation/public_endpoints/decoderecord.md")]
+    async fn decode_record(&self, record_bytes: String, expected_owner: Option<String>) -> Result<RecordInfo, RpcError>;
+
+    #[doc = include_str!("./documentation/public_endpoints/decoderecords.md")]
+    async fn decode_records(&self, records: Vec<String>) -> Result<Vec<DecodedRecord>, RpcError>;
+
     #[doc = include_str!("./documentation/public_endpoints/getblock.md")]
     async fn get_block(&self, block_height: u32) -> Result<Block<N>, RpcError>;
 
     #[doc = include_str!("./documentation/public_endpoints/getblocks.md")]
     async fn get_blocks(&self, start_block_height: u32, end_block_height: u32) -> Result<Vec<Block<N>>, RpcError>;
 
+    #[doc = include_str!("./documentation/public_endpoints/getblocksbyminer.md")]
+    async fn get_blocks_by_miner(&self, address: serde_json::Value) -> Result<Vec<u32>, RpcError>;
+
+    #[doc = include_str!("./documentation/public_endpoints/getblockchaininfo.md")]
+    async fn get_blockchain_info(&self) -> Result<BlockchainInfo, RpcError>;
+
+    #[doc = include_str!("./documentation/public_endpoints/getblockcount.md")]
+    async fn get_block_count(&self) -> Result<BlockCountInfo, RpcError>;
+
+    #[doc = include_str!("./documentation/public_endpoints/getchaintips.md")]
+    async fn get_chain_tips(&self) -> Result<Vec<ChainTip>, RpcError>;
+
     #[doc = include_str!("./documentation/public_endpoints/getblockheight.md")]
     async fn get_block_height(&self, block_hash: serde_json::Value) -> Result<u32, RpcError>;
 
@@ -63,12 +106,18 @@ pub trait RpcFunctions<N: Network> {
     #[doc = include_str!("./documentation/public_endpoints/getblockheader.md")]
     async fn get_block_header(&self, block_height: u32) -> Result<BlockHeader<N>, RpcError>;
 
+    #[doc = include_str!("./documentation/public_endpoints/getblockstats.md")]
+    async fn get_block_stats(&self, block_height: u32) -> Result<BlockStats, RpcError>;
+
     #[doc = include_str!("./documentation/public_endpoints/getblocktemplate.md")]
-    async fn get_block_template(&self) -> Result<serde_json::Value, RpcError>;
+    async fn get_block_template(&self, selection: Option<TransactionSelection>) -> Result<serde_json::Value, RpcError>;
 
     #[doc = include_str!("./documentation/public_endpoints/getblocktransactions.md")]
     async fn get_block_transactions(&self, block_height: u32) -> Result<Transactions<N>, RpcError>;
 
+    #[doc = include_str!("./documentation/public_endpoints/getblocktransactionids.md")]
+    async fn get_block_transaction_ids(&self, block_hash: serde_json::Value) -> Result<Vec<N::TransactionID>, RpcError>;
+
     #[doc = include_str!("./documentation/public_endpoints/getciphertext.md")]
     async fn get_ciphertext(&self, commitment: serde_json::Value) -> Result<N::RecordCiphertext, RpcError>;
 
@@ -78,20 +127,74 @@ pub trait RpcFunctions<N: Network> {
     #[doc = include_str!("./documentation/public_endpoints/getmemorypool.md")]
     async fn get_memory_pool(&self) -> Result<Vec<Transaction<N>>, RpcError>;
 
+    #[doc = include_str!("./documentation/public_endpoints/getmininginfo.md")]
+    async fn get_mining_info(&self) -> Result<MiningInfo, RpcError>;
+
+    #[doc = include_str!("./documentation/public_endpoints/getpoolinfo.md")]
+    async fn get_pool_info(&self) -> Result<PoolInfo, RpcError>;
+
+    #[doc = include_str!("./documentation/public_endpoints/getrawmempool.md")]
+    async fn get_raw_mempool(&self, verbose: bool) -> Result<RawMempool, RpcError>;
+
     #[doc = include_str!("./documentation/public_endpoints/gettransaction.md")]
     async fn get_transaction(&self, transaction_id: serde_json::Value) -> Result<serde_json::Value, RpcError>;
 
+    #[doc = include_str!("./documentation/public_endpoints/getrawtransaction.md")]
+    async fn get_raw_transaction(&self, transaction_id: serde_json::Value) -> Result<RawTransaction, RpcError>;
+
+    #[doc = include_str!("./documentation/public_endpoints/decoderawtransaction.md")]
+    async fn decode_raw_transaction(&self, transaction_hex: String) -> Result<TransactionInfo, RpcError>;
+
     #[doc = include_str!("./documentation/public_endpoints/gettransition.md")]
     async fn get_transition(&self, transition_id: serde_json::Value) -> Result<Transition<N>, RpcError>;
 
+    #[doc = include_str!("./documentation/public_endpoints/getconnectioncount.md")]
+    async fn get_connection_count(&self) -> Result<ConnectionCount, RpcError>;
+
     #[doc = include_str!("./documentation/public_endpoints/getconnectedpeers.md")]
     async fn get_connected_peers(&self) -> Result<Vec<SocketAddr>, RpcError>;
 
+    #[doc = include_str!("./documentation/public_endpoints/getpeerchaininfo.md")]
+    async fn get_peer_chain_info(&self, peer_ip: serde_json::Value) -> Result<PeerChainInfo, RpcError>;
+
+    #[doc = include_str!("./documentation/public_endpoints/getpeerinfo.md")]
+    async fn get_peer_info(&self) -> Result<Vec<PeerInfo>, RpcError>;
+
+    #[doc = include_str!("./documentation/public_endpoints/listpeers.md")]
+    async fn list_peers(&self) -> Result<PeerList, RpcError>;
+
+    #[doc = include_str!("./documentation/public_endpoints/getpeermessagehistory.md")]
+    async fn get_peer_message_history(&self, peer_ip: serde_json::Value) -> Result<Vec<(String, u64)>, RpcError>;
+
+    #[doc = include_str!("./documentation/public_endpoints/getsyncstatus.md")]
+    async fn get_sync_status(&self, target_block_height: Option<u32>) -> Result<SyncStatus, RpcError>;
+
+    #[doc = include_str!("./documentation/public_endpoints/gettaskstatus.md")]
+    async fn get_task_status(&self) -> Result<TaskStatus, RpcError>;
+
     #[doc = include_str!("./documentation/public_endpoints/getnodestate.md")]
     async fn get_node_state(&self) -> Result<serde_json::Value, RpcError>;
 
+    #[doc = include_str!("./documentation/public_endpoints/ping.md")]
+    async fn ping(&self) -> Result<PingInfo, RpcError>;
+
+    #[doc = include_str!("./documentation/public_endpoints/rebuildminerindex.md")]
+    async fn rebuild_miner_index(&self) -> Result<(), RpcError>;
+
+    #[doc = include_str!("./documentation/public_endpoints/pausepool.md")]
+    async fn pause_pool(&self) -> Result<(), RpcError>;
+
+    #[doc = include_str!("./documentation/public_endpoints/resumepool.md")]
+    async fn resume_pool(&self) -> Result<(), RpcError>;
+
+    #[doc = include_str!("./documentation/public_endpoints/estimatefee.md")]
+    async fn estimate_fee(&self, transaction_size: usize, target_blocks: u32) -> Result<u64, RpcError>;
+
     #[doc = include_str!("./documentation/public_endpoints/sendtransaction.md")]
-    async fn send_transaction(&self, transaction_bytes: String) -> Result<N::TransactionID, RpcError>;
+    async fn send_transaction(&self, transaction_bytes: String) -> Result<SendTransactionResponse, RpcError>;
+
+    #[doc = include_str!("./documentation/public_endpoints/resendtransaction.md")]
+    async fn resend_transaction(&self, transaction_id: serde_json::Value) -> Result<ResendTransactionResponse, RpcError>;
 }
 
 // /// Definition of private RPC endpoints that require authentication.
@@ -0,0 +1,432 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Structured response types returned by the RPC endpoints.
+
+use crate::{
+    helpers::{NodeType, State, SyncEstimate},
+    ConnectionDirection,
+};
+
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, iter::FromIterator, net::SocketAddr};
+
+/// A one-call summary of the canonical chain and the sync state of this node.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BlockchainInfo {
+    /// The ID of the network this node is running on.
+    pub network_id: u16,
+    /// The latest block height of the canonical chain.
+    pub chain_height: u32,
+    /// The latest block hash of the canonical chain.
+    pub best_block_hash: String,
+    /// The difficulty target of the latest block.
+    pub difficulty_target: u64,
+    /// The cumulative weight of the canonical chain.
+    pub cumulative_weight: u128,
+    /// Returns `true` if the node is neither peering nor syncing.
+    pub is_synced: bool,
+    /// The estimated progress of the sync, as a percentage.
+    pub sync_progress_percent: f64,
+    /// The number of connected peers.
+    pub connected_peers: usize,
+    /// The type of the node.
+    pub node_type: String,
+    /// The version of the node software.
+    pub version: String,
+}
+
+/// The tip of the canonical chain, or of a fork branch that is retained by the node.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainTip {
+    /// The block height of the tip.
+    pub height: u32,
+    /// The block hash of the tip.
+    pub hash: String,
+    /// The number of blocks in the branch, or 0 for the canonical chain.
+    pub branch_length: u32,
+    /// The status of the tip, which is `active` for the canonical chain, and `fork` for a fork branch.
+    pub status: String,
+}
+
+/// The number of blocks and transactions in the canonical chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockCountInfo {
+    /// The number of blocks in the canonical chain, including the genesis block.
+    pub block_count: u32,
+    /// The total number of confirmed transactions in the canonical chain.
+    pub transactions_total: u64,
+}
+
+/// Aggregate statistics on the transactions of a block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockStats {
+    /// The height of the block.
+    pub height: u32,
+    /// The hash of the block.
+    pub block_hash: String,
+    /// The number of transactions in the block.
+    pub number_of_transactions: usize,
+    /// The size of the serialized block in bytes.
+    pub size_in_bytes: usize,
+    /// The sum of the fees of the transactions in the block, in gates.
+    pub total_fees: i64,
+    /// The smallest fee of a transaction in the block, if any fees are known.
+    pub min_fee: Option<i64>,
+    /// The largest fee of a transaction in the block, if any fees are known.
+    pub max_fee: Option<i64>,
+    /// The median fee of the transactions in the block, if any fees are known.
+    pub median_fee: Option<i64>,
+    /// The number of transactions excluded from the fee statistics, as they do not pay a fee.
+    pub number_of_excluded_transactions: usize,
+    /// The number of input records (serial numbers) in the block.
+    pub number_of_inputs: usize,
+    /// The number of output records (commitments) in the block.
+    pub number_of_outputs: usize,
+}
+
+/// The version of the `listpeers` schema, which is incremented if a field is added to `PeerListEntry`.
+pub const PEER_LIST_VERSION: u32 = 1;
+
+/// The connected peers of this node, in the stable schema of `listpeers`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerList {
+    /// The version of the schema, see `PEER_LIST_VERSION`.
+    pub version: u32,
+    /// The connected peers, ordered by IP address.
+    pub peers: Vec<PeerListEntry>,
+}
+
+///
+/// A connected peer, in the stable schema of `listpeers`.
+///
+/// Unlike `PeerInfo`, existing fields are never renamed, removed, or retyped across versions,
+/// so monitoring tools may rely on them. New fields are only added with a new `PEER_LIST_VERSION`.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerListEntry {
+    /// The IP address of the peer.
+    pub address: SocketAddr,
+    /// The direction of the connection, either `inbound` or `outbound`.
+    pub direction: String,
+    /// The node type of the peer.
+    pub node_type: String,
+    /// The block height last advertised by the peer, or `null` if the peer has not advertised its chain state yet.
+    pub height: Option<u32>,
+}
+
+impl PeerListEntry {
+    /// Returns a new entry for a connected peer.
+    pub fn new(address: SocketAddr, node_type: NodeType, direction: ConnectionDirection, height: Option<u32>) -> Self {
+        let direction = match direction {
+            ConnectionDirection::Inbound => "inbound",
+            ConnectionDirection::Outbound => "outbound",
+        };
+        Self {
+            address,
+            direction: direction.to_string(),
+            node_type: node_type.to_string(),
+            height,
+        }
+    }
+}
+
+/// The number of connected peers, by connection direction and by node type.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectionCount {
+    /// The total number of connected peers.
+    pub total: usize,
+    /// The number of peers that connected to this node.
+    pub inbound: usize,
+    /// The number of peers that this node connected to.
+    pub outbound: usize,
+    /// The number of connected peers of each node type.
+    pub by_type: HashMap<String, usize>,
+}
+
+impl FromIterator<(NodeType, ConnectionDirection)> for ConnectionCount {
+    fn from_iter<I: IntoIterator<Item = (NodeType, ConnectionDirection)>>(connections: I) -> Self {
+        let mut connection_count = Self::default();
+        for (node_type, direction) in connections {
+            connection_count.total += 1;
+            match direction {
+                ConnectionDirection::Inbound => connection_count.inbound += 1,
+                ConnectionDirection::Outbound => connection_count.outbound += 1,
+            }
+            *connection_count.by_type.entry(node_type.to_string()).or_default() += 1;
+        }
+        connection_count
+    }
+}
+
+/// The fields of a decoded record.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordInfo {
+    /// The address of the owner of the record.
+    pub owner: String,
+    /// The value of the record, in gates.
+    pub value: i64,
+    /// The payload of the record, as a hex string.
+    pub payload: String,
+    /// The ID of the program the record belongs to.
+    pub program_id: String,
+    /// The commitment of the record.
+    pub commitment: String,
+}
+
+/// The result of decoding one record in a batch, as either its fields or the reason it failed to decode.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecodedRecord {
+    /// The fields of the decoded record.
+    Info(RecordInfo),
+    /// The reason the record failed to decode.
+    Error(String),
+}
+
+/// A summary of the mining state of this node.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MiningInfo {
+    /// The current state of the node.
+    pub status: State,
+    /// Returns `true` if the node is currently mining a block.
+    pub is_mining: bool,
+    /// Returns `true` if mining is paused by the operator of the node.
+    pub is_mining_paused: bool,
+    /// The latest block height of the canonical chain.
+    pub latest_block_height: u32,
+    /// The number of transactions in the memory pool.
+    pub memory_pool_size: usize,
+    /// The time elapsed in milliseconds on the proof currently being generated, if the node is proving.
+    pub elapsed_ms: Option<u64>,
+    /// The estimated total time in milliseconds of a proof, from the recent proofs of the node, if any.
+    pub estimated_total_ms: Option<u64>,
+    /// Returns `true` if the node is connected to at least one peer.
+    pub connected: bool,
+    /// The block height of the current block template, or `0` if the node has not started on one.
+    pub current_template_height: u32,
+    /// The time elapsed in milliseconds since the current block template was received or built.
+    pub template_age_ms: u64,
+    /// The estimated number of proof attempts per second, since the first proof attempt of the node.
+    pub hashrate_estimate: f64,
+    /// The total number of proof attempts, counting each block a miner attempts to mine as one attempt.
+    pub proof_attempts_total: u64,
+    /// The total number of blocks found by the node.
+    pub blocks_mined: u64,
+    /// The time elapsed in seconds since the node last found a block, if it found one.
+    pub last_block_found_secs_ago: Option<u64>,
+}
+
+/// A summary of the provers contributing to the pool of this node.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PoolInfo {
+    /// The provers known to the pool.
+    pub provers: Vec<ProverEntry>,
+    /// Returns `true` if the pool is paused for maintenance.
+    pub pool_paused: bool,
+    /// The number of shares queued while the pool is paused.
+    pub queued_responses: u32,
+}
+
+/// The state of a prover contributing to the pool of this node.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProverEntry {
+    /// The address of the prover.
+    pub address: String,
+    /// The current share difficulty of the prover.
+    pub share_difficulty: u64,
+    /// The number of shares submitted by the prover.
+    pub shares: u64,
+    /// The variance of the intervals between the recent shares of the prover.
+    pub submission_interval_variance_ms: f64,
+    /// Returns `true` if the prover submits shares at suspiciously regular intervals.
+    pub is_flagged: bool,
+}
+
+/// A transaction encoded as hex bytes, along with its ID.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RawTransaction {
+    /// The hex-encoded bytes of the transaction.
+    pub hex: String,
+    /// The ID of the transaction.
+    pub transaction_id: String,
+}
+
+/// A summary of a transaction decoded from its hex-encoded bytes.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionInfo {
+    /// The ID of the transaction.
+    pub transaction_id: String,
+    /// The ledger root used to prove inclusion of ledger-consumed records.
+    pub ledger_root: String,
+    /// The IDs of the transitions of the transaction.
+    pub transition_ids: Vec<String>,
+}
+
+/// A selection of the memory pool transactions to include in a block template, by transaction ID.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionSelection {
+    /// The transactions to include first, in the given order.
+    #[serde(default)]
+    pub prioritize: Vec<String>,
+    /// The transactions to leave out.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+/// The result of a transaction that was rebroadcast from the memory pool of this node.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResendTransactionResponse {
+    /// The ID of the rebroadcast transaction.
+    pub transaction_id: String,
+    /// The number of connected peers the transaction was dispatched to, before their relay filters are applied.
+    /// A peer that has already sent or received the transaction skips it, so fewer peers may receive it.
+    pub dispatched_to_peers: usize,
+}
+
+/// The result of a transaction that was validated and broadcast by this node.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SendTransactionResponse {
+    /// The ID of the sent transaction.
+    pub transaction_id: String,
+    /// The time taken to validate the transaction, in milliseconds.
+    pub validation_time_ms: u64,
+}
+
+/// A lightweight liveness report of this node, suitable for health checks.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PingInfo {
+    /// The number of seconds since the node was launched.
+    pub uptime_in_secs: u64,
+    /// The current state of the node.
+    pub status: State,
+    /// The number of connected peers.
+    pub connected_peers: usize,
+    /// The latest block height of the canonical chain.
+    pub latest_block_height: u32,
+}
+
+/// The sync status of this node, with the estimated time remaining to reach the chain tip of its peers.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SyncStatus {
+    /// The latest block height of the canonical chain.
+    pub latest_block_height: u32,
+    /// The target block height, by default the median block height advertised by the peers, or the latest block height if higher.
+    pub target_block_height: u32,
+    /// The number of blocks added to the canonical chain per second, as measured over the sampling window.
+    pub blocks_per_second: f64,
+    /// The estimated time remaining to reach the target block height.
+    pub estimated_time_to_sync: SyncEstimate,
+}
+
+/// The number of background tasks of this node, by whether they are still running.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskStatus {
+    /// The number of background tasks that are still running.
+    pub running_tasks: usize,
+    /// The number of background tasks that have completed.
+    pub completed_tasks: usize,
+}
+
+/// A peer connected to this node, with the block height it last advertised.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerInfo {
+    /// The IP address of the peer.
+    pub peer_ip: SocketAddr,
+    /// The node type of the peer, or `null` if the peer has not advertised its chain state yet.
+    pub node_type: Option<NodeType>,
+    /// The block height last advertised by the peer, or `null` if the peer has not advertised its chain state yet.
+    pub sync_height: Option<u32>,
+    /// Returns `true` if the peer last advertised that it is syncing, and is thus an unreliable source of blocks.
+    pub is_syncing: bool,
+}
+
+/// The chain state last advertised by a peer, as seen by the fork-choice logic of this node.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerChainInfo {
+    /// The IP address of the peer.
+    pub peer_ip: SocketAddr,
+    /// Returns `true` if the peer has advertised its chain state since connecting.
+    pub has_recent_data: bool,
+    /// The node type of the peer.
+    pub node_type: Option<NodeType>,
+    /// The last advertised status of the peer.
+    pub status: Option<State>,
+    /// The last advertised block height of the peer.
+    pub latest_block_height: Option<u32>,
+    /// The last advertised block hash of the peer.
+    pub latest_block_hash: Option<String>,
+    /// The last advertised cumulative weight of the peer.
+    pub cumulative_weight: Option<u128>,
+    /// Returns `true` if the peer is on a fork, or `null` if the fork status is undecided.
+    pub is_fork: Option<bool>,
+}
+
+impl PeerChainInfo {
+    /// Returns a response for a peer that has not advertised its chain state.
+    pub fn no_recent_data(peer_ip: SocketAddr) -> Self {
+        Self {
+            peer_ip,
+            has_recent_data: false,
+            node_type: None,
+            status: None,
+            latest_block_height: None,
+            latest_block_hash: None,
+            cumulative_weight: None,
+            is_fork: None,
+        }
+    }
+}
+
+/// The metadata of a transaction in the memory pool.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MempoolEntry {
+    /// The ID of the transaction.
+    pub transaction_id: String,
+    /// The size of the transaction in bytes.
+    pub size_in_bytes: usize,
+    /// The fee of the transaction, which is its value balance, or `0` for a coinbase transaction.
+    pub fee: i64,
+    /// The number of transitions in the transaction.
+    pub number_of_transitions: usize,
+    /// The UNIX timestamp at which the transaction entered the memory pool, if known.
+    pub time_received: Option<i64>,
+}
+
+/// The transactions in the memory pool, as either transaction IDs or their metadata.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RawMempool {
+    /// The IDs of the transactions in the memory pool.
+    TransactionIds(Vec<String>),
+    /// The metadata of the transactions in the memory pool.
+    Entries(Vec<MempoolEntry>),
+}
+
+impl RawMempool {
+    /// Returns the number of transactions.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::TransactionIds(transaction_ids) => transaction_ids.len(),
+            Self::Entries(entries) => entries.len(),
+        }
+    }
+
+    /// Returns `true` if there are no transactions.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use snarkvm::dpc::Network;
+
+use anyhow::{anyhow, Result};
+use std::{collections::BTreeMap, str::FromStr};
+
+///
+/// A set of trusted block hashes at fixed block heights.
+///
+/// Blocks at or below the latest checkpoint are trusted, and skip the expensive verification
+/// of their proofs, while any block that disagrees with a checkpoint is rejected.
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoints<N: Network> {
+    checkpoints: BTreeMap<u32, N::BlockHash>,
+}
+
+impl<N: Network> Checkpoints<N> {
+    ///
+    /// Initializes a new set of checkpoints, given a list of block heights and their block hashes.
+    ///
+    pub fn new(checkpoints: Vec<(u32, N::BlockHash)>) -> Self {
+        Self {
+            checkpoints: checkpoints.into_iter().collect(),
+        }
+    }
+
+    ///
+    /// Initializes a new set of checkpoints, given a list of block heights and their encoded block hashes.
+    ///
+    pub fn from_strs(checkpoints: &[(u32, &str)]) -> Result<Self> {
+        let checkpoints = checkpoints
+            .iter()
+            .map(|(block_height, block_hash)| match N::BlockHash::from_str(block_hash) {
+                Ok(block_hash) => Ok((*block_height, block_hash)),
+                Err(_) => Err(anyhow!("Invalid block hash '{}' for checkpoint {}", block_hash, block_height)),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::new(checkpoints))
+    }
+
+    /// Returns the block hash of the checkpoint at the given block height, if it exists.
+    pub fn get(&self, block_height: u32) -> Option<&N::BlockHash> {
+        self.checkpoints.get(&block_height)
+    }
+
+    /// Returns the block height of the latest checkpoint, if one exists.
+    pub fn latest_block_height(&self) -> Option<u32> {
+        self.checkpoints.keys().next_back().copied()
+    }
+
+    /// Returns `true` if the given block height is at or below the latest checkpoint.
+    pub fn is_trusted(&self, block_height: u32) -> bool {
+        self.latest_block_height().map_or(false, |latest| block_height <= latest)
+    }
+
+    /// Returns `true` if the given block hash agrees with the checkpoint at its block height, if it exists.
+    pub fn is_consistent(&self, block_height: u32, block_hash: &N::BlockHash) -> bool {
+        self.get(block_height).map_or(true, |checkpoint| checkpoint == block_hash)
+    }
+}
+
+impl<N: Network> Default for Checkpoints<N> {
+    fn default() -> Self {
+        Self::new(vec![])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::dpc::testnet2::Testnet2;
+
+    #[test]
+    fn test_checkpoints() {
+        let genesis = Testnet2::genesis_block();
+
+        let checkpoints = Checkpoints::<Testnet2>::from_strs(&[(0, &genesis.hash().to_string())]).unwrap();
+        assert_eq!(Some(&genesis.hash()), checkpoints.get(0));
+        assert_eq!(Some(0), checkpoints.latest_block_height());
+        assert!(checkpoints.is_trusted(0));
+        assert!(!checkpoints.is_trusted(1));
+        assert!(checkpoints.is_consistent(0, &genesis.hash()));
+        assert!(checkpoints.is_consistent(1, &genesis.hash()));
+        assert!(!checkpoints.is_consistent(0, &genesis.previous_block_hash()));
+
+        // Ensure an empty set of checkpoints trusts no blocks.
+        let checkpoints = Checkpoints::<Testnet2>::default();
+        assert_eq!(None, checkpoints.latest_block_height());
+        assert!(!checkpoints.is_trusted(0));
+
+        // Ensure malformed block hashes are rejected.
+        assert!(Checkpoints::<Testnet2>::from_strs(&[(0, "ab1invalid")]).is_err());
+    }
+}
@@ -0,0 +1,178 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use snarkvm::dpc::{Address, AleoAmount, Network};
+
+use anyhow::{anyhow, Result};
+use std::str::FromStr;
+
+///
+/// A helper struct to split the coinbase reward of a block across multiple recipients.
+///
+/// The coinbase reward is split proportionally to the weight of each recipient,
+/// and any rounding remainder is given to the first (primary) recipient.
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CoinbaseSplit<N: Network> {
+    recipients: Vec<(Address<N>, u64)>,
+}
+
+impl<N: Network> CoinbaseSplit<N> {
+    ///
+    /// Initializes a new coinbase split, given a list of recipients and their weights.
+    ///
+    pub fn new(recipients: Vec<(Address<N>, u64)>) -> Result<Self> {
+        // Ensure there is at least one recipient.
+        if recipients.is_empty() {
+            return Err(anyhow!("The coinbase split requires at least one recipient"));
+        }
+        // Ensure the weights do not sum to zero.
+        if recipients.iter().map(|(_, weight)| *weight as u128).sum::<u128>() == 0 {
+            return Err(anyhow!("The weights of the coinbase split must not sum to zero"));
+        }
+        Ok(Self { recipients })
+    }
+
+    ///
+    /// Returns the recipients and their weights.
+    ///
+    pub fn recipients(&self) -> &[(Address<N>, u64)] {
+        &self.recipients
+    }
+
+    ///
+    /// Returns the primary recipient, which receives any rounding remainder.
+    ///
+    pub fn primary_recipient(&self) -> Address<N> {
+        self.recipients[0].0
+    }
+
+    ///
+    /// Returns the amount owed to each recipient, given the coinbase reward.
+    /// The amounts are guaranteed to sum to the coinbase reward.
+    ///
+    pub fn split(&self, coinbase_reward: AleoAmount) -> Result<Vec<(Address<N>, AleoAmount)>> {
+        // Ensure the coinbase reward is not negative.
+        if coinbase_reward.is_negative() {
+            return Err(anyhow!("Invalid coinbase reward of {}", coinbase_reward));
+        }
+
+        let total_reward = coinbase_reward.0 as u128;
+        let total_weight = self.recipients.iter().map(|(_, weight)| *weight as u128).sum::<u128>();
+
+        // Compute the proportional amount for each recipient, rounding down.
+        let mut amounts = self
+            .recipients
+            .iter()
+            .map(|(recipient, weight)| (*recipient, total_reward * (*weight as u128) / total_weight))
+            .collect::<Vec<_>>();
+
+        // Give the rounding remainder to the primary recipient.
+        let remainder = total_reward - amounts.iter().map(|(_, amount)| amount).sum::<u128>();
+        amounts[0].1 += remainder;
+
+        Ok(amounts
+            .into_iter()
+            .map(|(recipient, amount)| (recipient, AleoAmount(amount as i64)))
+            .collect())
+    }
+}
+
+impl<N: Network> From<Address<N>> for CoinbaseSplit<N> {
+    fn from(recipient: Address<N>) -> Self {
+        Self {
+            recipients: vec![(recipient, 1)],
+        }
+    }
+}
+
+impl<N: Network> FromStr for CoinbaseSplit<N> {
+    type Err = anyhow::Error;
+
+    /// Parses a comma-separated list of `address:weight` pairs.
+    fn from_str(split: &str) -> Result<Self, Self::Err> {
+        let mut recipients = Vec::new();
+        for pair in split.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+            let (address, weight) = pair
+                .split_once(':')
+                .ok_or_else(|| anyhow!("Invalid coinbase split entry '{}', expected 'address:weight'", pair))?;
+            let address = Address::<N>::from_str(address.trim()).map_err(|error| anyhow!("Invalid address '{}': {}", address, error))?;
+            let weight = weight.trim().parse::<u64>()?;
+            recipients.push((address, weight));
+        }
+        Self::new(recipients)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::dpc::{testnet2::Testnet2, Account};
+
+    use rand::{thread_rng, Rng};
+
+    #[test]
+    fn test_coinbase_split() {
+        let rng = &mut thread_rng();
+
+        for _ in 0..10 {
+            let number_of_recipients = rng.gen_range(1..8);
+            let recipients = (0..number_of_recipients)
+                .map(|_| (Account::<Testnet2>::new(rng).address(), rng.gen_range(0..1_000_000)))
+                .collect::<Vec<_>>();
+            let coinbase_split = match CoinbaseSplit::<Testnet2>::new(recipients) {
+                Ok(coinbase_split) => coinbase_split,
+                Err(_) => continue,
+            };
+
+            let coinbase_reward = AleoAmount(rng.gen_range(0..i64::MAX));
+            let amounts = coinbase_split.split(coinbase_reward).unwrap();
+
+            // Ensure the split sums exactly to the coinbase reward.
+            assert_eq!(number_of_recipients, amounts.len());
+            assert_eq!(coinbase_reward.0, amounts.iter().map(|(_, amount)| amount.0).sum::<i64>());
+        }
+    }
+
+    #[test]
+    fn test_coinbase_split_remainder() {
+        let rng = &mut thread_rng();
+        let first = Account::<Testnet2>::new(rng).address();
+        let second = Account::<Testnet2>::new(rng).address();
+        let third = Account::<Testnet2>::new(rng).address();
+
+        // Ensure the rounding remainder is given to the first recipient.
+        let coinbase_split = CoinbaseSplit::<Testnet2>::new(vec![(first, 1), (second, 1), (third, 1)]).unwrap();
+        let amounts = coinbase_split.split(AleoAmount(100)).unwrap();
+        assert_eq!(
+            vec![(first, AleoAmount(34)), (second, AleoAmount(33)), (third, AleoAmount(33))],
+            amounts
+        );
+    }
+
+    #[test]
+    fn test_coinbase_split_zero_weights() {
+        let address = Account::<Testnet2>::new(&mut thread_rng()).address();
+
+        assert!(CoinbaseSplit::<Testnet2>::new(vec![]).is_err());
+        assert!(CoinbaseSplit::<Testnet2>::new(vec![(address, 0), (address, 0)]).is_err());
+        assert!(CoinbaseSplit::<Testnet2>::from_str(&format!("{}:0", address)).is_err());
+        assert_eq!(
+            CoinbaseSplit::<Testnet2>::new(vec![(address, 3), (address, 1)]).unwrap(),
+            CoinbaseSplit::<Testnet2>::from_str(&format!("{}:3, {}:1", address, address)).unwrap()
+        );
+    }
+}
@@ -16,3 +16,9 @@
 
 pub mod block_locators;
 pub use block_locators::*;
+
+pub mod checkpoints;
+pub use checkpoints::*;
+
+pub mod coinbase_split;
+pub use coinbase_split::*;
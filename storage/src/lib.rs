@@ -21,10 +21,11 @@
 extern crate tracing;
 
 mod helpers;
-pub use helpers::BlockLocators;
+pub use helpers::{BlockLocators, Checkpoints, CoinbaseSplit};
 
 pub(crate) mod state;
 pub use state::{
+    InvalidBlock,
     LedgerState,
     Metadata,
     OperatorState,
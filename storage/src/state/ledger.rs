@@ -15,7 +15,7 @@
 // along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-    helpers::BlockLocators,
+    helpers::{BlockLocators, Checkpoints, CoinbaseSplit},
     storage::{DataMap, Map, MapId, Storage},
 };
 use snarkvm::dpc::prelude::*;
@@ -23,12 +23,13 @@ use snarkvm::dpc::prelude::*;
 use anyhow::{anyhow, Result};
 use circular_queue::CircularQueue;
 use itertools::Itertools;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use rand::{CryptoRng, Rng};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{BTreeMap, HashSet},
+    fmt,
     path::Path,
     sync::{
         atomic::{AtomicBool, AtomicU32, Ordering},
@@ -45,6 +46,20 @@ pub const MAXIMUM_QUADRATIC_BLOCK_LOCATORS: u32 = 32;
 /// The total maximum number of block locators.
 pub const MAXIMUM_BLOCK_LOCATORS: u32 = MAXIMUM_LINEAR_BLOCK_LOCATORS.saturating_add(MAXIMUM_QUADRATIC_BLOCK_LOCATORS);
 
+///
+/// The error for a block that is rejected by `Block::is_valid`, with its block height.
+///
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct InvalidBlock(pub u32);
+
+impl fmt::Display for InvalidBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Block {} is invalid", self.0)
+    }
+}
+
+impl std::error::Error for InvalidBlock {}
+
 ///
 /// A helper struct containing transaction metadata.
 ///
@@ -143,6 +158,18 @@ impl<N: Network> LedgerState<N> {
             // The map lock goes out of scope on its own.
         }
 
+        // If the storage predates the transactions totals, regenerate them from the canonical blocks.
+        if !ledger.blocks.transactions_totals.contains_key(&latest_block_height)? {
+            debug!("Regenerating the transactions totals up to block {}", latest_block_height);
+
+            // Acquire the map lock to ensure the following operations aren't interrupted by a shutdown.
+            let _map_lock = ledger.map_lock.read();
+
+            ledger.blocks.regenerate_transactions_totals(latest_block_height)?;
+
+            // The map lock goes out of scope on its own.
+        }
+
         // Check that all canonical block headers exist in storage.
         let count = ledger.blocks.get_block_header_count()?;
         assert_eq!(count, latest_block_height.saturating_add(1));
@@ -408,6 +435,16 @@ impl<N: Network> LedgerState<N> {
         self.blocks.get_cumulative_weight(block_height)
     }
 
+    /// Returns the total number of transactions up to a given block height (inclusive) for the canonical chain.
+    pub fn get_transactions_total(&self, block_height: u32) -> Result<u64> {
+        self.blocks.get_transactions_total(block_height)
+    }
+
+    /// Returns the heights of the canonical blocks with a public coinbase record owned by the given address, in increasing order.
+    pub fn get_blocks_by_miner(&self, address: &Address<N>) -> Result<Vec<u32>> {
+        self.blocks.get_blocks_by_miner(address)
+    }
+
     /// Returns the block height for the given block hash.
     pub fn get_block_height(&self, block_hash: &N::BlockHash) -> Result<u32> {
         self.blocks.get_block_height(block_hash)
@@ -443,6 +480,11 @@ impl<N: Network> LedgerState<N> {
         self.blocks.get_block_transactions(block_height)
     }
 
+    /// Returns the transaction IDs from the block of the given block hash.
+    pub fn get_block_transaction_ids(&self, block_hash: &N::BlockHash) -> Result<Vec<N::TransactionID>> {
+        self.blocks.get_block_transaction_ids(block_hash)
+    }
+
     /// Returns the block for a given block height.
     pub fn get_block(&self, block_height: u32) -> Result<Block<N>> {
         self.blocks.get_block(block_height)
@@ -590,6 +632,18 @@ impl<N: Network> LedgerState<N> {
         is_public: bool,
         transactions: &[Transaction<N>],
         rng: &mut R,
+    ) -> Result<BlockTemplate<N>> {
+        self.get_block_template_with_coinbase_split(&CoinbaseSplit::from(recipient), is_public, transactions, rng)
+    }
+
+    /// Returns a block template based on the latest state of the ledger,
+    /// with the coinbase reward split across the recipients of the given coinbase split.
+    pub fn get_block_template_with_coinbase_split<R: Rng + CryptoRng>(
+        &self,
+        coinbase_split: &CoinbaseSplit<N>,
+        is_public: bool,
+        transactions: &[Transaction<N>],
+        rng: &mut R,
     ) -> Result<BlockTemplate<N>> {
         // Fetch the latest state of the ledger.
         let latest_block = self.latest_block();
@@ -660,7 +714,7 @@ impl<N: Network> LedgerState<N> {
         coinbase_reward = coinbase_reward.add(transaction_fees);
 
         // Craft a coinbase transaction, and append it to the list of transactions.
-        let (coinbase_transaction, coinbase_record) = Transaction::<N>::new_coinbase(recipient, coinbase_reward, is_public, rng)?;
+        let (coinbase_transaction, coinbase_record) = self.craft_coinbase_transaction(coinbase_split, coinbase_reward, is_public, rng)?;
         transactions.push(coinbase_transaction);
 
         // Construct the new block transactions.
@@ -688,7 +742,21 @@ impl<N: Network> LedgerState<N> {
         terminator: &AtomicBool,
         rng: &mut R,
     ) -> Result<(Block<N>, Record<N>)> {
-        let template = self.get_block_template(recipient, is_public, transactions, rng)?;
+        self.mine_next_block_with_coinbase_split(&CoinbaseSplit::from(recipient), is_public, transactions, terminator, rng)
+    }
+
+    /// Mines a new block using the latest state of the given ledger,
+    /// with the coinbase reward split across the recipients of the given coinbase split.
+    /// The returned coinbase record belongs to the primary recipient.
+    pub fn mine_next_block_with_coinbase_split<R: Rng + CryptoRng>(
+        &self,
+        coinbase_split: &CoinbaseSplit<N>,
+        is_public: bool,
+        transactions: &[Transaction<N>],
+        terminator: &AtomicBool,
+        rng: &mut R,
+    ) -> Result<(Block<N>, Record<N>)> {
+        let template = self.get_block_template_with_coinbase_split(coinbase_split, is_public, transactions, rng)?;
         let coinbase_record = template.coinbase_record().clone();
 
         // Mine the next block.
@@ -698,16 +766,75 @@ impl<N: Network> LedgerState<N> {
         }
     }
 
+    /// Returns a coinbase transaction paying each recipient of the coinbase split its share of the coinbase reward,
+    /// along with the coinbase record of the primary recipient.
+    fn craft_coinbase_transaction<R: Rng + CryptoRng>(
+        &self,
+        coinbase_split: &CoinbaseSplit<N>,
+        coinbase_reward: AleoAmount,
+        is_public: bool,
+        rng: &mut R,
+    ) -> Result<(Transaction<N>, Record<N>)> {
+        let outputs = coinbase_split.split(coinbase_reward)?;
+
+        // If there is only one recipient, craft a standard coinbase transaction.
+        if let [(recipient, amount)] = outputs[..] {
+            return Transaction::<N>::new_coinbase(recipient, amount, is_public, rng);
+        }
+
+        // Execute a coinbase request for each recipient, so all shares are paid out in a single coinbase transaction.
+        // Any (non-primary) recipients whose share rounds down to zero are skipped.
+        let mut vm = VirtualMachine::<N>::new(self.latest_ledger_root())?;
+        let mut coinbase_record = None;
+        for (index, (recipient, amount)) in outputs.into_iter().enumerate() {
+            if index > 0 && amount == AleoAmount::ZERO {
+                continue;
+            }
+            let request = Request::new_coinbase(recipient, amount, is_public, rng)?;
+            let (next_vm, response) = vm.execute(&request, rng)?;
+            // Retain the coinbase record of the primary recipient.
+            if index == 0 {
+                coinbase_record = response.records().first().cloned();
+            }
+            vm = next_vm;
+        }
+
+        match coinbase_record {
+            Some(coinbase_record) => Ok((vm.finalize()?, coinbase_record)),
+            None => Err(anyhow!("Missing the coinbase record of the primary recipient")),
+        }
+    }
+
     /// Adds the given block as the next block in the ledger to storage.
     pub fn add_next_block(&self, block: &Block<N>) -> Result<()> {
+        self.add_next_block_with_checkpoints(block, &Checkpoints::default())
+    }
+
+    ///
+    /// Adds the given block as the next block in the ledger to storage.
+    ///
+    /// If the block is at or below the latest checkpoint, the proofs of the block are trusted, and are not verified.
+    /// Its transactions are still checked against its header, along with its coinbase and hash linkage.
+    /// Any block that disagrees with a checkpoint is rejected.
+    ///
+    pub fn add_next_block_with_checkpoints(&self, block: &Block<N>, checkpoints: &Checkpoints<N>) -> Result<()> {
         // If the storage is in read-only mode, this method cannot be called.
         if self.is_read_only() {
             return Err(anyhow!("Ledger is in read-only mode"));
         }
 
-        // Ensure the block itself is valid.
-        if !block.is_valid() {
-            return Err(anyhow!("Block {} is invalid", block.height()));
+        // Ensure the block agrees with the checkpoints.
+        if !checkpoints.is_consistent(block.height(), &block.hash()) {
+            return Err(anyhow!("Block {} ({}) conflicts with a checkpoint", block.height(), block.hash()));
+        }
+
+        // Ensure the block itself is valid, without verifying its proofs if it is trusted by a checkpoint.
+        let is_valid = match checkpoints.is_trusted(block.height()) {
+            true => Self::is_valid_without_proofs(block.header(), block.transactions(), block.height()),
+            false => block.is_valid(),
+        };
+        if !is_valid {
+            return Err(InvalidBlock(block.height()).into());
         }
 
         // Retrieve the current block.
@@ -743,14 +870,7 @@ impl<N: Network> LedgerState<N> {
         }
 
         // Compute the expected difficulty target.
-        let expected_difficulty_target = if N::NETWORK_ID == 2 && block_height <= snarkvm::dpc::testnet2::V12_UPGRADE_BLOCK_HEIGHT {
-            Blocks::<N>::compute_difficulty_target(current_block.header(), block.timestamp(), block.height())
-        } else if N::NETWORK_ID == 2 {
-            let anchor_block_header = self.get_block_header(snarkvm::dpc::testnet2::V12_UPGRADE_BLOCK_HEIGHT)?;
-            Blocks::<N>::compute_difficulty_target(&anchor_block_header, block.timestamp(), block.height())
-        } else {
-            Blocks::<N>::compute_difficulty_target(N::genesis_block().header(), block.timestamp(), block.height())
-        };
+        let expected_difficulty_target = self.get_expected_difficulty_target(block_height, block.timestamp())?;
 
         // Ensure the expected difficulty target is met.
         if block.difficulty_target() != expected_difficulty_target {
@@ -842,6 +962,83 @@ impl<N: Network> LedgerState<N> {
         Ok(())
     }
 
+    ///
+    /// Returns the expected difficulty target of a block at the given block height and timestamp, which only depends on
+    /// this ledger, and not on the difficulty target that the block declares. Prior to the V12 upgrade of testnet2,
+    /// this requires the previous block to be in the canonical chain.
+    ///
+    pub fn get_expected_difficulty_target(&self, block_height: u32, block_timestamp: i64) -> Result<u64> {
+        if N::NETWORK_ID == 2 && block_height <= snarkvm::dpc::testnet2::V12_UPGRADE_BLOCK_HEIGHT {
+            let previous_block_header = self.get_block_header(block_height.saturating_sub(1))?;
+            Ok(Blocks::<N>::compute_difficulty_target(&previous_block_header, block_timestamp, block_height))
+        } else if N::NETWORK_ID == 2 {
+            let anchor_block_header = self.get_block_header(snarkvm::dpc::testnet2::V12_UPGRADE_BLOCK_HEIGHT)?;
+            Ok(Blocks::<N>::compute_difficulty_target(&anchor_block_header, block_timestamp, block_height))
+        } else {
+            Ok(Blocks::<N>::compute_difficulty_target(N::genesis_block().header(), block_timestamp, block_height))
+        }
+    }
+
+    ///
+    /// Returns `true` if the given transactions belong to the given block header, and pay out the block reward
+    /// in a single coinbase transaction, without verifying the proofs of the block or its transactions.
+    ///
+    pub(crate) fn is_valid_without_proofs(header: &BlockHeader<N>, transactions: &Transactions<N>, block_height: u32) -> bool {
+        // Ensure the transactions are the ones committed to in the block header.
+        if header.transactions_root() != transactions.transactions_root() {
+            trace!("Block {} has transactions that do not match its transactions root", block_height);
+            return false;
+        }
+
+        // Ensure there is exactly one coinbase transaction.
+        if transactions.iter().filter(|transaction| transaction.value_balance().is_negative()).count() != 1 {
+            trace!("Block {} must contain exactly one coinbase transaction", block_height);
+            return false;
+        }
+
+        // Ensure the coinbase transaction pays out exactly the block reward, along with the transaction fees.
+        let net_value_balance = transactions
+            .iter()
+            .fold(AleoAmount::ZERO, |balance, transaction| balance.add(transaction.value_balance()));
+        if net_value_balance != AleoAmount::ZERO.sub(Block::<N>::block_reward(block_height)) {
+            trace!("Block {} has an incorrect coinbase reward", block_height);
+            return false;
+        }
+
+        // Ensure the transactions, serial numbers, and commitments in the block are unique.
+        let transaction_ids: HashSet<_> = transactions.iter().map(|transaction| transaction.transaction_id()).collect();
+        let serial_numbers: Vec<_> = transactions.iter().flat_map(|transaction| transaction.serial_numbers()).collect();
+        let commitments: Vec<_> = transactions.iter().flat_map(|transaction| transaction.commitments()).collect();
+        if transaction_ids.len() != transactions.len()
+            || serial_numbers.iter().collect::<HashSet<_>>().len() != serial_numbers.len()
+            || commitments.iter().collect::<HashSet<_>>().len() != commitments.len()
+        {
+            trace!("Block {} contains duplicate transactions, serial numbers, or commitments", block_height);
+            return false;
+        }
+
+        true
+    }
+
+    ///
+    /// Rebuilds the index of miners to their blocks from the canonical chain.
+    /// The index is maintained as blocks are added and removed, so this is only needed
+    /// to recover from a corrupt index, or to index the blocks of storage that predates it.
+    ///
+    pub fn rebuild_miner_index(&self) -> Result<()> {
+        // If the storage is in read-only mode, this method cannot be called.
+        if self.is_read_only() {
+            return Err(anyhow!("Ledger is in read-only mode"));
+        }
+
+        // Acquire the map lock to ensure the following operations aren't interrupted by a shutdown.
+        let _map_lock = self.map_lock.read();
+
+        self.blocks.regenerate_miner_blocks(self.latest_block_height())
+
+        // The map lock goes out of scope on its own.
+    }
+
     /// Reverts the ledger state back to the given block height, returning the removed blocks on success.
     pub fn revert_to_block_height(&self, block_height: u32) -> Result<Vec<Block<N>>> {
         // If the storage is in read-only mode, this method cannot be called.
@@ -1170,12 +1367,21 @@ impl<N: Network> LedgerState<N> {
                 }
             };
 
+            // Remove the block from the miner index, if its coinbase transaction can still be retrieved.
+            let block = self.blocks.get_block(current_block_height);
+            if let Ok(coinbase_transaction) = block.and_then(|block| block.to_coinbase_transaction()) {
+                let miners = BlockState::miners(&coinbase_transaction);
+                self.blocks.remove_miner_blocks(&miners, current_block_height)?;
+            }
+
             // Remove the block height.
             self.blocks.block_heights.remove(&current_block_height)?;
             // Remove the block header.
             self.blocks.block_headers.remove(&block_hash)?;
             // Remove the block transactions.
             self.blocks.block_transactions.remove(&block_hash)?;
+            // Remove the transactions total.
+            self.blocks.transactions_totals.remove(&current_block_height)?;
             // Remove the transactions.
             for transaction_ids in transaction_ids.iter() {
                 self.blocks.transactions.remove_transaction(transaction_ids)?;
@@ -1237,6 +1443,9 @@ struct BlockState<N: Network> {
     block_heights: DataMap<u32, N::BlockHash>,
     block_headers: DataMap<N::BlockHash, BlockHeader<N>>,
     block_transactions: DataMap<N::BlockHash, Vec<N::TransactionID>>,
+    transactions_totals: DataMap<u32, u64>,
+    miner_blocks: DataMap<(Address<N>, u32), ()>,
+    miner_blocks_lock: Arc<Mutex<()>>,
     transactions: TransactionState<N>,
 }
 
@@ -1247,6 +1456,9 @@ impl<N: Network> BlockState<N> {
             block_heights: storage.open_map(MapId::BlockHeights)?,
             block_headers: storage.open_map(MapId::BlockHeaders)?,
             block_transactions: storage.open_map(MapId::BlockTransactions)?,
+            transactions_totals: storage.open_map(MapId::TransactionsTotals)?,
+            miner_blocks: storage.open_map(MapId::MinerBlocks)?,
+            miner_blocks_lock: Default::default(),
             transactions: TransactionState::open(storage)?,
         })
     }
@@ -1301,6 +1513,14 @@ impl<N: Network> BlockState<N> {
         Ok(self.get_block_header(block_height)?.cumulative_weight())
     }
 
+    /// Returns the total number of transactions up to a given block height (inclusive) for the canonical chain.
+    fn get_transactions_total(&self, block_height: u32) -> Result<u64> {
+        match self.transactions_totals.get(&block_height)? {
+            Some(transactions_total) => Ok(transactions_total),
+            None => Err(anyhow!("Block {} missing from transactions totals map", block_height)),
+        }
+    }
+
     /// Returns the block height for the given block hash.
     fn get_block_height(&self, block_hash: &N::BlockHash) -> Result<u32> {
         match self.block_headers.get(block_hash)? {
@@ -1369,16 +1589,21 @@ impl<N: Network> BlockState<N> {
         Ok(count as u32)
     }
 
+    /// Returns the transaction IDs from the block of the given block hash.
+    fn get_block_transaction_ids(&self, block_hash: &N::BlockHash) -> Result<Vec<N::TransactionID>> {
+        match self.block_transactions.get(block_hash)? {
+            Some(transaction_ids) => Ok(transaction_ids),
+            None => Err(anyhow!("Block {} missing from block transactions map", block_hash)),
+        }
+    }
+
     /// Returns the transactions from the block of the given block height.
     fn get_block_transactions(&self, block_height: u32) -> Result<Transactions<N>> {
         // Retrieve the block hash.
         let block_hash = self.get_block_hash(block_height)?;
 
         // Retrieve the block transaction IDs.
-        let transaction_ids = match self.block_transactions.get(&block_hash)? {
-            Some(transaction_ids) => transaction_ids,
-            None => return Err(anyhow!("Block {} missing from block transactions map", block_hash)),
-        };
+        let transaction_ids = self.get_block_transaction_ids(&block_hash)?;
 
         // Retrieve the block transactions.
         let transactions = {
@@ -1437,12 +1662,25 @@ impl<N: Network> BlockState<N> {
             let transactions = block.transactions();
             let transaction_ids = transactions.transaction_ids().collect::<Vec<_>>();
 
+            // Compute the total number of transactions up to and including this block.
+            let previous_transactions_total = match block_height {
+                0 => 0,
+                _ => self.get_transactions_total(block_height - 1)?,
+            };
+            let transactions_total = previous_transactions_total.saturating_add(transaction_ids.len() as u64);
+            // Determine the miners of the block.
+            let miners = Self::miners(&block.to_coinbase_transaction()?);
+
             // Insert the block height.
             self.block_heights.insert(&block_height, &block_hash)?;
             // Insert the block header.
             self.block_headers.insert(&block_hash, block_header)?;
             // Insert the block transactions.
             self.block_transactions.insert(&block_hash, &transaction_ids)?;
+            // Insert the transactions total.
+            self.transactions_totals.insert(&block_height, &transactions_total)?;
+            // Insert the block into the miner index.
+            self.add_miner_blocks(&miners, block_height)?;
             // Insert the transactions.
             for (index, transaction) in transactions.iter().enumerate() {
                 let metadata = Metadata::<N>::new(block_height, block_hash, block.timestamp(), index as u16);
@@ -1480,6 +1718,8 @@ impl<N: Network> BlockState<N> {
 
             // Retrieve the block height.
             let block_height = block_header.height();
+            // Determine the miners of the block.
+            let miners = Self::miners(&self.get_block(block_height)?.to_coinbase_transaction()?);
 
             // Remove the block height.
             self.block_heights.remove(&block_height)?;
@@ -1487,6 +1727,10 @@ impl<N: Network> BlockState<N> {
             self.block_headers.remove(&block_hash)?;
             // Remove the block transactions.
             self.block_transactions.remove(&block_hash)?;
+            // Remove the transactions total.
+            self.transactions_totals.remove(&block_height)?;
+            // Remove the block from the miner index.
+            self.remove_miner_blocks(&miners, block_height)?;
             // Remove the transactions.
             for transaction_ids in transaction_ids.iter() {
                 self.transactions.remove_transaction(transaction_ids)?;
@@ -1495,6 +1739,96 @@ impl<N: Network> BlockState<N> {
             Ok(())
         }
     }
+
+    /// Regenerates the total number of transactions for each block height up to the given block height (inclusive).
+    fn regenerate_transactions_totals(&self, latest_block_height: u32) -> Result<()> {
+        let mut transactions_total = 0u64;
+        for block_height in 0..=latest_block_height {
+            let block_hash = self.get_block_hash(block_height)?;
+            let number_of_transactions = self.get_block_transaction_ids(&block_hash)?.len();
+
+            transactions_total = transactions_total.saturating_add(number_of_transactions as u64);
+            self.transactions_totals.insert(&block_height, &transactions_total)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the heights of the blocks mined by the given address, in increasing order.
+    fn get_blocks_by_miner(&self, address: &Address<N>) -> Result<Vec<u32>> {
+        let mut block_heights = self
+            .miner_blocks
+            .keys_with_prefix(address)?
+            .into_iter()
+            .map(|(_, block_height)| block_height)
+            .collect::<Vec<_>>();
+        block_heights.sort_unstable();
+        Ok(block_heights)
+    }
+
+    ///
+    /// Returns the owners of the records in the given coinbase transaction that are publicly revealed.
+    /// The miners of a block with a private coinbase record cannot be determined, and are not indexed.
+    ///
+    fn miners(coinbase_transaction: &Transaction<N>) -> Vec<Address<N>> {
+        let mut miners = Vec::new();
+        for record in coinbase_transaction.to_records() {
+            if !miners.contains(&record.owner()) {
+                miners.push(record.owner());
+            }
+        }
+        miners
+    }
+
+    /// Adds the given block height to the miner index of each of the given miners.
+    fn add_miner_blocks(&self, miners: &[Address<N>], block_height: u32) -> Result<()> {
+        let _miner_blocks_lock = self.miner_blocks_lock.lock();
+        for miner in miners {
+            self.miner_blocks.insert(&(*miner, block_height), &())?;
+        }
+        Ok(())
+    }
+
+    /// Removes the given block height from the miner index of each of the given miners.
+    fn remove_miner_blocks(&self, miners: &[Address<N>], block_height: u32) -> Result<()> {
+        let _miner_blocks_lock = self.miner_blocks_lock.lock();
+        for miner in miners {
+            self.miner_blocks.remove(&(*miner, block_height))?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Regenerates the miner index from the blocks up to the given block height (inclusive).
+    /// The new index is built next to the existing index, which keeps serving queries and indexing new blocks,
+    /// and only the differences are applied to the existing index, under its lock.
+    ///
+    fn regenerate_miner_blocks(&self, latest_block_height: u32) -> Result<()> {
+        // Index the miners of each block, without holding the lock of the miner index.
+        let latest_block_hash = self.get_block_hash(latest_block_height)?;
+        let mut miner_blocks = HashSet::new();
+        for block_height in 0..=latest_block_height {
+            let coinbase_transaction = self.get_block(block_height)?.to_coinbase_transaction()?;
+            miner_blocks.extend(Self::miners(&coinbase_transaction).into_iter().map(|miner| (miner, block_height)));
+        }
+
+        let _miner_blocks_lock = self.miner_blocks_lock.lock();
+
+        // Ensure the indexed blocks were not reverted in the meantime, as the tip hash commits to its ancestors.
+        if self.get_block_hash(latest_block_height).ok() != Some(latest_block_hash) {
+            return Err(anyhow!("The ledger was reverted while regenerating the miner index"));
+        }
+        // Remove the stale entries of the indexed blocks, retaining the entries of the blocks added in the meantime.
+        for (miner, block_height) in self.miner_blocks.keys().collect::<Vec<_>>() {
+            if block_height <= latest_block_height && !miner_blocks.remove(&(miner, block_height)) {
+                self.miner_blocks.remove(&(miner, block_height))?;
+            }
+        }
+        // Insert the missing entries of the indexed blocks.
+        for entry in miner_blocks {
+            self.miner_blocks.insert(&entry, &())?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug)]
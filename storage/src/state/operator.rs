@@ -26,6 +26,8 @@ use std::{collections::HashMap, path::Path, sync::Arc};
 #[derive(Debug)]
 pub struct OperatorState<N: Network> {
     shares: SharesState<N>,
+    /// Set to `true` if the pool is paused for maintenance.
+    pool_paused: DataMap<(), bool>,
 }
 
 impl<N: Network> OperatorState<N> {
@@ -40,6 +42,7 @@ impl<N: Network> OperatorState<N> {
 
         // Initialize the operator.
         let operator = Self {
+            pool_paused: storage.open_map(MapId::PoolStatus)?,
             shares: SharesState::open(storage)?,
         };
 
@@ -76,6 +79,16 @@ impl<N: Network> OperatorState<N> {
     pub fn remove_shares(&self, block_height: u32, coinbase_record: Record<N>) -> Result<()> {
         self.shares.remove_shares(block_height, coinbase_record)
     }
+
+    /// Returns `true` if the pool is paused for maintenance.
+    pub fn is_pool_paused(&self) -> Result<bool> {
+        Ok(self.pool_paused.get(&())?.unwrap_or(false))
+    }
+
+    /// Sets whether the pool is paused for maintenance, so that the pause survives a restart.
+    pub fn set_pool_paused(&self, is_paused: bool) -> Result<()> {
+        self.pool_paused.insert(&(), &is_paused)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -16,7 +16,10 @@
 
 use crate::{
     storage::{rocksdb::RocksDB, Storage},
+    Checkpoints,
+    CoinbaseSplit,
     LedgerState,
+    OperatorState,
 };
 use snarkvm::dpc::{prelude::*, testnet2::Testnet2};
 
@@ -55,6 +58,22 @@ fn test_genesis() {
     assert_eq!(ledger_tree.root(), ledger.latest_ledger_root());
 }
 
+#[test]
+fn test_get_block_transaction_ids() {
+    // Initialize a new ledger.
+    let ledger = create_new_ledger::<Testnet2, RocksDB>();
+
+    // Retrieve the genesis block.
+    let genesis = Testnet2::genesis_block();
+
+    // Ensure the transaction IDs of the genesis block are returned.
+    let expected = genesis.transactions().transaction_ids().collect::<Vec<_>>();
+    assert_eq!(expected, ledger.get_block_transaction_ids(&genesis.hash()).unwrap());
+
+    // Ensure an unknown block hash is rejected.
+    assert!(ledger.get_block_transaction_ids(&Default::default()).is_err());
+}
+
 #[test]
 fn test_add_next_block() {
     let rng = &mut thread_rng();
@@ -149,6 +168,43 @@ fn test_remove_last_block() {
     assert_eq!(ledger_tree.root(), ledger.latest_ledger_root());
 }
 
+#[test]
+fn test_transactions_total() {
+    let rng = &mut thread_rng();
+    let terminator = AtomicBool::new(false);
+
+    // Initialize a new ledger.
+    let directory = temp_dir();
+    let ledger = LedgerState::<Testnet2>::open_writer::<RocksDB, _>(&directory).expect("Failed to initialize ledger");
+
+    // Ensure the genesis transactions are counted.
+    let genesis_transactions_total = Testnet2::genesis_block().transactions().len() as u64;
+    assert_eq!(genesis_transactions_total, ledger.get_transactions_total(0).unwrap());
+
+    // Initialize a new account.
+    let account = Account::<Testnet2>::new(&mut thread_rng());
+    let address = account.address();
+
+    // Mine the next block.
+    let (block, _) = ledger
+        .mine_next_block(address, true, &[], &terminator, rng)
+        .expect("Failed to mine");
+    ledger.add_next_block(&block).expect("Failed to add next block to ledger");
+
+    let expected = genesis_transactions_total + block.transactions().len() as u64;
+    assert_eq!(expected, ledger.get_transactions_total(1).unwrap());
+
+    // Ensure the transactions total survives a restart.
+    drop(ledger);
+    let ledger = LedgerState::<Testnet2>::open_writer::<RocksDB, _>(&directory).expect("Failed to reopen ledger");
+    assert_eq!(expected, ledger.get_transactions_total(1).unwrap());
+
+    // Ensure the transactions total is removed with the block.
+    ledger.revert_to_block_height(0).expect("Failed to remove the last block");
+    assert_eq!(genesis_transactions_total, ledger.get_transactions_total(0).unwrap());
+    assert!(ledger.get_transactions_total(1).is_err());
+}
+
 #[test]
 fn test_remove_last_2_blocks() {
     let rng = &mut thread_rng();
@@ -344,3 +400,185 @@ fn test_transaction_fees() {
     assert_eq!(new_coinbase_record.value(), expected_block_reward);
     assert_eq!(output_record.value(), amount);
 }
+
+#[test]
+fn test_mine_next_block_with_coinbase_split() {
+    let rng = &mut thread_rng();
+    let terminator = AtomicBool::new(false);
+
+    // Initialize a new ledger.
+    let ledger = create_new_ledger::<Testnet2, RocksDB>();
+    assert_eq!(0, ledger.latest_block_height());
+
+    // Initialize the recipient accounts.
+    let first = Account::<Testnet2>::new(rng);
+    let second = Account::<Testnet2>::new(rng);
+    let coinbase_split = CoinbaseSplit::new(vec![(first.address(), 2), (second.address(), 1)]).unwrap();
+
+    // Mine the next block.
+    let (block, coinbase_record) = ledger
+        .mine_next_block_with_coinbase_split(&coinbase_split, true, &[], &terminator, rng)
+        .expect("Failed to mine");
+    ledger.add_next_block(&block).expect("Failed to add next block to ledger");
+    assert_eq!(1, ledger.latest_block_height());
+
+    // Ensure the coinbase reward is split across the recipients.
+    let expected_block_reward = Block::<Testnet2>::block_reward(1);
+    let expected_amounts = coinbase_split.split(expected_block_reward).unwrap();
+
+    let coinbase_transaction = &block.transactions()[0];
+    assert_eq!(AleoAmount::ZERO.sub(expected_block_reward), coinbase_transaction.value_balance());
+
+    let first_record = &coinbase_transaction.to_decrypted_records(first.view_key())[0];
+    let second_record = &coinbase_transaction.to_decrypted_records(second.view_key())[0];
+    assert_eq!(&coinbase_record, first_record);
+    assert_eq!(expected_amounts[0].1, first_record.value());
+    assert_eq!(expected_amounts[1].1, second_record.value());
+    assert_eq!(expected_block_reward, first_record.value().add(second_record.value()));
+}
+
+#[test]
+fn test_add_next_block_conflicting_with_checkpoint() {
+    let rng = &mut thread_rng();
+    let terminator = AtomicBool::new(false);
+
+    // Initialize a new ledger.
+    let ledger = create_new_ledger::<Testnet2, RocksDB>();
+    assert_eq!(0, ledger.latest_block_height());
+
+    // Mine the next block.
+    let address = Account::<Testnet2>::new(rng).address();
+    let (block, _) = ledger
+        .mine_next_block(address, true, &[], &terminator, rng)
+        .expect("Failed to mine");
+
+    // Ensure the block is rejected if it conflicts with a checkpoint.
+    let checkpoints = Checkpoints::<Testnet2>::new(vec![(1, Testnet2::genesis_block().hash())]);
+    assert!(ledger.add_next_block_with_checkpoints(&block, &checkpoints).is_err());
+    assert_eq!(0, ledger.latest_block_height());
+
+    // Ensure the block is accepted if it agrees with the checkpoint.
+    let checkpoints = Checkpoints::<Testnet2>::new(vec![(1, block.hash())]);
+    ledger
+        .add_next_block_with_checkpoints(&block, &checkpoints)
+        .expect("Failed to add next block to ledger");
+    assert_eq!(1, ledger.latest_block_height());
+    assert_eq!(block.hash(), ledger.latest_block_hash());
+}
+
+#[test]
+fn test_add_next_block_with_tampered_transactions_below_checkpoint() {
+    let rng = &mut thread_rng();
+    let terminator = AtomicBool::new(false);
+
+    // Mine a block at the same height on two ledgers, with different coinbase transactions.
+    let ledger = create_new_ledger::<Testnet2, RocksDB>();
+    let other_ledger = create_new_ledger::<Testnet2, RocksDB>();
+    let (block, _) = ledger
+        .mine_next_block(Account::<Testnet2>::new(rng).address(), true, &[], &terminator, rng)
+        .expect("Failed to mine");
+    let (other_block, _) = other_ledger
+        .mine_next_block(Account::<Testnet2>::new(rng).address(), true, &[], &terminator, rng)
+        .expect("Failed to mine");
+
+    // Ensure the header of a trusted block is still bound to its transactions.
+    assert!(LedgerState::<Testnet2>::is_valid_without_proofs(block.header(), block.transactions(), 1));
+    assert!(!LedgerState::<Testnet2>::is_valid_without_proofs(
+        block.header(),
+        other_block.transactions(),
+        1
+    ));
+
+    // Ensure a trusted block with the transactions of another block is rejected.
+    let checkpoints = Checkpoints::<Testnet2>::new(vec![(1, block.hash())]);
+    if let Ok(tampered_block) = Block::from(block.previous_block_hash(), block.header().clone(), other_block.transactions().clone()) {
+        assert!(ledger.add_next_block_with_checkpoints(&tampered_block, &checkpoints).is_err());
+    }
+    assert_eq!(0, ledger.latest_block_height());
+
+    // Ensure the untampered block is accepted.
+    ledger
+        .add_next_block_with_checkpoints(&block, &checkpoints)
+        .expect("Failed to add next block to ledger");
+    assert_eq!(1, ledger.latest_block_height());
+}
+
+#[test]
+fn test_miner_index() {
+    let rng = &mut thread_rng();
+    let terminator = AtomicBool::new(false);
+
+    // Initialize a new ledger.
+    let directory = temp_dir();
+    let ledger = LedgerState::<Testnet2>::open_writer::<RocksDB, _>(&directory).expect("Failed to initialize ledger");
+
+    // Initialize three miners.
+    let miners = (0..3).map(|_| Account::<Testnet2>::new(rng).address()).collect::<Vec<_>>();
+
+    // Mine 10 blocks, attributed to the miners in turn.
+    for block_height in 1..=10u32 {
+        let miner = miners[block_height as usize % 3];
+        let (block, _) = ledger.mine_next_block(miner, true, &[], &terminator, rng).expect("Failed to mine");
+        ledger.add_next_block(&block).expect("Failed to add next block to ledger");
+    }
+
+    // Ensure each miner is indexed with its blocks, in increasing order.
+    let expected_blocks = |miner: usize, latest_block_height: u32| -> Vec<u32> {
+        (1..=latest_block_height)
+            .filter(|block_height| *block_height as usize % 3 == miner)
+            .collect()
+    };
+    for (index, miner) in miners.iter().enumerate() {
+        assert_eq!(expected_blocks(index, 10), ledger.get_blocks_by_miner(miner).unwrap());
+    }
+
+    // Ensure an address that has not mined a block has no blocks.
+    let address = Account::<Testnet2>::new(rng).address();
+    assert!(ledger.get_blocks_by_miner(&address).unwrap().is_empty());
+
+    // Simulate a reorganization, where the last 3 blocks are replaced by blocks from a new miner.
+    ledger.revert_to_block_height(7).expect("Failed to revert the ledger");
+    for (index, miner) in miners.iter().enumerate() {
+        assert_eq!(expected_blocks(index, 7), ledger.get_blocks_by_miner(miner).unwrap());
+    }
+    for _ in 8..=10 {
+        let (block, _) = ledger
+            .mine_next_block(address, true, &[], &terminator, rng)
+            .expect("Failed to mine");
+        ledger.add_next_block(&block).expect("Failed to add next block to ledger");
+    }
+
+    // Ensure the index reflects the new canonical chain.
+    for (index, miner) in miners.iter().enumerate() {
+        assert_eq!(expected_blocks(index, 7), ledger.get_blocks_by_miner(miner).unwrap());
+    }
+    assert_eq!(vec![8, 9, 10], ledger.get_blocks_by_miner(&address).unwrap());
+
+    // Ensure the rebuilt index matches, and survives a restart.
+    ledger.rebuild_miner_index().expect("Failed to rebuild the miner index");
+    drop(ledger);
+    let ledger = LedgerState::<Testnet2>::open_writer::<RocksDB, _>(&directory).expect("Failed to reopen ledger");
+    for (index, miner) in miners.iter().enumerate() {
+        assert_eq!(expected_blocks(index, 7), ledger.get_blocks_by_miner(miner).unwrap());
+    }
+    assert_eq!(vec![8, 9, 10], ledger.get_blocks_by_miner(&address).unwrap());
+}
+
+#[test]
+fn test_pool_paused() {
+    let directory = temp_dir();
+
+    // Ensure a new pool is not paused.
+    let operator = OperatorState::<Testnet2>::open_writer::<RocksDB, _>(&directory).expect("Failed to initialize operator");
+    assert!(!operator.is_pool_paused().unwrap());
+
+    // Ensure the pause survives a restart.
+    operator.set_pool_paused(true).expect("Failed to pause the pool");
+    drop(operator);
+    let operator = OperatorState::<Testnet2>::open_writer::<RocksDB, _>(&directory).expect("Failed to reopen operator");
+    assert!(operator.is_pool_paused().unwrap());
+
+    // Ensure the pool may be resumed.
+    operator.set_pool_paused(false).expect("Failed to resume the pool");
+    assert!(!operator.is_pool_paused().unwrap());
+}
@@ -23,9 +23,12 @@ pub enum MapId {
     BlockTransactions,
     Commitments,
     LedgerRoots,
+    MinerBlocks,
+    PoolStatus,
     Records,
     SerialNumbers,
     Transactions,
+    TransactionsTotals,
     Transitions,
     Shares,
     #[cfg(test)]
@@ -40,9 +43,12 @@ impl MapId {
             Self::BlockTransactions => b"block_transactions",
             Self::Commitments => b"commitments",
             Self::LedgerRoots => b"ledger_roots",
+            Self::MinerBlocks => b"miner_blocks",
+            Self::PoolStatus => b"pool_status",
             Self::Records => b"records",
             Self::SerialNumbers => b"serial_numbers",
             Self::Transactions => b"transactions",
+            Self::TransactionsTotals => b"transactions_totals",
             Self::Transitions => b"transitions",
             Self::Shares => b"shares",
             #[cfg(test)]
@@ -172,3 +178,24 @@ impl<'a, K: Serialize + DeserializeOwned, V: Serialize + DeserializeOwned> Map<'
         false
     }
 }
+
+impl<K: Serialize + DeserializeOwned, V: Serialize + DeserializeOwned> DataMap<K, V> {
+    ///
+    /// Returns the keys in the map whose serialization begins with the serialization of the given prefix,
+    /// such as the tuple keys that begin with the given first element.
+    ///
+    pub fn keys_with_prefix<Q: Serialize + ?Sized>(&self, prefix: &Q) -> Result<Vec<K>> {
+        let mut key_prefix = self.context.clone();
+        bincode::serialize_into(&mut key_prefix, prefix)?;
+
+        let mut db_iter = self.rocksdb.raw_iterator();
+        db_iter.seek(&key_prefix);
+
+        let mut keys = Vec::new();
+        while let Some(key) = db_iter.key().filter(|key| key.starts_with(&key_prefix)) {
+            keys.push(bincode::deserialize(&key[self.context.len()..])?);
+            db_iter.next();
+        }
+        Ok(keys)
+    }
+}
@@ -85,6 +85,21 @@ fn test_insert_and_keys() {
     assert_eq!(None, keys.next());
 }
 
+#[test]
+fn test_insert_and_keys_with_prefix() {
+    let storage = RocksDB::open(temp_dir(), 0, false).expect("Failed to open storage");
+    let map = storage.open_map::<(u32, u32), ()>(MapId::Test).expect("Failed to open data map");
+    for key in [(1, 30), (1, 10), (2, 20), (1, 20)] {
+        map.insert(&key, &()).expect("Failed to insert");
+    }
+
+    let mut keys = map.keys_with_prefix(&1u32).expect("Failed to get keys");
+    keys.sort_unstable();
+    assert_eq!(vec![(1, 10), (1, 20), (1, 30)], keys);
+    assert_eq!(vec![(2, 20)], map.keys_with_prefix(&2u32).expect("Failed to get keys"));
+    assert!(map.keys_with_prefix(&3u32).expect("Failed to get keys").is_empty());
+}
+
 #[test]
 fn test_insert_and_values() {
     let storage = RocksDB::open(temp_dir(), 0, false).expect("Failed to open storage");
@@ -36,6 +36,16 @@ impl ClientNode {
         self.server.peers().connected_peers().await
     }
 
+    /// Returns the list of peers the node shares in a `PeerResponse`.
+    pub async fn peer_response(&self) -> Vec<SocketAddr> {
+        self.server.peers().peer_response().await
+    }
+
+    /// Returns the types of the most recent messages received from the given peer, with their timestamps.
+    pub async fn message_history(&self, addr: SocketAddr) -> Vec<(String, u64)> {
+        self.server.peers().message_history(addr).await
+    }
+
     /// Resets the node's known peers. This is practical, as it makes the node not reconnect
     /// to known peers in test cases where it's undesirable.
     pub async fn reset_known_peers(&self) {
@@ -79,5 +89,7 @@ impl Drop for ClientNode {
             "Storage cleanup failed! The expected path \"{}\" doesn't exist",
             db_path
         );
+        // The peer reputation store is only written periodically and on shutdown, so it may not exist.
+        let _ = fs::remove_file(format!("/tmp/snarkos-test-peers-{}", self.local_addr().port()));
     }
 }
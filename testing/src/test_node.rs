@@ -15,7 +15,7 @@
 // along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
 
 use snarkos::{
-    helpers::{NodeType, State, Status},
+    helpers::{FeatureFlags, NodeCapabilities, NodeType, State, Status},
     Client,
     Data,
     Environment,
@@ -84,6 +84,8 @@ pub struct ClientState {
     /// this collection facilitates the snarkOS peering experience to align with snarkOS logic.
     pub peers: Arc<Mutex<Vec<ClientPeer>>>,
     pub status: Status,
+    /// The listener port advertised in the handshake; if `None`, the port of the listening address is advertised.
+    pub listener_port: Option<u16>,
 }
 
 impl Default for ClientState {
@@ -92,6 +94,7 @@ impl Default for ClientState {
             local_nonce: thread_rng().gen(),
             peers: Default::default(),
             status: Status::new(),
+            listener_port: None,
         }
     }
 }
@@ -146,6 +149,7 @@ impl TestNode {
                 node.state(),
                 genesis.hash(),
                 Data::Object(genesis.header().clone()),
+                0,
             );
 
             loop {
@@ -166,7 +170,7 @@ impl TestNode {
                 let num_connections = node.node().num_connected() + node.node().num_connecting();
                 if num_connections < DESIRED_CONNECTIONS && node.node().num_connected() != 0 {
                     info!(parent: node.node().span(), "I'd like to have {} more peers; asking peers for their peers", DESIRED_CONNECTIONS - num_connections);
-                    node.send_broadcast(ClientMessage::PeerRequest);
+                    node.send_broadcast(ClientMessage::PeerRequest(node.node().listening_addr().ok()));
                 }
                 tokio::time::sleep(Duration::from_secs(PEER_INTERVAL_SECS)).await;
             }
@@ -192,13 +196,18 @@ impl Handshake for TestNode {
 
         let genesis_block_header = Testnet2::genesis_block().header();
 
+        // Exchange the `Hello` with the peer. The test node supports no connection features, so its messages are uncompressed.
+        connection.writer().write_all(&[FeatureFlags::empty().bits()]).await?;
+        connection.reader().read_u8().await?;
+
         // Send a challenge request to the peer.
         let own_request = ClientMessage::ChallengeRequest(
             MESSAGE_VERSION,
             MAXIMUM_FORK_DEPTH,
             NodeType::Client,
+            NodeCapabilities::from(NodeType::Client),
             State::Ready,
-            own_ip.port(),
+            self.state.listener_port.unwrap_or_else(|| own_ip.port()),
             self.state.local_nonce,
             0,
         );
@@ -222,6 +231,7 @@ impl Handshake for TestNode {
             peer_version,
             _peer_fork_depth,
             _peer_node_type,
+            _peer_capabilities,
             _peer_status,
             peer_listening_port,
             peer_nonce,
@@ -322,10 +332,10 @@ impl Reading for TestNode {
         match message {
             ClientMessage::BlockRequest(_start_block_height, _end_block_height) => {}
             ClientMessage::BlockResponse(_block) => {}
-            ClientMessage::Disconnect => {}
-            ClientMessage::PeerRequest => self.process_peer_request(source).await?,
-            ClientMessage::PeerResponse(peer_ips) => self.process_peer_response(source, peer_ips).await?,
-            ClientMessage::Ping(version, _fork_depth, _peer_type, _peer_state, _block_hash, block_header) => {
+            ClientMessage::Disconnect(..) => {}
+            ClientMessage::PeerRequest(..) => self.process_peer_request(source).await?,
+            ClientMessage::PeerResponse(_, peer_ips) => self.process_peer_response(source, peer_ips).await?,
+            ClientMessage::Ping(version, _fork_depth, _peer_type, _peer_state, _block_hash, block_header, _mempool_size) => {
                 // Deserialise the block header.
                 let block_header = block_header.deserialize().await.unwrap();
                 self.process_ping(source, version, block_header.height()).await?
@@ -333,6 +343,10 @@ impl Reading for TestNode {
             ClientMessage::Pong(_is_fork, _block_locators) => {}
             ClientMessage::UnconfirmedBlock(_block_height, _block_hash, _block) => {}
             ClientMessage::UnconfirmedTransaction(_transaction) => {}
+            ClientMessage::Throttle(..) => {}
+            ClientMessage::BlockUnavailable(..) => {}
+            ClientMessage::PongDiff(..) => {}
+            ClientMessage::BlockResponseEnd(..) => {}
             _ => return Err(io::ErrorKind::InvalidData.into()), // Peer is not following the protocol.
         }
 
@@ -376,7 +390,7 @@ impl TestNode {
             .iter()
             .map(|peer| peer.listening_addr)
             .collect::<Vec<_>>();
-        let msg = ClientMessage::PeerResponse(peers);
+        let msg = ClientMessage::PeerResponse(Some(source), peers);
         info!(parent: self.node().span(), "sending a PeerResponse to {}", source);
 
         self.send_direct_message(source, msg)
@@ -63,6 +63,26 @@ pub async fn spawn_test_node_with_nonce(local_nonce: ClientNonce) -> TestNode {
     node
 }
 
+/// Spawns a `TestNode` that advertises the given listener port in the handshake.
+pub async fn spawn_test_node_with_listener_port(listener_port: u16) -> TestNode {
+    let config = Config {
+        listener_ip: Some(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+        ..Default::default()
+    };
+
+    let pea2pea_node = pea2pea::Node::new(Some(config)).await.unwrap();
+    let client_state = ClientState {
+        listener_port: Some(listener_port),
+        ..Default::default()
+    };
+
+    let node = TestNode::new(pea2pea_node, client_state);
+    node.enable_handshake();
+    node.enable_reading();
+    node.enable_writing();
+    node
+}
+
 /// A helper function making memory use values more human-readable.
 pub fn display_bytes(bytes: f64) -> String {
     const GB: f64 = 1_000_000_000.0;
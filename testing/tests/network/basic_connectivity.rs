@@ -14,15 +14,21 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{common::spawn_test_node_with_nonce, wait_until};
+use crate::{
+    common::{spawn_test_node_with_listener_port, spawn_test_node_with_nonce},
+    wait_until,
+};
 use snarkos_testing::{ClientNode, TestNode, MAXIMUM_NUMBER_OF_PEERS};
 
 use pea2pea::Pea2Pea;
-use std::sync::{
-    atomic::{AtomicU8, Ordering::*},
-    Arc,
+use std::{
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU8, Ordering::*},
+        Arc,
+    },
 };
-use tokio::task;
+use tokio::{net::TcpListener, task};
 
 #[tokio::test]
 async fn client_nodes_can_connect_to_each_other() {
@@ -171,6 +177,33 @@ async fn connection_limits_are_obeyed() {
     assert!(extra_test_node.node().connect(client_node.local_addr()).await.is_err());
 }
 
+#[tokio::test]
+async fn only_peers_with_verified_listeners_are_shared() {
+    // Start a snarkOS node.
+    let client_node = ClientNode::default().await;
+
+    // Start a test node that advertises its open listener port.
+    let open_test_node = TestNode::default().await;
+    let open_test_node_addr = open_test_node.node().listening_addr().unwrap();
+
+    // Start a test node that advertises a closed listener port.
+    let closed_port = TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap().port();
+    let closed_test_node = spawn_test_node_with_listener_port(closed_port).await;
+    let closed_test_node_addr = SocketAddr::from((open_test_node_addr.ip(), closed_port));
+
+    // Both test nodes complete the handshake with the snarkOS node.
+    open_test_node.node().connect(client_node.local_addr()).await.unwrap();
+    closed_test_node.node().connect(client_node.local_addr()).await.unwrap();
+    wait_until!(1, client_node.connected_peers().await.len() == 2);
+
+    // Ensure only the test node with the open listener port is shared in a `PeerResponse`.
+    wait_until!(5, client_node.peer_response().await.contains(&open_test_node_addr));
+    assert!(!client_node.peer_response().await.contains(&closed_test_node_addr));
+
+    // Ensure both test nodes remain connected for data exchange.
+    assert!(client_node.connected_peers().await.contains(&closed_test_node_addr));
+}
+
 #[tokio::test]
 async fn peer_accounting_works() {
     // Start a snarkOS node.
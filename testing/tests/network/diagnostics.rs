@@ -0,0 +1,73 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::wait_until;
+use snarkos::{Client, Message};
+use snarkos_testing::{ClientNode, TestNode};
+use snarkvm::dpc::testnet2::Testnet2;
+
+use pea2pea::{protocols::Writing, Pea2Pea};
+use std::net::SocketAddr;
+
+type ClientMessage = Message<Testnet2, Client<Testnet2>>;
+
+/// Returns the history of the message types sent by the test, ignoring any messages sent by the test node on its own.
+async fn sent_history(client_node: &ClientNode, addr: SocketAddr) -> Vec<(String, u64)> {
+    client_node
+        .message_history(addr)
+        .await
+        .into_iter()
+        .filter(|(message_type, _)| message_type == "Throttle" || message_type == "BlockUnavailable")
+        .collect()
+}
+
+#[tokio::test]
+async fn message_history_lists_inbound_messages_in_order() {
+    // Start a test node.
+    let test_node = TestNode::default().await;
+    let test_node_addr = test_node.node().listening_addr().unwrap();
+
+    // Start a snarkOS node.
+    let client_node = ClientNode::default().await;
+
+    // Connect the snarkOS node to the test node.
+    client_node.connect(test_node_addr).await.unwrap();
+    wait_until!(1, test_node.node().num_connected() == 1);
+    let client_node_addr = test_node.node().connected_addrs()[0];
+
+    // Send 5 messages of two different types, which the test node does not send on its own.
+    let messages = vec![
+        ClientMessage::Throttle(0),
+        ClientMessage::BlockUnavailable(0, 0),
+        ClientMessage::Throttle(0),
+        ClientMessage::Throttle(0),
+        ClientMessage::BlockUnavailable(0, 0),
+    ];
+    let expected = messages.iter().map(|message| message.name().to_string()).collect::<Vec<_>>();
+    for message in messages {
+        test_node.send_direct_message(client_node_addr, message).unwrap();
+    }
+
+    // Wait for the snarkOS node to receive all of the messages.
+    wait_until!(1, sent_history(&client_node, test_node_addr).await.len() == 5);
+
+    // Ensure the history lists all 5 messages in order, with non-decreasing timestamps.
+    let history = sent_history(&client_node, test_node_addr).await;
+    let message_types = history.iter().map(|(message_type, _)| message_type.clone()).collect::<Vec<_>>();
+    assert_eq!(expected, message_types);
+    assert!(history.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    assert!(history.iter().all(|(_, timestamp)| *timestamp > 0));
+}
@@ -16,5 +16,6 @@
 
 mod basic_connectivity;
 mod cleanups;
+mod diagnostics;
 mod manual_testing;
 mod perf;